@@ -1,5 +1,6 @@
 const GIT_COMMIT: &&str = &"GIT_COMMIT";
 const BARRETENBERG_BIN_DIR: &&str = &"BARRETENBERG_BIN_DIR";
+const MINIMAL_BARRETENBERG_WASM: &&str = &"barretenberg-minimal.wasm";
 
 fn main() -> Result<(), String> {
     if std::env::var(GIT_COMMIT).is_err() {
@@ -8,18 +9,23 @@ fn main() -> Result<(), String> {
         build_data::no_debug_rebuilds();
     }
 
-    match std::env::var(BARRETENBERG_BIN_DIR) {
-        Ok(bindir) => {
-            println!("cargo:rustc-env={BARRETENBERG_BIN_DIR}={bindir}");
-            Ok(())
-        }
+    let bindir = match std::env::var(BARRETENBERG_BIN_DIR) {
+        Ok(bindir) => bindir,
         Err(_) => {
             if let Ok(bindir) = pkg_config::get_variable("barretenberg", "bindir") {
-                println!("cargo:rustc-env={BARRETENBERG_BIN_DIR}={bindir}");
-                Ok(())
+                bindir
             } else {
-                Err("Unable to locate barretenberg.wasm - Please set the BARRETENBERG_BIN_DIR env var to the directory where it exists".into())
+                return Err("Unable to locate barretenberg.wasm - Please set the BARRETENBERG_BIN_DIR env var to the directory where it exists".into());
             }
         }
+    };
+
+    if std::env::var("CARGO_FEATURE_MINIMAL_BARRETENBERG").is_ok()
+        && !std::path::Path::new(&bindir).join(MINIMAL_BARRETENBERG_WASM).exists()
+    {
+        return Err(format!("Unable to locate {MINIMAL_BARRETENBERG_WASM} in {bindir} - Please generate it with ./strip-barretenberg.sh and set the BARRETENBERG_BIN_DIR env var to the directory where it exists"));
     }
+
+    println!("cargo:rustc-env={BARRETENBERG_BIN_DIR}={bindir}");
+    Ok(())
 }
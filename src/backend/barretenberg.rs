@@ -0,0 +1,44 @@
+use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
+
+use super::{BackendFuture, ProvingBackend};
+use crate::{
+    estimate::{circuit_cost, default_cost_weights},
+    execute::read_circuit,
+};
+
+/// The Barretenberg embedded in this package.
+///
+/// The embedded build only contains the functions needed to solve blackbox opcodes so can't create or verify
+/// proofs. Its cost model is the default weights used by `estimateCircuitCost`.
+pub(super) struct BarretenbergBackend;
+
+impl BarretenbergBackend {
+    fn unsupported<T>(action: &str) -> Result<T, String> {
+        Err(format!(
+            "The embedded Barretenberg can't {action} as it only contains blackbox function implementations. \
+             Provide a ProvingBackend to {action}."
+        ))
+    }
+}
+
+impl ProvingBackend for BarretenbergBackend {
+    fn prove<'a>(&'a self, _: &'a [u8], _: WitnessMap) -> BackendFuture<'a, Vec<u8>> {
+        Box::pin(async { Self::unsupported("create proofs") })
+    }
+
+    fn verify<'a>(&'a self, _: &'a [u8], _: &'a [u8], _: WitnessMap) -> BackendFuture<'a, bool> {
+        Box::pin(async { Self::unsupported("verify proofs") })
+    }
+
+    fn verification_key<'a>(&'a self, _: &'a [u8]) -> BackendFuture<'a, Vec<u8>> {
+        Box::pin(async { Self::unsupported("create verification keys") })
+    }
+
+    fn estimate_gates<'a>(&'a self, circuit: &'a [u8]) -> BackendFuture<'a, u64> {
+        Box::pin(async move {
+            let circuit: Circuit = read_circuit(circuit).map_err(|err| err.to_string())?;
+            let cost = circuit_cost(&circuit, &default_cost_weights());
+            Ok(cost.total_gates.ceil() as u64)
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use acvm::acir::native_types::WitnessMap;
+use js_sys::{Function, Promise, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+use super::{BackendFuture, ProvingBackend};
+use crate::{execute::read_function, JsWitnessMap};
+
+/// A proving backend implemented in JS.
+pub(super) struct JsBackend {
+    name: String,
+    prove: Option<Function>,
+    verify: Option<Function>,
+    get_verification_key: Option<Function>,
+    estimate_gates: Option<Function>,
+}
+
+impl TryFrom<JsValue> for JsBackend {
+    type Error = String;
+
+    fn try_from(js_backend: JsValue) -> Result<Self, Self::Error> {
+        if !js_backend.is_object() {
+            return Err("backend must be an object".to_string());
+        }
+
+        let name = Reflect::get(&js_backend, &JsValue::from_str("name"))
+            .map_err(|_| "could not read name".to_string())?;
+        let name = match name.as_string() {
+            Some(name) => name,
+            None if name.is_undefined() => "ProvingBackend".to_string(),
+            None => return Err("name must be a string".to_string()),
+        };
+
+        Ok(JsBackend {
+            name,
+            prove: read_function(&js_backend, "prove")?,
+            verify: read_function(&js_backend, "verify")?,
+            get_verification_key: read_function(&js_backend, "getVerificationKey")?,
+            estimate_gates: read_function(&js_backend, "estimateGates")?,
+        })
+    }
+}
+
+impl JsBackend {
+    /// Calls the backend's implementation of `function`, waiting on the result if it returns a Promise.
+    async fn call(
+        &self,
+        function: &Option<Function>,
+        function_name: &str,
+        args: &[JsValue],
+    ) -> Result<JsValue, String> {
+        let Some(function) = function else {
+            return Err(format!("{} does not implement {function_name}", self.name));
+        };
+
+        let args: js_sys::Array = args.iter().collect();
+        let result = function
+            .apply(&JsValue::NULL, &args)
+            .map_err(|err| self.failure(function_name, err))?;
+        JsFuture::from(Promise::resolve(&result))
+            .await
+            .map_err(|err| self.failure(function_name, err))
+    }
+
+    fn failure(&self, function_name: &str, err: JsValue) -> String {
+        let message = err
+            .as_string()
+            .or_else(|| err.dyn_ref::<js_sys::Error>().map(|err| String::from(err.message())))
+            .unwrap_or_else(|| "Unknown".to_owned());
+        format!("{}.{function_name} threw an error: {message}", self.name)
+    }
+
+    fn invalid_result(&self, function_name: &str, expected: &str) -> String {
+        format!("{}.{function_name} must return {expected}", self.name)
+    }
+
+    fn bytes(&self, function_name: &str, result: JsValue) -> Result<Vec<u8>, String> {
+        result
+            .dyn_into::<Uint8Array>()
+            .map(|bytes| bytes.to_vec())
+            .map_err(|_| self.invalid_result(function_name, "a Uint8Array"))
+    }
+}
+
+impl ProvingBackend for JsBackend {
+    fn prove<'a>(&'a self, circuit: &'a [u8], witness: WitnessMap) -> BackendFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let args = [Uint8Array::from(circuit).into(), JsWitnessMap::from(witness).into()];
+            let result = self.call(&self.prove, "prove", &args).await?;
+            self.bytes("prove", result)
+        })
+    }
+
+    fn verify<'a>(
+        &'a self,
+        circuit: &'a [u8],
+        proof: &'a [u8],
+        public_inputs: WitnessMap,
+    ) -> BackendFuture<'a, bool> {
+        Box::pin(async move {
+            let args = [
+                Uint8Array::from(circuit).into(),
+                Uint8Array::from(proof).into(),
+                JsWitnessMap::from(public_inputs).into(),
+            ];
+            let result = self.call(&self.verify, "verify", &args).await?;
+            result.as_bool().ok_or_else(|| self.invalid_result("verify", "a boolean"))
+        })
+    }
+
+    fn verification_key<'a>(&'a self, circuit: &'a [u8]) -> BackendFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let args = [Uint8Array::from(circuit).into()];
+            let result = self.call(&self.get_verification_key, "getVerificationKey", &args).await?;
+            self.bytes("getVerificationKey", result)
+        })
+    }
+
+    fn estimate_gates<'a>(&'a self, circuit: &'a [u8]) -> BackendFuture<'a, u64> {
+        Box::pin(async move {
+            let args = [Uint8Array::from(circuit).into()];
+            let result = self.call(&self.estimate_gates, "estimateGates", &args).await?;
+            match result.as_f64() {
+                Some(gates) if gates >= 0.0 && gates.fract() == 0.0 => Ok(gates as u64),
+                _ => Err(self.invalid_result("estimateGates", "a non-negative integer")),
+            }
+        })
+    }
+}
@@ -0,0 +1,193 @@
+//! A proof system agnostic interface to proving backends.
+//!
+//! Proving, verification and cost estimation are routed through a [`ProvingBackend`] so that they aren't tied
+//! to a single proof system. The embedded Barretenberg is used unless a backend is provided from JS, either per call
+//! or through `configureSimulator`.
+
+use std::{future::Future, pin::Pin};
+
+use acvm::acir::native_types::WitnessMap;
+use js_sys::{JsString, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::{config::with_config, js_witness_map::JsWitnessMapLike};
+
+mod barretenberg;
+mod js;
+
+use barretenberg::BarretenbergBackend;
+use js::JsBackend;
+
+#[wasm_bindgen(typescript_custom_section)]
+const PROVING_BACKEND: &'static str = r#"
+/**
+* @typedef {Object} ProvingBackend - A proof system which can prove and verify ACIR circuits.
+* Each function may return its result directly or as a Promise. Functions which are not provided are unsupported.
+* @property {string} name - A name for the backend, used in error messages.
+* @property {Function} prove - Returns a proof that `witness` satisfies `circuit`.
+* @property {Function} verify - Returns whether `proof` is a valid proof of `circuit` with the given public inputs.
+* @property {Function} getVerificationKey - Returns the verification key for `circuit`.
+* @property {Function} estimateGates - Returns the number of gates needed to prove `circuit`.
+*/
+export type ProvingBackend = {
+  name?: string;
+  prove?: (circuit: Uint8Array, witness: WitnessMap) => Uint8Array | Promise<Uint8Array>;
+  verify?: (circuit: Uint8Array, proof: Uint8Array, publicInputs: WitnessMap) => boolean | Promise<boolean>;
+  getVerificationKey?: (circuit: Uint8Array) => Uint8Array | Promise<Uint8Array>;
+  estimateGates?: (circuit: Uint8Array) => number | Promise<number>;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ProvingBackend")]
+    pub type JsProvingBackend;
+
+    #[wasm_bindgen(extends = js_sys::Promise, typescript_type = "Promise<Uint8Array>")]
+    pub type JsBytesPromise;
+
+    #[wasm_bindgen(extends = js_sys::Promise, typescript_type = "Promise<boolean>")]
+    pub type JsBooleanPromise;
+
+    #[wasm_bindgen(extends = js_sys::Promise, typescript_type = "Promise<number>")]
+    pub type JsNumberPromise;
+}
+
+/// The result of an operation performed by a [`ProvingBackend`], which may need to wait on JS.
+pub(crate) type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + 'a>>;
+
+/// A proof system which can prove and verify ACIR circuits.
+///
+/// Circuits are passed in their serialized form as that is what most backends consume.
+pub(crate) trait ProvingBackend {
+    /// Creates a proof that `witness` satisfies `circuit`.
+    fn prove<'a>(&'a self, circuit: &'a [u8], witness: WitnessMap) -> BackendFuture<'a, Vec<u8>>;
+
+    /// Checks whether `proof` is a valid proof of `circuit` with the given `public_inputs`.
+    fn verify<'a>(
+        &'a self,
+        circuit: &'a [u8],
+        proof: &'a [u8],
+        public_inputs: WitnessMap,
+    ) -> BackendFuture<'a, bool>;
+
+    /// Creates the verification key for `circuit`.
+    fn verification_key<'a>(&'a self, circuit: &'a [u8]) -> BackendFuture<'a, Vec<u8>>;
+
+    /// Estimates the number of gates needed to prove `circuit`.
+    fn estimate_gates<'a>(&'a self, circuit: &'a [u8]) -> BackendFuture<'a, u64>;
+}
+
+/// Returns the backend provided from JS, falling back to the embedded Barretenberg if there isn't one.
+pub(crate) fn select_backend(
+    backend: Option<JsProvingBackend>,
+) -> Result<Box<dyn ProvingBackend>, JsString> {
+    // Fall back to any backend set through `configureSimulator` before the embedded Barretenberg.
+    let backend =
+        backend.map(JsValue::from).or_else(|| with_config(|config| config.backend.clone()));
+    match backend {
+        Some(backend) => {
+            let backend =
+                JsBackend::try_from(backend).map_err(|err| format!("Invalid backend: {err}"))?;
+            Ok(Box::new(backend))
+        }
+        None => Ok(Box::new(BarretenbergBackend)),
+    }
+}
+
+/// Checks that `backend` describes a valid `ProvingBackend`.
+pub(crate) fn validate_backend(backend: &JsValue) -> Result<(), String> {
+    JsBackend::try_from(backend.clone()).map(|_| ())
+}
+
+/// Creates a proof that a solved witness satisfies an ACIR circuit.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} solved_witness - The solved witness for `circuit`, as returned by `executeCircuit`.
+/// @param {ProvingBackend} backend - The proof system to prove with. Defaults to the embedded Barretenberg.
+/// @returns {Promise<Uint8Array>} The proof.
+#[wasm_bindgen(skip_jsdoc)]
+pub fn prove(
+    circuit: Vec<u8>,
+    solved_witness: JsWitnessMapLike,
+    backend: Option<JsProvingBackend>,
+) -> Result<JsBytesPromise, JsString> {
+    console_error_panic_hook::set_once();
+    let backend = select_backend(backend)?;
+    let solved_witness = WitnessMap::try_from(solved_witness)?;
+
+    let promise = future_to_promise(async move {
+        let proof = backend.prove(&circuit, solved_witness).await.map_err(JsString::from)?;
+        Ok(Uint8Array::from(proof.as_slice()).into())
+    });
+    Ok(JsValue::from(promise).into())
+}
+
+/// Checks whether a proof of an ACIR circuit is valid.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {Uint8Array} proof - The proof to verify.
+/// @param {WitnessMapLike} public_inputs - The public inputs of `circuit` which `proof` should be verified against.
+/// @param {ProvingBackend} backend - The proof system which created `proof`. Defaults to the embedded Barretenberg.
+/// @returns {Promise<boolean>} Whether `proof` is valid.
+#[wasm_bindgen(js_name = verifyProof, skip_jsdoc)]
+pub fn verify_proof(
+    circuit: Vec<u8>,
+    proof: Vec<u8>,
+    public_inputs: JsWitnessMapLike,
+    backend: Option<JsProvingBackend>,
+) -> Result<JsBooleanPromise, JsString> {
+    console_error_panic_hook::set_once();
+    let backend = select_backend(backend)?;
+    let public_inputs = WitnessMap::try_from(public_inputs)?;
+
+    let promise = future_to_promise(async move {
+        let valid =
+            backend.verify(&circuit, &proof, public_inputs).await.map_err(JsString::from)?;
+        Ok(JsValue::from_bool(valid))
+    });
+    Ok(JsValue::from(promise).into())
+}
+
+/// Creates the verification key for an ACIR circuit.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {ProvingBackend} backend - The proof system to create the key for. Defaults to the embedded Barretenberg.
+/// @returns {Promise<Uint8Array>} The verification key.
+#[wasm_bindgen(js_name = getVerificationKey, skip_jsdoc)]
+pub fn get_verification_key(
+    circuit: Vec<u8>,
+    backend: Option<JsProvingBackend>,
+) -> Result<JsBytesPromise, JsString> {
+    console_error_panic_hook::set_once();
+    let backend = select_backend(backend)?;
+
+    let promise = future_to_promise(async move {
+        let key = backend.verification_key(&circuit).await.map_err(JsString::from)?;
+        Ok(Uint8Array::from(key.as_slice()).into())
+    });
+    Ok(JsValue::from(promise).into())
+}
+
+/// Estimates the number of gates needed to prove an ACIR circuit.
+///
+/// The embedded Barretenberg uses the default weights of `estimateCircuitCost`.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {ProvingBackend} backend - The proof system to estimate the cost for. Defaults to the embedded Barretenberg.
+/// @returns {Promise<number>} The estimated number of gates.
+#[wasm_bindgen(js_name = estimateGates, skip_jsdoc)]
+pub fn estimate_gates(
+    circuit: Vec<u8>,
+    backend: Option<JsProvingBackend>,
+) -> Result<JsNumberPromise, JsString> {
+    console_error_panic_hook::set_once();
+    let backend = select_backend(backend)?;
+
+    let promise = future_to_promise(async move {
+        let gates = backend.estimate_gates(&circuit).await.map_err(JsString::from)?;
+        Ok(JsValue::from(gates as f64))
+    });
+    Ok(JsValue::from(promise).into())
+}
@@ -8,7 +8,11 @@ mod barretenberg_structures;
 pub(crate) mod pedersen;
 pub(crate) mod scalar_mul;
 pub(crate) mod schnorr;
+mod virtual_fs;
 use barretenberg_structures::Assignments;
+pub(crate) use virtual_fs::{with_virtual_files, VirtualFiles};
+
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
 
 /// The number of bytes necessary to store a `FieldElement`.
 const FIELD_BYTES: usize = 32;
@@ -35,6 +39,10 @@ pub(crate) enum FeatureError {
     InvalidUsize { value: i32, source: std::num::TryFromIntError },
     #[error("Value expected to be 0 or 1 representing a boolean")]
     InvalidBool,
+    #[error(
+        "Could not call {name} as a previous call trapped and the instance must be reinitialized"
+    )]
+    Trapped { name: String },
 }
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
@@ -46,24 +54,127 @@ impl From<FeatureError> for BackendError {
     }
 }
 
+thread_local! {
+    /// The state of the random number generator exposed to the wasm, set while in deterministic mode.
+    static DETERMINISTIC_RNG: std::cell::Cell<Option<u64>> = std::cell::Cell::new(None);
+}
+
+/// The seed of the random number generator exposed to the wasm in deterministic mode.
+const DETERMINISTIC_SEED: u64 = 0x5eed_5eed_5eed_5eed;
+
+/// Runs `f`, exposing a seeded random number generator and a fixed clock to the wasm if `deterministic` is set.
+///
+/// The random number generator is reseeded on each call so its output doesn't depend on any previous calls.
+pub(crate) fn with_determinism<T>(deterministic: bool, f: impl FnOnce() -> T) -> T {
+    if !deterministic {
+        return f();
+    }
+
+    let previous = DETERMINISTIC_RNG.with(|rng| rng.replace(Some(DETERMINISTIC_SEED)));
+    let result = f();
+    DETERMINISTIC_RNG.with(|rng| rng.set(previous));
+    result
+}
+
+/// The random bytes drawn by the wasm during an execution, either as they're recorded or while they're replayed.
+#[derive(Debug)]
+pub(crate) enum RandomTape {
+    Recording(Vec<u8>),
+    Replaying(VecDeque<u8>),
+}
+
+impl RandomTape {
+    /// Fills `buf` with the next bytes from the tape when replaying. Otherwise `buf` is filled by `draw` and recorded.
+    fn fill(
+        &mut self,
+        buf: &mut [u8],
+        draw: impl FnOnce(&mut [u8]) -> Result<(), ()>,
+    ) -> Result<(), ()> {
+        match self {
+            RandomTape::Recording(recorded) => {
+                draw(buf)?;
+                recorded.extend_from_slice(buf);
+                Ok(())
+            }
+            RandomTape::Replaying(remaining) => {
+                if remaining.len() < buf.len() {
+                    return Err(());
+                }
+                let len = buf.len();
+                for (byte, replayed) in buf.iter_mut().zip(remaining.drain(..len)) {
+                    *byte = replayed;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// The tape which the random bytes drawn by the wasm are recorded to or replayed from, if any.
+    static RANDOM_TAPE: RefCell<Option<Rc<RefCell<RandomTape>>>> = RefCell::new(None);
+}
+
+/// Runs `f`, recording the random bytes drawn by the wasm to `tape` or replaying them from it if provided.
+pub(crate) fn with_random_tape<T>(
+    tape: Option<&Rc<RefCell<RandomTape>>>,
+    f: impl FnOnce() -> T,
+) -> T {
+    let Some(tape) = tape else {
+        return f();
+    };
+
+    let previous = RANDOM_TAPE.with(|current| current.replace(Some(tape.clone())));
+    let result = f();
+    RANDOM_TAPE.with(|current| current.replace(previous));
+    result
+}
+
 #[derive(Debug)]
 pub(crate) struct Barretenberg {
     store: std::cell::RefCell<wasmer::Store>,
     memory: wasmer::Memory,
     instance: wasmer::Instance,
+    /// Set if a call into the wasm has trapped. The instance's memory may have been left in an
+    /// inconsistent state so it must not be used for any further calls.
+    trapped: std::cell::Cell<bool>,
+    /// Buffers on the wasm heap which are reused for the inputs to calls into the wasm.
+    arena: std::cell::RefCell<HeapArena>,
+}
+
+/// A buffer allocated on the wasm heap.
+#[derive(Debug, Clone, Copy)]
+struct HeapBlock {
+    ptr: i32,
+    capacity: usize,
+}
+
+/// Buffers allocated on the wasm heap which are handed out in turn and reused once the arena is reset.
+///
+/// Allocating and freeing a fresh buffer for each input passed to the wasm shows up prominently in profiles of batch
+/// executions, so buffers are kept around to be reused by later executions instead.
+#[derive(Debug, Default)]
+struct HeapArena {
+    blocks: Vec<HeapBlock>,
+    /// The index of the next block to hand out.
+    next: usize,
 }
 
 mod wasm {
     use js_sys::WebAssembly::{self};
-    use log::debug;
-    use std::cell::RefCell;
+    use log::{debug, info, warn};
+    use std::cell::{Cell, RefCell};
 
     use wasmer::{
-        imports, AsJs, Function, FunctionEnv, FunctionEnvMut, Instance, Memory, MemoryType, Store,
-        Value, WasmPtr,
+        imports, AsJs, Function, FunctionEnv, FunctionEnvMut, Imports, Instance, Memory,
+        MemoryType, MemoryView, Store, Value, WasmPtr,
     };
 
-    use super::{Barretenberg, Error, FeatureError};
+    use super::{
+        virtual_fs::{self, ERRNO_FAULT, ERRNO_SUCCESS, STDERR},
+        Barretenberg, Error, FeatureError, HeapBlock, DETERMINISTIC_RNG, RANDOM_TAPE,
+    };
+    use crate::config::{with_config, INITIAL_MEMORY_PAGES};
 
     /// The number of bytes necessary to represent a pointer to memory inside the wasm.
     // pub(super) const POINTER_BYTES: usize = 4;
@@ -78,15 +189,60 @@ mod wasm {
     pub(super) const WASM_SCRATCH_BYTES: usize = 1024;
 
     /// Embed the Barretenberg WASM file
+    #[cfg(not(feature = "minimal-barretenberg"))]
     #[derive(rust_embed::RustEmbed)]
     #[folder = "$BARRETENBERG_BIN_DIR"]
     #[include = "barretenberg.wasm"]
     struct Wasm;
 
+    /// Embed the stripped Barretenberg WASM file which only exports the functions used by the black box solvers.
+    #[cfg(feature = "minimal-barretenberg")]
+    #[derive(rust_embed::RustEmbed)]
+    #[folder = "$BARRETENBERG_BIN_DIR"]
+    #[include = "barretenberg-minimal.wasm"]
+    struct Wasm;
+
+    #[cfg(not(feature = "minimal-barretenberg"))]
+    const WASM_FILE_NAME: &str = "barretenberg.wasm";
+    #[cfg(feature = "minimal-barretenberg")]
+    const WASM_FILE_NAME: &str = "barretenberg-minimal.wasm";
+
     impl Barretenberg {
         pub(crate) async fn new() -> Barretenberg {
             let (instance, memory, store) = instance_load().await;
-            Barretenberg { memory, instance, store: RefCell::new(store) }
+            Barretenberg {
+                memory,
+                instance,
+                store: RefCell::new(store),
+                trapped: Cell::new(false),
+                arena: RefCell::default(),
+            }
+        }
+
+        /// Creates a new instance, compiling the wasm synchronously.
+        ///
+        /// Some browsers refuse to synchronously compile large modules on the main thread
+        /// so [`Barretenberg::new`] should be preferred where possible.
+        pub(crate) fn new_sync() -> Barretenberg {
+            let (instance, memory, store) = instance_load_sync();
+            Barretenberg {
+                memory,
+                instance,
+                store: RefCell::new(store),
+                trapped: Cell::new(false),
+                arena: RefCell::default(),
+            }
+        }
+
+        /// Returns `false` if a previous call into the wasm trapped, in which case the instance
+        /// should be discarded and a new one created.
+        pub(crate) fn is_healthy(&self) -> bool {
+            !self.trapped.get()
+        }
+
+        /// Marks the instance as having trapped, as happens when a call into the wasm fails.
+        pub(crate) fn mark_trapped(&self) {
+            self.trapped.set(true);
         }
     }
 
@@ -210,6 +366,10 @@ mod wasm {
             // We take in a reference to values, since they do not implement Copy.
             // We then clone them inside of this function, so that the API does not have a bunch of Clones everywhere
 
+            if self.trapped.get() {
+                return Err(FeatureError::Trapped { name: name.to_string() }.into());
+            }
+
             let mut args: Vec<Value> = vec![];
             for param in params.into_iter().cloned() {
                 args.push(param.try_into()?)
@@ -219,6 +379,7 @@ mod wasm {
                     FeatureError::InvalidExport { name: name.to_string(), source }
                 })?;
             let boxed_value = func.call(&mut self.store.borrow_mut(), &args).map_err(|source| {
+                self.trapped.set(true);
                 FeatureError::FunctionCallFailed { name: name.to_string(), source }
             })?;
             let option_value = boxed_value.first().cloned();
@@ -226,28 +387,110 @@ mod wasm {
             Ok(WASMValue(option_value))
         }
 
-        /// Creates a pointer and allocates the bytes that the pointer references to, to the heap
+        /// Copies `bytes` onto the heap, returning a pointer to them.
+        ///
+        /// The bytes are written into the next buffer of the heap arena, which is only valid until the arena is
+        /// reset so must not be held onto beyond the call it's passed to.
         pub(crate) fn allocate(&self, bytes: &[u8]) -> Result<WASMValue, Error> {
-            let ptr: i32 = self.call("bbmalloc", &bytes.len().into())?.try_into()?;
+            let next = self.arena.borrow().next;
+            let reusable = self.arena.borrow().blocks.get(next).copied();
+            let block = match reusable {
+                Some(block) if block.capacity >= bytes.len() => block,
+                _ => {
+                    // Capacities are rounded up so that inputs which grow slightly between executions can reuse them.
+                    let capacity = bytes.len().next_power_of_two();
+                    let ptr: i32 = self.call("bbmalloc", &capacity.into())?.try_into()?;
+                    let block = HeapBlock { ptr, capacity };
+
+                    let mut arena = self.arena.borrow_mut();
+                    match arena.blocks.get_mut(next) {
+                        Some(previous) => {
+                            let previous = std::mem::replace(previous, block);
+                            drop(arena);
+                            self.free(previous.ptr.into())?;
+                        }
+                        None => arena.blocks.push(block),
+                    }
+                    block
+                }
+            };
+            self.arena.borrow_mut().next += 1;
 
-            let i32_bytes = ptr.to_be_bytes();
+            let i32_bytes = block.ptr.to_be_bytes();
             let u32_bytes = u32::from_be_bytes(i32_bytes);
 
             self.transfer_to_heap(bytes, u32_bytes as usize);
-            Ok(ptr.into())
+            Ok(block.ptr.into())
+        }
+
+        pub(super) fn free(&self, pointer: WASMValue) -> Result<(), Error> {
+            self.call("bbfree", &pointer)?;
+            Ok(())
         }
 
-        // pub(super) fn free(&self, pointer: WASMValue) -> Result<(), Error> {
-        //     self.call("bbfree", &pointer)?;
-        //     Ok(())
-        // }
+        /// Allows the buffers handed out by [`Barretenberg::allocate`] to be reused.
+        ///
+        /// Buffers are only read by the wasm during the call they're passed to, so this is safe to call between any
+        /// two calls into the wasm.
+        pub(crate) fn reset_arena(&self) {
+            self.arena.borrow_mut().next = 0;
+        }
     }
 
     async fn instance_load() -> (Instance, Memory, Store) {
+        let (mut store, memory, custom_imports) = instance_imports();
+
+        let wasm_binary = Wasm::get(WASM_FILE_NAME).unwrap().data;
+        let js_bytes: js_sys::Uint8Array;
+        unsafe {
+            js_bytes = js_sys::Uint8Array::view(&wasm_binary);
+        }
+        debug!("> Will compile black box functions vendor module");
+        let js_module_promise = WebAssembly::compile(&js_bytes);
+        let js_module: js_sys::WebAssembly::Module =
+            wasm_bindgen_futures::JsFuture::from(js_module_promise).await.unwrap().into();
+
+        debug!("> Will create black box functions vendor instance");
+        let js_instance_promise =
+            WebAssembly::instantiate_module(&js_module, &custom_imports.as_jsvalue(&store).into());
+        let js_instance = wasm_bindgen_futures::JsFuture::from(js_instance_promise).await.unwrap();
+        let module: wasmer::Module = (js_module, wasm_binary).into();
+        let instance: wasmer::Instance = Instance::from_jsvalue(&mut store, &module, &js_instance)
+            .map_err(|_| "Error while creating BlackBox Functions vendor instance")
+            .unwrap();
+
+        (instance, memory, store)
+    }
+
+    fn instance_load_sync() -> (Instance, Memory, Store) {
+        let (mut store, memory, custom_imports) = instance_imports();
+
+        let wasm_binary = Wasm::get(WASM_FILE_NAME).unwrap().data;
+        let js_bytes: js_sys::Uint8Array;
+        unsafe {
+            js_bytes = js_sys::Uint8Array::view(&wasm_binary);
+        }
+        debug!("> Will synchronously compile black box functions vendor module");
+        let js_module = WebAssembly::Module::new(&js_bytes).unwrap();
+
+        debug!("> Will synchronously create black box functions vendor instance");
+        let js_instance =
+            WebAssembly::Instance::new(&js_module, &custom_imports.as_jsvalue(&store).into())
+                .unwrap();
+        let module: wasmer::Module = (js_module, wasm_binary).into();
+        let instance: wasmer::Instance = Instance::from_jsvalue(&mut store, &module, &js_instance)
+            .map_err(|_| "Error while creating BlackBox Functions vendor instance")
+            .unwrap();
+
+        (instance, memory, store)
+    }
+
+    fn instance_imports() -> (Store, Memory, Imports) {
         debug!("> Will Load black box functions vendor binary");
         let mut store = Store::default();
 
-        let mem_type = MemoryType::new(23, None, false);
+        let max_pages = with_config(|config| config.max_memory_pages);
+        let mem_type = MemoryType::new(INITIAL_MEMORY_PAGES, max_pages, false);
         let memory = Memory::new(&mut store, mem_type).unwrap();
 
         let function_env = FunctionEnv::new(&mut store, memory.clone());
@@ -265,43 +508,44 @@ mod wasm {
                 "memory" => memory.clone(),
             },
             "wasi_snapshot_preview1" => {
-                "fd_read" => Function::new_typed(&mut store, fd_read),
+                "fd_read" => Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    fd_read
+                ),
                 "fd_close" => Function::new_typed(&mut store, fd_close),
                 "proc_exit" =>  Function::new_typed(&mut store, proc_exit),
-                "fd_fdstat_get" => Function::new_typed(&mut store, fd_fdstat_get),
+                "fd_fdstat_get" => Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    fd_fdstat_get
+                ),
                 "random_get" => Function::new_typed_with_env(
                     &mut store,
                     &function_env,
                     random_get
                 ),
-                "fd_seek" => Function::new_typed(&mut store, fd_seek),
-                "fd_write" => Function::new_typed(&mut store, fd_write),
+                "fd_seek" => Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    fd_seek
+                ),
+                "fd_write" => Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    fd_write
+                ),
                 "environ_sizes_get" => Function::new_typed(&mut store, environ_sizes_get),
                 "environ_get" => Function::new_typed(&mut store, environ_get),
-                "clock_time_get" => Function::new_typed(&mut store, clock_time_get),
+                "clock_time_get" => Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    clock_time_get
+                ),
             },
         };
 
-        let wasm_binary = Wasm::get("barretenberg.wasm").unwrap().data;
-        let js_bytes: js_sys::Uint8Array;
-        unsafe {
-            js_bytes = js_sys::Uint8Array::view(&wasm_binary);
-        }
-        debug!("> Will compile black box functions vendor module");
-        let js_module_promise = WebAssembly::compile(&js_bytes);
-        let js_module: js_sys::WebAssembly::Module =
-            wasm_bindgen_futures::JsFuture::from(js_module_promise).await.unwrap().into();
-
-        debug!("> Will create black box functions vendor instance");
-        let js_instance_promise =
-            WebAssembly::instantiate_module(&js_module, &custom_imports.as_jsvalue(&store).into());
-        let js_instance = wasm_bindgen_futures::JsFuture::from(js_instance_promise).await.unwrap();
-        let module: wasmer::Module = (js_module, wasm_binary).into();
-        let instance: wasmer::Instance = Instance::from_jsvalue(&mut store, &module, &js_instance)
-            .map_err(|_| "Error while creating BlackBox Functions vendor instance")
-            .unwrap();
-
-        (instance, memory, store)
+        (store, memory, custom_imports)
     }
 
     fn logstr(mut env: FunctionEnvMut<Memory>, ptr: i32) {
@@ -319,7 +563,17 @@ mod wasm {
     // Based on https://github.com/wasmerio/wasmer/blob/2.3.0/lib/wasi/src/syscalls/mod.rs#L2537
     fn random_get(mut env: FunctionEnvMut<Memory>, buf_ptr: i32, buf_len: i32) -> i32 {
         let mut u8_buffer = vec![0; buf_len as usize];
-        let res = getrandom::getrandom(&mut u8_buffer);
+        let draw = |buf: &mut [u8]| match DETERMINISTIC_RNG.with(|rng| rng.get()) {
+            Some(state) => {
+                DETERMINISTIC_RNG.with(|rng| rng.set(Some(fill_deterministic(state, buf))));
+                Ok(())
+            }
+            None => getrandom::getrandom(buf).map_err(|_| ()),
+        };
+        let res = match RANDOM_TAPE.with(|tape| tape.borrow().clone()) {
+            Some(tape) => tape.borrow_mut().fill(&mut u8_buffer, draw),
+            None => draw(&mut u8_buffer),
+        };
         match res {
             Ok(()) => {
                 let (memory, store) = env.data_and_store_mut();
@@ -339,32 +593,162 @@ mod wasm {
         }
     }
 
-    fn clock_time_get(_: i32, _: i64, _: i32) -> i32 {
-        unimplemented!("proc_exit: clock_time_get is not implemented")
+    /// Fills `buf` using a SplitMix64 generator in `state`, returning the generator's new state.
+    fn fill_deterministic(mut state: u64, buf: &mut [u8]) -> u64 {
+        for chunk in buf.chunks_mut(8) {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            chunk.copy_from_slice(&z.to_le_bytes()[..chunk.len()]);
+        }
+        state
+    }
+
+    fn clock_time_get(mut env: FunctionEnvMut<Memory>, _: i32, _: i64, time_ptr: i32) -> i32 {
+        // Outside of deterministic mode there is no clock, which the wasm is told of rather than trapping.
+        if DETERMINISTIC_RNG.with(|rng| rng.get()).is_none() {
+            return 52_i32; // __WASI_ENOSYS
+        }
+
+        // In deterministic mode the clock is fixed at the Unix epoch.
+        let (memory, store) = env.data_and_store_mut();
+        let memory_view = memory.view(&store);
+        match memory_view.write(time_ptr as u64, &0_u64.to_le_bytes()) {
+            Ok(_) => 0_i32,   // __WASI_ESUCCESS
+            Err(_) => 29_i32, // __WASI_EIO
+        }
     }
 
     fn proc_exit(_: i32) {
         unimplemented!("proc_exit is not implemented")
     }
 
-    fn fd_write(_: i32, _: i32, _: i32, _: i32) -> i32 {
-        unimplemented!("fd_write is not implemented")
+    // The file descriptor imports are backed by the virtual filesystem in `virtual_fs`.
+    // See https://github.com/WebAssembly/WASI/blob/main/legacy/preview1/docs.md for the layouts used.
+
+    fn fd_write(
+        mut env: FunctionEnvMut<Memory>,
+        fd: i32,
+        iovs: i32,
+        iovs_len: i32,
+        nwritten_ptr: i32,
+    ) -> i32 {
+        let (memory, store) = env.data_and_store_mut();
+        let memory_view = memory.view(&store);
+
+        let result = virtual_fs::check_writable(fd as u32)
+            .and_then(|_| read_iovecs(&memory_view, iovs, iovs_len))
+            .and_then(|iovecs| {
+                let mut data = Vec::new();
+                for (buf, len) in iovecs {
+                    let mut chunk = vec![0; len];
+                    memory_view.read(buf, &mut chunk).map_err(|_| ERRNO_FAULT)?;
+                    data.extend(chunk);
+                }
+                // The wasm's output is logged so that it respects the configured `logLevel` and `logSink`.
+                let output = String::from_utf8_lossy(&data);
+                let output = output.trim_end();
+                if !output.is_empty() {
+                    match fd as u32 {
+                        STDERR => warn!("{output}"),
+                        _ => info!("{output}"),
+                    }
+                }
+                write_u32(&memory_view, nwritten_ptr, data.len() as u32)
+            });
+        into_errno(result)
+    }
+
+    fn fd_seek(
+        mut env: FunctionEnvMut<Memory>,
+        fd: i32,
+        offset: i64,
+        whence: i32,
+        newoffset_ptr: i32,
+    ) -> i32 {
+        let (memory, store) = env.data_and_store_mut();
+        let memory_view = memory.view(&store);
+
+        let result = virtual_fs::seek(fd as u32, offset, whence as u8).and_then(|position| {
+            memory_view
+                .write(newoffset_ptr as u32 as u64, &position.to_le_bytes())
+                .map_err(|_| ERRNO_FAULT)
+        });
+        into_errno(result)
+    }
+
+    fn fd_read(
+        mut env: FunctionEnvMut<Memory>,
+        fd: i32,
+        iovs: i32,
+        iovs_len: i32,
+        nread_ptr: i32,
+    ) -> i32 {
+        let (memory, store) = env.data_and_store_mut();
+        let memory_view = memory.view(&store);
+
+        let result = read_iovecs(&memory_view, iovs, iovs_len).and_then(|iovecs| {
+            let capacity = iovecs.iter().map(|(_, len)| len).sum();
+            let data = virtual_fs::read(fd as u32, capacity)?;
+
+            // Scatter the data across the buffers in order, filling each before moving onto the next.
+            let mut remaining = data.as_slice();
+            for (buf, len) in iovecs {
+                let (chunk, rest) = remaining.split_at(len.min(remaining.len()));
+                memory_view.write(buf, chunk).map_err(|_| ERRNO_FAULT)?;
+                remaining = rest;
+            }
+            write_u32(&memory_view, nread_ptr, data.len() as u32)
+        });
+        into_errno(result)
+    }
+
+    fn fd_fdstat_get(mut env: FunctionEnvMut<Memory>, fd: i32, stat_ptr: i32) -> i32 {
+        let (memory, store) = env.data_and_store_mut();
+        let memory_view = memory.view(&store);
+
+        let result = virtual_fs::fdstat(fd as u32).and_then(|(filetype, rights)| {
+            // `fdstat` is laid out as the filetype (u8) followed by the flags (u16) at offset 2,
+            // the base rights (u64) at offset 8 and the inheriting rights (u64) at offset 16.
+            let mut fdstat = [0; 24];
+            fdstat[0] = filetype;
+            fdstat[8..16].copy_from_slice(&rights.to_le_bytes());
+            memory_view.write(stat_ptr as u32 as u64, &fdstat).map_err(|_| ERRNO_FAULT)
+        });
+        into_errno(result)
     }
 
-    fn fd_seek(_: i32, _: i64, _: i32, _: i32) -> i32 {
-        unimplemented!("fd_seek is not implemented")
+    fn fd_close(fd: i32) -> i32 {
+        into_errno(virtual_fs::close(fd as u32))
     }
 
-    fn fd_read(_: i32, _: i32, _: i32, _: i32) -> i32 {
-        unimplemented!("fd_read is not implemented")
+    /// Reads the `(buf, buf_len)` pairs of the `iovs_len` iovecs starting at `iovs`.
+    fn read_iovecs(
+        memory_view: &MemoryView,
+        iovs: i32,
+        iovs_len: i32,
+    ) -> Result<Vec<(u64, usize)>, i32> {
+        (0..iovs_len as u32 as u64)
+            .map(|index| {
+                let mut iovec = [0; 8];
+                memory_view
+                    .read(iovs as u32 as u64 + 8 * index, &mut iovec)
+                    .map_err(|_| ERRNO_FAULT)?;
+                let buf = u32::from_le_bytes(iovec[..4].try_into().unwrap());
+                let buf_len = u32::from_le_bytes(iovec[4..].try_into().unwrap());
+                Ok((buf as u64, buf_len as usize))
+            })
+            .collect()
     }
 
-    fn fd_fdstat_get(_: i32, _: i32) -> i32 {
-        unimplemented!("fd_fdstat_get is not implemented")
+    fn write_u32(memory_view: &MemoryView, ptr: i32, value: u32) -> Result<(), i32> {
+        memory_view.write(ptr as u32 as u64, &value.to_le_bytes()).map_err(|_| ERRNO_FAULT)
     }
 
-    fn fd_close(_: i32) -> i32 {
-        unimplemented!("fd_close is not implemented")
+    fn into_errno(result: Result<(), i32>) -> i32 {
+        result.err().unwrap_or(ERRNO_SUCCESS)
     }
 
     fn environ_sizes_get(_: i32, _: i32) -> i32 {
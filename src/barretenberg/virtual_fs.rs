@@ -0,0 +1,150 @@
+//! An in-memory, read-only filesystem backing the WASI file descriptor imports of the Barretenberg wasm.
+//!
+//! The wasm doesn't import `path_open` so files can't be opened by name. Instead each file provided to an
+//! execution is preopened, with the file at index `i` assigned the descriptor `FIRST_FILE_DESCRIPTOR + i`.
+
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+/// The first file descriptor assigned to a virtual file, following stdin, stdout and stderr.
+const FIRST_FILE_DESCRIPTOR: u32 = 3;
+
+const STDIN: u32 = 0;
+const STDOUT: u32 = 1;
+pub(super) const STDERR: u32 = 2;
+
+pub(super) const ERRNO_SUCCESS: i32 = 0;
+const ERRNO_BADF: i32 = 8;
+pub(super) const ERRNO_FAULT: i32 = 21;
+const ERRNO_INVAL: i32 = 28;
+const ERRNO_SPIPE: i32 = 70;
+const ERRNO_NOTCAPABLE: i32 = 76;
+
+const FILETYPE_CHARACTER_DEVICE: u8 = 2;
+const FILETYPE_REGULAR_FILE: u8 = 4;
+
+const RIGHTS_FD_READ: u64 = 1 << 1;
+const RIGHTS_FD_SEEK: u64 = 1 << 2;
+const RIGHTS_FD_TELL: u64 = 1 << 5;
+const RIGHTS_FD_WRITE: u64 = 1 << 6;
+
+/// The read-only files exposed to the Barretenberg wasm during an execution.
+#[derive(Debug, Default, Hash, PartialEq, Eq)]
+pub(crate) struct VirtualFiles(Vec<Rc<[u8]>>);
+
+impl VirtualFiles {
+    pub(crate) fn new(files: Vec<Vec<u8>>) -> VirtualFiles {
+        VirtualFiles(files.into_iter().map(Rc::from).collect())
+    }
+}
+
+#[derive(Debug)]
+struct OpenFile {
+    contents: Rc<[u8]>,
+    position: u64,
+}
+
+thread_local! {
+    /// The virtual files which are currently open, keyed by their file descriptor.
+    static OPEN_FILES: RefCell<BTreeMap<u32, OpenFile>> = RefCell::default();
+}
+
+/// Runs `f`, exposing `files` to the wasm through its file descriptor imports if provided.
+///
+/// Files are reopened on each call so reads don't depend on any previous calls.
+pub(crate) fn with_virtual_files<T>(files: Option<&VirtualFiles>, f: impl FnOnce() -> T) -> T {
+    let Some(files) = files else {
+        return f();
+    };
+
+    let open_files = files
+        .0
+        .iter()
+        .enumerate()
+        .map(|(index, contents)| {
+            let file = OpenFile { contents: contents.clone(), position: 0 };
+            (FIRST_FILE_DESCRIPTOR + index as u32, file)
+        })
+        .collect();
+    let previous = OPEN_FILES.with(|open| open.replace(open_files));
+    let result = f();
+    OPEN_FILES.with(|open| open.replace(previous));
+    result
+}
+
+/// Reads up to `max_len` bytes from `fd`, advancing its position past them.
+///
+/// stdin is always empty.
+pub(super) fn read(fd: u32, max_len: usize) -> Result<Vec<u8>, i32> {
+    if fd == STDIN {
+        return Ok(Vec::new());
+    }
+
+    OPEN_FILES.with(|open| {
+        let mut open = open.borrow_mut();
+        let file = open.get_mut(&fd).ok_or(ERRNO_BADF)?;
+        let start = usize::try_from(file.position)
+            .map_or(file.contents.len(), |position| file.contents.len().min(position));
+        let end = file.contents.len().min(start.saturating_add(max_len));
+        file.position = end as u64;
+        Ok(file.contents[start..end].to_vec())
+    })
+}
+
+/// Checks that `fd` may be written to. Only stdout and stderr are writable, the virtual files being read-only.
+pub(super) fn check_writable(fd: u32) -> Result<(), i32> {
+    match fd {
+        STDOUT | STDERR => Ok(()),
+        _ if is_open_file(fd) => Err(ERRNO_NOTCAPABLE),
+        _ => Err(ERRNO_BADF),
+    }
+}
+
+/// Moves the position of `fd` by `offset` relative to `whence`, returning the new position.
+pub(super) fn seek(fd: u32, offset: i64, whence: u8) -> Result<u64, i32> {
+    if matches!(fd, STDIN | STDOUT | STDERR) {
+        return Err(ERRNO_SPIPE);
+    }
+
+    OPEN_FILES.with(|open| {
+        let mut open = open.borrow_mut();
+        let file = open.get_mut(&fd).ok_or(ERRNO_BADF)?;
+        let base = match whence {
+            0 => 0,
+            1 => file.position,
+            2 => file.contents.len() as u64,
+            _ => return Err(ERRNO_INVAL),
+        };
+        let position = i64::try_from(base)
+            .ok()
+            .and_then(|base| base.checked_add(offset))
+            .and_then(|position| u64::try_from(position).ok())
+            .ok_or(ERRNO_INVAL)?;
+        file.position = position;
+        Ok(position)
+    })
+}
+
+/// Returns the file type of `fd` along with the rights it was opened with.
+pub(super) fn fdstat(fd: u32) -> Result<(u8, u64), i32> {
+    match fd {
+        STDIN => Ok((FILETYPE_CHARACTER_DEVICE, RIGHTS_FD_READ)),
+        STDOUT | STDERR => Ok((FILETYPE_CHARACTER_DEVICE, RIGHTS_FD_WRITE)),
+        _ if is_open_file(fd) => {
+            Ok((FILETYPE_REGULAR_FILE, RIGHTS_FD_READ | RIGHTS_FD_SEEK | RIGHTS_FD_TELL))
+        }
+        _ => Err(ERRNO_BADF),
+    }
+}
+
+/// Closes `fd`. Closing stdin, stdout or stderr has no effect.
+pub(super) fn close(fd: u32) -> Result<(), i32> {
+    if matches!(fd, STDIN | STDOUT | STDERR) {
+        return Ok(());
+    }
+
+    OPEN_FILES.with(|open| open.borrow_mut().remove(&fd).map(|_| ()).ok_or(ERRNO_BADF))
+}
+
+fn is_open_file(fd: u32) -> bool {
+    OPEN_FILES.with(|open| open.borrow().contains_key(&fd))
+}
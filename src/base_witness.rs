@@ -0,0 +1,66 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use acvm::acir::native_types::{Witness, WitnessMap};
+use js_sys::JsString;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::js_witness_map::JsWitnessMapLike;
+
+/// A read-only witness which is shared between many executions, e.g. constants common to every transaction.
+///
+/// The witness is converted from JS once when the `BaseWitness` is created. Executions which use it only
+/// need to provide the witnesses which differ between them and only receive those witnesses back.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct BaseWitness {
+    witness: Rc<WitnessMap>,
+}
+
+#[wasm_bindgen]
+impl BaseWitness {
+    /// Creates a new `BaseWitness` holding the witnesses shared between executions.
+    ///
+    /// @param {WitnessMapLike} witness - The witnesses which are common to every execution using this base.
+    #[wasm_bindgen(constructor, skip_jsdoc)]
+    pub fn new(witness: JsWitnessMapLike) -> Result<BaseWitness, JsString> {
+        console_error_panic_hook::set_once();
+        let witness = WitnessMap::try_from(witness)?;
+        Ok(BaseWitness { witness: Rc::new(witness) })
+    }
+}
+
+impl BaseWitness {
+    /// Returns the initial witness for an execution, made of this base with `overlay` applied on top of it, along
+    /// with the witnesses which `overlay` assigned.
+    ///
+    /// Values in `overlay` take precedence over those in the base. The base itself is left unchanged. It's copied as
+    /// the ACVM takes ownership of the initial witness, while `overlay` is moved into the copy.
+    pub(crate) fn with_overlay(&self, overlay: WitnessMap) -> (WitnessMap, BTreeSet<Witness>) {
+        let mut witness = WitnessMap::clone(&self.witness);
+        let mut overlaid = BTreeSet::new();
+        for (witness_index, value) in overlay {
+            overlaid.insert(witness_index);
+            witness.insert(witness_index, value);
+        }
+        (witness, overlaid)
+    }
+
+    /// Removes the witnesses taken from this base from `solved_witness`, leaving those solved by the execution
+    /// along with any in `overlaid`.
+    pub(crate) fn strip(
+        &self,
+        solved_witness: WitnessMap,
+        overlaid: &BTreeSet<Witness>,
+    ) -> WitnessMap {
+        solved_witness
+            .into_iter()
+            .filter(|(witness, _)| {
+                !self.witness.contains_key(witness) || overlaid.contains(witness)
+            })
+            .collect::<BTreeMap<_, _>>()
+            .into()
+    }
+}
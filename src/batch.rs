@@ -0,0 +1,200 @@
+use std::collections::BTreeSet;
+
+use acvm::acir::{
+    circuit::Circuit,
+    native_types::{Witness, WitnessMap},
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::Array;
+use serde::Serialize;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+use crate::{
+    execute::{execute_circuit_with_backend, read_circuit, ExecutionOptions, SimulatedBackend},
+    foreign_call::ForeignCallHandler,
+    js_witness_map::{field_element_to_js_string, JsWitnessMapLike},
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const BATCH_EXECUTION_RESULT: &'static str = r#"
+// The outcome of executing a circuit on a single set of inputs within a batch.
+// Failed executions are reported as an `Error` rather than rejecting the whole batch.
+export type BatchExecutionResult = WitnessMap | Error;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Array, typescript_type = "WitnessMapLike[]")]
+    pub type JsWitnessMapArray;
+
+    #[wasm_bindgen(extends = Array, typescript_type = "BatchExecutionResult[]")]
+    pub type JsBatchExecutionResults;
+}
+
+/// Executes an ACIR circuit on several sets of inputs, generating a solved witness for each.
+///
+/// The circuit is deserialized and the backend is initialized once for the whole batch.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike[]} initial_witnesses - The initial witness maps defining each set of inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @returns {BatchExecutionResult[]} The solved witness for each set of inputs, or an `Error` if execution failed.
+#[wasm_bindgen(js_name = executeCircuitBatch, skip_jsdoc)]
+pub async fn execute_circuit_batch(
+    circuit: Vec<u8>,
+    initial_witnesses: JsWitnessMapArray,
+    foreign_call_handler: ForeignCallHandler,
+) -> Result<JsBatchExecutionResults, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+
+    let mut backend = SimulatedBackend::initialize().await;
+
+    let results = Array::new();
+    for initial_witness in initial_witnesses.iter() {
+        // A trap while solving a previous input set must not cause the remaining ones to fail.
+        backend.ensure_healthy().await;
+
+        let initial_witness: JsWitnessMapLike = initial_witness.unchecked_into();
+        let initial_witness = match WitnessMap::try_from(initial_witness) {
+            Ok(initial_witness) => initial_witness,
+            Err(message) => {
+                results.push(&js_sys::Error::new(&String::from(message)));
+                continue;
+            }
+        };
+
+        let result = execute_circuit_with_backend(
+            &backend,
+            circuit.opcodes.clone(),
+            initial_witness,
+            &foreign_call_handler,
+            &ExecutionOptions::default(),
+        )
+        .await;
+
+        match result {
+            Ok(witness_map) => results.push(&JsWitnessMap::from(witness_map)),
+            Err(err) => results.push(&js_sys::Error::new(&err.message)),
+        };
+    }
+
+    Ok(results.unchecked_into())
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EXECUTION_COMPARISON: &'static str = r#"
+/**
+* @typedef {Object} ExecutionComparison - The public outputs of a circuit across several sets of inputs.
+* @property {number[]} witnesses - The witness indices of the circuit's public parameters and return values, in ascending order.
+* @property {ComparedExecution[]} executions - The outcome of executing the circuit on each set of inputs, in order.
+* @property {boolean[]} differs - Whether each of `witnesses` took more than one value across the successful executions.
+*/
+export type ExecutionComparison = {
+  witnesses: number[];
+  executions: ComparedExecution[];
+  differs: boolean[];
+}
+
+// The values taken by each of the compared witnesses on a single set of inputs, or the error if execution failed.
+export type ComparedExecution = { values: string[] } | { error: string };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ExecutionComparison")]
+    pub type JsExecutionComparison;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum ComparedExecution {
+    Values(Vec<String>),
+    Error(String),
+}
+
+#[derive(Serialize)]
+struct ExecutionComparison {
+    witnesses: Vec<u32>,
+    executions: Vec<ComparedExecution>,
+    differs: Vec<bool>,
+}
+
+/// Executes an ACIR circuit on several sets of inputs and compares the resulting public outputs.
+///
+/// This shows which of the circuit's public parameters and return values are sensitive to the changes between the
+/// input sets. Executions which fail are reported alongside the others and are left out of the comparison.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike[]} initial_witnesses - The initial witness maps defining each set of inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @returns {ExecutionComparison} The public outputs for each set of inputs and which of them differ.
+#[wasm_bindgen(js_name = compareExecutions, skip_jsdoc)]
+pub async fn compare_executions(
+    circuit: Vec<u8>,
+    initial_witnesses: JsWitnessMapArray,
+    foreign_call_handler: ForeignCallHandler,
+) -> Result<JsExecutionComparison, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let witnesses: Vec<Witness> = circuit.public_inputs().0.into_iter().collect();
+
+    let mut backend = SimulatedBackend::initialize().await;
+
+    let mut executions = Vec::new();
+    for initial_witness in initial_witnesses.iter() {
+        backend.ensure_healthy().await;
+
+        let initial_witness: JsWitnessMapLike = initial_witness.unchecked_into();
+        let initial_witness = match WitnessMap::try_from(initial_witness) {
+            Ok(initial_witness) => initial_witness,
+            Err(message) => {
+                executions.push(ComparedExecution::Error(message.into()));
+                continue;
+            }
+        };
+
+        let result = execute_circuit_with_backend(
+            &backend,
+            circuit.opcodes.clone(),
+            initial_witness,
+            &foreign_call_handler,
+            &ExecutionOptions::default(),
+        )
+        .await;
+
+        executions.push(match result {
+            // A public input which the solver never assigned is reported rather than panicking.
+            Ok(witness_map) => witnesses
+                .iter()
+                .map(|witness| match witness_map.get(witness) {
+                    Some(value) => Ok(field_element_to_js_string(value).into()),
+                    None => Err(format!("Public input {} was not solved", witness.witness_index())),
+                })
+                .collect::<Result<Vec<String>, String>>()
+                .map_or_else(ComparedExecution::Error, ComparedExecution::Values),
+            Err(err) => ComparedExecution::Error(err.message),
+        });
+    }
+
+    let differs = (0..witnesses.len())
+        .map(|index| {
+            let values: BTreeSet<&String> = executions
+                .iter()
+                .filter_map(|execution| match execution {
+                    ComparedExecution::Values(values) => Some(&values[index]),
+                    ComparedExecution::Error(_) => None,
+                })
+                .collect();
+            values.len() > 1
+        })
+        .collect();
+
+    let comparison = ExecutionComparison {
+        witnesses: witnesses.iter().map(Witness::witness_index).collect(),
+        executions,
+        differs,
+    };
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&comparison).unwrap().into())
+}
@@ -24,14 +24,14 @@ extern "C" {
 }
 
 #[derive(Serialize, Deserialize)]
-struct BuildInfo {
+pub(crate) struct BuildInfo {
     #[serde(rename = "gitHash")]
     git_hash: &'static str,
     version: &'static str,
     dirty: bool,
 }
 
-const BUILD_INFO: BuildInfo = BuildInfo {
+pub(crate) const BUILD_INFO: BuildInfo = BuildInfo {
     git_hash: env!("GIT_COMMIT"),
     version: env!("CARGO_PKG_VERSION"),
     dirty: const_str::equal!(env!("GIT_DIRTY"), "true"),
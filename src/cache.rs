@@ -0,0 +1,132 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+use acvm::{
+    acir::native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+
+use crate::{
+    barretenberg::VirtualFiles, compact_witness::CompactWitnessMap, execute::ExecutionOptions,
+};
+
+/// The serialized bytes of a circuit along with their hash, so that the bytes aren't rehashed on each lookup.
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBytes {
+    hash: u64,
+    bytes: Rc<[u8]>,
+}
+
+impl CircuitBytes {
+    pub(crate) fn new(bytes: &[u8]) -> CircuitBytes {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        CircuitBytes { hash: hasher.finish(), bytes: Rc::from(bytes) }
+    }
+}
+
+impl PartialEq for CircuitBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.bytes == other.bytes
+    }
+}
+
+impl Eq for CircuitBytes {}
+
+impl Hash for CircuitBytes {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+/// Everything which determines the witness solved by an execution.
+///
+/// The whole key is held by the cache and compared on lookup so that a hash collision can't return the result of
+/// a different execution.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ExecutionKey {
+    circuit: CircuitBytes,
+    initial_witness: Vec<(Witness, FieldElement)>,
+    skip_signature_verification: bool,
+    skipped_signature_result: bool,
+    pedersen_domain_separator: Option<u32>,
+    virtual_files: Option<Rc<VirtualFiles>>,
+}
+
+/// A bounded cache of solved witnesses, keyed by the circuit and initial witness they were solved from.
+///
+/// Once full, the least recently inserted entry is evicted to make room for a new one. Witnesses are held in their
+/// compact form as the cache may hold onto many large witnesses for the lifetime of its context.
+#[derive(Debug)]
+pub(crate) struct ExecutionCache {
+    max_entries: usize,
+    entries: HashMap<Rc<ExecutionKey>, CompactWitnessMap>,
+    /// The keys of `entries` in the order in which they were inserted.
+    insertion_order: VecDeque<Rc<ExecutionKey>>,
+}
+
+impl ExecutionCache {
+    pub(crate) fn new(max_entries: usize) -> ExecutionCache {
+        ExecutionCache { max_entries, entries: HashMap::new(), insertion_order: VecDeque::new() }
+    }
+
+    /// Returns the key under which the result of executing `circuit` on `initial_witness` is stored.
+    ///
+    /// Returns `None` if the result can't be cached, as `options` provide blackbox implementations from JS or
+    /// observe or limit the execution in a way which returning a previous result would skip.
+    pub(crate) fn key(
+        circuit: &CircuitBytes,
+        initial_witness: &WitnessMap,
+        options: &ExecutionOptions,
+    ) -> Option<ExecutionKey> {
+        let observed = options.on_opcode_solved.is_some()
+            || options.on_foreign_calls_changed.is_some()
+            || options.on_breakpoint.is_some()
+            || options.on_brillig_step.is_some()
+            || options.on_watchpoint.is_some()
+            || options.on_brillig_pause.is_some()
+            || options.on_error_report.is_some()
+            || options.current_location.is_some()
+            || options.snapshot_opcodes.is_some()
+            || options.tracing.is_some()
+            || options.foreign_call_recorder.is_some();
+        let limited = options.max_brillig_steps.is_some()
+            || options.invariants.is_some()
+            || options.check_foreign_call_bit_sizes;
+        let replayed = options.foreign_call_transcript.is_some() || options.deterministic;
+        if options.blackbox_solver.is_some() || observed || limited || replayed {
+            return None;
+        }
+
+        Some(ExecutionKey {
+            circuit: circuit.clone(),
+            initial_witness: initial_witness.clone().into_iter().collect(),
+            // These options change the witness which is solved.
+            skip_signature_verification: options.skip_signature_verification,
+            skipped_signature_result: options.skipped_signature_result(),
+            pedersen_domain_separator: options.pedersen_domain_separator,
+            virtual_files: options.virtual_files.clone(),
+        })
+    }
+
+    pub(crate) fn get(&self, key: &ExecutionKey) -> Option<WitnessMap> {
+        self.entries.get(key).map(WitnessMap::from)
+    }
+
+    pub(crate) fn insert(&mut self, key: ExecutionKey, solved_witness: &WitnessMap) {
+        if self.max_entries == 0 || self.entries.contains_key(&key) {
+            return;
+        }
+
+        let key = Rc::new(key);
+        self.entries.insert(key.clone(), CompactWitnessMap::from(solved_witness));
+        self.insertion_order.push_back(key);
+        if self.insertion_order.len() > self.max_entries {
+            let evicted = self.insertion_order.pop_front().expect("cache should not be empty");
+            self.entries.remove(&evicted);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::{cell::RefCell, rc::Rc};
+
+use acvm::acir::{
+    circuit::Circuit,
+    native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+use js_sys::{Function, JsString, Object, Promise, Reflect, Symbol, Uint32Array, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{
+    execute::{
+        execute_circuit_with_backend, read_circuit, ExecutionOptions, JsExecutionOptions,
+        SimulatedBackend,
+    },
+    foreign_call::ForeignCallHandler,
+    js_witness_map::JsWitnessMapLike,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const WITNESS_CHUNK: &'static str = r#"
+/**
+* @typedef {Object} WitnessChunk - A contiguous range of a solved witness, packed into typed arrays.
+* @property {number} start - The lowest witness index in the chunk.
+* @property {number} end - The highest witness index in the chunk.
+* @property {Uint32Array} indices - The index of each witness in the chunk, in ascending order.
+* @property {Uint8Array} values - The value of each witness in the chunk as a 32 byte big-endian field element.
+*/
+export type WitnessChunk = {
+  start: number;
+  end: number;
+  indices: Uint32Array;
+  values: Uint8Array;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "AsyncIterableIterator<WitnessChunk>")]
+    pub type JsWitnessChunks;
+}
+
+/// The size in bytes of a packed field element.
+const FIELD_ELEMENT_BYTES: usize = 32;
+
+/// Executes an ACIR circuit to generate the solved witness from the initial witness, returning it in chunks.
+///
+/// Building a `WitnessMap` for a very large witness can block the main thread for several seconds.
+/// The chunks returned here are only converted into JS values as they are requested.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {number} chunk_size - The maximum number of witnesses to include in each chunk.
+/// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+/// @returns {Promise<AsyncIterableIterator<WitnessChunk>>} The solved witness, in ascending order of witness index.
+#[wasm_bindgen(js_name = executeCircuitChunked, skip_jsdoc)]
+pub async fn execute_circuit_chunked(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    chunk_size: u32,
+    options: Option<JsExecutionOptions>,
+) -> Result<JsWitnessChunks, JsValue> {
+    console_error_panic_hook::set_once();
+    if chunk_size == 0 {
+        return Err(JsString::from("Chunk size must be greater than zero").into());
+    }
+    let options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+
+    let backend = SimulatedBackend::initialize().await;
+    let witness_map = execute_circuit_with_backend(
+        &backend,
+        circuit.opcodes,
+        initial_witness,
+        &foreign_call_handler,
+        &options,
+    )
+    .await
+    .map_err(|err| err.into_js_value(&options))?;
+
+    Ok(witness_chunks(witness_map, chunk_size as usize))
+}
+
+/// Wraps `witness_map` in a JS async iterator which yields it `chunk_size` witnesses at a time.
+fn witness_chunks(witness_map: WitnessMap, chunk_size: usize) -> JsWitnessChunks {
+    let witnesses = Rc::new(RefCell::new(witness_map.into_iter()));
+    let next = Closure::<dyn FnMut() -> Promise>::new(move || {
+        let chunk: Vec<_> = witnesses.borrow_mut().by_ref().take(chunk_size).collect();
+
+        let result = Object::new();
+        if chunk.is_empty() {
+            set(&result, "done", &JsValue::TRUE);
+            set(&result, "value", &JsValue::UNDEFINED);
+        } else {
+            set(&result, "done", &JsValue::FALSE);
+            set(&result, "value", &pack_chunk(&chunk));
+        }
+        Promise::resolve(&result)
+    });
+
+    let iterator = Object::new();
+    set(&iterator, "next", &next.into_js_value());
+    Reflect::set(&iterator, &Symbol::async_iterator(), &Function::new_no_args("return this"))
+        .expect("setting a property on an `Object` should not fail");
+    iterator.unchecked_into()
+}
+
+fn pack_chunk(chunk: &[(Witness, FieldElement)]) -> Object {
+    let indices: Vec<u32> = chunk.iter().map(|(witness, _)| witness.witness_index()).collect();
+    let mut values = Vec::with_capacity(chunk.len() * FIELD_ELEMENT_BYTES);
+    for (_, value) in chunk {
+        values.extend(value.to_be_bytes());
+    }
+
+    let js_chunk = Object::new();
+    set(&js_chunk, "start", &JsValue::from(indices[0]));
+    set(&js_chunk, "end", &JsValue::from(indices[indices.len() - 1]));
+    set(&js_chunk, "indices", &Uint32Array::from(indices.as_slice()));
+    set(&js_chunk, "values", &Uint8Array::from(values.as_slice()));
+    js_chunk
+}
+
+fn set(object: &Object, key: &str, value: &JsValue) {
+    Reflect::set(object, &JsValue::from_str(key), value)
+        .expect("setting a property on an `Object` should not fail");
+}
@@ -0,0 +1,246 @@
+use acvm::FieldElement;
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::JsString;
+use serde::Deserialize;
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::js_witness_map::{field_element_to_js_string, js_value_to_field_element};
+
+#[wasm_bindgen(typescript_custom_section)]
+const BYTE_ENCODING_OPTIONS: &'static str = r#"
+export type Endianness = "big" | "little";
+
+/**
+* @typedef {Object} ByteEncodingOptions - Options controlling how values are converted to and from bytes.
+* @property {Endianness} endianness - The byte order of each encoded field element. Defaults to `"big"`.
+*/
+export type ByteEncodingOptions = {
+  endianness?: Endianness;
+}
+
+/**
+* @typedef {Object} BytePackingOptions - Options controlling how bytes are packed into field elements.
+* @property {Endianness} endianness - The byte order of each packed chunk. Defaults to `"big"`.
+* @property {number} bytesPerField - The number of bytes packed into each field element, at most 31. Defaults to 31.
+*/
+export type BytePackingOptions = {
+  endianness?: Endianness;
+  bytesPerField?: number;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ByteEncodingOptions")]
+    pub type JsByteEncodingOptions;
+
+    #[wasm_bindgen(typescript_type = "BytePackingOptions")]
+    pub type JsBytePackingOptions;
+
+    #[wasm_bindgen(extends = js_sys::Array, typescript_type = "string[]")]
+    pub type JsPackedFields;
+}
+
+/// The number of bytes in an encoded field element.
+const FIELD_ELEMENT_BYTES: usize = 32;
+
+/// The largest number of bytes which can be packed into a field element without risk of exceeding the modulus.
+const MAX_BYTES_PER_FIELD: usize = FIELD_ELEMENT_BYTES - 1;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Endianness {
+    #[default]
+    Big,
+    Little,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct ByteEncodingOptions {
+    pub(crate) endianness: Endianness,
+}
+
+impl TryFrom<Option<JsByteEncodingOptions>> for ByteEncodingOptions {
+    type Error = String;
+
+    fn try_from(js_options: Option<JsByteEncodingOptions>) -> Result<Self, Self::Error> {
+        let Some(js_options) = js_options else {
+            return Ok(ByteEncodingOptions::default());
+        };
+
+        <JsValue as JsValueSerdeExt>::into_serde(&js_options)
+            .map_err(|err| format!("Invalid byte encoding options: {err}"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct BytePackingOptions {
+    endianness: Endianness,
+    bytes_per_field: usize,
+}
+
+impl Default for BytePackingOptions {
+    fn default() -> Self {
+        BytePackingOptions {
+            endianness: Endianness::default(),
+            bytes_per_field: MAX_BYTES_PER_FIELD,
+        }
+    }
+}
+
+impl TryFrom<Option<JsBytePackingOptions>> for BytePackingOptions {
+    type Error = String;
+
+    fn try_from(js_options: Option<JsBytePackingOptions>) -> Result<Self, Self::Error> {
+        let Some(js_options) = js_options else {
+            return Ok(BytePackingOptions::default());
+        };
+
+        let options: BytePackingOptions = <JsValue as JsValueSerdeExt>::into_serde(&js_options)
+            .map_err(|err| format!("Invalid byte packing options: {err}"))?;
+        if !(1..=MAX_BYTES_PER_FIELD).contains(&options.bytes_per_field) {
+            return Err(format!(
+                "Invalid byte packing options: bytesPerField must be between 1 and {MAX_BYTES_PER_FIELD}"
+            ));
+        }
+        Ok(options)
+    }
+}
+
+impl Endianness {
+    /// Converts `bytes` between big-endian order and this byte order. The conversion is its own inverse.
+    pub(crate) fn convert_big_endian(self, mut bytes: Vec<u8>) -> Vec<u8> {
+        if self == Endianness::Little {
+            bytes.reverse();
+        }
+        bytes
+    }
+}
+
+/// Encodes `field_element` as exactly [`FIELD_ELEMENT_BYTES`] bytes in the requested byte order.
+fn encode_field_element(field_element: FieldElement, endianness: Endianness) -> Vec<u8> {
+    endianness.convert_big_endian(field_element.to_be_bytes())
+}
+
+/// Decodes a field element from exactly [`FIELD_ELEMENT_BYTES`] bytes in the requested byte order.
+///
+/// Values which are not less than the field modulus are rejected rather than being reduced.
+fn decode_field_element(bytes: &[u8], endianness: Endianness) -> Result<FieldElement, String> {
+    if bytes.len() != FIELD_ELEMENT_BYTES {
+        return Err(format!(
+            "Expected {FIELD_ELEMENT_BYTES} bytes for a field element but received {}",
+            bytes.len()
+        ));
+    }
+
+    let be_bytes = endianness.convert_big_endian(bytes.to_vec());
+    let field_element = FieldElement::from_be_bytes_reduce(&be_bytes);
+    if encode_field_element(field_element, Endianness::Big) != be_bytes {
+        return Err("Field element must be less than the field modulus".to_string());
+    }
+    Ok(field_element)
+}
+
+/// Encodes a field element as 32 bytes.
+///
+/// @param {string} value - A hex encoded field element.
+/// @param {ByteEncodingOptions} options - Optional settings controlling the encoding. Values are big-endian by default.
+/// @returns {Uint8Array} The 32 byte encoding of `value`.
+#[wasm_bindgen(js_name = fieldToBytes, skip_jsdoc)]
+pub fn field_to_bytes(
+    value: String,
+    options: Option<JsByteEncodingOptions>,
+) -> Result<Vec<u8>, JsString> {
+    console_error_panic_hook::set_once();
+    let options = ByteEncodingOptions::try_from(options)?;
+    let field_element = js_value_to_field_element(value.into())?;
+
+    Ok(encode_field_element(field_element, options.endianness))
+}
+
+/// Decodes a field element from 32 bytes.
+///
+/// @param {Uint8Array} bytes - The 32 byte encoding of a field element.
+/// @param {ByteEncodingOptions} options - Optional settings controlling the encoding. Values are big-endian by default.
+/// @returns {string} The hex encoded field element.
+#[wasm_bindgen(js_name = fieldFromBytes, skip_jsdoc)]
+pub fn field_from_bytes(
+    bytes: Vec<u8>,
+    options: Option<JsByteEncodingOptions>,
+) -> Result<JsString, JsString> {
+    console_error_panic_hook::set_once();
+    let options = ByteEncodingOptions::try_from(options)?;
+    let field_element = decode_field_element(&bytes, options.endianness)?;
+
+    Ok(field_element_to_js_string(&field_element))
+}
+
+/// Packs bytes into field elements, `bytesPerField` bytes at a time.
+///
+/// Each chunk of bytes is interpreted as an integer in the requested byte order. The final chunk holds any
+/// remaining bytes so may be shorter than the others.
+///
+/// @param {Uint8Array} bytes - The bytes to pack.
+/// @param {BytePackingOptions} options - Optional settings controlling the packing. Defaults to 31 big-endian bytes per field.
+/// @returns {string[]} The hex encoded field elements holding `bytes`.
+#[wasm_bindgen(js_name = bytesToFields, skip_jsdoc)]
+pub fn bytes_to_fields(
+    bytes: Vec<u8>,
+    options: Option<JsBytePackingOptions>,
+) -> Result<JsPackedFields, JsString> {
+    console_error_panic_hook::set_once();
+    let options = BytePackingOptions::try_from(options)?;
+
+    let fields: js_sys::Array = bytes
+        .chunks(options.bytes_per_field)
+        .map(|chunk| {
+            let chunk = options.endianness.convert_big_endian(chunk.to_vec());
+            field_element_to_js_string(&FieldElement::from_be_bytes_reduce(&chunk))
+        })
+        .collect();
+    Ok(fields.unchecked_into())
+}
+
+/// Unpacks bytes from field elements produced by `bytesToFields`.
+///
+/// An error is thrown if any field element does not fit within its chunk, rather than truncating it.
+///
+/// @param {string[]} fields - The hex encoded field elements holding the packed bytes.
+/// @param {number} length - The total number of packed bytes. This determines the size of the final chunk.
+/// @param {BytePackingOptions} options - Optional settings controlling the packing. Defaults to 31 big-endian bytes per field.
+/// @returns {Uint8Array} The unpacked bytes.
+#[wasm_bindgen(js_name = fieldsToBytes, skip_jsdoc)]
+pub fn fields_to_bytes(
+    fields: JsPackedFields,
+    length: usize,
+    options: Option<JsBytePackingOptions>,
+) -> Result<Vec<u8>, JsString> {
+    console_error_panic_hook::set_once();
+    let options = BytePackingOptions::try_from(options)?;
+
+    let expected_fields = (length + options.bytes_per_field - 1) / options.bytes_per_field;
+    if fields.length() as usize != expected_fields {
+        return Err(format!(
+            "Expected {expected_fields} field elements to hold {length} bytes but received {}",
+            fields.length()
+        )
+        .into());
+    }
+
+    let mut bytes = Vec::with_capacity(length);
+    for (index, field) in fields.iter().enumerate() {
+        let chunk_length = options.bytes_per_field.min(length - bytes.len());
+        let field_element = js_value_to_field_element(field)?;
+        let be_bytes = field_element.to_be_bytes();
+        let (overflow, chunk) = be_bytes.split_at(be_bytes.len() - chunk_length);
+        if overflow.iter().any(|&byte| byte != 0) {
+            return Err(
+                format!("Field element {index} does not fit within {chunk_length} bytes").into()
+            );
+        }
+        bytes.extend(options.endianness.convert_big_endian(chunk.to_vec()));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,143 @@
+use acvm::{
+    acir::native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+use js_sys::JsString;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    js_witness_map::{field_element_to_js_string, JsWitnessMapLike},
+    JsWitnessMap,
+};
+
+/// Marks a value in [`CompactWitnessMap::values`] which doesn't fit in a `u64` and is held in full instead.
+const LARGE_VALUE: u64 = u64::MAX;
+
+/// A witness map which stores values that fit in a `u64` (most witnesses, e.g. booleans, bytes and indices)
+/// without their full field representation.
+///
+/// A `WitnessMap` spends a 32 byte field element plus the overhead of a `BTreeMap` node on each witness, whereas
+/// small values here take 12 bytes. Values are only promoted back to field elements when they're read.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CompactWitnessMap {
+    /// The index of each witness in ascending order.
+    witnesses: Vec<u32>,
+    /// The value of the witness at the same position in `witnesses`, or [`LARGE_VALUE`] if it's in `large_values`.
+    values: Vec<u64>,
+    /// The values which don't fit in a `u64`, keyed by their position in `witnesses`.
+    large_values: Vec<(usize, FieldElement)>,
+}
+
+impl CompactWitnessMap {
+    pub(crate) fn len(&self) -> usize {
+        self.witnesses.len()
+    }
+
+    pub(crate) fn get(&self, witness: Witness) -> Option<FieldElement> {
+        let position = self.witnesses.binary_search(&witness.witness_index()).ok()?;
+        Some(self.value_at(position))
+    }
+
+    /// Returns the value of the witness at `position` in `witnesses`, promoting it to a field element.
+    fn value_at(&self, position: usize) -> FieldElement {
+        match self.values[position] {
+            LARGE_VALUE => {
+                let index = self
+                    .large_values
+                    .binary_search_by_key(&position, |(position, _)| *position)
+                    .expect("large values should be stored in full");
+                self.large_values[index].1
+            }
+            value => FieldElement::from(value as u128),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Witness, FieldElement)> + '_ {
+        self.witnesses
+            .iter()
+            .enumerate()
+            .map(|(position, &witness)| (Witness(witness), self.value_at(position)))
+    }
+
+    /// Returns the number of bytes used to hold the witnesses, excluding any spare capacity.
+    pub(crate) fn byte_length(&self) -> usize {
+        self.witnesses.len() * std::mem::size_of::<u32>()
+            + self.values.len() * std::mem::size_of::<u64>()
+            + self.large_values.len() * std::mem::size_of::<(usize, FieldElement)>()
+    }
+}
+
+impl From<&WitnessMap> for CompactWitnessMap {
+    fn from(witness_map: &WitnessMap) -> Self {
+        let mut compact = CompactWitnessMap::default();
+        // `WitnessMap` iterates in ascending order of witness index so `witnesses` ends up sorted.
+        for (position, (witness, value)) in witness_map.clone().into_iter().enumerate() {
+            compact.witnesses.push(witness.witness_index());
+            if value.num_bits() <= 64 && value.to_u128() < LARGE_VALUE as u128 {
+                compact.values.push(value.to_u128() as u64);
+            } else {
+                compact.values.push(LARGE_VALUE);
+                compact.large_values.push((position, value));
+            }
+        }
+        compact
+    }
+}
+
+impl From<&CompactWitnessMap> for WitnessMap {
+    fn from(compact: &CompactWitnessMap) -> Self {
+        compact.iter().collect::<std::collections::BTreeMap<_, _>>().into()
+    }
+}
+
+/// A witness map held in memory in a compact form, for keeping large solved witnesses around without the cost of
+/// a JS `Map` of hex strings.
+///
+/// Values which fit in 64 bits are stored as integers rather than full field elements and are only converted back
+/// into hex strings when they're read.
+#[wasm_bindgen]
+pub struct CompactWitness {
+    witness: CompactWitnessMap,
+}
+
+#[wasm_bindgen]
+impl CompactWitness {
+    /// Creates a new `CompactWitness` holding the values of `witness`.
+    ///
+    /// @param {WitnessMapLike} witness - The witness map to store.
+    #[wasm_bindgen(constructor, skip_jsdoc)]
+    pub fn new(witness: JsWitnessMapLike) -> Result<CompactWitness, JsString> {
+        console_error_panic_hook::set_once();
+        let witness = WitnessMap::try_from(witness)?;
+        Ok(CompactWitness { witness: CompactWitnessMap::from(&witness) })
+    }
+
+    /// Returns the value of a witness.
+    ///
+    /// @param {number} witness_index - The index of the witness to read.
+    /// @returns {string | undefined} The hex encoded value of the witness, or `undefined` if it has no value.
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn get(&self, witness_index: u32) -> Option<JsString> {
+        self.witness.get(Witness(witness_index)).map(|value| field_element_to_js_string(&value))
+    }
+
+    /// The number of witnesses held.
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.witness.len()
+    }
+
+    /// The approximate number of bytes of memory used to hold the witnesses.
+    #[wasm_bindgen(getter, js_name = byteLength)]
+    pub fn byte_length(&self) -> usize {
+        self.witness.byte_length()
+    }
+
+    /// Expands the witnesses back into a `WitnessMap`.
+    ///
+    /// @returns {WitnessMap} A witness map holding every witness.
+    #[wasm_bindgen(js_name = toWitnessMap, skip_jsdoc)]
+    pub fn to_witness_map(&self) -> JsWitnessMap {
+        WitnessMap::from(&self.witness).into()
+    }
+}
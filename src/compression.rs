@@ -1,8 +1,44 @@
+use std::io::Read;
+
 use acvm::acir::native_types::WitnessMap;
-use js_sys::JsString;
-use wasm_bindgen::prelude::wasm_bindgen;
+use flate2::{bufread::GzDecoder, bufread::GzEncoder, Compression};
+use js_sys::{Array, JsString, Object, Reflect};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+use crate::{js_witness_map::JsWitnessMapLike, JsWitnessMap};
+
+#[wasm_bindgen(typescript_custom_section)]
+const WITNESS_STACK: &'static str = r#"
+// The solved witness of each function called while executing a multi-function program, in the order they were solved.
+// `index` identifies the function within the program.
+export type WitnessStack = { index: number; witness: WitnessMap }[];
 
-use crate::JsWitnessMap;
+// A witness stack whose witnesses may be given as anything which can be converted into a `WitnessMap`.
+export type WitnessStackLike = { index: number; witness: WitnessMapLike }[];
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Array, typescript_type = "WitnessStack")]
+    pub type JsWitnessStack;
+
+    #[wasm_bindgen(extends = Array, typescript_type = "WitnessStackLike")]
+    pub type JsWitnessStackLike;
+}
+
+/// A single function's witness within a [`WitnessStack`].
+#[derive(Debug, Serialize, Deserialize)]
+struct StackItem {
+    index: u32,
+    witness: WitnessMap,
+}
+
+/// The witnesses of a multi-function program, laid out as Nargo serializes them.
+#[derive(Debug, Serialize, Deserialize)]
+struct WitnessStack {
+    stack: Vec<StackItem>,
+}
 
 /// Compresses a `WitnessMap` into the binary format outputted by Nargo.
 ///
@@ -32,3 +68,121 @@ pub fn decompress_witness(compressed_witness: Vec<u8>) -> Result<JsWitnessMap, J
 
     Ok(witness_map.into())
 }
+
+/// The version of the format output by `compressBytes`, which prefixes the compressed bytes.
+const COMPRESSED_BYTES_VERSION: u8 = 1;
+
+/// The first byte of a gzip stream, which identifies bytes compressed by Nargo without a version prefix.
+const GZIP_MAGIC_BYTE: u8 = 0x1f;
+
+/// Compresses `bytes` using the gzip encoding which Nargo applies to witnesses and circuits.
+fn gzip(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encoder = GzEncoder::new(bytes, Compression::best());
+    let mut compressed_bytes = Vec::new();
+    encoder.read_to_end(&mut compressed_bytes).map_err(|err| err.to_string())?;
+    Ok(compressed_bytes)
+}
+
+/// Decompresses gzip encoded `compressed_bytes`.
+fn gunzip(compressed_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = GzDecoder::new(compressed_bytes);
+    let mut bytes = Vec::new();
+    decoder.read_to_end(&mut bytes).map_err(|err| err.to_string())?;
+    Ok(bytes)
+}
+
+/// Compresses arbitrary bytes using the same gzip encoding which Nargo applies to witnesses and circuits.
+///
+/// The output is prefixed with a byte identifying the version of its format, so that it can change without older
+/// outputs being misread.
+///
+/// @param {Uint8Array} bytes - The bytes to be compressed.
+/// @returns {Uint8Array} The compressed bytes.
+#[wasm_bindgen(js_name = compressBytes, skip_jsdoc)]
+pub fn compress_bytes(bytes: Vec<u8>) -> Result<Vec<u8>, JsString> {
+    console_error_panic_hook::set_once();
+
+    let mut compressed_bytes = vec![COMPRESSED_BYTES_VERSION];
+    compressed_bytes.extend(gzip(&bytes)?);
+
+    Ok(compressed_bytes)
+}
+
+/// Decompresses bytes which were compressed using `compressBytes`, or gzip encoded bytes as output by Nargo.
+///
+/// @param {Uint8Array} compressed_bytes - The compressed bytes.
+/// @returns {Uint8Array} The decompressed bytes.
+#[wasm_bindgen(js_name = decompressBytes, skip_jsdoc)]
+pub fn decompress_bytes(compressed_bytes: Vec<u8>) -> Result<Vec<u8>, JsString> {
+    console_error_panic_hook::set_once();
+
+    let bytes = match compressed_bytes.first() {
+        Some(&COMPRESSED_BYTES_VERSION) => gunzip(&compressed_bytes[1..])?,
+        Some(&GZIP_MAGIC_BYTE) => gunzip(&compressed_bytes)?,
+        Some(version) => {
+            return Err(format!("Unsupported compressed bytes version {version}").into())
+        }
+        None => return Err("Compressed bytes are empty".into()),
+    };
+
+    Ok(bytes)
+}
+
+/// Compresses a `WitnessStack` into the binary format outputted by Nargo.
+///
+/// @param {WitnessStackLike} witness_stack - The witnesses of a multi-function program.
+/// @returns {Uint8Array} A compressed witness stack.
+#[wasm_bindgen(js_name = compressWitnessStack, skip_jsdoc)]
+pub fn compress_witness_stack(witness_stack: JsWitnessStackLike) -> Result<Vec<u8>, JsString> {
+    console_error_panic_hook::set_once();
+
+    let read = |item: &JsValue, key: &str| {
+        Reflect::get(item, &JsValue::from_str(key))
+            .ok()
+            .filter(|value| !value.is_undefined())
+            .ok_or_else(|| format!("Invalid witness stack: missing {key}"))
+    };
+    let stack = witness_stack
+        .iter()
+        .map(|item| {
+            // Non-finite and fractional indices fail the `fract` check.
+            let index = read(&item, "index")?
+                .as_f64()
+                .filter(|index| index.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(index))
+                .ok_or("Invalid witness stack: index must be an integer between 0 and 2^32 - 1")?;
+            let witness: JsWitnessMapLike = read(&item, "witness")?.unchecked_into();
+            let witness = WitnessMap::try_from(witness)
+                .map_err(|err| format!("Invalid witness stack: {}", String::from(err)))?;
+            Ok(StackItem { index: index as u32, witness })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let bytes = bincode::serialize(&WitnessStack { stack }).map_err(|err| err.to_string())?;
+    Ok(gzip(&bytes)?)
+}
+
+/// Decompresses a compressed witness stack as outputted by Nargo into a `WitnessStack`.
+///
+/// @param {Uint8Array} compressed_witness_stack - A compressed witness stack.
+/// @returns {WitnessStack} The decompressed witness stack.
+#[wasm_bindgen(js_name = decompressWitnessStack, skip_jsdoc)]
+pub fn decompress_witness_stack(
+    compressed_witness_stack: Vec<u8>,
+) -> Result<JsWitnessStack, JsString> {
+    console_error_panic_hook::set_once();
+
+    let bytes = gunzip(&compressed_witness_stack)?;
+    let witness_stack: WitnessStack =
+        bincode::deserialize(&bytes).map_err(|err| err.to_string())?;
+
+    let js_stack = Array::new();
+    for StackItem { index, witness } in witness_stack.stack {
+        let js_item = Object::new();
+        Reflect::set(&js_item, &JsValue::from_str("index"), &JsValue::from(index))
+            .expect("setting a property on an `Object` should not fail");
+        Reflect::set(&js_item, &JsValue::from_str("witness"), &JsWitnessMap::from(witness))
+            .expect("setting a property on an `Object` should not fail");
+        js_stack.push(&js_item);
+    }
+    Ok(js_stack.unchecked_into())
+}
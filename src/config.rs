@@ -0,0 +1,136 @@
+//! Package-wide settings applied through `configureSimulator`.
+
+use std::{cell::RefCell, str::FromStr};
+
+use js_sys::{Function, JsString, Reflect};
+use log::LevelFilter;
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{backend::validate_backend, logging::init_logging};
+
+#[wasm_bindgen(typescript_custom_section)]
+const SIMULATOR_CONFIG: &'static str = r#"
+/**
+* A callback which receives the package's log messages in place of the console.
+* @callback LogSink
+* @param {LogLevel} level - The level of the message.
+* @param {string} message - The message.
+*/
+export type LogSink = (level: LogLevel, message: string) => void;
+
+/**
+* @typedef {Object} SimulatorConfig - Settings which apply to every execution.
+* @property {number} maxMemoryPages - The maximum number of 64KiB pages of memory the embedded Barretenberg may grow to. Must be at least 23. `null` removes the limit.
+* @property {ProvingBackend} backend - The proof system used when none is passed to `prove`, `verifyProof` and similar. Defaults to the embedded Barretenberg, which `null` restores.
+* @property {LogLevel} logLevel - The maximum level of logging to be emitted.
+* @property {LogSink} logSink - A callback which receives log messages in place of the console, which `null` restores.
+*/
+export type SimulatorConfig = {
+  maxMemoryPages?: number | null;
+  backend?: ProvingBackend | null;
+  logLevel?: LogLevel;
+  logSink?: LogSink | null;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "SimulatorConfig")]
+    pub type JsSimulatorConfig;
+}
+
+/// The number of pages of memory which the embedded Barretenberg starts with.
+pub(crate) const INITIAL_MEMORY_PAGES: u32 = 23;
+
+#[derive(Debug, Default)]
+pub(crate) struct SimulatorConfig {
+    /// The maximum number of pages of memory which the embedded Barretenberg may grow to.
+    pub(crate) max_memory_pages: Option<u32>,
+    /// The proving backend to use when none is provided.
+    pub(crate) backend: Option<JsValue>,
+    /// The callback which receives log messages in place of the console.
+    pub(crate) log_sink: Option<Function>,
+}
+
+thread_local! {
+    static CONFIG: RefCell<SimulatorConfig> = RefCell::default();
+}
+
+/// Reads a setting from the current configuration.
+pub(crate) fn with_config<T>(read: impl FnOnce(&SimulatorConfig) -> T) -> T {
+    CONFIG.with(|config| read(&config.borrow()))
+}
+
+/// Configures the package as a whole: memory limits, the default proving backend and logging.
+///
+/// Settings apply to executions started after this is called, so it should be called before the first execution.
+/// Settings which are omitted keep their current values. `logLevel` replaces any level set by `initLogLevel`.
+///
+/// @param {SimulatorConfig} config - The settings to apply.
+#[wasm_bindgen(js_name = configureSimulator, skip_jsdoc)]
+pub fn configure_simulator(config: JsSimulatorConfig) -> Result<(), JsString> {
+    console_error_panic_hook::set_once();
+    let config: JsValue = config.into();
+    let read = |key: &str| -> Result<Option<JsValue>, String> {
+        let value = Reflect::get(&config, &JsValue::from_str(key))
+            .map_err(|_| format!("Invalid simulator config: could not read {key}"))?;
+        Ok((!value.is_undefined()).then_some(value))
+    };
+
+    let max_memory_pages = read("maxMemoryPages")?
+        .map(|pages| match pages.as_f64() {
+            None if pages.is_null() => Ok(None),
+            Some(pages)
+                if pages.fract() == 0.0
+                    && (INITIAL_MEMORY_PAGES as f64..=u32::MAX as f64).contains(&pages) =>
+            {
+                Ok(Some(pages as u32))
+            }
+            _ => Err(format!(
+                "Invalid simulator config: maxMemoryPages must be an integer of at least {INITIAL_MEMORY_PAGES}"
+            )),
+        })
+        .transpose()?;
+    let backend = read("backend")?;
+    if let Some(backend) = backend.as_ref().filter(|backend| !backend.is_null()) {
+        validate_backend(backend).map_err(|err| format!("Invalid simulator config: {err}"))?;
+    }
+    let log_level = read("logLevel")?
+        .map(|level| {
+            let level = level.as_string().unwrap_or_default();
+            LevelFilter::from_str(&level)
+                .map_err(|_| format!("Invalid simulator config: unknown logLevel '{level}'"))
+        })
+        .transpose()?;
+    let log_sink = read("logSink")?
+        .map(|sink| {
+            if sink.is_null() {
+                return Ok(None);
+            }
+            sink.dyn_into::<Function>()
+                .map(Some)
+                .map_err(|_| "Invalid simulator config: logSink must be a function".to_owned())
+        })
+        .transpose()?;
+
+    if log_level.is_some() || log_sink.is_some() {
+        init_logging();
+    }
+    if let Some(log_level) = log_level {
+        log::set_max_level(log_level);
+    }
+
+    CONFIG.with(|config| {
+        let mut config = config.borrow_mut();
+        if let Some(log_sink) = log_sink {
+            config.log_sink = log_sink;
+        }
+        if let Some(max_memory_pages) = max_memory_pages {
+            config.max_memory_pages = max_memory_pages;
+        }
+        if let Some(backend) = backend {
+            config.backend = (!backend.is_null()).then_some(backend);
+        }
+    });
+    Ok(())
+}
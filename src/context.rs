@@ -0,0 +1,272 @@
+use std::{cell::RefCell, rc::Rc};
+
+use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
+use js_sys::{JsString, Promise};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::{
+    base_witness::BaseWitness,
+    cache::{CircuitBytes, ExecutionCache},
+    execute::{
+        execute_circuit_with_backend, read_circuit, ExecutionOptions, JsExecutionOptions,
+        SimulatedBackend,
+    },
+    foreign_call::{ForeignCallHandler, ForeignCallQueue, JsPendingForeignCalls},
+    js_witness_map::JsWitnessMapLike,
+    JsWitnessMap,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Promise, typescript_type = "Promise<WitnessMap>")]
+    pub type JsWitnessMapPromise;
+}
+
+struct ContextState {
+    // Executions hold onto their own handle to the backend so that it can be swapped out
+    // after a trap without waiting for any in-flight executions to complete.
+    backend: RefCell<Rc<SimulatedBackend>>,
+    /// The circuit this context was created with, along with its serialized bytes.
+    circuit: Option<(Circuit, CircuitBytes)>,
+    /// The foreign calls made by all executions in this context which have not yet resolved.
+    foreign_calls: Rc<ForeignCallQueue>,
+    /// Solved witnesses from previous executions, if caching has been enabled.
+    cache: RefCell<Option<ExecutionCache>>,
+}
+
+/// A reusable context for executing circuits which owns an initialized backend.
+///
+/// Initializing the backend is expensive so callers which execute many circuits should create a single
+/// `ExecutionContext` and reuse it rather than calling `executeCircuit` repeatedly.
+#[wasm_bindgen]
+pub struct ExecutionContext {
+    state: Rc<ContextState>,
+}
+
+#[wasm_bindgen]
+impl ExecutionContext {
+    /// Creates a new `ExecutionContext`, optionally binding it to a circuit which will be used by `execute`.
+    ///
+    /// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+    /// @returns {Promise<ExecutionContext>} A context which can be used to execute circuits.
+    #[wasm_bindgen(skip_jsdoc)]
+    pub async fn create(circuit: Option<Vec<u8>>) -> Result<ExecutionContext, JsValue> {
+        console_error_panic_hook::set_once();
+        let circuit = circuit
+            .map(|circuit| {
+                read_circuit(&circuit).map(|parsed| (parsed, CircuitBytes::new(&circuit)))
+            })
+            .transpose()?;
+
+        let backend = SimulatedBackend::initialize().await;
+        let state = ContextState {
+            backend: RefCell::new(Rc::new(backend)),
+            circuit,
+            foreign_calls: Rc::default(),
+            cache: RefCell::new(None),
+        };
+        Ok(ExecutionContext { state: Rc::new(state) })
+    }
+
+    /// Executes the circuit this context was created with to generate the solved witness from the initial witness.
+    ///
+    /// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to the circuit.
+    /// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+    /// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+    /// @returns {Promise<WitnessMap>} The solved witness calculated by executing the circuit on the provided inputs.
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn execute(
+        &self,
+        initial_witness: JsWitnessMapLike,
+        foreign_call_handler: ForeignCallHandler,
+        options: Option<JsExecutionOptions>,
+    ) -> Result<JsWitnessMapPromise, JsString> {
+        console_error_panic_hook::set_once();
+        if self.state.circuit.is_none() {
+            return Err("ExecutionContext was not created with a circuit".into());
+        }
+
+        Ok(self.execute_inner(None, None, initial_witness, foreign_call_handler, options))
+    }
+
+    /// Executes the circuit this context was created with, taking any witnesses missing from `overlay` from `base`.
+    ///
+    /// `base` is never modified so can be shared between any number of executions. Only the witnesses in `overlay`
+    /// and those solved by the execution are returned, rather than the whole witness.
+    ///
+    /// @param {BaseWitness} base - The witnesses shared between many executions.
+    /// @param {WitnessMapLike} overlay - The witnesses specific to this execution. These take precedence over `base`.
+    /// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+    /// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+    /// @returns {Promise<WitnessMap>} The witnesses in `overlay` along with those solved by executing the circuit.
+    #[wasm_bindgen(js_name = executeWithBase, skip_jsdoc)]
+    pub fn execute_with_base(
+        &self,
+        base: &BaseWitness,
+        overlay: JsWitnessMapLike,
+        foreign_call_handler: ForeignCallHandler,
+        options: Option<JsExecutionOptions>,
+    ) -> Result<JsWitnessMapPromise, JsString> {
+        console_error_panic_hook::set_once();
+        if self.state.circuit.is_none() {
+            return Err("ExecutionContext was not created with a circuit".into());
+        }
+
+        Ok(self.execute_inner(None, Some(base.clone()), overlay, foreign_call_handler, options))
+    }
+
+    /// Executes an ACIR circuit to generate the solved witness from the initial witness, reusing this context's backend.
+    ///
+    /// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+    /// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+    /// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+    /// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+    /// @returns {Promise<WitnessMap>} The solved witness calculated by executing the circuit on the provided inputs.
+    #[wasm_bindgen(js_name = executeCircuit, skip_jsdoc)]
+    pub fn execute_circuit(
+        &self,
+        circuit: Vec<u8>,
+        initial_witness: JsWitnessMapLike,
+        foreign_call_handler: ForeignCallHandler,
+        options: Option<JsExecutionOptions>,
+    ) -> JsWitnessMapPromise {
+        console_error_panic_hook::set_once();
+        let circuit_bytes = CircuitBytes::new(&circuit);
+        let circuit: Circuit = match read_circuit(&circuit) {
+            Ok(circuit) => circuit,
+            Err(err) => return JsValue::from(Promise::reject(&err.into())).into(),
+        };
+
+        self.execute_inner(
+            Some((circuit, circuit_bytes)),
+            None,
+            initial_witness,
+            foreign_call_handler,
+            options,
+        )
+    }
+
+    /// Caches the solved witness of each execution in this context so that repeating an execution with the same
+    /// circuit and initial witness returns the previous result without solving the circuit again.
+    ///
+    /// Foreign calls are not made when a cached result is returned, so this should only be enabled if the circuit's
+    /// foreign calls always return the same response for the same inputs. Executions which use a `blackboxSolver`,
+    /// replay a `foreignCallTranscript` or are observed or limited by options such as `onOpcodeSolved`,
+    /// `breakpoints`, `invariants` or `maxBrilligSteps` are never cached.
+    ///
+    /// @param {number} max_entries - The maximum number of solved witnesses to hold. Once reached, the oldest is evicted.
+    #[wasm_bindgen(js_name = enableCache, skip_jsdoc)]
+    pub fn enable_cache(&self, max_entries: u32) {
+        *self.state.cache.borrow_mut() = Some(ExecutionCache::new(max_entries as usize));
+    }
+
+    /// Removes all cached results and stops caching the results of further executions.
+    #[wasm_bindgen(js_name = disableCache, skip_jsdoc)]
+    pub fn disable_cache(&self) {
+        *self.state.cache.borrow_mut() = None;
+    }
+
+    /// The foreign calls made by executions in this context which are still waiting on a response.
+    ///
+    /// @returns {PendingForeignCalls} The number of pending foreign calls along with the name of each.
+    #[wasm_bindgen(getter, js_name = pendingForeignCalls, skip_jsdoc)]
+    pub fn pending_foreign_calls(&self) -> JsPendingForeignCalls {
+        self.state.foreign_calls.to_js()
+    }
+}
+
+impl ExecutionContext {
+    /// Marks the context's backend as having trapped, so that it's replaced before the next execution.
+    pub(crate) fn mark_backend_trapped(&self) {
+        self.state.backend.borrow().mark_trapped();
+    }
+
+    /// Executes `circuit`, falling back to the context's circuit if none is provided.
+    ///
+    /// If a `base` witness is provided then `initial_witness` is applied on top of it.
+    fn execute_inner(
+        &self,
+        circuit: Option<(Circuit, CircuitBytes)>,
+        base: Option<BaseWitness>,
+        initial_witness: JsWitnessMapLike,
+        foreign_call_handler: ForeignCallHandler,
+        options: Option<JsExecutionOptions>,
+    ) -> JsWitnessMapPromise {
+        let state = self.state.clone();
+        let promise = future_to_promise(async move {
+            let mut options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+            options.foreign_call_queue = Some(state.foreign_calls.clone());
+            let initial_witness = WitnessMap::try_from(initial_witness)?;
+            let (initial_witness, overlaid) = match &base {
+                Some(base) => {
+                    let (initial_witness, overlaid) = base.with_overlay(initial_witness);
+                    (initial_witness, Some(overlaid))
+                }
+                None => (initial_witness, None),
+            };
+            let circuit_bytes = match &circuit {
+                Some((_, circuit_bytes)) => circuit_bytes,
+                None => &state.circuit.as_ref().expect("checked by caller").1,
+            };
+            let cache_key = state
+                .cache
+                .borrow()
+                .as_ref()
+                .and_then(|_| ExecutionCache::key(circuit_bytes, &initial_witness, &options));
+            let cached_witness = cache_key
+                .as_ref()
+                .and_then(|key| state.cache.borrow().as_ref().and_then(|cache| cache.get(key)));
+
+            let witness_map = match cached_witness {
+                Some(witness_map) => witness_map,
+                None => {
+                    let opcodes = match circuit {
+                        Some((circuit, _)) => circuit.opcodes,
+                        None => {
+                            state.circuit.as_ref().expect("checked by caller").0.opcodes.clone()
+                        }
+                    };
+                    let backend = state.healthy_backend().await;
+                    let witness_map = execute_circuit_with_backend(
+                        &backend,
+                        opcodes,
+                        initial_witness,
+                        &foreign_call_handler,
+                        &options,
+                    )
+                    .await
+                    .map_err(|err| err.into_js_value(&options))?;
+
+                    if let (Some(key), Some(cache)) = (cache_key, &mut *state.cache.borrow_mut()) {
+                        cache.insert(key, &witness_map);
+                    }
+                    witness_map
+                }
+            };
+
+            let witness_map = match (base, overlaid) {
+                (Some(base), Some(overlaid)) => base.strip(witness_map, &overlaid),
+                _ => witness_map,
+            };
+            Ok(JsValue::from(JsWitnessMap::from(witness_map)))
+        });
+
+        JsValue::from(promise).into()
+    }
+}
+
+impl ContextState {
+    /// Returns the context's backend, replacing it first if a previous call into it trapped.
+    async fn healthy_backend(&self) -> Rc<SimulatedBackend> {
+        let backend = self.backend.borrow().clone();
+        if backend.is_healthy() {
+            return backend;
+        }
+
+        log::warn!("Barretenberg instance trapped during a previous call, reinitializing");
+        let backend = Rc::new(SimulatedBackend::initialize().await);
+        *self.backend.borrow_mut() = backend.clone();
+        backend
+    }
+}
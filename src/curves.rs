@@ -0,0 +1,366 @@
+//! Elliptic curve utilities for the curves used by ACIR's blackbox functions.
+//!
+//! Grumpkin is the curve used for Schnorr signatures, Pedersen commitments and `FixedBaseScalarMul`. Its base field
+//! is the BN254 scalar field, i.e. the field which ACIR witnesses belong to, so its points can be constructed in-circuit.
+
+use ark_bn254::{Fq, Fr};
+use ark_ec::{
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveConfig, CurveGroup,
+};
+use ark_ff::{BigInteger, Field, MontFp, PrimeField, Zero};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::JsString;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::codec::{ByteEncodingOptions, Endianness, JsByteEncodingOptions};
+
+#[wasm_bindgen(typescript_custom_section)]
+const CURVE_POINT: &'static str = r#"
+export type Curve = "bn254" | "grumpkin";
+
+/**
+* @typedef {Object} CurvePoint - A point on an elliptic curve in affine coordinates.
+* The point at infinity is represented with both coordinates set to zero.
+* @property {string} x - The hex encoded x coordinate of the point.
+* @property {string} y - The hex encoded y coordinate of the point.
+*/
+export type CurvePoint = {
+  x: string;
+  y: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = JsString, typescript_type = "Curve")]
+    pub type JsCurve;
+
+    #[wasm_bindgen(typescript_type = "CurvePoint")]
+    pub type JsCurvePoint;
+}
+
+/// The number of bytes needed to store a coordinate of a point on either curve.
+const COORDINATE_BYTES: usize = 32;
+
+/// The Grumpkin curve `y^2 = x^3 - 17`, defined over the BN254 scalar field.
+struct GrumpkinConfig;
+
+impl CurveConfig for GrumpkinConfig {
+    type BaseField = Fr;
+    type ScalarField = Fq;
+
+    const COFACTOR: &'static [u64] = &[0x1];
+    const COFACTOR_INV: Fq = Fq::ONE;
+}
+
+impl SWCurveConfig for GrumpkinConfig {
+    const COEFF_A: Fr = Fr::ZERO;
+    const COEFF_B: Fr = MontFp!("-17");
+
+    /// The generator matches that used by Barretenberg, `(1, sqrt(-16))`.
+    const GENERATOR: Affine<Self> = Affine::new_unchecked(
+        Fr::ONE,
+        MontFp!("17631683881184975370165255887551781615748388533673675138860"),
+    );
+}
+
+enum Curve {
+    Bn254,
+    Grumpkin,
+}
+
+impl TryFrom<JsCurve> for Curve {
+    type Error = String;
+
+    fn try_from(curve: JsCurve) -> Result<Self, Self::Error> {
+        let curve = curve.as_string().unwrap_or_default();
+        match curve.as_str() {
+            "bn254" => Ok(Curve::Bn254),
+            "grumpkin" => Ok(Curve::Grumpkin),
+            _ => Err(format!("Unsupported curve: '{curve}'")),
+        }
+    }
+}
+
+/// Calls `$function` with the curve config corresponding to `$curve`.
+macro_rules! with_curve {
+    ($curve:expr, $function:ident($($arg:expr),*)) => {
+        match Curve::try_from($curve)? {
+            Curve::Bn254 => $function::<ark_bn254::g1::Config>($($arg),*),
+            Curve::Grumpkin => $function::<GrumpkinConfig>($($arg),*),
+        }
+    };
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CurvePoint {
+    pub(crate) x: String,
+    pub(crate) y: String,
+}
+
+impl From<CurvePoint> for JsCurvePoint {
+    fn from(point: CurvePoint) -> Self {
+        <JsValue as JsValueSerdeExt>::from_serde(&point).unwrap().into()
+    }
+}
+
+fn parse_field_element<F: PrimeField>(hex_str: &str) -> Result<F, String> {
+    let invalid = || format!("Invalid field element: '{hex_str}'");
+
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    let padded = format!("{hex_str:0>width$}", width = 2 * COORDINATE_BYTES);
+    let bytes = decode_hex(&padded).ok_or_else(invalid)?;
+    if bytes.len() != COORDINATE_BYTES {
+        return Err(invalid());
+    }
+
+    // Reducing the value would silently accept values larger than the modulus.
+    let field_element = F::from_be_bytes_mod_order(&bytes);
+    if field_element.into_bigint().to_bytes_be() != bytes {
+        return Err(invalid());
+    }
+    Ok(field_element)
+}
+
+fn field_element_to_hex<F: PrimeField>(field_element: &F) -> String {
+    let bytes = field_element.into_bigint().to_bytes_be();
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{hex}")
+}
+
+pub(crate) fn decode_hex(hex_str: &str) -> Option<Vec<u8>> {
+    if hex_str.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex_str.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex_str.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_point<P: SWCurveConfig>(point: JsCurvePoint) -> Result<Affine<P>, String>
+where
+    P::BaseField: PrimeField,
+{
+    let point: CurvePoint = <JsValue as JsValueSerdeExt>::into_serde(&point)
+        .map_err(|err| format!("Invalid curve point: {err}"))?;
+    let x: P::BaseField = parse_field_element(&point.x)?;
+    let y: P::BaseField = parse_field_element(&point.y)?;
+    Ok(point_from_coordinates(x, y))
+}
+
+fn point_from_coordinates<P: SWCurveConfig>(x: P::BaseField, y: P::BaseField) -> Affine<P> {
+    if x.is_zero() && y.is_zero() {
+        Affine::identity()
+    } else {
+        Affine::new_unchecked(x, y)
+    }
+}
+
+fn parse_point_on_curve<P: SWCurveConfig>(point: JsCurvePoint) -> Result<Affine<P>, String>
+where
+    P::BaseField: PrimeField,
+{
+    let point = parse_point::<P>(point)?;
+    if !point.is_on_curve() {
+        return Err("Point is not on the curve".to_string());
+    }
+    Ok(point)
+}
+
+fn point_to_js<P: SWCurveConfig>(point: Affine<P>) -> JsCurvePoint
+where
+    P::BaseField: PrimeField,
+{
+    let (x, y) = point.xy().map(|(x, y)| (*x, *y)).unwrap_or_default();
+    CurvePoint { x: field_element_to_hex(&x), y: field_element_to_hex(&y) }.into()
+}
+
+fn generator<P: SWCurveConfig>() -> Result<JsCurvePoint, String>
+where
+    P::BaseField: PrimeField,
+{
+    Ok(point_to_js(P::GENERATOR))
+}
+
+fn add<P: SWCurveConfig>(lhs: JsCurvePoint, rhs: JsCurvePoint) -> Result<JsCurvePoint, String>
+where
+    P::BaseField: PrimeField,
+{
+    let lhs = parse_point_on_curve::<P>(lhs)?;
+    let rhs = parse_point_on_curve::<P>(rhs)?;
+    Ok(point_to_js((lhs + rhs).into_affine()))
+}
+
+fn scalar_mul<P: SWCurveConfig>(point: JsCurvePoint, scalar: &str) -> Result<JsCurvePoint, String>
+where
+    P::BaseField: PrimeField,
+{
+    let point = parse_point_on_curve::<P>(point)?;
+    let scalar_hex = scalar.strip_prefix("0x").unwrap_or(scalar);
+    let padded = format!("{scalar_hex:0>width$}", width = 2 * COORDINATE_BYTES);
+    let scalar_bytes = decode_hex(&padded).ok_or_else(|| format!("Invalid scalar: '{scalar}'"))?;
+    let scalar = P::ScalarField::from_be_bytes_mod_order(&scalar_bytes);
+
+    Ok(point_to_js((point * scalar).into_affine()))
+}
+
+fn point_to_bytes<P: SWCurveConfig>(
+    point: JsCurvePoint,
+    endianness: Endianness,
+) -> Result<Vec<u8>, String>
+where
+    P::BaseField: PrimeField,
+{
+    let point = parse_point::<P>(point)?;
+    let (x, y) = point.xy().map(|(x, y)| (*x, *y)).unwrap_or_default();
+
+    let mut bytes = endianness.convert_big_endian(x.into_bigint().to_bytes_be());
+    bytes.extend(endianness.convert_big_endian(y.into_bigint().to_bytes_be()));
+    Ok(bytes)
+}
+
+fn point_from_bytes<P: SWCurveConfig>(
+    bytes: &[u8],
+    endianness: Endianness,
+) -> Result<JsCurvePoint, String>
+where
+    P::BaseField: PrimeField,
+{
+    if bytes.len() != 2 * COORDINATE_BYTES {
+        return Err(format!(
+            "Expected {} bytes for a curve point but received {}",
+            2 * COORDINATE_BYTES,
+            bytes.len()
+        ));
+    }
+    let (x_bytes, y_bytes) = bytes.split_at(COORDINATE_BYTES);
+    let x_bytes = endianness.convert_big_endian(x_bytes.to_vec());
+    let y_bytes = endianness.convert_big_endian(y_bytes.to_vec());
+    let x = P::BaseField::from_be_bytes_mod_order(&x_bytes);
+    let y = P::BaseField::from_be_bytes_mod_order(&y_bytes);
+    if x.into_bigint().to_bytes_be() != x_bytes || y.into_bigint().to_bytes_be() != y_bytes {
+        return Err("Curve point coordinates must be less than the field modulus".to_string());
+    }
+
+    let point = point_from_coordinates::<P>(x, y);
+    if !point.is_on_curve() {
+        return Err("Point is not on the curve".to_string());
+    }
+    Ok(point_to_js(point))
+}
+
+fn is_on_curve<P: SWCurveConfig>(point: JsCurvePoint) -> Result<bool, String>
+where
+    P::BaseField: PrimeField,
+{
+    Ok(parse_point::<P>(point)?.is_on_curve())
+}
+
+fn is_in_subgroup<P: SWCurveConfig>(point: JsCurvePoint) -> Result<bool, String>
+where
+    P::BaseField: PrimeField,
+{
+    let point = parse_point::<P>(point)?;
+    Ok(point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+}
+
+/// Returns the generator point of `curve`.
+///
+/// @param {Curve} curve - The curve to use.
+/// @returns {CurvePoint} The generator of `curve`.
+#[wasm_bindgen(js_name = curveGenerator, skip_jsdoc)]
+pub fn curve_generator(curve: JsCurve) -> Result<JsCurvePoint, JsString> {
+    console_error_panic_hook::set_once();
+    Ok(with_curve!(curve, generator())?)
+}
+
+/// Adds two points on `curve`.
+///
+/// @param {Curve} curve - The curve to use.
+/// @param {CurvePoint} lhs - A point on `curve`.
+/// @param {CurvePoint} rhs - A point on `curve`.
+/// @returns {CurvePoint} The sum of `lhs` and `rhs`.
+#[wasm_bindgen(js_name = curveAdd, skip_jsdoc)]
+pub fn curve_add(
+    curve: JsCurve,
+    lhs: JsCurvePoint,
+    rhs: JsCurvePoint,
+) -> Result<JsCurvePoint, JsString> {
+    console_error_panic_hook::set_once();
+    Ok(with_curve!(curve, add(lhs, rhs))?)
+}
+
+/// Multiplies a point on `curve` by a scalar.
+///
+/// @param {Curve} curve - The curve to use.
+/// @param {CurvePoint} point - A point on `curve`.
+/// @param {string} scalar - A hex encoded scalar. This is reduced modulo the order of `curve`.
+/// @returns {CurvePoint} The product of `point` and `scalar`.
+#[wasm_bindgen(js_name = curveScalarMul, skip_jsdoc)]
+pub fn curve_scalar_mul(
+    curve: JsCurve,
+    point: JsCurvePoint,
+    scalar: String,
+) -> Result<JsCurvePoint, JsString> {
+    console_error_panic_hook::set_once();
+    Ok(with_curve!(curve, scalar_mul(point, &scalar))?)
+}
+
+/// Serializes a point on `curve` as the concatenation of its x and y coordinates.
+///
+/// @param {Curve} curve - The curve to use.
+/// @param {CurvePoint} point - A point on `curve`.
+/// @param {ByteEncodingOptions} options - Optional settings controlling the encoding. Coordinates are big-endian by default.
+/// @returns {Uint8Array} The 64 byte serialization of `point`.
+#[wasm_bindgen(js_name = curvePointToBytes, skip_jsdoc)]
+pub fn curve_point_to_bytes(
+    curve: JsCurve,
+    point: JsCurvePoint,
+    options: Option<JsByteEncodingOptions>,
+) -> Result<Vec<u8>, JsString> {
+    console_error_panic_hook::set_once();
+    let options = ByteEncodingOptions::try_from(options)?;
+    Ok(with_curve!(curve, point_to_bytes(point, options.endianness))?)
+}
+
+/// Deserializes a point on `curve` from the concatenation of its x and y coordinates.
+///
+/// @param {Curve} curve - The curve to use.
+/// @param {Uint8Array} bytes - The 64 byte serialization of a point on `curve`.
+/// @param {ByteEncodingOptions} options - Optional settings controlling the encoding. Coordinates are big-endian by default.
+/// @returns {CurvePoint} The deserialized point.
+#[wasm_bindgen(js_name = curvePointFromBytes, skip_jsdoc)]
+pub fn curve_point_from_bytes(
+    curve: JsCurve,
+    bytes: Vec<u8>,
+    options: Option<JsByteEncodingOptions>,
+) -> Result<JsCurvePoint, JsString> {
+    console_error_panic_hook::set_once();
+    let options = ByteEncodingOptions::try_from(options)?;
+    Ok(with_curve!(curve, point_from_bytes(&bytes, options.endianness))?)
+}
+
+/// Checks whether a point lies on `curve`.
+///
+/// @param {Curve} curve - The curve to use.
+/// @param {CurvePoint} point - The point to check.
+/// @returns {boolean} Whether `point` lies on `curve`.
+#[wasm_bindgen(js_name = curveIsOnCurve, skip_jsdoc)]
+pub fn curve_is_on_curve(curve: JsCurve, point: JsCurvePoint) -> Result<bool, JsString> {
+    console_error_panic_hook::set_once();
+    Ok(with_curve!(curve, is_on_curve(point))?)
+}
+
+/// Checks whether a point lies in the prime order subgroup of `curve`.
+///
+/// @param {Curve} curve - The curve to use.
+/// @param {CurvePoint} point - The point to check.
+/// @returns {boolean} Whether `point` lies in the prime order subgroup of `curve`.
+#[wasm_bindgen(js_name = curveIsInSubgroup, skip_jsdoc)]
+pub fn curve_is_in_subgroup(curve: JsCurve, point: JsCurvePoint) -> Result<bool, JsString> {
+    console_error_panic_hook::set_once();
+    Ok(with_curve!(curve, is_in_subgroup(point))?)
+}
@@ -0,0 +1,310 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{JsString, Reflect};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::{
+    context::JsWitnessMapPromise,
+    execute::{
+        execute_circuit_with_backend, read_circuit, CurrentLocation, ExecutionOptions,
+        JsExecutionOptions, OpcodeLocation, SimulatedBackend,
+    },
+    foreign_call::ForeignCallHandler,
+    js_witness_map::JsWitnessMapLike,
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const DEBUG_ARTIFACT: &'static str = r#"
+/**
+* @typedef {Object} DebugArtifact - The debug symbols emitted by the Noir compiler alongside a circuit.
+* @property {Object} debug_symbols - Holds the source locations of each opcode under `locations`, keyed by opcode index or by `acirIndex.brilligIndex` for Brillig instructions.
+* @property {Object} file_map - The source and path of each file referenced by `debug_symbols`, keyed by file id.
+*/
+export type DebugArtifact = {
+  debug_symbols: {
+    locations: Record<string, DebugLocation | DebugLocation[]>;
+  };
+  file_map: Record<number, { source: string; path: string }>;
+}
+
+// A span of source code in one of the files of a `DebugArtifact`, as emitted by the Noir compiler.
+export type DebugLocation = {
+  span: { start: number; end: number };
+  file: number;
+}
+
+/**
+* @typedef {Object} SourceLocation - A position within a Noir source file.
+* @property {string} path - The path of the source file.
+* @property {number} line - The line of the start of the span, starting from 1.
+* @property {number} column - The column of the start of the span, starting from 1.
+* @property {string} text - The source code covered by the span.
+*/
+export type SourceLocation = {
+  path: string;
+  line: number;
+  column: number;
+  text: string;
+}
+
+/**
+* @typedef {Object} StackFrame - A call within the Noir call stack leading to an opcode.
+* @property {string | null} function - The name of the function making the call, if it could be found in the source.
+*/
+export type StackFrame = SourceLocation & {
+  function: string | null;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "DebugArtifact")]
+    pub type JsDebugArtifact;
+
+    #[wasm_bindgen(typescript_type = "SourceLocation")]
+    pub type JsSourceLocation;
+}
+
+#[derive(Debug, Deserialize)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct DebugLocation {
+    span: Span,
+    file: u32,
+}
+
+/// The locations of an opcode, which may be a single location or the call stack leading to it.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DebugLocations {
+    Single(DebugLocation),
+    CallStack(Vec<DebugLocation>),
+}
+
+impl DebugLocations {
+    /// Returns the locations making up the call stack, with the outermost call first.
+    fn call_stack(&self) -> &[DebugLocation] {
+        match self {
+            DebugLocations::Single(location) => std::slice::from_ref(location),
+            DebugLocations::CallStack(call_stack) => call_stack,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DebugInfo {
+    locations: BTreeMap<String, DebugLocations>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DebugFile {
+    source: String,
+    path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DebugArtifact {
+    debug_symbols: DebugInfo,
+    file_map: BTreeMap<u32, DebugFile>,
+}
+
+#[derive(Serialize)]
+struct SourceLocation {
+    path: String,
+    line: usize,
+    column: usize,
+    text: String,
+}
+
+#[derive(Serialize)]
+struct StackFrame {
+    function: Option<String>,
+    #[serde(flatten)]
+    location: SourceLocation,
+}
+
+impl DebugArtifact {
+    /// Returns the debug locations of the opcode, or Brillig instruction, at `location`.
+    ///
+    /// Brillig instructions without their own debug symbols are reported at the location of their Brillig opcode.
+    fn debug_locations(&self, location: OpcodeLocation) -> &[DebugLocation] {
+        let locations = &self.debug_symbols.locations;
+        location
+            .brillig_index
+            .and_then(|brillig_index| {
+                locations.get(&format!("{}.{brillig_index}", location.acir_index))
+            })
+            .or_else(|| locations.get(&location.acir_index.to_string()))
+            .map_or(&[], DebugLocations::call_stack)
+    }
+
+    /// Returns the source location of the opcode, or Brillig instruction, at `location`.
+    fn source_location(&self, location: OpcodeLocation) -> Option<SourceLocation> {
+        self.resolve(self.debug_locations(location).last()?)
+            .map(|(_, source_location)| source_location)
+    }
+
+    /// Returns the call stack leading to the opcode, or Brillig instruction, at `location`, innermost call first.
+    fn call_stack(&self, location: OpcodeLocation) -> Vec<StackFrame> {
+        self.debug_locations(location)
+            .iter()
+            .rev()
+            .filter_map(|debug_location| {
+                let (file, location) = self.resolve(debug_location)?;
+                let start = debug_location.span.start.min(file.source.len());
+                let function = enclosing_function(&file.source[..start]);
+                Some(StackFrame { function, location })
+            })
+            .collect()
+    }
+
+    /// Returns the source location of `debug_location` along with the file it lies within.
+    fn resolve(&self, debug_location: &DebugLocation) -> Option<(&DebugFile, SourceLocation)> {
+        let file = self.file_map.get(&debug_location.file)?;
+        let start = debug_location.span.start.min(file.source.len());
+        let end = debug_location.span.end.clamp(start, file.source.len());
+        let preceding = file.source.get(..start)?;
+        let line = preceding.matches('\n').count() + 1;
+        let line_start = preceding.rfind('\n').map_or(0, |index| index + 1);
+        let column = preceding[line_start..].chars().count() + 1;
+
+        let source_location = SourceLocation {
+            path: file.path.clone(),
+            line,
+            column,
+            text: file.source.get(start..end)?.to_owned(),
+        };
+        Some((file, source_location))
+    }
+}
+
+/// Returns the name of the last function declared in `preceding`, the source code before a location.
+///
+/// The debug symbols don't name the functions which make up the call stack so they're recovered from the source.
+/// This assumes the location lies within the body of that function, which holds for the spans emitted by the Noir
+/// compiler.
+fn enclosing_function(preceding: &str) -> Option<String> {
+    let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    preceding.rmatch_indices("fn ").find_map(|(index, _)| {
+        if preceding[..index].chars().last().map_or(false, is_identifier) {
+            return None;
+        }
+        let name: String =
+            preceding[index + 3..].trim_start().chars().take_while(|&c| is_identifier(c)).collect();
+        (!name.is_empty()).then_some(name)
+    })
+}
+
+struct SessionState {
+    debug_artifact: DebugArtifact,
+    /// The opcode currently being attempted by the session's execution, if it's running.
+    current_location: CurrentLocation,
+}
+
+/// Executes a circuit while mapping the opcode being executed back to the Noir source it was compiled from.
+///
+/// This is intended to be driven from the `onBreakpoint` and `onBrilligStep` callbacks, which can look up where
+/// execution has paused with `getCurrentSourceLocation`. Errors from failing opcodes carry the Noir call stack which
+/// led to the opcode as `callStack`.
+#[wasm_bindgen]
+pub struct DebugSession {
+    state: Rc<SessionState>,
+}
+
+#[wasm_bindgen]
+impl DebugSession {
+    /// Creates a new `DebugSession` for circuits compiled with `debug_artifact`.
+    ///
+    /// @param {DebugArtifact} debug_artifact - The debug symbols and source files emitted by the Noir compiler.
+    #[wasm_bindgen(constructor, skip_jsdoc)]
+    pub fn new(debug_artifact: JsDebugArtifact) -> Result<DebugSession, JsString> {
+        console_error_panic_hook::set_once();
+        let debug_artifact: DebugArtifact =
+            <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(debug_artifact))
+                .map_err(|err| format!("Invalid debug artifact: {err}"))?;
+
+        let state = SessionState { debug_artifact, current_location: CurrentLocation::default() };
+        Ok(DebugSession { state: Rc::new(state) })
+    }
+
+    /// Executes an ACIR circuit to generate the solved witness from the initial witness, tracking the opcode being
+    /// executed.
+    ///
+    /// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+    /// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+    /// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+    /// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+    /// @returns {Promise<WitnessMap>} The solved witness calculated by executing the circuit on the provided inputs.
+    #[wasm_bindgen(js_name = executeCircuit, skip_jsdoc)]
+    pub fn execute_circuit(
+        &self,
+        circuit: Vec<u8>,
+        initial_witness: JsWitnessMapLike,
+        foreign_call_handler: ForeignCallHandler,
+        options: Option<JsExecutionOptions>,
+    ) -> JsWitnessMapPromise {
+        console_error_panic_hook::set_once();
+        let state = self.state.clone();
+        let promise = future_to_promise(async move {
+            let mut options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+            options.current_location = Some(state.current_location.clone());
+            let circuit: Circuit = read_circuit(&circuit)?;
+            let initial_witness = WitnessMap::try_from(initial_witness)?;
+
+            let backend = SimulatedBackend::initialize().await;
+            let result = execute_circuit_with_backend(
+                &backend,
+                circuit.opcodes,
+                initial_witness,
+                &foreign_call_handler,
+                &options,
+            )
+            .await;
+            let location = state.current_location.take();
+
+            let witness_map = result.map_err(|err| {
+                // A stalled execution wasn't attempting any particular opcode when it gave up.
+                let call_stack = match (&err.stalled, location) {
+                    (None, Some(location)) => state.debug_artifact.call_stack(location),
+                    _ => Vec::new(),
+                };
+                let error = err.into_js_value(&options);
+                if call_stack.is_empty() {
+                    return error;
+                }
+                let error = match error.as_string() {
+                    Some(message) => js_sys::Error::new(&message).into(),
+                    None => error,
+                };
+                let call_stack = <JsValue as JsValueSerdeExt>::from_serde(&call_stack).unwrap();
+                Reflect::set(&error, &"callStack".into(), &call_stack)
+                    .expect("setting a property on an `Error` should not fail");
+                error
+            })?;
+            Ok(JsValue::from(JsWitnessMap::from(witness_map)))
+        });
+
+        JsValue::from(promise).into()
+    }
+
+    /// Returns the location in the Noir source of the opcode currently being executed, or of the Brillig
+    /// instruction when stepping through Brillig.
+    ///
+    /// @returns {SourceLocation | undefined} The source location, or `undefined` if nothing is being executed or
+    /// the opcode has no debug symbols.
+    #[wasm_bindgen(js_name = getCurrentSourceLocation, skip_jsdoc)]
+    pub fn get_current_source_location(&self) -> Option<JsSourceLocation> {
+        let location = self.state.current_location.get()?;
+        let source_location = self.state.debug_artifact.source_location(location)?;
+        Some(<JsValue as JsValueSerdeExt>::from_serde(&source_location).unwrap().into())
+    }
+}
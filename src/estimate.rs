@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use acvm::acir::circuit::{Circuit, Opcode};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::JsString;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    config::{with_config, INITIAL_MEMORY_PAGES},
+    execute::{decompress_circuit, read_circuit, OpcodeStream},
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const MEMORY_ESTIMATE: &'static str = r#"
+/**
+* @typedef {Object} MemoryEstimate - A rough estimate of the peak memory (in bytes) needed to execute a circuit.
+* @property {number} witnessBytes - Memory used to store the solved witness, both in wasm and as the returned `WitnessMap`.
+* @property {number} circuitBytes - Memory used to hold the deserialized circuit while it is being solved.
+* @property {number} backendBytes - Memory used by the embedded Barretenberg instance, including as many pages as `maxMemoryPages` allows it to grow to.
+* @property {number} totalBytes - The sum of all of the above.
+*/
+export type MemoryEstimate = {
+  witnessBytes: number;
+  circuitBytes: number;
+  backendBytes: number;
+  totalBytes: number;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CIRCUIT_COST: &'static str = r#"
+/**
+* @typedef {Object} CircuitCost - An approximate count of the gates a proving backend would need to prove a circuit.
+* @property {Record<string, number>} opcodeCounts - The number of opcodes of each type in the circuit, keyed by opcode name.
+* @property {Record<string, number>} gates - The estimated number of gates contributed by each type of opcode.
+* @property {number} totalGates - The estimated number of gates for the whole circuit.
+*/
+export type CircuitCost = {
+  opcodeCounts: Record<string, number>;
+  gates: Record<string, number>;
+  totalGates: number;
+}
+
+/**
+* The number of gates to count for each opcode, keyed by opcode name, e.g. `arithmetic`, `range` or `sha256`.
+* Memory blocks (`block`, `rom` and `ram`) are weighted per operation in their trace.
+* Any opcodes which are not included use the default weight.
+*/
+export type CircuitCostWeights = Record<string, number>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "MemoryEstimate")]
+    pub type JsMemoryEstimate;
+
+    #[wasm_bindgen(typescript_type = "CircuitCost")]
+    pub type JsCircuitCost;
+
+    #[wasm_bindgen(typescript_type = "CircuitCostWeights")]
+    pub type JsCircuitCostWeights;
+}
+
+/// Approximate cost of storing a single witness in a `WitnessMap` (index, field element and tree overhead).
+const WITNESS_MAP_ENTRY_BYTES: usize = 64;
+/// Approximate cost of a single entry in the JS `Map` returned to the caller (hex string and map overhead).
+const JS_WITNESS_MAP_ENTRY_BYTES: usize = 128;
+/// The ACVM holds a copy of the opcodes alongside the deserialized circuit, each of which is
+/// larger in memory than its serialized form.
+const CIRCUIT_EXPANSION_FACTOR: usize = 4;
+/// The size of a page of wasm memory.
+const WASM_PAGE_BYTES: usize = 64 * 1024;
+/// Approximate size of the compiled Barretenberg module, which sits alongside its memory.
+const BACKEND_MODULE_BYTES: usize = 8 * 1024 * 1024;
+
+/// Returns the memory used by the Barretenberg instance: the pages it may grow to if `maxMemoryPages` has been
+/// configured, or else those it's created with, on top of which sits the compiled module.
+fn backend_bytes() -> usize {
+    let pages = with_config(|config| config.max_memory_pages).unwrap_or(INITIAL_MEMORY_PAGES);
+    pages as usize * WASM_PAGE_BYTES + BACKEND_MODULE_BYTES
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MemoryEstimate {
+    witness_bytes: usize,
+    circuit_bytes: usize,
+    backend_bytes: usize,
+    total_bytes: usize,
+}
+
+/// Produces a rough estimate of the peak memory needed to execute a circuit.
+///
+/// This is intended to help decide up front whether execution is feasible on constrained devices
+/// and should not be relied upon as an exact figure.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @returns {MemoryEstimate} An estimate of the memory required to execute `circuit`.
+#[wasm_bindgen(js_name = estimateExecutionMemory, skip_jsdoc)]
+pub fn estimate_execution_memory(circuit: Vec<u8>) -> Result<JsMemoryEstimate, JsValue> {
+    console_error_panic_hook::set_once();
+
+    let serialized_circuit = decompress_circuit(&circuit)?;
+    let circuit: Circuit = read_circuit(&circuit)?;
+
+    let witness_bytes =
+        circuit.num_vars() as usize * (WITNESS_MAP_ENTRY_BYTES + JS_WITNESS_MAP_ENTRY_BYTES);
+    let circuit_bytes = serialized_circuit.len() * CIRCUIT_EXPANSION_FACTOR;
+    let backend_bytes = backend_bytes();
+
+    let estimate = MemoryEstimate {
+        witness_bytes,
+        circuit_bytes,
+        backend_bytes,
+        total_bytes: witness_bytes + circuit_bytes + backend_bytes,
+    };
+
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&estimate).unwrap().into())
+}
+
+/// Rough gate counts for each opcode, loosely based on an UltraPlonk backend.
+///
+/// Directives and Brillig opcodes only compute witnesses so don't contribute any gates.
+const DEFAULT_COST_WEIGHTS: &[(&str, f64)] = &[
+    ("arithmetic", 1.0),
+    ("invert", 0.0),
+    ("quotient", 0.0),
+    ("to_le_radix", 0.0),
+    ("permutation_sort", 0.0),
+    ("log", 0.0),
+    ("brillig", 0.0),
+    ("block", 1.0),
+    ("rom", 1.0),
+    ("ram", 2.0),
+    ("mem", 2.0),
+    ("init memory block", 1.0),
+    ("and", 8.0),
+    ("xor", 8.0),
+    ("range", 2.0),
+    ("sha256", 7_000.0),
+    ("blake2s", 3_000.0),
+    ("keccak256", 20_000.0),
+    ("pedersen", 250.0),
+    ("hash_to_field_128_security", 3_000.0),
+    ("schnorr_verify", 4_000.0),
+    ("ecdsa_secp256k1", 40_000.0),
+    ("ecdsa_secp256r1", 40_000.0),
+    ("fixed_base_scalar_mul", 3_000.0),
+    ("recursive_aggregation", 500_000.0),
+];
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CircuitCost {
+    opcode_counts: BTreeMap<String, u32>,
+    gates: BTreeMap<String, f64>,
+    pub(crate) total_gates: f64,
+}
+
+impl CircuitCost {
+    /// Adds the cost of `opcode`, weighted by its entry in `cost_weights`, which must hold every opcode name.
+    fn add(&mut self, opcode: &Opcode, cost_weights: &BTreeMap<String, f64>) {
+        let name = opcode.name();
+        let units = match opcode {
+            Opcode::Block(block) | Opcode::ROM(block) | Opcode::RAM(block) => block.trace.len(),
+            _ => 1,
+        };
+        let gates = cost_weights[name] * units as f64;
+        *self.opcode_counts.entry(name.to_string()).or_default() += 1;
+        *self.gates.entry(name.to_string()).or_default() += gates;
+        self.total_gates += gates;
+    }
+}
+
+pub(crate) fn default_cost_weights() -> BTreeMap<String, f64> {
+    DEFAULT_COST_WEIGHTS.iter().map(|(name, weight)| (name.to_string(), *weight)).collect()
+}
+
+/// Weights each opcode in `circuit` by its entry in `cost_weights`, which must hold every opcode name.
+pub(crate) fn circuit_cost(circuit: &Circuit, cost_weights: &BTreeMap<String, f64>) -> CircuitCost {
+    let mut cost = CircuitCost::default();
+    for opcode in &circuit.opcodes {
+        cost.add(opcode, cost_weights);
+    }
+    cost
+}
+
+/// Produces an approximate count of the gates needed to prove a circuit by weighting each of its opcodes.
+///
+/// This gives quick feedback on the cost of a circuit without a proving backend. The result depends heavily
+/// on the backend so should only be used to compare circuits against each other.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {CircuitCostWeights} weights - Overrides for the number of gates counted for each opcode.
+/// @returns {CircuitCost} The number of each opcode in `circuit` and the estimated gates they contribute.
+#[wasm_bindgen(js_name = estimateCircuitCost, skip_jsdoc)]
+pub fn estimate_circuit_cost(
+    circuit: Vec<u8>,
+    weights: Option<JsCircuitCostWeights>,
+) -> Result<JsCircuitCost, JsValue> {
+    console_error_panic_hook::set_once();
+
+    let mut cost_weights = default_cost_weights();
+    if let Some(weights) = weights {
+        let weights: BTreeMap<String, f64> = <JsValue as JsValueSerdeExt>::into_serde(&weights)
+            .map_err(|err| JsString::from(format!("Invalid cost weights: {err}")))?;
+        for (name, weight) in weights {
+            match cost_weights.get_mut(&name) {
+                Some(cost_weight) => *cost_weight = weight,
+                None => {
+                    return Err(JsString::from(format!(
+                        "Invalid cost weights: unknown opcode `{name}`"
+                    ))
+                    .into())
+                }
+            }
+        }
+    }
+
+    // Opcodes are weighed as they're parsed so the whole circuit is never held in memory.
+    let mut cost = CircuitCost::default();
+    for opcode in OpcodeStream::new(&circuit)? {
+        cost.add(&opcode?, &cost_weights);
+    }
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&cost).unwrap().into())
+}
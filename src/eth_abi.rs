@@ -0,0 +1,517 @@
+use std::collections::BTreeMap;
+
+use acvm::{
+    acir::{
+        circuit::Circuit,
+        native_types::{Witness, WitnessMap},
+    },
+    FieldElement,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::JsString;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    curves::decode_hex, execute::read_circuit, js_witness_map::JsWitnessMapLike, JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ETH_ABI_WITNESS_MAPPING: &'static str = r#"
+/**
+* The first witness index assigned to each decoded Ethereum ABI parameter, keyed by the parameter's position.
+* Parameters which are missing from the mapping are decoded but not assigned to any witness.
+*/
+export type EthAbiWitnessMapping = Record<number, number>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = js_sys::Array, typescript_type = "string[]")]
+    pub type JsEthAbiTypes;
+
+    #[wasm_bindgen(typescript_type = "EthAbiWitnessMapping")]
+    pub type JsEthAbiWitnessMapping;
+}
+
+/// The number of bytes in each word of the Ethereum ABI encoding.
+const WORD_BYTES: usize = 32;
+
+/// The number of bytes in the function selector which prefixes calldata.
+const SELECTOR_BYTES: usize = 4;
+
+/// An Ethereum ABI type which can be mapped onto witnesses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EthAbiType {
+    Uint(usize),
+    Int(usize),
+    Address,
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<EthAbiType>),
+    FixedArray(Box<EthAbiType>, usize),
+}
+
+impl std::str::FromStr for EthAbiType {
+    type Err = String;
+
+    fn from_str(abi_type: &str) -> Result<Self, Self::Err> {
+        let unsupported = || format!("Unsupported Ethereum ABI type: '{abi_type}'");
+
+        if let Some(element_type) = abi_type.strip_suffix("[]") {
+            return Ok(EthAbiType::Array(Box::new(element_type.parse()?)));
+        }
+        if let Some((element_type, length)) =
+            abi_type.strip_suffix(']').and_then(|abi_type| abi_type.rsplit_once('['))
+        {
+            return match length.parse() {
+                Ok(length) if length > 0 => {
+                    Ok(EthAbiType::FixedArray(Box::new(element_type.parse()?), length))
+                }
+                _ => Err(unsupported()),
+            };
+        }
+
+        let bit_size = |bits: &str| match bits {
+            "" => Ok(256),
+            bits => match bits.parse::<usize>() {
+                Ok(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => Ok(bits),
+                _ => Err(unsupported()),
+            },
+        };
+        match abi_type {
+            "address" => Ok(EthAbiType::Address),
+            "bool" => Ok(EthAbiType::Bool),
+            "bytes" => Ok(EthAbiType::Bytes),
+            "string" => Ok(EthAbiType::String),
+            _ => {
+                if let Some(bits) = abi_type.strip_prefix("uint") {
+                    Ok(EthAbiType::Uint(bit_size(bits)?))
+                } else if let Some(bits) = abi_type.strip_prefix("int") {
+                    Ok(EthAbiType::Int(bit_size(bits)?))
+                } else if let Some(length) = abi_type.strip_prefix("bytes") {
+                    match length.parse::<usize>() {
+                        Ok(length) if (1..=WORD_BYTES).contains(&length) => {
+                            Ok(EthAbiType::FixedBytes(length))
+                        }
+                        _ => Err(unsupported()),
+                    }
+                } else {
+                    Err(unsupported())
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for EthAbiType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EthAbiType::Uint(bits) => write!(f, "uint{bits}"),
+            EthAbiType::Int(bits) => write!(f, "int{bits}"),
+            EthAbiType::Address => write!(f, "address"),
+            EthAbiType::Bool => write!(f, "bool"),
+            EthAbiType::FixedBytes(length) => write!(f, "bytes{length}"),
+            EthAbiType::Bytes => write!(f, "bytes"),
+            EthAbiType::String => write!(f, "string"),
+            EthAbiType::Array(element_type) => write!(f, "{element_type}[]"),
+            EthAbiType::FixedArray(element_type, length) => write!(f, "{element_type}[{length}]"),
+        }
+    }
+}
+
+impl EthAbiType {
+    /// Returns whether values of this type are encoded out of line, with only an offset held in place.
+    fn is_dynamic(&self) -> bool {
+        match self {
+            EthAbiType::Bytes | EthAbiType::String | EthAbiType::Array(_) => true,
+            EthAbiType::FixedArray(element_type, _) => element_type.is_dynamic(),
+            _ => false,
+        }
+    }
+
+    /// The number of witnesses taken up by a value of this type, or `None` if this depends on the value's length.
+    fn num_witnesses(&self) -> Option<usize> {
+        match self {
+            EthAbiType::FixedBytes(length) => Some(*length),
+            EthAbiType::Bytes | EthAbiType::String | EthAbiType::Array(_) => None,
+            EthAbiType::FixedArray(element_type, length) => {
+                element_type.num_witnesses().map(|num_witnesses| num_witnesses * length)
+            }
+            _ => Some(1),
+        }
+    }
+
+    /// The number of bytes this type takes up in the head of an encoded tuple.
+    fn head_size(&self) -> usize {
+        match self {
+            EthAbiType::FixedArray(element_type, length) if !self.is_dynamic() => {
+                element_type.head_size() * length
+            }
+            _ => WORD_BYTES,
+        }
+    }
+}
+
+/// Reads the word starting at `position` in `data`.
+fn read_word(data: &[u8], position: usize) -> Result<&[u8; WORD_BYTES], String> {
+    position
+        .checked_add(WORD_BYTES)
+        .and_then(|end| data.get(position..end))
+        .map(|word| word.try_into().expect("slice is one word long"))
+        .ok_or_else(|| format!("Calldata is too short, expected a word at byte {position}"))
+}
+
+/// Reads an offset or length from the word starting at `position` in `data`.
+fn read_usize(data: &[u8], position: usize) -> Result<usize, String> {
+    let word = read_word(data, position)?;
+    let (high, low) = word.split_at(WORD_BYTES - 8);
+    if high.iter().any(|&byte| byte != 0) {
+        return Err(format!("Invalid offset or length at byte {position}"));
+    }
+    usize::try_from(u64::from_be_bytes(low.try_into().expect("slice is 8 bytes long")))
+        .map_err(|_| format!("Invalid offset or length at byte {position}"))
+}
+
+/// Converts a big-endian integer to a field element, rejecting it if it's not less than the field modulus.
+fn integer_to_field(bytes: &[u8], position: usize) -> Result<FieldElement, String> {
+    let field_element = FieldElement::from_be_bytes_reduce(bytes);
+    let significant_bytes = &bytes[bytes.iter().take_while(|&&byte| byte == 0).count()..];
+    let field_bytes = field_element.to_be_bytes();
+    if !field_bytes.ends_with(significant_bytes)
+        || field_bytes[..field_bytes.len() - significant_bytes.len()].iter().any(|&byte| byte != 0)
+    {
+        return Err(format!("Value at byte {position} does not fit in a field element"));
+    }
+    Ok(field_element)
+}
+
+/// Decodes the value of `abi_type` whose encoding starts at `position` in `data`, flattened into field elements.
+fn decode_value(
+    abi_type: &EthAbiType,
+    data: &[u8],
+    position: usize,
+) -> Result<Vec<FieldElement>, String> {
+    let invalid = || format!("Invalid value for {abi_type} at byte {position}");
+
+    match abi_type {
+        EthAbiType::Uint(bits) => {
+            let word = read_word(data, position)?;
+            let (padding, _) = word.split_at(WORD_BYTES - bits / 8);
+            if padding.iter().any(|&byte| byte != 0) {
+                return Err(invalid());
+            }
+            Ok(vec![integer_to_field(word, position)?])
+        }
+        EthAbiType::Int(bits) => {
+            // Signed integers are held in witnesses as their two's complement representation.
+            let word = read_word(data, position)?;
+            let (padding, value) = word.split_at(WORD_BYTES - bits / 8);
+            let sign_extension = if value[0] & 0x80 == 0 { 0x00 } else { 0xff };
+            if padding.iter().any(|&byte| byte != sign_extension) {
+                return Err(invalid());
+            }
+            Ok(vec![integer_to_field(value, position)?])
+        }
+        EthAbiType::Address => {
+            let word = read_word(data, position)?;
+            if word[..WORD_BYTES - 20].iter().any(|&byte| byte != 0) {
+                return Err(invalid());
+            }
+            Ok(vec![FieldElement::from_be_bytes_reduce(word)])
+        }
+        EthAbiType::Bool => match read_usize(data, position)? {
+            0 => Ok(vec![FieldElement::zero()]),
+            1 => Ok(vec![FieldElement::one()]),
+            _ => Err(invalid()),
+        },
+        EthAbiType::FixedBytes(length) => {
+            let word = read_word(data, position)?;
+            let (bytes, padding) = word.split_at(*length);
+            if padding.iter().any(|&byte| byte != 0) {
+                return Err(invalid());
+            }
+            Ok(bytes.iter().map(|&byte| FieldElement::from(byte as u128)).collect())
+        }
+        EthAbiType::Bytes | EthAbiType::String => {
+            let length = read_usize(data, position)?;
+            let start = position.checked_add(WORD_BYTES).ok_or_else(invalid)?;
+            let bytes = start.checked_add(length).and_then(|end| data.get(start..end)).ok_or_else(
+                || format!("Calldata is too short, expected {length} bytes at byte {start}"),
+            )?;
+            Ok(bytes.iter().map(|&byte| FieldElement::from(byte as u128)).collect())
+        }
+        EthAbiType::Array(element_type) => {
+            let length = read_usize(data, position)?;
+            let start = position.checked_add(WORD_BYTES).ok_or_else(invalid)?;
+            // Elements other than empty fixed arrays take up at least a word, which bounds the length before allocating.
+            if length > data.len().saturating_sub(start) / WORD_BYTES {
+                return Err(format!(
+                    "Calldata is too short, expected {length} array elements at byte {start}"
+                ));
+            }
+            let element_types = vec![element_type.as_ref().clone(); length];
+            let elements = decode_tuple(&element_types, data, start)?;
+            Ok(elements.into_iter().flatten().collect())
+        }
+        EthAbiType::FixedArray(element_type, length) => {
+            let element_types = vec![element_type.as_ref().clone(); *length];
+            let elements = decode_tuple(&element_types, data, position)?;
+            Ok(elements.into_iter().flatten().collect())
+        }
+    }
+}
+
+/// Decodes a tuple of `abi_types` whose encoding starts at `base` in `data`, returning the value of each element.
+fn decode_tuple(
+    abi_types: &[EthAbiType],
+    data: &[u8],
+    base: usize,
+) -> Result<Vec<Vec<FieldElement>>, String> {
+    let mut head = base;
+    let mut values = Vec::with_capacity(abi_types.len());
+    for abi_type in abi_types {
+        let position = if abi_type.is_dynamic() {
+            base.checked_add(read_usize(data, head)?)
+                .ok_or_else(|| format!("Invalid offset at byte {head}"))?
+        } else {
+            head
+        };
+        values.push(decode_value(abi_type, data, position)?);
+        head += abi_type.head_size();
+    }
+    Ok(values)
+}
+
+/// Reads the Ethereum ABI types named in `types`.
+fn parse_types(types: JsEthAbiTypes) -> Result<Vec<EthAbiType>, String> {
+    types
+        .iter()
+        .map(|abi_type| {
+            abi_type
+                .as_string()
+                .ok_or_else(|| "Ethereum ABI types must be strings".to_string())?
+                .parse()
+        })
+        .collect()
+}
+
+/// Decodes Ethereum ABI encoded data, assigning the selected parameters to witnesses.
+///
+/// Each parameter is assigned to consecutive witnesses starting at the index given for it in `mapping`. Integers,
+/// addresses and booleans take up a single witness, while `bytesN`, `bytes` and `string` values take up a witness
+/// for each byte and arrays take up the witnesses of each of their elements in turn. Signed integers are assigned
+/// their two's complement representation.
+///
+/// @param {string[]} types - The Ethereum ABI type of each encoded parameter, e.g. `["uint256", "address[]"]`.
+/// @param {string} calldata - The hex encoded parameters. Any 4 byte function selector prefixing them is skipped.
+/// @param {EthAbiWitnessMapping} mapping - The first witness index to assign each selected parameter to.
+/// @returns {WitnessMap} A witness map holding the selected parameters.
+#[wasm_bindgen(js_name = witnessFromEthAbi, skip_jsdoc)]
+pub fn witness_from_eth_abi(
+    types: JsEthAbiTypes,
+    calldata: String,
+    mapping: JsEthAbiWitnessMapping,
+) -> Result<JsWitnessMap, JsString> {
+    console_error_panic_hook::set_once();
+    let types = parse_types(types)?;
+    let mapping: BTreeMap<usize, u32> =
+        <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(mapping))
+            .map_err(|err| format!("Invalid Ethereum ABI witness mapping: {err}"))?;
+
+    let hex_str = calldata.strip_prefix("0x").unwrap_or(&calldata);
+    let data = decode_hex(hex_str).ok_or_else(|| format!("Invalid calldata: '{calldata}'"))?;
+    let data = match data.len() % WORD_BYTES {
+        SELECTOR_BYTES => &data[SELECTOR_BYTES..],
+        _ => &data[..],
+    };
+
+    let values = decode_tuple(&types, data, 0)?;
+    let mut witness_map = WitnessMap::new();
+    for (&parameter, &first_witness) in &mapping {
+        let values = values.get(parameter).ok_or_else(|| {
+            let num_parameters = values.len();
+            format!("Invalid Ethereum ABI witness mapping: there is no parameter {parameter} out of {num_parameters}")
+        })?;
+        for (offset, value) in values.iter().enumerate() {
+            let witness_index = u32::try_from(offset)
+                .ok()
+                .and_then(|offset| first_witness.checked_add(offset))
+                .ok_or_else(|| {
+                    format!("Invalid Ethereum ABI witness mapping: parameter {parameter} overflows the witness indices")
+                })?;
+            witness_map.insert(Witness(witness_index), *value);
+        }
+    }
+    Ok(witness_map.into())
+}
+
+/// Returns the ABI encoding of a word holding `value`.
+fn encode_usize(value: usize) -> [u8; WORD_BYTES] {
+    let mut word = [0; WORD_BYTES];
+    word[WORD_BYTES - 8..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+/// Returns `values` as bytes, failing if any of them doesn't fit within a byte.
+fn field_elements_to_bytes(values: &[FieldElement]) -> Result<Vec<u8>, String> {
+    values
+        .iter()
+        .map(|value| {
+            if value.num_bits() > 8 {
+                return Err(format!("Value {} does not fit in a byte", value.to_hex()));
+            }
+            Ok(value.to_u128() as u8)
+        })
+        .collect()
+}
+
+/// Encodes the value of `abi_type` held by `values`. Dynamic values are encoded as they appear in the tail of a tuple.
+fn encode_value(abi_type: &EthAbiType, values: &[FieldElement]) -> Result<Vec<u8>, String> {
+    let value = values.first().copied().unwrap_or_else(FieldElement::zero);
+    let fits = |bits: usize| {
+        if value.num_bits() as usize > bits {
+            return Err(format!("Value {} does not fit in {abi_type}", value.to_hex()));
+        }
+        Ok(value.to_be_bytes())
+    };
+
+    match abi_type {
+        EthAbiType::Uint(bits) => fits(*bits),
+        EthAbiType::Int(bits) => {
+            // Signed integers are held in witnesses as their two's complement representation, which is then
+            // sign extended to fill the word.
+            let mut word = fits(*bits)?;
+            let (padding, value) = word.split_at_mut(WORD_BYTES - bits / 8);
+            if value[0] & 0x80 != 0 {
+                padding.fill(0xff);
+            }
+            Ok(word)
+        }
+        EthAbiType::Address => fits(160),
+        EthAbiType::Bool => fits(1),
+        EthAbiType::FixedBytes(_) => {
+            let mut word = field_elements_to_bytes(values)?;
+            word.resize(WORD_BYTES, 0);
+            Ok(word)
+        }
+        EthAbiType::Bytes | EthAbiType::String => {
+            let mut encoded = encode_usize(values.len()).to_vec();
+            encoded.extend(field_elements_to_bytes(values)?);
+            let padded_len = (values.len() + WORD_BYTES - 1) / WORD_BYTES * WORD_BYTES;
+            encoded.resize(WORD_BYTES + padded_len, 0);
+            Ok(encoded)
+        }
+        EthAbiType::Array(element_type) => {
+            let num_witnesses = element_type.num_witnesses().ok_or_else(|| {
+                format!("Unsupported Ethereum ABI type for public inputs: '{abi_type}'")
+            })?;
+            if values.len() % num_witnesses != 0 {
+                return Err(format!(
+                    "{} values can't be split evenly into elements of {element_type}",
+                    values.len()
+                ));
+            }
+            let elements: Vec<&[FieldElement]> = values.chunks(num_witnesses).collect();
+            let element_types = vec![element_type.as_ref().clone(); elements.len()];
+            let mut encoded = encode_usize(elements.len()).to_vec();
+            encoded.extend(encode_tuple(&element_types, &elements)?);
+            Ok(encoded)
+        }
+        EthAbiType::FixedArray(element_type, length) => {
+            let num_witnesses = values.len() / length;
+            let elements: Vec<&[FieldElement]> = values.chunks(num_witnesses).collect();
+            encode_tuple(&vec![element_type.as_ref().clone(); *length], &elements)
+        }
+    }
+}
+
+/// Encodes a tuple of `abi_types`, the value of each element being held by the corresponding entry of `values`.
+fn encode_tuple(abi_types: &[EthAbiType], values: &[&[FieldElement]]) -> Result<Vec<u8>, String> {
+    let head_size: usize = abi_types.iter().map(EthAbiType::head_size).sum();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+    for (abi_type, values) in abi_types.iter().zip(values) {
+        let encoded = encode_value(abi_type, values)?;
+        if abi_type.is_dynamic() {
+            head.extend(encode_usize(head_size + tail.len()));
+            tail.extend(encoded);
+        } else {
+            head.extend(encoded);
+        }
+    }
+    head.extend(tail);
+    Ok(head)
+}
+
+/// Encodes a circuit's public inputs as Ethereum ABI encoded parameters, e.g. for calldata to a Solidity verifier.
+///
+/// Public inputs are ordered by witness index, covering both the circuit's public parameters and return values.
+/// They are assigned to each of `types` in turn, taking up witnesses as described for `witnessFromEthAbi`. A type
+/// whose length isn't fixed, e.g. `uint256[]`, may only come last and takes up all of the remaining public inputs.
+///
+/// Verifiers which take their public inputs as `bytes32[]` can be passed the encoding for `["uint256[]"]`.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} witness_map - The completed witness map after executing the circuit.
+/// @param {string[]} types - The Ethereum ABI type of each parameter the public inputs are encoded as.
+/// @returns {Uint8Array} The ABI encoded parameters, without a function selector.
+#[wasm_bindgen(js_name = publicInputsToEthAbi, skip_jsdoc)]
+pub fn public_inputs_to_eth_abi(
+    circuit: Vec<u8>,
+    witness_map: JsWitnessMapLike,
+    types: JsEthAbiTypes,
+) -> Result<Vec<u8>, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let witness_map = WitnessMap::try_from(witness_map)?;
+    let types = parse_types(types)?;
+
+    let public_inputs = circuit
+        .public_inputs()
+        .0
+        .into_iter()
+        .map(|witness| {
+            witness_map.get(&witness).copied().ok_or(format!(
+                "Failed to extract witness {} from witness map. Witness not found.",
+                witness.0
+            ))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut remaining = public_inputs.as_slice();
+    let mut values = Vec::with_capacity(types.len());
+    for (index, abi_type) in types.iter().enumerate() {
+        let num_witnesses = match abi_type.num_witnesses() {
+            Some(num_witnesses) => num_witnesses,
+            None if index + 1 == types.len() => remaining.len(),
+            None => {
+                let message = format!(
+                    "Ethereum ABI type '{abi_type}' has a dynamic length so must come last"
+                );
+                return Err(message.into());
+            }
+        };
+        if num_witnesses > remaining.len() {
+            return Err(format!(
+                "The circuit has {} public inputs which is too few for the Ethereum ABI types",
+                public_inputs.len()
+            )
+            .into());
+        }
+        let (value, rest) = remaining.split_at(num_witnesses);
+        values.push(value);
+        remaining = rest;
+    }
+    if !remaining.is_empty() {
+        return Err(format!(
+            "The circuit has {} public inputs but the Ethereum ABI types only cover {}",
+            public_inputs.len(),
+            public_inputs.len() - remaining.len()
+        )
+        .into());
+    }
+
+    Ok(encode_tuple(&types, &values)?)
+}
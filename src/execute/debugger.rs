@@ -0,0 +1,491 @@
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use acvm::{
+    acir::{
+        brillig::{ForeignCallResult, Value},
+        circuit::{brillig::Brillig, Opcode},
+        native_types::{Witness, WitnessMap},
+    },
+    brillig_vm::VMStatus,
+    FieldElement,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Function, Promise};
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::{js_witness_map::field_element_to_js_string, JsWitnessMap};
+
+use super::{step_limit::load_brillig_vm, SimulatedBackend};
+
+#[wasm_bindgen(typescript_custom_section)]
+const DEBUGGER_CALLBACKS: &'static str = r#"
+/**
+* A command which resumes execution after it has paused.
+* - `continue` runs until the next breakpoint, or to the end of the Brillig call when stepping through Brillig.
+* - `step` pauses again before the next opcode is attempted, or after the next Brillig instruction when stepping through Brillig.
+*   A Brillig instruction which makes a foreign call is stepped over, with the `ForeignCallHandler` being called before it's reported.
+* - `stepOver` is the same as `step`.
+* - `stepInto` is the same as `step`, except that stepping through Brillig also pauses before a foreign call is made with its inputs.
+*/
+export type DebuggerCommand = "continue" | "step" | "stepOver" | "stepInto";
+
+/**
+* A callback which is notified when execution pauses before attempting an opcode with a breakpoint.
+* Execution resumes once the returned command resolves, continuing if none is returned. Throwing or rejecting aborts execution.
+* @callback BreakpointCallback
+* @param {number} index - The index of the opcode within the circuit.
+* @param {string} opcode - A human readable description of the opcode.
+* @param {WitnessMap} witness - The witness solved so far.
+* @returns {DebuggerCommand | Promise<DebuggerCommand>} How execution should resume.
+*/
+export type BreakpointCallback = (index: number, opcode: string, witness: WitnessMap) => void | DebuggerCommand | Promise<void | DebuggerCommand>;
+
+/**
+* A callback which is notified whenever a watched witness is assigned.
+* Execution resumes once the returned promise resolves. Throwing or rejecting aborts execution.
+* @callback WatchpointCallback
+* @param {number} witness - The index of the watched witness.
+* @param {string} value - The value which was assigned to the witness.
+* @param {number} opcodeIndex - The index of the opcode which assigned the witness.
+* @param {string} opcode - A human readable description of the opcode which assigned the witness.
+* @returns {void | Promise<void>}
+*/
+export type WatchpointCallback = (witness: number, value: string, opcodeIndex: number, opcode: string) => void | Promise<void>;
+
+/**
+* @typedef {Object} BrilligStep - A single Brillig instruction which has been executed.
+* @property {number} opcodeIndex - The index of the Brillig opcode within the circuit.
+* @property {number} programCounter - The position of the instruction within the Brillig bytecode.
+* @property {string} instruction - A human readable description of the instruction.
+* @property {Record<number, string>} modifiedRegisters - The new values of the registers which the instruction modified, keyed by register index.
+* @property {string[]} registers - The values of all of the VM's registers after the instruction was executed.
+* @property {string[]} memory - The contents of the VM's memory after the instruction was executed.
+* @property {BrilligForeignCall} foreignCall - Only present when stepping into a foreign call, in which case the instruction hasn't been executed yet and the `ForeignCallHandler` is called once execution resumes.
+*/
+export type BrilligStep = {
+  opcodeIndex: number;
+  programCounter: number;
+  instruction: string;
+  modifiedRegisters: Record<number, string>;
+  registers: string[];
+  memory: string[];
+  foreignCall?: BrilligForeignCall;
+}
+
+/**
+* @typedef {Object} BrilligForeignCall - A foreign call which a Brillig instruction is about to make.
+* @property {string} name - The name of the foreign call.
+* @property {string[][]} inputs - The inputs which will be passed to the `ForeignCallHandler`.
+*/
+export type BrilligForeignCall = {
+  name: string;
+  inputs: string[][];
+}
+
+/**
+* A callback which is notified after each Brillig instruction is executed.
+* Execution resumes once the returned command resolves, with `continue` running the rest of the Brillig call without stepping.
+* Throwing or rejecting aborts execution.
+* @callback BrilligStepCallback
+* @param {BrilligStep} step - The instruction which was executed.
+* @returns {DebuggerCommand | Promise<DebuggerCommand>} How execution should resume.
+*/
+export type BrilligStepCallback = (step: BrilligStep) => void | DebuggerCommand | Promise<void | DebuggerCommand>;
+
+/**
+* @typedef {Object} BrilligState - The state of the Brillig VM at the point where it halted.
+* @property {number} opcodeIndex - The index of the Brillig opcode within the circuit.
+* @property {number} programCounter - The position within the Brillig bytecode of the instruction the VM halted at.
+* @property {string[]} registers - The values of all of the VM's registers.
+* @property {string[]} memory - The contents of the VM's memory.
+*/
+export type BrilligState = {
+  opcodeIndex: number;
+  programCounter: number;
+  registers: string[];
+  memory: string[];
+}
+
+/**
+* The reason a Brillig call halted.
+* - `foreignCall` when it's waiting on the result of a foreign call, which is made once the callback returns.
+* - `failure` when it trapped, with execution failing once the callback returns.
+*/
+export type BrilligPauseReason = "foreignCall" | "failure";
+
+/**
+* A callback which is notified with the state of the Brillig VM whenever a Brillig call halts before finishing.
+* Execution resumes once the returned promise resolves. Throwing or rejecting aborts execution.
+* @callback BrilligPauseCallback
+* @param {BrilligPauseReason} reason - Why the Brillig call halted.
+* @param {BrilligState} state - The state of the Brillig VM.
+* @returns {void | Promise<void>}
+*/
+export type BrilligPauseCallback = (reason: BrilligPauseReason, state: BrilligState) => void | Promise<void>;
+"#;
+
+/// The position of execution within a circuit: the opcode being attempted, along with the instruction within it
+/// when stepping through Brillig.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OpcodeLocation {
+    pub(crate) acir_index: usize,
+    pub(crate) brillig_index: Option<usize>,
+}
+
+/// Where execution currently is, shared with any debugging tools which report on it.
+pub(crate) type CurrentLocation = Rc<Cell<Option<OpcodeLocation>>>;
+
+/// How execution resumes after pausing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DebuggerCommand {
+    Continue,
+    Step,
+    /// As with `Step`, but also pausing before Brillig makes a foreign call.
+    StepInto,
+}
+
+/// Calls `callback` with `args`, returning the command it resumes execution with once it resolves.
+///
+/// Should the callback throw or reject, the error message is passed to `aborted` to describe the failure.
+async fn wait_for_command(
+    callback: &Function,
+    args: &Array,
+    aborted: impl Fn(String) -> String,
+) -> Result<DebuggerCommand, String> {
+    let aborted = |err: JsValue| {
+        let message = err
+            .as_string()
+            .or_else(|| err.dyn_ref::<js_sys::Error>().map(|err| String::from(err.message())))
+            .unwrap_or_else(|| "Unknown".to_owned());
+        aborted(message)
+    };
+
+    let command = callback.apply(&JsValue::NULL, args).map_err(aborted)?;
+    let command = JsFuture::from(Promise::resolve(&command)).await.map_err(aborted)?;
+    match command.as_string().as_deref() {
+        None if command.is_undefined() => Ok(DebuggerCommand::Continue),
+        Some("continue") => Ok(DebuggerCommand::Continue),
+        Some("step" | "stepOver") => Ok(DebuggerCommand::Step),
+        Some("stepInto") => Ok(DebuggerCommand::StepInto),
+        _ => Err(format!("Invalid debugger command: {command:?}")),
+    }
+}
+
+/// The state needed to pause execution at breakpoints, handing control to JS until it resumes execution.
+pub(crate) struct Breakpoints {
+    /// Indices of the opcodes which execution pauses before attempting.
+    opcode_indices: BTreeSet<usize>,
+    on_breakpoint: Function,
+    /// Whether execution pauses before attempting every opcode, regardless of whether it has a breakpoint.
+    stepping: bool,
+}
+
+impl Breakpoints {
+    pub(crate) fn new(opcode_indices: &[usize], on_breakpoint: Function) -> Self {
+        Breakpoints {
+            opcode_indices: opcode_indices.iter().copied().collect(),
+            on_breakpoint,
+            stepping: false,
+        }
+    }
+
+    /// Returns whether execution should pause before attempting the opcode at `opcode_index`.
+    pub(crate) fn pauses_at(&self, opcode_index: usize) -> bool {
+        self.stepping || self.opcode_indices.contains(&opcode_index)
+    }
+
+    /// Hands control to the `onBreakpoint` callback, returning once it has resumed execution.
+    pub(crate) async fn pause(
+        &mut self,
+        opcode_index: usize,
+        opcode: &Opcode,
+        witness_map: &WitnessMap,
+    ) -> Result<(), String> {
+        let args = Array::of3(
+            &JsValue::from(opcode_index as u32),
+            &JsValue::from(opcode.to_string()),
+            &JsWitnessMap::from(witness_map.clone()),
+        );
+        let command = wait_for_command(&self.on_breakpoint, &args, |message| {
+            format!("Execution aborted at breakpoint on opcode {opcode_index}: {message}")
+        })
+        .await?;
+
+        self.stepping = command != DebuggerCommand::Continue;
+        Ok(())
+    }
+}
+
+/// The state needed to pause execution whenever a watched witness is assigned, handing control to JS until it
+/// resumes execution.
+pub(crate) struct Watchpoints {
+    witnesses: BTreeSet<Witness>,
+    on_watchpoint: Function,
+}
+
+impl Watchpoints {
+    pub(crate) fn new(witnesses: &[u32], on_watchpoint: Function) -> Self {
+        Watchpoints { witnesses: witnesses.iter().copied().map(Witness).collect(), on_watchpoint }
+    }
+
+    /// Hands each watched witness among `assigned_witnesses` to the `onWatchpoint` callback in turn, returning once
+    /// the callback has resumed execution.
+    pub(crate) async fn notify(
+        &self,
+        opcode_index: usize,
+        opcode: &Opcode,
+        assigned_witnesses: &BTreeMap<Witness, FieldElement>,
+    ) -> Result<(), String> {
+        let watched =
+            assigned_witnesses.iter().filter(|(witness, _)| self.witnesses.contains(witness));
+        for (witness, value) in watched {
+            let args = Array::of4(
+                &JsValue::from(witness.witness_index()),
+                &field_element_to_js_string(value),
+                &JsValue::from(opcode_index as u32),
+                &JsValue::from(opcode.to_string()),
+            );
+            wait_for_command(&self.on_watchpoint, &args, |message| {
+                format!(
+                    "Execution aborted at watchpoint on witness {}: {message}",
+                    witness.witness_index()
+                )
+            })
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BrilligStep {
+    opcode_index: usize,
+    program_counter: usize,
+    instruction: String,
+    modified_registers: BTreeMap<usize, String>,
+    registers: Vec<String>,
+    memory: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    foreign_call: Option<BrilligForeignCall>,
+}
+
+#[derive(Serialize)]
+struct BrilligForeignCall {
+    name: String,
+    inputs: Vec<Vec<String>>,
+}
+
+/// Hex encodes `values` held by a Brillig VM's registers or memory.
+fn encode_values(values: &[Value]) -> Vec<String> {
+    values.iter().map(|value| field_element_to_js_string(&value.to_field()).into()).collect()
+}
+
+/// Steps through Brillig calls one instruction at a time, handing control to JS after each instruction.
+///
+/// The ACVM runs Brillig to completion without any way to interrupt it, so each Brillig call is stepped through
+/// on a separate VM ahead of the ACVM running it.
+pub(crate) struct BrilligStepper {
+    on_brillig_step: Function,
+    /// Updated with each instruction as it's stepped through, if set.
+    current_location: Option<CurrentLocation>,
+}
+
+impl BrilligStepper {
+    pub(crate) fn new(
+        on_brillig_step: Function,
+        current_location: Option<CurrentLocation>,
+    ) -> Self {
+        BrilligStepper { on_brillig_step, current_location }
+    }
+
+    /// Steps through `brillig` until it finishes or requires a foreign call beyond `foreign_call_results`.
+    ///
+    /// `stepped` is the number of instructions which were stepped through before the Brillig call last halted for a
+    /// foreign call. Returns the number of instructions stepped through so far, or `None` if the `onBrilligStep`
+    /// callback asked for the rest of the call to be run without stepping.
+    ///
+    /// A foreign call is only paused before if the callback stepped into it, as there's no earlier command to step
+    /// into a foreign call made by the first instruction.
+    pub(crate) async fn step(
+        &self,
+        backend: &SimulatedBackend,
+        opcode_index: usize,
+        brillig: &Brillig,
+        witness_map: &WitnessMap,
+        foreign_call_results: &[ForeignCallResult],
+        mut stepped: usize,
+    ) -> Result<Option<usize>, String> {
+        let Some(mut vm) = load_brillig_vm(&backend, brillig, witness_map, foreign_call_results)
+        else {
+            return Ok(None);
+        };
+        // The VM can't be resumed once the ACVM has resolved a foreign call so the instructions which were
+        // already stepped through are replayed.
+        for _ in 0..stepped {
+            vm.process_opcode();
+        }
+
+        let mut step_into = false;
+        loop {
+            let program_counter = vm.clone().program_counter();
+            let previous_registers = vm.get_registers().inner.clone();
+            let status = vm.process_opcode();
+            if let VMStatus::ForeignCallWait { function, inputs } = &status {
+                // The instruction is executed once the ACVM has resolved the foreign call.
+                if !step_into {
+                    return Ok(Some(stepped));
+                }
+                let brillig_step = BrilligStep {
+                    opcode_index,
+                    program_counter,
+                    instruction: format!("{:?}", brillig.bytecode[program_counter]),
+                    modified_registers: BTreeMap::new(),
+                    registers: encode_values(&previous_registers),
+                    memory: encode_values(vm.get_memory()),
+                    foreign_call: Some(BrilligForeignCall {
+                        name: function.clone(),
+                        inputs: inputs.iter().map(|input| encode_values(input)).collect(),
+                    }),
+                };
+                let command = self.pause(&brillig_step).await?;
+                return Ok((command != DebuggerCommand::Continue).then_some(stepped));
+            }
+            stepped += 1;
+
+            let registers = &vm.get_registers().inner;
+            let modified_registers = registers
+                .iter()
+                .enumerate()
+                .filter(|(index, value)| match previous_registers.get(*index) {
+                    Some(previous) => previous != *value,
+                    // Writing past the last register fills the registers before it with zero.
+                    None => index + 1 == registers.len() || !value.to_field().is_zero(),
+                })
+                .map(|(index, value)| (index, field_element_to_js_string(&value.to_field()).into()))
+                .collect();
+            let brillig_step = BrilligStep {
+                opcode_index,
+                program_counter,
+                instruction: format!("{:?}", brillig.bytecode[program_counter]),
+                modified_registers,
+                registers: encode_values(registers),
+                memory: encode_values(vm.get_memory()),
+                foreign_call: None,
+            };
+            let command = self.pause(&brillig_step).await?;
+            step_into = command == DebuggerCommand::StepInto;
+
+            if command == DebuggerCommand::Continue {
+                return Ok(None);
+            }
+            if !matches!(status, VMStatus::InProgress) {
+                return Ok(Some(stepped));
+            }
+        }
+    }
+
+    /// Hands `brillig_step` to the `onBrilligStep` callback, returning the command it resumes execution with.
+    async fn pause(&self, brillig_step: &BrilligStep) -> Result<DebuggerCommand, String> {
+        let opcode_index = brillig_step.opcode_index;
+        if let Some(current_location) = &self.current_location {
+            current_location.set(Some(OpcodeLocation {
+                acir_index: opcode_index,
+                brillig_index: Some(brillig_step.program_counter),
+            }));
+        }
+        let args = Array::of1(&<JsValue as JsValueSerdeExt>::from_serde(brillig_step).unwrap());
+        wait_for_command(&self.on_brillig_step, &args, |message| {
+            format!(
+                "Execution aborted while stepping through Brillig opcode {opcode_index}: {message}"
+            )
+        })
+        .await
+    }
+}
+
+/// Why a Brillig call halted before finishing.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BrilligPauseReason {
+    ForeignCall,
+    Failure,
+}
+
+impl BrilligPauseReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            BrilligPauseReason::ForeignCall => "foreignCall",
+            BrilligPauseReason::Failure => "failure",
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BrilligState {
+    opcode_index: usize,
+    program_counter: usize,
+    registers: Vec<String>,
+    memory: Vec<String>,
+}
+
+/// Exposes the registers and memory of the Brillig VM to JS whenever a Brillig call halts before finishing.
+///
+/// As with stepping, the ACVM gives no access to its Brillig VM so the Brillig call is rerun on a separate VM to
+/// recover its state.
+pub(crate) struct BrilligInspector {
+    on_brillig_pause: Function,
+    /// The step limit on Brillig calls, past which the VM isn't run as the Brillig call will have failed there.
+    max_steps: Option<usize>,
+}
+
+impl BrilligInspector {
+    pub(crate) fn new(on_brillig_pause: Function, max_steps: Option<usize>) -> Self {
+        BrilligInspector { on_brillig_pause, max_steps }
+    }
+
+    /// Hands the state of `brillig` after running it with `foreign_call_results` to the `onBrilligPause` callback,
+    /// returning once the callback has resolved.
+    pub(crate) async fn pause(
+        &self,
+        reason: BrilligPauseReason,
+        backend: &SimulatedBackend,
+        opcode_index: usize,
+        brillig: &Brillig,
+        witness_map: &WitnessMap,
+        foreign_call_results: &[ForeignCallResult],
+    ) -> Result<(), String> {
+        let Some(mut vm) = load_brillig_vm(&backend, brillig, witness_map, foreign_call_results)
+        else {
+            return Ok(());
+        };
+        let mut steps = 0;
+        while self.max_steps.map_or(true, |max_steps| steps < max_steps)
+            && matches!(vm.process_opcode(), VMStatus::InProgress)
+        {
+            steps += 1;
+        }
+
+        let state = BrilligState {
+            opcode_index,
+            program_counter: vm.clone().program_counter(),
+            registers: encode_values(&vm.get_registers().inner),
+            memory: encode_values(vm.get_memory()),
+        };
+        let args = Array::of2(
+            &JsValue::from(reason.as_str()),
+            &<JsValue as JsValueSerdeExt>::from_serde(&state).unwrap(),
+        );
+        wait_for_command(&self.on_brillig_pause, &args, |message| {
+            format!("Execution aborted while inspecting Brillig opcode {opcode_index}: {message}")
+        })
+        .await?;
+        Ok(())
+    }
+}
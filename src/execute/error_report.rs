@@ -0,0 +1,82 @@
+use acvm::blackbox_solver::sha256;
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{encode_bytes, read_circuit, ExecutionError, ExecutionOptions};
+use crate::build_info::{BuildInfo, BUILD_INFO};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ERROR_REPORT: &'static str = r#"
+/**
+* @typedef {Object} ErrorReport - A description of a failed execution which is safe to send to an error reporting service.
+* It holds no witness values, foreign call data or error messages, which may contain private inputs.
+* @property {string} errorCode - `"SolverStalled"` if no progress could be made on the remaining opcodes, otherwise `"OpcodeFailed"`.
+* @property {number} opcodeIndex - The index of the opcode being attempted when execution failed, if it was attempting one.
+* @property {string} opcodeKind - The kind of that opcode, e.g. `"brillig"` or `"arithmetic"`.
+* @property {string} circuitHash - A sha256 hash of the serialized circuit.
+* @property {BuildInfo} buildInfo - Information on how the installed package was built.
+*/
+export type ErrorReport = {
+  errorCode: "SolverStalled" | "OpcodeFailed";
+  opcodeIndex?: number;
+  opcodeKind?: string;
+  circuitHash: string;
+  buildInfo: BuildInfo;
+}
+
+/**
+* A callback which receives a report each time execution fails. Anything it returns or throws is ignored.
+* @callback ErrorReportCallback
+* @param {ErrorReport} report - A description of the failure which holds no witness values.
+*/
+export type ErrorReportCallback = (report: ErrorReport) => void;
+"#;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorReport {
+    error_code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opcode_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opcode_kind: Option<String>,
+    circuit_hash: String,
+    build_info: &'static BuildInfo,
+}
+
+/// Hands a report on `err`, the failure of an execution of `circuit`, to any `onErrorReport` callback in `options`.
+///
+/// Only the shape of the failure is reported so the report can't leak the inputs to the circuit.
+pub(super) fn report_error(options: &ExecutionOptions, circuit: &[u8], err: &ExecutionError) {
+    let Some(on_error_report) = &options.on_error_report else {
+        return;
+    };
+
+    // A stalled execution wasn't attempting any particular opcode when it gave up.
+    let opcode_index = match &err.stalled {
+        Some(_) => None,
+        None => options
+            .current_location
+            .as_ref()
+            .and_then(|location| location.get())
+            .map(|location| location.acir_index),
+    };
+    // The circuit is only parsed again on failure so that executions which succeed don't hold onto its opcodes.
+    let opcode_kind = opcode_index.and_then(|index| {
+        let circuit = read_circuit(circuit).ok()?;
+        circuit.opcodes.get(index).map(|opcode| opcode.name().to_owned())
+    });
+    let hash = sha256(circuit).expect("sha256 should not fail");
+    let report = ErrorReport {
+        error_code: if err.stalled.is_some() { "SolverStalled" } else { "OpcodeFailed" },
+        opcode_index,
+        opcode_kind,
+        circuit_hash: format!("0x{}", encode_bytes(&hash)),
+        build_info: &BUILD_INFO,
+    };
+
+    let report = <JsValue as JsValueSerdeExt>::from_serde(&report).unwrap();
+    // Failing to report an error shouldn't mask the error itself.
+    let _ = on_error_report.call1(&JsValue::NULL, &report);
+}
@@ -0,0 +1,24 @@
+use acvm::blackbox_solver::sha256;
+
+use crate::{build_info::BUILD_INFO, foreign_call::ForeignCallTranscript};
+
+use super::encode_bytes;
+
+/// Returns a fingerprint of an execution of `circuit` which made `foreign_calls`, hex encoded.
+///
+/// The fingerprint covers the serialized circuit, the foreign calls made regardless of the order in which they were
+/// made, and the build of this package. Two executions with the same fingerprint simulated the same computation with
+/// the same oracle data.
+pub(super) fn execution_fingerprint(
+    circuit: &[u8],
+    foreign_calls: &ForeignCallTranscript,
+) -> String {
+    let hash = |bytes: &[u8]| sha256(bytes).expect("sha256 should not fail");
+    let build_info = rmp_serde::to_vec(&BUILD_INFO).expect("build info should always serialize");
+
+    let mut bytes = Vec::new();
+    bytes.extend(hash(circuit));
+    bytes.extend(foreign_calls.sorted_hash());
+    bytes.extend(hash(&build_info));
+    format!("0x{}", encode_bytes(&hash(&bytes)))
+}
@@ -0,0 +1,284 @@
+use std::io::Read;
+
+use acvm::acir::circuit::{Circuit, Opcode, PublicInputs};
+use flate2::bufread::{DeflateDecoder, GzDecoder};
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CIRCUIT_DESERIALIZATION_ERROR: &'static str = r#"
+export type CircuitFormat = "gzip-bincode" | "deflate-messagepack";
+
+/**
+* An error thrown when a circuit cannot be deserialized.
+* @property {CircuitFormat} format - The serialization format which the circuit was expected to be encoded in.
+* @property {"decompress" | "decode"} stage - Whether the circuit failed to decompress or failed to decode once decompressed.
+* @property {number} offset - The byte offset at which parsing failed. This is an offset into the serialized circuit when
+* decompressing and an offset into the decompressed circuit when decoding.
+*/
+export type CircuitDeserializationError = Error & {
+  format: CircuitFormat;
+  stage: "decompress" | "decode";
+  offset: number;
+}
+"#;
+
+/// The first bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The serialization formats in which ACIR circuits have been distributed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitFormat {
+    /// Bincode compressed with gzip. This is the format produced by `Circuit::write`.
+    GzipBincode,
+    /// MessagePack compressed with raw deflate, as produced by ACIR's `serialize-messagepack` feature.
+    DeflateMessagePack,
+}
+
+impl CircuitFormat {
+    fn detect(bytes: &[u8]) -> CircuitFormat {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            CircuitFormat::GzipBincode
+        } else {
+            CircuitFormat::DeflateMessagePack
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            CircuitFormat::GzipBincode => "gzip-bincode",
+            CircuitFormat::DeflateMessagePack => "deflate-messagepack",
+        }
+    }
+}
+
+/// The step of deserialization at which a [`CircuitDeserializationError`] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeserializationStage {
+    Decompress,
+    Decode,
+}
+
+/// An error encountered while deserializing a circuit, along with where parsing failed.
+#[derive(Debug)]
+pub(crate) struct CircuitDeserializationError {
+    format: CircuitFormat,
+    stage: DeserializationStage,
+    offset: usize,
+    message: String,
+}
+
+impl std::fmt::Display for CircuitDeserializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stage = match self.stage {
+            DeserializationStage::Decompress => "decompress",
+            DeserializationStage::Decode => "decode",
+        };
+        write!(
+            f,
+            "Failed to deserialize circuit: could not {stage} {} at byte {}: {}",
+            self.format.name(),
+            self.offset,
+            self.message
+        )
+    }
+}
+
+impl From<CircuitDeserializationError> for JsValue {
+    fn from(err: CircuitDeserializationError) -> Self {
+        let error = js_sys::Error::new(&err.to_string());
+        let stage = match err.stage {
+            DeserializationStage::Decompress => "decompress",
+            DeserializationStage::Decode => "decode",
+        };
+        for (key, value) in [
+            ("format", JsValue::from_str(err.format.name())),
+            ("stage", JsValue::from_str(stage)),
+            ("offset", JsValue::from(err.offset as u32)),
+        ] {
+            Reflect::set(&error, &JsValue::from_str(key), &value)
+                .expect("setting a property on an `Error` should not fail");
+        }
+        error.into()
+    }
+}
+
+/// Decompresses a serialized circuit, detecting which of the supported serialization formats it is encoded in.
+pub(crate) fn decompress_circuit(bytes: &[u8]) -> Result<Vec<u8>, CircuitDeserializationError> {
+    let format = CircuitFormat::detect(bytes);
+    let mut remaining = bytes;
+    let mut serialized = Vec::new();
+    let result = match format {
+        CircuitFormat::GzipBincode => GzDecoder::new(&mut remaining).read_to_end(&mut serialized),
+        CircuitFormat::DeflateMessagePack => {
+            DeflateDecoder::new(&mut remaining).read_to_end(&mut serialized)
+        }
+    };
+
+    result.map_err(|err| CircuitDeserializationError {
+        format,
+        stage: DeserializationStage::Decompress,
+        offset: bytes.len() - remaining.len(),
+        message: err.to_string(),
+    })?;
+    Ok(serialized)
+}
+
+/// Deserializes a circuit, detecting which of the supported serialization formats it is encoded in.
+pub(crate) fn read_circuit(bytes: &[u8]) -> Result<Circuit, CircuitDeserializationError> {
+    let format = CircuitFormat::detect(bytes);
+    let serialized = decompress_circuit(bytes)?;
+
+    // Decoding from a slice reader leaves the undecoded bytes in `remaining`, which locates any failure.
+    let mut remaining = serialized.as_slice();
+    let result = match format {
+        CircuitFormat::GzipBincode => {
+            bincode::deserialize_from(&mut remaining).map_err(|err| err.to_string())
+        }
+        CircuitFormat::DeflateMessagePack => {
+            rmp_serde::from_read(&mut remaining).map_err(|err| err.to_string())
+        }
+    };
+
+    result.map_err(|message| CircuitDeserializationError {
+        format,
+        stage: DeserializationStage::Decode,
+        offset: serialized.len() - remaining.len(),
+        message,
+    })
+}
+
+/// Counts the bytes read through it, to locate decoding errors within a decompressed stream.
+struct CountingReader<R> {
+    inner: R,
+    count: usize,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read;
+        Ok(read)
+    }
+}
+
+/// Where an [`OpcodeStream`] takes its opcodes from.
+enum OpcodeSource<'a> {
+    /// Opcodes decoded one at a time as a gzip-bincode circuit is decompressed, with the number still to decode.
+    Streamed { decoder: CountingReader<GzDecoder<&'a [u8]>>, remaining: u64 },
+    /// The opcodes of a circuit which was deserialized in full, as MessagePack circuits can't be streamed.
+    Deserialized {
+        opcodes: std::vec::IntoIter<Opcode>,
+        public_inputs: (PublicInputs, PublicInputs),
+    },
+}
+
+/// Deserializes the opcodes of a circuit one at a time, without holding the whole `Circuit` in memory.
+///
+/// Only gzip-bincode circuits are streamed. Circuits in other formats are deserialized in full up front.
+pub(crate) struct OpcodeStream<'a> {
+    bytes: &'a [u8],
+    format: CircuitFormat,
+    pub(crate) current_witness_index: u32,
+    pub(crate) opcode_count: usize,
+    source: OpcodeSource<'a>,
+}
+
+impl<'a> OpcodeStream<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Result<OpcodeStream<'a>, CircuitDeserializationError> {
+        let format = CircuitFormat::detect(bytes);
+        if format != CircuitFormat::GzipBincode {
+            let circuit = read_circuit(bytes)?;
+            return Ok(OpcodeStream {
+                bytes,
+                format,
+                current_witness_index: circuit.current_witness_index,
+                opcode_count: circuit.opcodes.len(),
+                source: OpcodeSource::Deserialized {
+                    opcodes: circuit.opcodes.into_iter(),
+                    public_inputs: (circuit.public_parameters, circuit.return_values),
+                },
+            });
+        }
+
+        let mut stream = OpcodeStream {
+            bytes,
+            format,
+            current_witness_index: 0,
+            opcode_count: 0,
+            source: OpcodeSource::Streamed {
+                decoder: CountingReader { inner: GzDecoder::new(bytes), count: 0 },
+                remaining: 0,
+            },
+        };
+        // A `Circuit` is encoded as its witness count followed by the length of its opcodes.
+        stream.current_witness_index = stream.decode()?;
+        let opcode_count: u64 = stream.decode()?;
+        stream.opcode_count = opcode_count as usize;
+        if let OpcodeSource::Streamed { remaining, .. } = &mut stream.source {
+            *remaining = opcode_count;
+        }
+        Ok(stream)
+    }
+
+    pub(crate) fn format_name(&self) -> &'static str {
+        self.format.name()
+    }
+
+    /// Decodes the next value from a streamed circuit.
+    fn decode<T: serde::de::DeserializeOwned>(&mut self) -> Result<T, CircuitDeserializationError> {
+        let OpcodeSource::Streamed { decoder, .. } = &mut self.source else {
+            unreachable!("only streamed circuits are decoded incrementally");
+        };
+        bincode::deserialize_from(&mut *decoder).map_err(|err| match *err {
+            // Reading from the decoder fails if the circuit can't be decompressed.
+            bincode::ErrorKind::Io(err) => CircuitDeserializationError {
+                format: self.format,
+                stage: DeserializationStage::Decompress,
+                offset: self.bytes.len() - decoder.inner.get_ref().len(),
+                message: err.to_string(),
+            },
+            err => CircuitDeserializationError {
+                format: self.format,
+                stage: DeserializationStage::Decode,
+                offset: decoder.count,
+                message: err.to_string(),
+            },
+        })
+    }
+
+    /// Consumes any opcodes left in the stream, returning the circuit's public parameters and return values.
+    pub(crate) fn finish(
+        mut self,
+    ) -> Result<(PublicInputs, PublicInputs), CircuitDeserializationError> {
+        for opcode in self.by_ref() {
+            opcode?;
+        }
+        match self.source {
+            OpcodeSource::Streamed { .. } => Ok((self.decode()?, self.decode()?)),
+            OpcodeSource::Deserialized { public_inputs, .. } => Ok(public_inputs),
+        }
+    }
+}
+
+impl Iterator for OpcodeStream<'_> {
+    type Item = Result<Opcode, CircuitDeserializationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.source {
+            OpcodeSource::Streamed { remaining: 0, .. } => None,
+            OpcodeSource::Streamed { remaining, .. } => {
+                *remaining -= 1;
+                let opcode = self.decode();
+                if opcode.is_err() {
+                    // Nothing after a malformed opcode can be decoded.
+                    if let OpcodeSource::Streamed { remaining, .. } = &mut self.source {
+                        *remaining = 0;
+                    }
+                }
+                Some(opcode)
+            }
+            OpcodeSource::Deserialized { opcodes, .. } => opcodes.next().map(Ok),
+        }
+    }
+}
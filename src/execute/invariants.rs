@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use acvm::{
+    acir::native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+use js_sys::{Array, Function, JsString, Promise, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::JsWitnessMap;
+
+use super::overrides::read_function;
+
+#[wasm_bindgen(typescript_custom_section)]
+const INVARIANT: &'static str = r#"
+/**
+* @typedef {Object} Invariant - An application-level check on the witness, evaluated after each pass over the opcodes.
+* @property {string} name - A name for the invariant, included in the error when it's violated.
+* @property {number[]} witnesses - Indices of the witnesses which are passed to `check`.
+* @property {InvariantCheck} check - Decides whether the invariant holds.
+*/
+export type Invariant = {
+  name: string;
+  witnesses: number[];
+  check: InvariantCheck;
+}
+
+/**
+* A callback which checks that an invariant holds for the witnesses solved so far.
+* @callback InvariantCheck
+* @param {WitnessMap} values - The values of the invariant's witnesses which have been solved so far. Not called until at least one is solved.
+* @returns {boolean | string | void | Promise<boolean | string | void>} `false`, or a message describing the violation, to abort execution.
+*/
+export type InvariantCheck = (values: WitnessMap) => boolean | string | void | Promise<boolean | string | void>;
+"#;
+
+#[derive(Debug)]
+struct Invariant {
+    name: String,
+    witnesses: Vec<Witness>,
+    check: Function,
+}
+
+impl TryFrom<JsValue> for Invariant {
+    type Error = String;
+
+    fn try_from(js_invariant: JsValue) -> Result<Self, Self::Error> {
+        if !js_invariant.is_object() {
+            return Err("each invariant must be an object".to_string());
+        }
+
+        let name = Reflect::get(&js_invariant, &JsValue::from_str("name"))
+            .ok()
+            .and_then(|name| name.as_string())
+            .ok_or_else(|| "each invariant must have a name".to_string())?;
+        let witnesses = Reflect::get(&js_invariant, &JsValue::from_str("witnesses"))
+            .ok()
+            .and_then(|witnesses| witnesses.dyn_into::<Array>().ok())
+            .ok_or_else(|| format!("invariant {name} must have an array of witnesses"))?
+            .iter()
+            .map(|witness| {
+                witness
+                    .as_f64()
+                    .filter(|index| index.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(index))
+                    .map(|index| Witness(index as u32))
+                    .ok_or_else(|| format!("invariant {name} has an invalid witness index"))
+            })
+            .collect::<Result<_, _>>()?;
+        let check = read_function(&js_invariant, "check")?
+            .ok_or_else(|| format!("invariant {name} must have a check function"))?;
+
+        Ok(Invariant { name, witnesses, check })
+    }
+}
+
+impl Invariant {
+    /// Calls the invariant's check with the values of its witnesses in `witness_map`, returning a description of the
+    /// violation if it doesn't hold.
+    async fn check(&self, witness_map: &WitnessMap) -> Result<(), String> {
+        let values: BTreeMap<Witness, FieldElement> = self
+            .witnesses
+            .iter()
+            .filter_map(|witness| Some((*witness, *witness_map.get(witness)?)))
+            .collect();
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let violated = |message: String| format!("Invariant {} violated: {message}", self.name);
+        let describe = |err: JsValue| {
+            err.as_string()
+                .or_else(|| err.dyn_ref::<js_sys::Error>().map(|err| String::from(err.message())))
+                .unwrap_or_else(|| "Unknown".to_owned())
+        };
+
+        let result = self
+            .check
+            .call1(&JsValue::NULL, &JsWitnessMap::from(WitnessMap::from(values)))
+            .map_err(|err| violated(describe(err)))?;
+        let result = JsFuture::from(Promise::resolve(&result))
+            .await
+            .map_err(|err| violated(describe(err)))?;
+        match (result.as_bool(), result.dyn_ref::<JsString>()) {
+            (Some(false), _) => Err(format!("Invariant {} violated", self.name)),
+            (_, Some(message)) => Err(violated(String::from(message))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Application-level checks on the witness which abort execution as soon as they're violated.
+#[derive(Debug, Default)]
+pub(crate) struct Invariants {
+    invariants: Vec<Invariant>,
+}
+
+impl TryFrom<JsValue> for Invariants {
+    type Error = String;
+
+    fn try_from(js_invariants: JsValue) -> Result<Self, Self::Error> {
+        let js_invariants: Array =
+            js_invariants.dyn_into().map_err(|_| "invariants must be an array".to_string())?;
+        let invariants = js_invariants.iter().map(Invariant::try_from).collect::<Result<_, _>>()?;
+        Ok(Invariants { invariants })
+    }
+}
+
+impl Invariants {
+    /// Checks each invariant in turn against `witness_map`, returning the first violation.
+    pub(crate) async fn check(&self, witness_map: &WitnessMap) -> Result<(), String> {
+        for invariant in &self.invariants {
+            invariant.check(witness_map).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,674 @@
+use std::rc::Rc;
+
+use acvm::{
+    acir::{
+        circuit::{Circuit, Opcode, OpcodeLabel},
+        native_types::WitnessMap,
+        BlackBoxFunc,
+    },
+    pwg::{ACVMStatus, OpcodeResolutionError, ACVM},
+    BlackBoxFunctionSolver, BlackBoxResolutionError, FieldElement,
+};
+
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Function, JsString};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+use crate::{
+    barretenberg::{
+        pedersen::Pedersen, scalar_mul::ScalarMul, schnorr::SchnorrSig, with_determinism,
+        with_random_tape, with_virtual_files, Barretenberg, VirtualFiles,
+    },
+    foreign_call::{resolve_brillig, ForeignCallBitSizes, ForeignCallHandler, ForeignCallRecorder},
+    js_witness_map::JsWitnessMapLike,
+    JsWitnessMap,
+};
+
+mod debugger;
+mod error_report;
+mod fingerprint;
+mod format;
+mod invariants;
+mod options;
+mod overrides;
+mod profile;
+mod profiling_session;
+mod prove;
+mod report;
+mod requirements;
+mod result;
+mod solver;
+mod step_limit;
+mod streaming;
+mod suggestions;
+mod support;
+mod trace;
+mod trace_file;
+mod transforms;
+mod verify;
+
+use debugger::{Breakpoints, BrilligInspector, BrilligStepper, Watchpoints};
+pub(crate) use debugger::{CurrentLocation, OpcodeLocation};
+use error_report::report_error;
+use fingerprint::execution_fingerprint;
+pub(crate) use format::{decompress_circuit, read_circuit, OpcodeStream};
+pub(crate) use options::{ExecutionOptions, JsExecutionOptions};
+pub(crate) use overrides::read_function;
+use overrides::BlackBoxOverrides;
+pub use profile::profile_execution;
+use profiling_session::ProfilingSession;
+pub use profiling_session::{end_profiling_session, start_profiling_session};
+pub use prove::simulate_and_prove;
+pub use report::generate_execution_report;
+pub use requirements::analyze_witness_requirements;
+use result::{ExecutionResult, JsExecutionResult};
+use solver::{Solver, SolverError, SolverStalled, SolverStats};
+pub use streaming::parse_circuit_streaming;
+use suggestions::ErrorSuggestion;
+pub use support::analyze_blackbox_support;
+use trace::{encode_bytes, ExecutionTracing, TracedOutput};
+pub use trace::{record_execution, replay_execution};
+pub use trace_file::{export_trace, import_trace};
+pub use verify::verify_witness;
+
+pub(crate) struct SimulatedBackend {
+    blackbox_vendor: Rc<Barretenberg>,
+    overrides: Option<BlackBoxOverrides>,
+    /// Whether the Barretenberg instance should see seeded randomness and a fixed clock.
+    deterministic: bool,
+    /// The read-only files exposed to the Barretenberg instance.
+    virtual_files: Option<Rc<VirtualFiles>>,
+    /// Set if the blackbox function calls are being recorded into a trace, or replayed from one.
+    tracing: Option<Rc<ExecutionTracing>>,
+}
+
+impl SimulatedBackend {
+    pub(crate) async fn initialize() -> SimulatedBackend {
+        let blackbox_vendor = Rc::new(Barretenberg::new().await);
+        SimulatedBackend {
+            blackbox_vendor,
+            overrides: None,
+            deterministic: false,
+            virtual_files: None,
+            tracing: None,
+        }
+    }
+
+    pub(crate) fn initialize_sync() -> SimulatedBackend {
+        let blackbox_vendor = Rc::new(Barretenberg::new_sync());
+        SimulatedBackend {
+            blackbox_vendor,
+            overrides: None,
+            deterministic: false,
+            virtual_files: None,
+            tracing: None,
+        }
+    }
+
+    /// Returns a backend sharing this backend's Barretenberg instance which prefers any
+    /// blackbox function implementations provided in `options`.
+    pub(crate) fn with_options(&self, options: &ExecutionOptions) -> SimulatedBackend {
+        SimulatedBackend {
+            blackbox_vendor: self.blackbox_vendor.clone(),
+            overrides: options.blackbox_solver.clone(),
+            deterministic: options.deterministic,
+            virtual_files: options.virtual_files.clone(),
+            tracing: options.tracing.clone(),
+        }
+    }
+
+    /// Runs `f`, which calls into the Barretenberg instance, within the environment set up by this backend's options.
+    fn with_environment<T>(&self, f: impl FnOnce() -> T) -> T {
+        let random_tape = self.tracing.as_ref().map(|tracing| tracing.random_tape());
+        with_virtual_files(self.virtual_files.as_deref(), || {
+            with_random_tape(random_tape, || with_determinism(self.deterministic, f))
+        })
+    }
+
+    /// Solves a call to the blackbox `function` with `solve`, recording its result if the execution is being traced.
+    /// When replaying a trace, the recorded result is used in place of calling `solve`.
+    fn traced<T: TracedOutput>(
+        &self,
+        function: BlackBoxFunc,
+        inputs: impl FnOnce() -> Vec<String>,
+        solve: impl FnOnce() -> Result<T, BlackBoxResolutionError>,
+    ) -> Result<T, BlackBoxResolutionError> {
+        match &self.tracing {
+            Some(tracing) => tracing.trace_blackbox(function, inputs(), solve),
+            None => solve(),
+        }
+    }
+
+    /// Allows the buffers used to pass inputs into the Barretenberg instance to be reused by the next execution.
+    pub(crate) fn reset_arena(&self) {
+        self.blackbox_vendor.reset_arena();
+    }
+
+    /// Returns `false` if a previous call into the Barretenberg instance trapped, leaving it in a corrupt state.
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.blackbox_vendor.is_healthy()
+    }
+
+    /// Marks the Barretenberg instance as having trapped, so that it's replaced before it's next used.
+    pub(crate) fn mark_trapped(&self) {
+        self.blackbox_vendor.mark_trapped();
+    }
+
+    /// Replaces the Barretenberg instance if a previous call into it trapped, leaving it in a corrupt state.
+    ///
+    /// This should be called before reusing a backend for a new execution.
+    pub(crate) async fn ensure_healthy(&mut self) {
+        if !self.is_healthy() {
+            log::warn!("Barretenberg instance trapped during a previous call, reinitializing");
+            self.blackbox_vendor = Rc::new(Barretenberg::new().await);
+        }
+    }
+}
+
+impl BlackBoxFunctionSolver for &SimulatedBackend {
+    fn schnorr_verify(
+        &self,
+        public_key_x: &FieldElement,
+        public_key_y: &FieldElement,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        let traced_inputs = || {
+            vec![
+                public_key_x.to_hex(),
+                public_key_y.to_hex(),
+                encode_bytes(signature),
+                encode_bytes(message),
+            ]
+        };
+        self.traced(BlackBoxFunc::SchnorrVerify, traced_inputs, || {
+            if let Some(result) = self.overrides.as_ref().and_then(|overrides| {
+                overrides.schnorr_verify(public_key_x, public_key_y, signature, message)
+            }) {
+                return result;
+            }
+
+            let pub_key_bytes: Vec<u8> = public_key_x
+                .to_be_bytes()
+                .iter()
+                .copied()
+                .chain(public_key_y.to_be_bytes())
+                .collect();
+
+            let pub_key: [u8; 64] = pub_key_bytes.try_into().unwrap();
+            let sig_s: [u8; 32] = signature[0..32].try_into().unwrap();
+            let sig_e: [u8; 32] = signature[32..64].try_into().unwrap();
+
+            self.with_environment(|| {
+                self.blackbox_vendor.verify_signature(pub_key, sig_s, sig_e, message)
+            })
+            .map_err(|err| {
+                BlackBoxResolutionError::Failed(BlackBoxFunc::SchnorrVerify, err.to_string())
+            })
+        })
+    }
+
+    fn pedersen(
+        &self,
+        inputs: &[FieldElement],
+        domain_separator: u32,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        let traced_inputs = || {
+            let mut traced_inputs: Vec<String> =
+                inputs.iter().map(|input| input.to_hex()).collect();
+            traced_inputs.push(domain_separator.to_string());
+            traced_inputs
+        };
+        self.traced(BlackBoxFunc::Pedersen, traced_inputs, || {
+            if let Some(result) = self
+                .overrides
+                .as_ref()
+                .and_then(|overrides| overrides.pedersen(inputs, domain_separator))
+            {
+                return result;
+            }
+
+            self.with_environment(|| {
+                self.blackbox_vendor.encrypt(inputs.to_vec(), domain_separator)
+            })
+            .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Pedersen, err.to_string()))
+        })
+    }
+
+    fn fixed_base_scalar_mul(
+        &self,
+        input: &FieldElement,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        self.traced(
+            BlackBoxFunc::FixedBaseScalarMul,
+            || vec![input.to_hex()],
+            || {
+                if let Some(result) = self
+                    .overrides
+                    .as_ref()
+                    .and_then(|overrides| overrides.fixed_base_scalar_mul(input))
+                {
+                    return result;
+                }
+
+                self.with_environment(|| self.blackbox_vendor.fixed_base(input)).map_err(|err| {
+                    BlackBoxResolutionError::Failed(
+                        BlackBoxFunc::FixedBaseScalarMul,
+                        err.to_string(),
+                    )
+                })
+            },
+        )
+    }
+}
+
+/// An error encountered while executing a circuit, along with the witness solved up to that point.
+#[derive(Debug)]
+pub(crate) struct ExecutionError {
+    pub(crate) message: String,
+    pub(crate) partial_witness: WitnessMap,
+    /// Set if execution failed as no progress could be made on the remaining opcodes.
+    pub(crate) stalled: Option<SolverStalled>,
+    /// Hints on how to fix the error, if requested by `errorSuggestions`.
+    pub(crate) suggestions: Vec<ErrorSuggestion>,
+}
+
+impl ExecutionError {
+    pub(crate) fn new(message: String, partial_witness: WitnessMap) -> ExecutionError {
+        ExecutionError { message, partial_witness, stalled: None, suggestions: Vec::new() }
+    }
+
+    pub(crate) fn from_solver_error(
+        err: SolverError,
+        partial_witness: WitnessMap,
+    ) -> ExecutionError {
+        let message = err.to_string();
+        let stalled = match err {
+            SolverError::Stalled(stalled) => Some(stalled),
+            SolverError::Failed(_) => None,
+        };
+        ExecutionError { message, partial_witness, stalled, suggestions: Vec::new() }
+    }
+
+    /// Converts the error into a JS value, attaching the partial witness if `options` requests it.
+    ///
+    /// Stalled executions are reported as a `SolverStalled` error listing the unsolved opcodes.
+    pub(crate) fn into_js_value(self, options: &ExecutionOptions) -> JsValue {
+        if !options.return_partial_witness && self.stalled.is_none() && self.suggestions.is_empty()
+        {
+            return JsString::from(self.message).into();
+        }
+
+        let error = js_sys::Error::new(&self.message);
+        let set = |key: &str, value: &JsValue| {
+            js_sys::Reflect::set(&error, &JsValue::from_str(key), value)
+                .expect("setting a property on an `Error` should not fail");
+        };
+        if let Some(stalled) = self.stalled {
+            error.set_name("SolverStalled");
+            let unsolved_opcodes: js_sys::Array =
+                stalled.unsolved_opcodes.iter().map(|&index| JsValue::from(index as u32)).collect();
+            set("unsolvedOpcodes", &unsolved_opcodes);
+            if let Some(passes) = stalled.passes {
+                set("passes", &JsValue::from(passes));
+            }
+        }
+        if options.return_partial_witness {
+            set("partialWitness", &JsWitnessMap::from(self.partial_witness));
+        }
+        if !self.suggestions.is_empty() {
+            set(
+                "suggestions",
+                &<JsValue as JsValueSerdeExt>::from_serde(&self.suggestions).unwrap(),
+            );
+        }
+        error.into()
+    }
+}
+
+/// Executes an ACIR circuit to generate the solved witness from the initial witness.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`..
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+/// @returns {WitnessMap} The solved witness calculated by executing the circuit on the provided inputs.
+#[wasm_bindgen(js_name = executeCircuit, skip_jsdoc)]
+pub async fn execute_circuit(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    options: Option<JsExecutionOptions>,
+) -> Result<JsWitnessMap, JsValue> {
+    console_error_panic_hook::set_once();
+    let options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+    let circuit_bytes = circuit;
+    let circuit: Circuit = read_circuit(&circuit_bytes)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+
+    let backend = SimulatedBackend::initialize().await;
+    let witness_map = execute_circuit_with_backend(
+        &backend,
+        circuit.opcodes,
+        initial_witness,
+        &foreign_call_handler,
+        &options,
+    )
+    .await
+    .map_err(|err| {
+        report_error(&options, &circuit_bytes, &err);
+        err.into_js_value(&options)
+    })?;
+
+    Ok(witness_map.into())
+}
+
+/// Synchronously executes an ACIR circuit to generate the solved witness from the initial witness.
+///
+/// This is only suitable for circuits which do not make any foreign calls or use memory, execution will fail if they do.
+/// Some browsers refuse to synchronously compile the embedded backend on the main thread so this should be called from a worker.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @returns {WitnessMap} The solved witness calculated by executing the circuit on the provided inputs.
+#[wasm_bindgen(js_name = executeCircuitSync, skip_jsdoc)]
+pub fn execute_circuit_sync(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+) -> Result<JsWitnessMap, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+    if uses_memory(&circuit.opcodes) {
+        return Err(JsValue::from(
+            "Circuit uses memory opcodes which cannot be solved during synchronous execution, use `executeCircuit` instead",
+        ));
+    }
+
+    let backend = SimulatedBackend::initialize_sync();
+    let mut acvm = ACVM::new(&backend, circuit.opcodes, initial_witness);
+
+    match acvm.solve() {
+        ACVMStatus::Solved => Ok(acvm.finalize().into()),
+        ACVMStatus::InProgress => {
+            unreachable!("Execution should not stop while in `InProgress` state.")
+        }
+        ACVMStatus::Failure(error) => Err(error.to_string().into()),
+        ACVMStatus::RequiresForeignCall => {
+            let foreign_call =
+                acvm.get_pending_foreign_call().expect("a foreign call should be pending");
+            Err(format!(
+                "Circuit made a foreign call to `{}` which cannot be resolved during synchronous execution",
+                foreign_call.function
+            )
+            .into())
+        }
+    }
+}
+
+/// Executes an ACIR circuit to generate the solved witness from the initial witness, along with statistics on how it was solved.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+/// @returns {ExecutionResult} The solved witness along with statistics gathered while solving it and the final contents of memory.
+#[wasm_bindgen(js_name = executeCircuitWithStats, skip_jsdoc)]
+pub async fn execute_circuit_with_stats(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    options: Option<JsExecutionOptions>,
+) -> Result<JsExecutionResult, JsValue> {
+    console_error_panic_hook::set_once();
+    let mut options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+    let circuit_bytes = circuit;
+    let circuit: Circuit = read_circuit(&circuit_bytes)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+    let recorder = Rc::new(ForeignCallRecorder::default());
+    options.foreign_call_recorder = Some(recorder.clone());
+
+    let backend = SimulatedBackend::initialize().await;
+    let opcodes = prepare_opcodes(circuit.opcodes, &options);
+    let mut result = execute_circuit_with_solver(
+        &backend,
+        &opcodes,
+        initial_witness,
+        &foreign_call_handler,
+        &options,
+        true,
+    )
+    .await
+    .map_err(|err| {
+        report_error(&options, &circuit_bytes, &err);
+        err.into_js_value(&options)
+    })?;
+    result.fingerprint = Some(execution_fingerprint(&circuit_bytes, &recorder.transcript()));
+
+    Ok(result.into())
+}
+
+/// Solves `opcodes` against an already initialized `backend`, resolving any foreign calls through `foreign_call_handler`.
+pub(crate) async fn execute_circuit_with_backend(
+    backend: &SimulatedBackend,
+    opcodes: Vec<Opcode>,
+    initial_witness: WitnessMap,
+    foreign_call_handler: &ForeignCallHandler,
+    options: &ExecutionOptions,
+) -> Result<WitnessMap, ExecutionError> {
+    let opcodes = prepare_opcodes(opcodes, options);
+    let backend = &backend.with_options(options);
+    backend.reset_arena();
+
+    if options.on_opcode_solved.is_some()
+        || options.max_brillig_steps.is_some()
+        || options.concurrent_foreign_calls
+        || options.on_breakpoint.is_some()
+        || options.on_brillig_step.is_some()
+        || options.on_brillig_pause.is_some()
+        || options.on_watchpoint.is_some()
+        || options.current_location.is_some()
+        || options.invariants.is_some()
+        || options.error_suggestions
+        || ProfilingSession::active().is_some()
+        || uses_memory(&opcodes)
+    {
+        let result = execute_circuit_with_solver(
+            backend,
+            &opcodes,
+            initial_witness,
+            foreign_call_handler,
+            options,
+            false,
+        )
+        .await?;
+        return Ok(result.witness);
+    }
+
+    let foreign_call_handler = &options.foreign_call_handler(foreign_call_handler);
+    let bit_sizes =
+        options.check_foreign_call_bit_sizes.then(|| ForeignCallBitSizes::from_opcodes(&opcodes));
+    let mut acvm = ACVM::new(backend, opcodes, initial_witness);
+
+    loop {
+        let solver_status = acvm.solve();
+
+        match solver_status {
+            ACVMStatus::Solved => break,
+            ACVMStatus::InProgress => {
+                unreachable!("Execution should not stop while in `InProgress` state.")
+            }
+            ACVMStatus::Failure(OpcodeResolutionError::OpcodeNotSolvable(reason)) => {
+                // The ACVM only reports a stall once a pass over the opcodes has made no progress.
+                let unsolved_opcodes = acvm
+                    .unresolved_opcodes()
+                    .iter()
+                    .filter_map(|(_, label)| match label {
+                        OpcodeLabel::Resolved(index) => Some(*index as usize),
+                        OpcodeLabel::Unresolved => None,
+                    })
+                    .collect();
+                let stalled = SolverStalled { reason, unsolved_opcodes, passes: None };
+                return Err(ExecutionError::from_solver_error(
+                    SolverError::Stalled(stalled),
+                    acvm.witness_map().clone(),
+                ));
+            }
+            ACVMStatus::Failure(error) => {
+                return Err(ExecutionError::new(error.to_string(), acvm.witness_map().clone()))
+            }
+            ACVMStatus::RequiresForeignCall => {
+                while let Some(foreign_call) = acvm.get_pending_foreign_call() {
+                    let result =
+                        resolve_brillig(foreign_call_handler, foreign_call, bit_sizes.as_ref())
+                            .await
+                            .map_err(|message| {
+                                ExecutionError::new(message, acvm.witness_map().clone())
+                            })?;
+
+                    acvm.resolve_pending_foreign_call(result);
+                }
+            }
+        }
+    }
+
+    Ok(acvm.finalize())
+}
+
+/// Solves the opcodes held by `solver`, notifying the `onOpcodeSolved` callback in `options` of each solved opcode.
+async fn solve_with_callback(
+    solver: &mut Solver<'_>,
+    foreign_call_handler: &ForeignCallHandler,
+    options: &ExecutionOptions,
+) -> Result<(), SolverError> {
+    let foreign_call_handler = options.foreign_call_handler(foreign_call_handler);
+    if options.check_foreign_call_bit_sizes {
+        solver.check_foreign_call_bit_sizes();
+    }
+    if let Some(max_brillig_steps) = options.max_brillig_steps {
+        solver.limit_brillig_steps(max_brillig_steps);
+    }
+    if options.concurrent_foreign_calls {
+        solver.enable_concurrent_foreign_calls();
+    }
+    if let Some(on_breakpoint) = &options.on_breakpoint {
+        let breakpoints = options.breakpoints.as_deref().unwrap_or_default();
+        solver.enable_breakpoints(Breakpoints::new(breakpoints, on_breakpoint.clone()));
+    }
+    if let Some(on_brillig_step) = &options.on_brillig_step {
+        let brillig_stepper =
+            BrilligStepper::new(on_brillig_step.clone(), options.current_location.clone());
+        solver.enable_brillig_stepping(brillig_stepper);
+    }
+    if let Some(on_watchpoint) = &options.on_watchpoint {
+        let watchpoints = options.watchpoints.as_deref().unwrap_or_default();
+        solver.enable_watchpoints(Watchpoints::new(watchpoints, on_watchpoint.clone()));
+    }
+    if let Some(current_location) = &options.current_location {
+        solver.track_location(current_location.clone());
+    }
+    if let Some(invariants) = &options.invariants {
+        solver.enable_invariants(invariants.clone());
+    }
+    if let Some(on_brillig_pause) = &options.on_brillig_pause {
+        let brillig_inspector =
+            BrilligInspector::new(on_brillig_pause.clone(), options.max_brillig_steps);
+        solver.enable_brillig_inspection(brillig_inspector);
+    }
+    solver
+        .solve(Some(&foreign_call_handler), |index, opcode, assigned_witnesses| {
+            let Some(on_opcode_solved) = &options.on_opcode_solved else {
+                return;
+            };
+            let result = on_opcode_solved.call3(
+                &JsValue::NULL,
+                &JsValue::from(index as u32),
+                &JsValue::from(opcode.to_string()),
+                &JsWitnessMap::from(assigned_witnesses),
+            );
+            if let Err(err) = result {
+                log::warn!("onOpcodeSolved callback threw an error: {err:?}");
+            }
+        })
+        .await
+}
+
+/// Applies any transformations to `opcodes` requested by `options`.
+/// Returns whether `opcodes` contain any memory opcodes.
+///
+/// The ACVM panics rather than returning an error when a memory opcode can't be solved, so these circuits must be
+/// executed by the [`Solver`].
+fn uses_memory(opcodes: &[Opcode]) -> bool {
+    opcodes
+        .iter()
+        .any(|opcode| matches!(opcode, Opcode::MemoryInit { .. } | Opcode::MemoryOp { .. }))
+}
+
+fn prepare_opcodes(mut opcodes: Vec<Opcode>, options: &ExecutionOptions) -> Vec<Opcode> {
+    if options.skip_signature_verification {
+        opcodes =
+            transforms::skip_signature_verification(opcodes, options.skipped_signature_result());
+    }
+    if let Some(domain_separator) = options.pedersen_domain_separator {
+        opcodes = transforms::override_pedersen_domain_separator(opcodes, domain_separator);
+    }
+    opcodes
+}
+
+/// Solves `opcodes` one at a time using the [`Solver`], reporting each solved opcode to any `onOpcodeSolved` callback.
+///
+/// This is slower than handing the whole circuit to the ACVM so is only used when the caller wants to observe execution,
+/// or when the circuit uses memory.
+/// If `meter_brillig_steps` is set then the stats record the number of steps taken by each Brillig opcode.
+async fn execute_circuit_with_solver(
+    backend: &SimulatedBackend,
+    opcodes: &[Opcode],
+    initial_witness: WitnessMap,
+    foreign_call_handler: &ForeignCallHandler,
+    options: &ExecutionOptions,
+    meter_brillig_steps: bool,
+) -> Result<ExecutionResult, ExecutionError> {
+    let backend = backend.with_options(options);
+    backend.reset_arena();
+    let mut solver = Solver::new(&backend, opcodes, initial_witness);
+    if meter_brillig_steps {
+        solver.enable_brillig_metering();
+    }
+    if let Some(snapshot_opcodes) = &options.snapshot_opcodes {
+        solver.enable_snapshots(snapshot_opcodes);
+    }
+    let profiling_session = ProfilingSession::active();
+    let foreign_call_handler = match &profiling_session {
+        Some(session) => {
+            solver.enable_profiling();
+            session.time_foreign_calls(foreign_call_handler)
+        }
+        None => Function::clone(foreign_call_handler).unchecked_into(),
+    };
+    let result = solve_with_callback(&mut solver, &foreign_call_handler, options).await;
+    if let Some(session) = &profiling_session {
+        session.record_execution(opcodes, solver.opcode_times().expect("profiling is enabled"));
+    }
+
+    match result {
+        Ok(()) => {
+            let stats = solver.stats().clone();
+            let memory_blocks = solver.memory_blocks();
+            let snapshots = solver.take_snapshots();
+            Ok(ExecutionResult {
+                witness: solver.finalize(),
+                stats,
+                memory_blocks,
+                snapshots,
+                fingerprint: None,
+            })
+        }
+        Err(err) => {
+            let err = ExecutionError::from_solver_error(err, solver.witness_map().clone());
+            match options.error_suggestions {
+                true => Err(err.with_suggestions(opcodes, options.abi.as_ref())),
+                false => Err(err),
+            }
+        }
+    }
+}
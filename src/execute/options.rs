@@ -0,0 +1,306 @@
+use std::rc::Rc;
+
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Function, Uint8Array};
+use serde::Deserialize;
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{
+    barretenberg::VirtualFiles,
+    foreign_call::{
+        ForeignCallHandler, ForeignCallQueue, ForeignCallRecorder, ForeignCallTranscript,
+    },
+    inspect::WitnessAbi,
+};
+
+use super::{
+    debugger::CurrentLocation,
+    invariants::Invariants,
+    overrides::{read_function, BlackBoxOverrides},
+    trace::ExecutionTracing,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const EXECUTION_OPTIONS: &'static str = r#"
+/**
+* @typedef {Object} ExecutionOptions - Options which modify how a circuit is executed.
+* @property {boolean} returnPartialWitness - Attach the witness solved up to the point of failure to any execution error.
+* @property {OpcodeSolvedCallback} onOpcodeSolved - Called each time an opcode is solved, in the order in which they are solved.
+* @property {boolean} skipSignatureVerification - Skip Schnorr and ECDSA signature verification, assigning `skippedSignatureResult` to their outputs instead.
+* @property {boolean} skippedSignatureResult - The result assigned to skipped signature verifications. Defaults to `true`.
+* @property {number} pedersenDomainSeparator - Overrides the generator index used by all Pedersen commitments in the circuit.
+* @property {BlackBoxSolver} blackboxSolver - Implementations of blackbox functions to use in place of the embedded Barretenberg.
+* @property {ForeignCallsChangedCallback} onForeignCallsChanged - Called each time a foreign call is made or resolves, with the foreign calls still awaiting a response.
+* @property {boolean} deterministic - Seed the randomness and fix the clock seen by the embedded Barretenberg, and fail if a foreign call is made without a `foreignCallTranscript`.
+* @property {ForeignCallTranscriptEntry[]} foreignCallTranscript - Responses to replay, in order, for the foreign calls made by the circuit in place of calling the `ForeignCallHandler`.
+* @property {boolean} checkForeignCallBitSizes - Fail if a foreign call returns a value which doesn't fit within the bit size its Brillig bytecode uses it as.
+* @property {number} maxBrilligSteps - The maximum number of Brillig opcodes which a single Brillig call may execute before execution is aborted.
+* @property {number[]} snapshotOpcodes - Indices of opcodes at which to snapshot the witnesses assigned since the previous snapshot. Snapshots are returned by `executeCircuitWithStats`.
+* @property {boolean} concurrentForeignCalls - Make the foreign calls of consecutive Brillig opcodes whose inputs are known at the same time rather than one after another. If one fails, the others are cancelled through the `AbortSignal` passed to the `ForeignCallHandler`.
+* @property {number[]} breakpoints - Indices of opcodes which execution pauses before attempting, handing control to `onBreakpoint` until it resumes execution. Requires `onBreakpoint`.
+* @property {BreakpointCallback} onBreakpoint - Called each time execution pauses at a breakpoint, with the witness solved so far.
+* @property {BrilligStepCallback} onBrilligStep - Step through each Brillig call one instruction at a time, calling this after each instruction until it asks to continue.
+* @property {number[]} watchpoints - Indices of witnesses which execution pauses after assigning, handing control to `onWatchpoint` until it resumes execution. Requires `onWatchpoint`.
+* @property {WatchpointCallback} onWatchpoint - Called each time a watched witness is assigned, with the opcode which assigned it.
+* @property {BrilligPauseCallback} onBrilligPause - Called with the registers and memory of the Brillig VM whenever a Brillig call halts for a foreign call or fails.
+* @property {Invariant[]} invariants - Application-level checks on the witness which are evaluated after each pass over the opcodes, aborting execution as soon as one is violated.
+* @property {Uint8Array[]} virtualFiles - Read-only files exposed to the embedded Barretenberg. The wasm can't open files by name so each is preopened, the first with file descriptor 3.
+* @property {boolean} errorSuggestions - Attach machine-readable `suggestions` on how to fix the error to any execution error, such as the inputs which are missing.
+* @property {ParameterWitnesses} abi - The ABI of the Noir program being executed, used to name the parameters in error suggestions.
+* @property {ErrorReportCallback} onErrorReport - Called with a diagnostic report, free of witness values, whenever `executeCircuit` or `executeCircuitWithStats` fails.
+*/
+export type ExecutionOptions = {
+  returnPartialWitness?: boolean;
+  onOpcodeSolved?: OpcodeSolvedCallback;
+  skipSignatureVerification?: boolean;
+  skippedSignatureResult?: boolean;
+  pedersenDomainSeparator?: number;
+  blackboxSolver?: BlackBoxSolver;
+  onForeignCallsChanged?: ForeignCallsChangedCallback;
+  deterministic?: boolean;
+  foreignCallTranscript?: ForeignCallTranscriptEntry[];
+  checkForeignCallBitSizes?: boolean;
+  maxBrilligSteps?: number;
+  snapshotOpcodes?: number[];
+  concurrentForeignCalls?: boolean;
+  breakpoints?: number[];
+  onBreakpoint?: BreakpointCallback;
+  onBrilligStep?: BrilligStepCallback;
+  watchpoints?: number[];
+  onWatchpoint?: WatchpointCallback;
+  onBrilligPause?: BrilligPauseCallback;
+  invariants?: Invariant[];
+  virtualFiles?: Uint8Array[];
+  onErrorReport?: ErrorReportCallback;
+  errorSuggestions?: boolean;
+  abi?: ParameterWitnesses;
+}
+
+/**
+* A callback which is notified each time an opcode is solved.
+* @callback OpcodeSolvedCallback
+* @param {number} index - The index of the solved opcode within the circuit.
+* @param {string} opcode - A human readable description of the solved opcode.
+* @param {WitnessMap} assignedWitnesses - The witnesses which were assigned values while solving the opcode.
+*/
+export type OpcodeSolvedCallback = (index: number, opcode: string, assignedWitnesses: WitnessMap) => void;
+
+/**
+* An error thrown when a circuit fails to execute.
+* @property {WitnessMap} partialWitness - The witness solved before execution failed. Only present if `returnPartialWitness` is set.
+* @property {StackFrame[]} callStack - The Noir call stack leading to the failing opcode, innermost call first. Only present for executions by a `DebugSession` whose debug symbols cover the opcode.
+* @property {ErrorSuggestion[]} suggestions - Hints on how to fix the error. Only present if `errorSuggestions` is set and a hint could be found.
+*/
+export type ExecutionError = Error & {
+  partialWitness?: WitnessMap;
+  callStack?: StackFrame[];
+  suggestions?: ErrorSuggestion[];
+}
+
+/**
+* An error thrown when no progress can be made on the remaining opcodes, e.g. as an input is missing from the initial witness.
+* @property {number[]} unsolvedOpcodes - The indices of the opcodes which remain unsolved, in ascending order.
+* @property {number} passes - The number of passes made over the opcodes before stalling. Only present if execution observed each opcode, e.g. due to `onOpcodeSolved`.
+*/
+export type SolverStalledError = ExecutionError & {
+  name: "SolverStalled";
+  unsolvedOpcodes: number[];
+  passes?: number;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ExecutionOptions")]
+    pub type JsExecutionOptions;
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct ExecutionOptions {
+    pub(crate) return_partial_witness: bool,
+    pub(crate) skip_signature_verification: bool,
+    pub(crate) skipped_signature_result: Option<bool>,
+    pub(crate) pedersen_domain_separator: Option<u32>,
+    pub(crate) deterministic: bool,
+    pub(crate) check_foreign_call_bit_sizes: bool,
+    pub(crate) max_brillig_steps: Option<usize>,
+    pub(crate) snapshot_opcodes: Option<Vec<usize>>,
+    pub(crate) concurrent_foreign_calls: bool,
+    pub(crate) breakpoints: Option<Vec<usize>>,
+    pub(crate) watchpoints: Option<Vec<u32>>,
+    pub(crate) error_suggestions: bool,
+    pub(crate) abi: Option<WitnessAbi>,
+
+    // Functions can't be deserialized so these are read from the options object directly.
+    #[serde(skip)]
+    pub(crate) on_opcode_solved: Option<Function>,
+    #[serde(skip)]
+    pub(crate) blackbox_solver: Option<BlackBoxOverrides>,
+    #[serde(skip)]
+    pub(crate) on_foreign_calls_changed: Option<Function>,
+    #[serde(skip)]
+    pub(crate) on_breakpoint: Option<Function>,
+    #[serde(skip)]
+    pub(crate) on_brillig_step: Option<Function>,
+    #[serde(skip)]
+    pub(crate) on_watchpoint: Option<Function>,
+    #[serde(skip)]
+    pub(crate) on_brillig_pause: Option<Function>,
+    #[serde(skip)]
+    pub(crate) on_error_report: Option<Function>,
+
+    /// Kept updated with where execution currently is, if set by a `DebugSession`.
+    #[serde(skip)]
+    pub(crate) current_location: Option<CurrentLocation>,
+    #[serde(skip)]
+    pub(crate) invariants: Option<Rc<Invariants>>,
+    #[serde(skip)]
+    pub(crate) foreign_call_transcript: Option<ForeignCallTranscript>,
+    #[serde(skip)]
+    pub(crate) virtual_files: Option<Rc<VirtualFiles>>,
+
+    /// The queue which pending foreign calls are added to, if they are tracked beyond this execution.
+    #[serde(skip)]
+    pub(crate) foreign_call_queue: Option<Rc<ForeignCallQueue>>,
+
+    /// Set if the execution is being recorded into a trace, or replayed from one.
+    #[serde(skip)]
+    pub(crate) tracing: Option<Rc<ExecutionTracing>>,
+
+    /// Records the foreign calls made by the execution, if they are needed for its fingerprint.
+    #[serde(skip)]
+    pub(crate) foreign_call_recorder: Option<Rc<ForeignCallRecorder>>,
+}
+
+impl ExecutionOptions {
+    /// The result to assign to signature verifications when `skip_signature_verification` is set.
+    pub(crate) fn skipped_signature_result(&self) -> bool {
+        self.skipped_signature_result.unwrap_or(true)
+    }
+
+    /// Returns the handler which should resolve an execution's foreign calls in place of `foreign_call_handler`.
+    ///
+    /// Foreign calls are answered from the transcript if one was provided, and are recorded and their pending calls
+    /// tracked if these options require it.
+    pub(crate) fn foreign_call_handler(
+        &self,
+        foreign_call_handler: &ForeignCallHandler,
+    ) -> ForeignCallHandler {
+        let foreign_call_handler = match &self.foreign_call_transcript {
+            Some(transcript) => transcript.clone().into_handler(),
+            None if self.deterministic => ForeignCallTranscript::missing_handler(),
+            None => Function::clone(foreign_call_handler).unchecked_into(),
+        };
+        let foreign_call_handler = match &self.tracing {
+            Some(tracing) => tracing.record_foreign_calls(foreign_call_handler),
+            None => foreign_call_handler,
+        };
+        let foreign_call_handler = match &self.foreign_call_recorder {
+            Some(recorder) => recorder.record(&foreign_call_handler),
+            None => foreign_call_handler,
+        };
+        if self.foreign_call_queue.is_none() && self.on_foreign_calls_changed.is_none() {
+            return foreign_call_handler;
+        }
+
+        let queue = self.foreign_call_queue.clone().unwrap_or_default();
+        queue.track(&foreign_call_handler, self.on_foreign_calls_changed.clone())
+    }
+}
+
+impl TryFrom<Option<JsExecutionOptions>> for ExecutionOptions {
+    type Error = String;
+
+    fn try_from(js_options: Option<JsExecutionOptions>) -> Result<Self, Self::Error> {
+        let Some(js_options) = js_options else {
+            return Ok(ExecutionOptions::default());
+        };
+
+        let mut options: ExecutionOptions =
+            <JsValue as JsValueSerdeExt>::into_serde(&js_options)
+                .map_err(|err| format!("Invalid execution options: {err}"))?;
+
+        options.on_opcode_solved = read_function(&js_options, "onOpcodeSolved")
+            .map_err(|err| format!("Invalid execution options: {err}"))?;
+        options.on_foreign_calls_changed = read_function(&js_options, "onForeignCallsChanged")
+            .map_err(|err| format!("Invalid execution options: {err}"))?;
+        options.on_breakpoint = read_function(&js_options, "onBreakpoint")
+            .map_err(|err| format!("Invalid execution options: {err}"))?;
+        options.on_brillig_step = read_function(&js_options, "onBrilligStep")
+            .map_err(|err| format!("Invalid execution options: {err}"))?;
+        options.on_watchpoint = read_function(&js_options, "onWatchpoint")
+            .map_err(|err| format!("Invalid execution options: {err}"))?;
+        options.on_brillig_pause = read_function(&js_options, "onBrilligPause")
+            .map_err(|err| format!("Invalid execution options: {err}"))?;
+        options.on_error_report = read_function(&js_options, "onErrorReport")
+            .map_err(|err| format!("Invalid execution options: {err}"))?;
+        if options.on_error_report.is_some() {
+            // Error reports include the opcode being attempted when execution failed.
+            options.current_location = Some(CurrentLocation::default());
+        }
+        if options.breakpoints.is_some() && options.on_breakpoint.is_none() {
+            return Err("Invalid execution options: breakpoints requires onBreakpoint".to_string());
+        }
+        if options.watchpoints.is_some() && options.on_watchpoint.is_none() {
+            return Err("Invalid execution options: watchpoints requires onWatchpoint".to_string());
+        }
+
+        let blackbox_solver =
+            js_sys::Reflect::get(&js_options, &JsValue::from_str("blackboxSolver")).map_err(
+                |_| "Invalid execution options: could not read blackboxSolver".to_string(),
+            )?;
+        if !blackbox_solver.is_undefined() {
+            options.blackbox_solver = Some(
+                BlackBoxOverrides::try_from(blackbox_solver)
+                    .map_err(|err| format!("Invalid execution options: {err}"))?,
+            );
+        }
+
+        let foreign_call_transcript =
+            js_sys::Reflect::get(&js_options, &JsValue::from_str("foreignCallTranscript"))
+                .map_err(|_| {
+                    "Invalid execution options: could not read foreignCallTranscript".to_string()
+                })?;
+        if !foreign_call_transcript.is_undefined() {
+            options.foreign_call_transcript = Some(
+                ForeignCallTranscript::try_from(foreign_call_transcript)
+                    .map_err(|err| format!("Invalid execution options: {err}"))?,
+            );
+        }
+
+        let invariants = js_sys::Reflect::get(&js_options, &JsValue::from_str("invariants"))
+            .map_err(|_| "Invalid execution options: could not read invariants".to_string())?;
+        if !invariants.is_undefined() {
+            options.invariants = Some(Rc::new(
+                Invariants::try_from(invariants)
+                    .map_err(|err| format!("Invalid execution options: {err}"))?,
+            ));
+        }
+
+        let virtual_files = js_sys::Reflect::get(&js_options, &JsValue::from_str("virtualFiles"))
+            .map_err(|_| {
+            "Invalid execution options: could not read virtualFiles".to_string()
+        })?;
+        if !virtual_files.is_undefined() {
+            options.virtual_files = Some(Rc::new(read_virtual_files(virtual_files)?));
+        }
+
+        Ok(options)
+    }
+}
+
+fn read_virtual_files(value: JsValue) -> Result<VirtualFiles, String> {
+    let files: Array = value
+        .dyn_into()
+        .map_err(|_| "Invalid execution options: virtualFiles must be an array".to_string())?;
+    let files = files
+        .iter()
+        .map(|file| {
+            file.dyn_into::<Uint8Array>().map(|file| file.to_vec()).map_err(|_| {
+                "Invalid execution options: each of virtualFiles must be a Uint8Array".to_string()
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(VirtualFiles::new(files))
+}
@@ -0,0 +1,141 @@
+use acvm::{acir::BlackBoxFunc, BlackBoxResolutionError, FieldElement};
+use js_sys::{Array, Function, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::js_witness_map::{field_element_to_js_string, js_value_to_field_element};
+
+#[wasm_bindgen(typescript_custom_section)]
+const BLACKBOX_SOLVER: &'static str = r#"
+/**
+* @typedef {Object} BlackBoxSolver - Implementations of blackbox functions to use in place of the embedded Barretenberg.
+* Each function is optional and must return synchronously. Field elements are hex encoded.
+* @property {Function} pedersen - Returns the x and y coordinates of the Pedersen commitment to `inputs`.
+* @property {Function} schnorrVerify - Returns whether `signature` is a valid Schnorr signature over `message`.
+* @property {Function} fixedBaseScalarMul - Returns the x and y coordinates of the Grumpkin generator multiplied by `input`.
+*/
+export type BlackBoxSolver = {
+  pedersen?: (inputs: string[], domainSeparator: number) => [string, string];
+  schnorrVerify?: (publicKeyX: string, publicKeyY: string, signature: Uint8Array, message: Uint8Array) => boolean;
+  fixedBaseScalarMul?: (input: string) => [string, string];
+}
+"#;
+
+/// User provided implementations of blackbox functions which take priority over those of the `SimulatedBackend`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BlackBoxOverrides {
+    pedersen: Option<Function>,
+    schnorr_verify: Option<Function>,
+    fixed_base_scalar_mul: Option<Function>,
+}
+
+impl TryFrom<JsValue> for BlackBoxOverrides {
+    type Error = String;
+
+    fn try_from(js_solver: JsValue) -> Result<Self, Self::Error> {
+        if !js_solver.is_object() {
+            return Err("blackboxSolver must be an object".to_string());
+        }
+
+        Ok(BlackBoxOverrides {
+            pedersen: read_function(&js_solver, "pedersen")?,
+            schnorr_verify: read_function(&js_solver, "schnorrVerify")?,
+            fixed_base_scalar_mul: read_function(&js_solver, "fixedBaseScalarMul")?,
+        })
+    }
+}
+
+/// Reads an optional function from the property `key` of `object`.
+pub(crate) fn read_function(object: &JsValue, key: &str) -> Result<Option<Function>, String> {
+    let value = Reflect::get(object, &JsValue::from_str(key))
+        .map_err(|_| format!("could not read {key}"))?;
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    value.dyn_into().map(Some).map_err(|_| format!("{key} must be a function"))
+}
+
+impl BlackBoxOverrides {
+    pub(crate) fn pedersen(
+        &self,
+        inputs: &[FieldElement],
+        domain_separator: u32,
+    ) -> Option<Result<(FieldElement, FieldElement), BlackBoxResolutionError>> {
+        let function = self.pedersen.as_ref()?;
+        let inputs: Array = inputs.iter().map(field_element_to_js_string).collect();
+        let result = function.call2(&JsValue::NULL, &inputs, &JsValue::from(domain_separator));
+        Some(decode_point(BlackBoxFunc::Pedersen, result))
+    }
+
+    pub(crate) fn schnorr_verify(
+        &self,
+        public_key_x: &FieldElement,
+        public_key_y: &FieldElement,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Option<Result<bool, BlackBoxResolutionError>> {
+        let function = self.schnorr_verify.as_ref()?;
+        let args = Array::of4(
+            &field_element_to_js_string(public_key_x),
+            &field_element_to_js_string(public_key_y),
+            &Uint8Array::from(signature),
+            &Uint8Array::from(message),
+        );
+        let result = function
+            .apply(&JsValue::NULL, &args)
+            .map_err(|err| failure(BlackBoxFunc::SchnorrVerify, err))
+            .and_then(|valid| {
+                valid.as_bool().ok_or_else(|| {
+                    BlackBoxResolutionError::Failed(
+                        BlackBoxFunc::SchnorrVerify,
+                        "schnorrVerify must return a boolean".to_string(),
+                    )
+                })
+            });
+        Some(result)
+    }
+
+    pub(crate) fn fixed_base_scalar_mul(
+        &self,
+        input: &FieldElement,
+    ) -> Option<Result<(FieldElement, FieldElement), BlackBoxResolutionError>> {
+        let function = self.fixed_base_scalar_mul.as_ref()?;
+        let result = function.call1(&JsValue::NULL, &field_element_to_js_string(input));
+        Some(decode_point(BlackBoxFunc::FixedBaseScalarMul, result))
+    }
+}
+
+/// Decodes the `[x, y]` coordinates returned by an override of `func`.
+fn decode_point(
+    func: BlackBoxFunc,
+    result: Result<JsValue, JsValue>,
+) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+    let point = result.map_err(|err| failure(func, err))?;
+    let invalid = || {
+        BlackBoxResolutionError::Failed(
+            func,
+            format!("{} must return an array of two field elements", func.name()),
+        )
+    };
+    if !Array::is_array(&point) {
+        return Err(invalid());
+    }
+
+    let point = Array::from(&point);
+    if point.length() != 2 {
+        return Err(invalid());
+    }
+    let x = js_value_to_field_element(point.get(0)).map_err(|_| invalid())?;
+    let y = js_value_to_field_element(point.get(1)).map_err(|_| invalid())?;
+    Ok((x, y))
+}
+
+fn failure(func: BlackBoxFunc, err: JsValue) -> BlackBoxResolutionError {
+    let message = err
+        .as_string()
+        .or_else(|| err.dyn_ref::<js_sys::Error>().map(|err| String::from(err.message())))
+        .unwrap_or_else(|| "Unknown".to_owned());
+    BlackBoxResolutionError::Failed(
+        func,
+        format!("{} override threw an error: {message}", func.name()),
+    )
+}
@@ -0,0 +1,174 @@
+use std::collections::{BTreeMap, HashMap};
+
+use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::JsString;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use super::{
+    prepare_opcodes, read_circuit, solve_with_callback, solver::Solver, ExecutionError,
+    ExecutionOptions, JsExecutionOptions, SimulatedBackend,
+};
+use crate::{foreign_call::ForeignCallHandler, js_witness_map::JsWitnessMapLike};
+
+#[wasm_bindgen(typescript_custom_section)]
+const EXECUTION_PROFILE: &'static str = r#"
+/**
+* @typedef {Object} SourceLocation - A span of source code within a file.
+* @property {{ start: number, end: number }} span - The byte offsets of the start and end of the span.
+* @property {number} file - The id of the file containing the span.
+*/
+export type SourceLocation = {
+  span: { start: number; end: number };
+  file: number;
+}
+
+/**
+* @typedef {Object} DebugInfo - The debug information emitted by the compiler alongside a circuit.
+* @property {Record<number, SourceLocation[]>} locations - The call stack which produced each opcode, outermost call first.
+* @property {Record<number, { path: string, source: string }>} files - The source of each file. Used to resolve spans to line numbers.
+*/
+export type DebugInfo = {
+  locations: Record<number, SourceLocation[]>;
+  files?: Record<number, { path: string; source: string }>;
+}
+
+/**
+* @typedef {Object} ExecutionProfile - Profiling data in the folded stack format accepted by flamegraph tools.
+* @property {string} opcodes - The number of opcodes produced by each call stack.
+* @property {string} solveTime - The time spent solving the opcodes produced by each call stack, in microseconds.
+*/
+export type ExecutionProfile = {
+  opcodes: string;
+  solveTime: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "DebugInfo")]
+    pub type JsDebugInfo;
+
+    #[wasm_bindgen(typescript_type = "ExecutionProfile")]
+    pub type JsExecutionProfile;
+}
+
+/// The frame used for opcodes which have no location in the debug info.
+const UNKNOWN_FRAME: &str = "unknown";
+
+#[derive(Deserialize)]
+struct Span {
+    start: usize,
+}
+
+#[derive(Deserialize)]
+struct SourceLocation {
+    span: Span,
+    file: u32,
+}
+
+#[derive(Deserialize)]
+struct DebugFile {
+    path: String,
+    source: String,
+}
+
+#[derive(Deserialize)]
+struct DebugInfo {
+    locations: HashMap<usize, Vec<SourceLocation>>,
+    #[serde(default)]
+    files: HashMap<u32, DebugFile>,
+}
+
+impl DebugInfo {
+    /// Returns the call stack which produced the opcode at `opcode_index` as a single folded stack line prefix.
+    fn folded_stack(&self, opcode_index: usize) -> String {
+        let Some(locations) =
+            self.locations.get(&opcode_index).filter(|locations| !locations.is_empty())
+        else {
+            return UNKNOWN_FRAME.to_string();
+        };
+
+        let frames: Vec<String> = locations
+            .iter()
+            .map(|location| match self.files.get(&location.file) {
+                Some(file) => {
+                    let offset = location.span.start.min(file.source.len());
+                    let line = file.source.as_bytes()[..offset]
+                        .iter()
+                        .filter(|&&byte| byte == b'\n')
+                        .count()
+                        + 1;
+                    format!("{}:{line}", file.path)
+                }
+                None => format!("file {}:{}", location.file, location.span.start),
+            })
+            .collect();
+        frames.join(";")
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecutionProfile {
+    opcodes: String,
+    solve_time: String,
+}
+
+/// Executes an ACIR circuit and attributes the circuit's opcodes and the time spent solving them to source locations.
+///
+/// The time attributed to an opcode includes any time spent waiting on the foreign calls it makes.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {DebugInfo} debug_info - The debug information emitted by the compiler for `circuit`.
+/// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+/// @returns {Promise<ExecutionProfile>} Folded stacks describing where the circuit's opcodes and solving time come from.
+#[wasm_bindgen(js_name = profileExecution, skip_jsdoc)]
+pub async fn profile_execution(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    debug_info: JsDebugInfo,
+    options: Option<JsExecutionOptions>,
+) -> Result<JsExecutionProfile, JsValue> {
+    console_error_panic_hook::set_once();
+    let options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+    let debug_info: DebugInfo = <JsValue as JsValueSerdeExt>::into_serde(&debug_info)
+        .map_err(|err| JsString::from(format!("Invalid debug info: {err}")))?;
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+
+    let backend = SimulatedBackend::initialize().await.with_options(&options);
+    let opcodes = prepare_opcodes(circuit.opcodes, &options);
+    let mut solver = Solver::new(&backend, &opcodes, initial_witness);
+    solver.enable_profiling();
+    solve_with_callback(&mut solver, &foreign_call_handler, &options).await.map_err(|err| {
+        let partial_witness = solver.witness_map().clone();
+        ExecutionError::from_solver_error(err, partial_witness).into_js_value(&options)
+    })?;
+
+    let opcode_times = solver.opcode_times().expect("profiling is enabled");
+    let mut opcode_counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut solve_times: BTreeMap<String, f64> = BTreeMap::new();
+    for (opcode_index, opcode_time) in opcode_times.iter().enumerate() {
+        let stack = debug_info.folded_stack(opcode_index);
+        *opcode_counts.entry(stack.clone()).or_default() += 1;
+        *solve_times.entry(stack).or_default() += opcode_time;
+    }
+
+    let profile = ExecutionProfile {
+        opcodes: fold(opcode_counts),
+        solve_time: fold(
+            solve_times.into_iter().map(|(stack, time)| (stack, (time * 1000.0).round() as u64)),
+        ),
+    };
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&profile).unwrap().into())
+}
+
+/// Renders each stack and its value as a line of folded stack output.
+fn fold(stacks: impl IntoIterator<Item = (String, u64)>) -> String {
+    stacks.into_iter().map(|(stack, value)| format!("{stack} {value}\n")).collect()
+}
@@ -0,0 +1,170 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    rc::Rc,
+};
+
+use acvm::acir::circuit::Opcode;
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Function, JsString, Promise};
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use super::solver::now;
+use crate::foreign_call::ForeignCallHandler;
+
+#[wasm_bindgen(typescript_custom_section)]
+const PROFILING_REPORT: &'static str = r#"
+/**
+* @typedef {Object} TimingSummary - The number of times something happened and how long it took, in milliseconds.
+*/
+export type TimingSummary = {
+  count: number;
+  totalTime: number;
+  maxTime: number;
+}
+
+/**
+* @typedef {Object} ProfilingReport - Timings aggregated across every execution made during a profiling session.
+* @property {number} executions - The number of executions made during the session, including those which failed.
+* @property {number} duration - The time between the start and end of the session, in milliseconds.
+* @property {Record<string, TimingSummary>} opcodes - The time spent attempting opcodes, keyed by the kind of opcode.
+* @property {Record<string, TimingSummary>} foreignCalls - The time taken to resolve foreign calls, keyed by name.
+*/
+export type ProfilingReport = {
+  executions: number;
+  duration: number;
+  opcodes: Record<string, TimingSummary>;
+  foreignCalls: Record<string, TimingSummary>;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ProfilingReport")]
+    pub type JsProfilingReport;
+}
+
+thread_local! {
+    static PROFILING_SESSION: RefCell<Option<Rc<ProfilingSession>>> = RefCell::default();
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimingSummary {
+    count: u32,
+    total_time: f64,
+    max_time: f64,
+}
+
+impl TimingSummary {
+    fn add(&mut self, time: f64) {
+        self.count += 1;
+        self.total_time += time;
+        self.max_time = self.max_time.max(time);
+    }
+}
+
+/// Timings gathered from the executions made since `startProfilingSession` was called.
+#[derive(Debug)]
+pub(super) struct ProfilingSession {
+    started_at: f64,
+    executions: Cell<u32>,
+    opcodes: RefCell<BTreeMap<String, TimingSummary>>,
+    foreign_calls: RefCell<BTreeMap<String, TimingSummary>>,
+}
+
+impl ProfilingSession {
+    /// Returns the session in progress, if any.
+    pub(super) fn active() -> Option<Rc<ProfilingSession>> {
+        PROFILING_SESSION.with(|session| session.borrow().clone())
+    }
+
+    /// Adds the time spent attempting each of `opcodes` in a single execution to the session.
+    pub(super) fn record_execution(&self, opcodes: &[Opcode], opcode_times: &[f64]) {
+        self.executions.set(self.executions.get() + 1);
+        let mut summaries = self.opcodes.borrow_mut();
+        for (opcode, &time) in opcodes.iter().zip(opcode_times) {
+            summaries.entry(opcode.name().to_owned()).or_default().add(time);
+        }
+    }
+
+    /// Wraps `foreign_call_handler` so that the time taken to resolve each foreign call is added to the session.
+    pub(super) fn time_foreign_calls(
+        self: &Rc<Self>,
+        foreign_call_handler: &ForeignCallHandler,
+    ) -> ForeignCallHandler {
+        let session = self.clone();
+        let foreign_call_handler = Function::clone(foreign_call_handler);
+
+        let timing_handler =
+            Closure::<dyn Fn(JsString, Array, JsValue) -> Result<Promise, JsValue>>::new(
+                move |name: JsString, inputs: Array, signal: JsValue| {
+                    let start = now();
+                    let outputs =
+                        foreign_call_handler.call3(&JsValue::NULL, &name, &inputs, &signal)?;
+
+                    let session = session.clone();
+                    Ok(future_to_promise(async move {
+                        let outputs = JsFuture::from(Promise::resolve(&outputs)).await;
+                        let mut foreign_calls = session.foreign_calls.borrow_mut();
+                        foreign_calls.entry(String::from(&name)).or_default().add(now() - start);
+                        outputs
+                    }))
+                },
+            );
+
+        timing_handler.into_js_value().unchecked_into()
+    }
+}
+
+/// Starts aggregating opcode and foreign call timings across every execution until `endProfilingSession` is called.
+///
+/// Executions are solved one opcode at a time while a session is in progress, which is slower than usual, so sessions
+/// should only be used for benchmarking. `executeCircuitSync` isn't included in the session.
+#[wasm_bindgen(js_name = startProfilingSession)]
+pub fn start_profiling_session() -> Result<(), JsString> {
+    console_error_panic_hook::set_once();
+    PROFILING_SESSION.with(|session| {
+        let mut session = session.borrow_mut();
+        if session.is_some() {
+            return Err("A profiling session is already in progress".into());
+        }
+        *session = Some(Rc::new(ProfilingSession {
+            started_at: now(),
+            executions: Cell::default(),
+            opcodes: RefCell::default(),
+            foreign_calls: RefCell::default(),
+        }));
+        Ok(())
+    })
+}
+
+/// Ends the profiling session in progress, returning the timings gathered over its executions.
+///
+/// @returns {ProfilingReport} Timings aggregated across every execution made during the session.
+#[wasm_bindgen(js_name = endProfilingSession, skip_jsdoc)]
+pub fn end_profiling_session() -> Result<JsProfilingReport, JsString> {
+    console_error_panic_hook::set_once();
+    let session = PROFILING_SESSION
+        .with(|session| session.borrow_mut().take())
+        .ok_or("No profiling session is in progress")?;
+
+    let report = ProfilingReport {
+        executions: session.executions.get(),
+        duration: now() - session.started_at,
+        opcodes: &session.opcodes.borrow(),
+        foreign_calls: &session.foreign_calls.borrow(),
+    };
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&report).unwrap().into())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfilingReport<'a> {
+    executions: u32,
+    duration: f64,
+    opcodes: &'a BTreeMap<String, TimingSummary>,
+    foreign_calls: &'a BTreeMap<String, TimingSummary>,
+}
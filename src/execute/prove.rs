@@ -0,0 +1,218 @@
+use acvm::{
+    acir::{
+        circuit::Circuit,
+        native_types::{Witness, WitnessMap},
+    },
+    blackbox_solver::sha256,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use super::{
+    encode_bytes, execute_circuit_with_solver, prepare_opcodes, read_circuit, ExecutionOptions,
+    SimulatedBackend,
+};
+use crate::{
+    backend::{select_backend, JsProvingBackend},
+    foreign_call::ForeignCallHandler,
+    js_witness_map::{field_element_to_js_string, js_value_to_field_element, JsWitnessMapLike},
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const SIMULATE_AND_PROVE: &'static str = r#"
+/**
+* @typedef {Object} SimulateAndProveOptions - Options which modify how a circuit is executed and proven.
+* @property {ExecutionOptions} execution - Settings which modify how the circuit is executed.
+* @property {ProvingBackend} backend - The proof system to prove with. Defaults to the embedded Barretenberg.
+* @property {string[]} expectedPublicInputs - The values which the circuit's public inputs must take, ordered by witness index. Proving is skipped if they differ.
+*/
+export type SimulateAndProveOptions = {
+  execution?: ExecutionOptions;
+  backend?: ProvingBackend;
+  expectedPublicInputs?: string[];
+}
+
+/**
+* @typedef {Object} SimulatedProof - A proof of a circuit along with the execution which produced its witness.
+* @property {Uint8Array} proof - The proof created by the backend.
+* @property {WitnessMap} publicInputs - The circuit's public inputs, which the proof should be verified against.
+* @property {string} witnessHash - A sha256 hash of the solved witness, for comparing witnesses without holding onto them.
+* @property {SolverStats} stats - Statistics gathered while solving the witness.
+*/
+export type SimulatedProof = {
+  proof: Uint8Array;
+  publicInputs: WitnessMap;
+  witnessHash: string;
+  stats: SolverStats;
+}
+
+/**
+* The stage of `simulateAndProve` at which an error was thrown, set as `stage` on the error.
+*/
+export type SimulateAndProveStage = "options" | "execute" | "publicInputs" | "prove";
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "SimulateAndProveOptions")]
+    pub type JsSimulateAndProveOptions;
+
+    #[wasm_bindgen(extends = Object, typescript_type = "SimulatedProof")]
+    pub type JsSimulatedProof;
+}
+
+struct SimulateAndProveOptions {
+    execution: ExecutionOptions,
+    backend: Option<JsProvingBackend>,
+    expected_public_inputs: Option<Array>,
+}
+
+impl TryFrom<Option<JsSimulateAndProveOptions>> for SimulateAndProveOptions {
+    type Error = String;
+
+    fn try_from(js_options: Option<JsSimulateAndProveOptions>) -> Result<Self, Self::Error> {
+        let js_options: JsValue = js_options.map_or(JsValue::UNDEFINED, JsValue::from);
+        let read = |key: &str| -> Result<Option<JsValue>, String> {
+            if js_options.is_undefined() {
+                return Ok(None);
+            }
+            let value = Reflect::get(&js_options, &JsValue::from_str(key))
+                .map_err(|_| format!("Invalid options: could not read {key}"))?;
+            Ok((!value.is_undefined()).then_some(value))
+        };
+
+        let execution =
+            ExecutionOptions::try_from(read("execution")?.map(JsValue::unchecked_into))?;
+        let backend = read("backend")?.map(JsValue::unchecked_into);
+        let expected_public_inputs = read("expectedPublicInputs")?
+            .map(|inputs| {
+                inputs
+                    .dyn_into::<Array>()
+                    .map_err(|_| "Invalid options: expectedPublicInputs must be an array")
+            })
+            .transpose()?;
+        Ok(SimulateAndProveOptions { execution, backend, expected_public_inputs })
+    }
+}
+
+/// Tags `error` with the `stage` of `simulateAndProve` which threw it, converting it to an `Error` if needed.
+fn stage_error(stage: &str, error: impl Into<JsValue>) -> JsValue {
+    let error: JsValue = error.into();
+    let error = match error.as_string() {
+        Some(message) => js_sys::Error::new(&message).into(),
+        None => error,
+    };
+    Reflect::set(&error, &"stage".into(), &stage.into())
+        .expect("setting a property on an `Error` should not fail");
+    error
+}
+
+/// Returns a hex encoded sha256 hash of `witness_map`, covering each witness index and value in ascending order.
+fn witness_hash(witness_map: &WitnessMap) -> String {
+    let mut bytes = Vec::new();
+    for (witness, value) in witness_map.clone() {
+        bytes.extend(witness.witness_index().to_be_bytes());
+        bytes.extend(value.to_be_bytes());
+    }
+    let hash = sha256(&bytes).expect("sha256 should not fail");
+    format!("0x{}", encode_bytes(&hash))
+}
+
+/// Checks the circuit's public inputs in `witness_map` against `expected`, describing the first which differs.
+fn check_public_inputs(
+    public_inputs: &[Witness],
+    witness_map: &WitnessMap,
+    expected: &Array,
+) -> Result<(), String> {
+    if public_inputs.len() != expected.length() as usize {
+        return Err(format!(
+            "Expected {} public inputs but the circuit has {}.",
+            expected.length(),
+            public_inputs.len()
+        ));
+    }
+    for (index, (witness, expected)) in public_inputs.iter().zip(expected.iter()).enumerate() {
+        let expected = js_value_to_field_element(expected)?;
+        let actual = witness_map[witness];
+        if actual != expected {
+            return Err(format!(
+                "Public input {index} (witness {}) is {} but {} was expected.",
+                witness.witness_index(),
+                String::from(field_element_to_js_string(&actual)),
+                String::from(field_element_to_js_string(&expected))
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Executes an ACIR circuit and proves the solved witness, checking its public inputs along the way.
+///
+/// This is equivalent to calling `executeCircuitWithStats`, `getPublicWitness`, `checkPublicInputs` and `prove` in
+/// turn. Errors are `Error`s whose `stage` is the step which failed: `"options"`, `"execute"`, `"publicInputs"` or
+/// `"prove"`.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {SimulateAndProveOptions} options - Optional settings which modify how the circuit is executed and proven.
+/// @returns {Promise<SimulatedProof>} The proof along with the public inputs it should be verified against.
+#[wasm_bindgen(js_name = simulateAndProve, skip_jsdoc)]
+pub async fn simulate_and_prove(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    options: Option<JsSimulateAndProveOptions>,
+) -> Result<JsSimulatedProof, JsValue> {
+    console_error_panic_hook::set_once();
+    let SimulateAndProveOptions { execution: options, backend, expected_public_inputs } =
+        SimulateAndProveOptions::try_from(options).map_err(|err| stage_error("options", err))?;
+    let backend = select_backend(backend).map_err(|err| stage_error("options", err))?;
+    let parsed_circuit: Circuit =
+        read_circuit(&circuit).map_err(|err| stage_error("options", err))?;
+    let initial_witness =
+        WitnessMap::try_from(initial_witness).map_err(|err| stage_error("options", err))?;
+
+    let public_inputs: Vec<Witness> = parsed_circuit.public_inputs().0.into_iter().collect();
+
+    let simulated_backend = SimulatedBackend::initialize().await;
+    let opcodes = prepare_opcodes(parsed_circuit.opcodes, &options);
+    let result = execute_circuit_with_solver(
+        &simulated_backend,
+        &opcodes,
+        initial_witness,
+        &foreign_call_handler,
+        &options,
+        true,
+    )
+    .await
+    .map_err(|err| stage_error("execute", err.into_js_value(&options)))?;
+
+    if let Some(expected) = &expected_public_inputs {
+        check_public_inputs(&public_inputs, &result.witness, expected)
+            .map_err(|err| stage_error("publicInputs", err))?;
+    }
+    let public_witness: WitnessMap = public_inputs
+        .iter()
+        .map(|witness| (*witness, result.witness[witness]))
+        .collect::<std::collections::BTreeMap<_, _>>()
+        .into();
+
+    let proof = backend
+        .prove(&circuit, result.witness.clone())
+        .await
+        .map_err(|err| stage_error("prove", err))?;
+
+    let js_result = Object::new();
+    let set = |key: &str, value: &JsValue| {
+        Reflect::set(&js_result, &JsValue::from_str(key), value)
+            .expect("setting a property on an `Object` should not fail");
+    };
+    set("proof", &Uint8Array::from(proof.as_slice()));
+    set("publicInputs", &JsWitnessMap::from(public_witness));
+    set("witnessHash", &JsValue::from(witness_hash(&result.witness)));
+    set("stats", &<JsValue as JsValueSerdeExt>::from_serde(&result.stats).unwrap());
+    Ok(js_result.unchecked_into())
+}
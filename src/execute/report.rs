@@ -0,0 +1,277 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use acvm::acir::{
+    circuit::{Circuit, PublicInputs},
+    native_types::WitnessMap,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{JsString, JSON};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{
+    prepare_opcodes, read_circuit, solve_with_callback, solver::Solver, ExecutionOptions,
+    JsExecutionOptions, SimulatedBackend, SolverStats,
+};
+use crate::{foreign_call::ForeignCallHandler, js_witness_map::JsWitnessMapLike};
+
+#[wasm_bindgen(typescript_custom_section)]
+const REPORT_FORMAT: &'static str = r#"
+export type ReportFormat = "json" | "html";
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = JsString, typescript_type = "ReportFormat")]
+    pub type JsReportFormat;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CircuitSummary {
+    opcodes: usize,
+    current_witness_index: u32,
+    /// The number of opcodes of each kind, keyed by the name of the opcode.
+    opcode_counts: BTreeMap<String, u32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Coverage {
+    solved_opcodes: usize,
+    total_opcodes: usize,
+    assigned_witnesses: usize,
+    total_witnesses: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PublicValue {
+    witness: u32,
+    /// The value assigned to the witness, or `None` if execution failed before it was solved.
+    value: Option<String>,
+}
+
+/// A summary of a single execution of a circuit, intended to be attached to CI runs.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecutionReport {
+    solved: bool,
+    error: Option<String>,
+    circuit: CircuitSummary,
+    stats: SolverStats,
+    coverage: Coverage,
+    public_parameters: Vec<PublicValue>,
+    return_values: Vec<PublicValue>,
+    warnings: Vec<String>,
+}
+
+/// Executes an ACIR circuit and produces a report describing the execution.
+///
+/// The report is generated even if execution fails, in which case it describes the progress made before the failure.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {ReportFormat} format - Whether to render the report as JSON or as a standalone HTML page.
+/// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+/// @returns {Promise<string>} The rendered report.
+#[wasm_bindgen(js_name = generateExecutionReport, skip_jsdoc)]
+pub async fn generate_execution_report(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    format: JsReportFormat,
+    options: Option<JsExecutionOptions>,
+) -> Result<JsString, JsValue> {
+    console_error_panic_hook::set_once();
+    let format = format.as_string().unwrap_or_default();
+    if format != "json" && format != "html" {
+        return Err(format!("Unknown report format: {format}").into());
+    }
+    let options = ExecutionOptions::try_from(options)?;
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+
+    let backend = SimulatedBackend::initialize().await.with_options(&options);
+    let opcodes = prepare_opcodes(circuit.opcodes.clone(), &options);
+    let mut solver = Solver::new(&backend, &opcodes, initial_witness);
+    solver.enable_brillig_metering();
+    let result = solve_with_callback(&mut solver, &foreign_call_handler, &options).await;
+
+    let report = build_report(&circuit, &solver, result.err().map(|err| err.to_string()), &options);
+    match format.as_str() {
+        "html" => Ok(render_html(&report).into()),
+        _ => {
+            let report = <JsValue as JsValueSerdeExt>::from_serde(&report).unwrap();
+            JSON::stringify_with_replacer_and_space(&report, &JsValue::NULL, &JsValue::from(2))
+                .map_err(|_| JsString::from("Failed to serialize execution report").into())
+        }
+    }
+}
+
+fn build_report(
+    circuit: &Circuit,
+    solver: &Solver,
+    error: Option<String>,
+    options: &ExecutionOptions,
+) -> ExecutionReport {
+    let witness_map = solver.witness_map();
+    let public_values = |public_inputs: &PublicInputs| {
+        public_inputs
+            .0
+            .iter()
+            .map(|witness| PublicValue {
+                witness: witness.witness_index(),
+                value: witness_map.get(witness).map(|value| format!("0x{}", value.to_hex())),
+            })
+            .collect()
+    };
+
+    let mut opcode_counts = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        *opcode_counts.entry(opcode.name().to_string()).or_default() += 1;
+    }
+
+    let total_opcodes = circuit.opcodes.len();
+    let coverage = Coverage {
+        solved_opcodes: total_opcodes - solver.unresolved_opcodes().count(),
+        total_opcodes,
+        assigned_witnesses: witness_map.clone().into_iter().count(),
+        total_witnesses: circuit.current_witness_index,
+    };
+
+    let mut warnings = Vec::new();
+    if options.skip_signature_verification {
+        warnings.push(format!(
+            "Signature verification was skipped, all signatures were treated as {}",
+            if options.skipped_signature_result() { "valid" } else { "invalid" }
+        ));
+    }
+    if let Some(domain_separator) = options.pedersen_domain_separator {
+        warnings.push(format!(
+            "All Pedersen commitments used the overridden domain separator {domain_separator}"
+        ));
+    }
+    if error.is_none() && coverage.assigned_witnesses < coverage.total_witnesses as usize {
+        warnings.push(format!(
+            "{} of {} witnesses were never assigned a value",
+            coverage.total_witnesses as usize - coverage.assigned_witnesses,
+            coverage.total_witnesses
+        ));
+    }
+
+    ExecutionReport {
+        solved: error.is_none(),
+        error,
+        circuit: CircuitSummary {
+            opcodes: total_opcodes,
+            current_witness_index: circuit.current_witness_index,
+            opcode_counts,
+        },
+        stats: solver.stats().clone(),
+        coverage,
+        public_parameters: public_values(&circuit.public_parameters),
+        return_values: public_values(&circuit.return_values),
+        warnings,
+    }
+}
+
+fn render_html(report: &ExecutionReport) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Execution report</title>\n</head>\n<body>\n",
+    );
+
+    let status = match &report.error {
+        None => "Solved".to_string(),
+        Some(error) => format!("Failed: {}", escape_html(error)),
+    };
+    writeln!(html, "<h1>Execution report</h1>\n<p>{status}</p>").unwrap();
+
+    if !report.warnings.is_empty() {
+        html.push_str("<h2>Warnings</h2>\n<ul>\n");
+        for warning in &report.warnings {
+            writeln!(html, "<li>{}</li>", escape_html(warning)).unwrap();
+        }
+        html.push_str("</ul>\n");
+    }
+
+    let coverage = &report.coverage;
+    render_table(
+        &mut html,
+        "Coverage",
+        [
+            (
+                "Opcodes solved".to_string(),
+                format!("{} / {}", coverage.solved_opcodes, coverage.total_opcodes),
+            ),
+            (
+                "Witnesses assigned".to_string(),
+                format!("{} / {}", coverage.assigned_witnesses, coverage.total_witnesses),
+            ),
+        ],
+    );
+
+    let stats = &report.stats;
+    let stall_rows = stats
+        .stalls
+        .iter()
+        .map(|(opcode, count)| (format!("Stalls ({opcode})"), count.to_string()));
+    let brillig_step_rows = stats
+        .brillig_steps
+        .iter()
+        .map(|(index, steps)| (format!("Brillig steps (opcode {index})"), steps.to_string()));
+    render_table(
+        &mut html,
+        "Solver statistics",
+        [
+            ("Passes".to_string(), stats.passes.to_string()),
+            ("Opcode attempts".to_string(), stats.opcode_attempts.to_string()),
+            ("Deferred Brillig calls".to_string(), stats.deferred_brillig_calls.to_string()),
+        ]
+        .into_iter()
+        .chain(stall_rows)
+        .chain(brillig_step_rows),
+    );
+
+    render_table(
+        &mut html,
+        "Opcodes",
+        report
+            .circuit
+            .opcode_counts
+            .iter()
+            .map(|(opcode, count)| (opcode.clone(), count.to_string())),
+    );
+
+    for (title, values) in
+        [("Public parameters", &report.public_parameters), ("Return values", &report.return_values)]
+    {
+        render_table(
+            &mut html,
+            title,
+            values.iter().map(|public_value| {
+                (
+                    public_value.witness.to_string(),
+                    public_value.value.clone().unwrap_or_else(|| "unsolved".to_string()),
+                )
+            }),
+        );
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_table(html: &mut String, title: &str, rows: impl IntoIterator<Item = (String, String)>) {
+    writeln!(html, "<h2>{title}</h2>\n<table>").unwrap();
+    for (key, value) in rows {
+        writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", escape_html(&key), escape_html(&value))
+            .unwrap();
+    }
+    html.push_str("</table>\n");
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeSet;
+
+use acvm::acir::{
+    circuit::{Circuit, Opcode},
+    native_types::WitnessMap,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{
+    read_circuit,
+    solver::{brillig_input_witnesses, opcode_witnesses, Solver, SolverError},
+    SimulatedBackend,
+};
+use crate::js_witness_map::JsWitnessMapLike;
+
+#[wasm_bindgen(typescript_custom_section)]
+const WITNESS_REQUIREMENTS: &'static str = r#"
+/**
+* @typedef {Object} UnsolvedOpcode - An opcode which could not be solved from the provided witness.
+* @property {number} index - The index of the opcode within the circuit.
+* @property {string} opcode - The name of the opcode.
+* @property {number[]} missingWitnesses - The witness indices referenced by the opcode which could not be assigned.
+* @property {boolean} requiresForeignCall - Whether the opcode is a Brillig call which could only be solved by making a foreign call.
+*/
+export type UnsolvedOpcode = {
+  index: number;
+  opcode: string;
+  missingWitnesses: number[];
+  requiresForeignCall: boolean;
+}
+
+/**
+* @typedef {Object} WitnessRequirements - A report of which parts of a circuit's witness could not be solved.
+* @property {boolean} solvable - Whether every opcode in the circuit could be solved.
+* @property {number[]} missingWitnesses - Every witness index referenced by an unsolved opcode which could not be assigned.
+* @property {UnsolvedOpcode[]} unsolvedOpcodes - The opcodes which could not be solved, in the order they appear in the circuit.
+* @property {string} [failure] - The error which stopped the analysis early, e.g. an unsatisfied constraint.
+*/
+export type WitnessRequirements = {
+  solvable: boolean;
+  missingWitnesses: number[];
+  unsolvedOpcodes: UnsolvedOpcode[];
+  failure?: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "WitnessRequirements")]
+    pub type JsWitnessRequirements;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UnsolvedOpcode {
+    index: usize,
+    opcode: String,
+    missing_witnesses: Vec<u32>,
+    requires_foreign_call: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WitnessRequirements {
+    solvable: bool,
+    missing_witnesses: BTreeSet<u32>,
+    unsolved_opcodes: Vec<UnsolvedOpcode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<String>,
+}
+
+/// Attempts to solve a circuit from a partial witness and reports every witness which could not be assigned.
+///
+/// Unlike `executeCircuit`, solving continues until no further progress can be made rather than stopping at the
+/// first opcode which can't be solved. No foreign calls are made so any Brillig opcode which requires one is
+/// reported as unsolved.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} partial_witness - The witness map holding the inputs to `circuit` which are currently known.
+/// @returns {Promise<WitnessRequirements>} A report of the opcodes and witnesses which could not be solved.
+#[wasm_bindgen(js_name = analyzeWitnessRequirements, skip_jsdoc)]
+pub async fn analyze_witness_requirements(
+    circuit: Vec<u8>,
+    partial_witness: JsWitnessMapLike,
+) -> Result<JsWitnessRequirements, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let partial_witness = WitnessMap::try_from(partial_witness)?;
+
+    let backend = SimulatedBackend::initialize().await;
+    let mut solver = Solver::new(&backend, &circuit.opcodes, partial_witness);
+    let failure = match solver.solve(None, |_, _, _| {}).await {
+        Ok(()) | Err(SolverError::Stalled(_)) => None,
+        Err(err @ SolverError::Failed(_)) => Some(err.to_string()),
+    };
+
+    let witness_map = solver.witness_map();
+    let is_missing = |witness: &_| !witness_map.contains_key(witness);
+
+    let mut unsolved_opcodes: Vec<_> = solver
+        .unresolved_opcodes()
+        .map(|(index, opcode)| {
+            let missing_witnesses: Vec<u32> = opcode_witnesses(opcode)
+                .into_iter()
+                .filter(is_missing)
+                .map(|witness| witness.witness_index())
+                .collect();
+            let requires_foreign_call = match opcode {
+                Opcode::Brillig(brillig) => {
+                    !brillig_input_witnesses(brillig).iter().any(is_missing)
+                }
+                _ => false,
+            };
+            UnsolvedOpcode {
+                index,
+                opcode: opcode.name().to_string(),
+                missing_witnesses,
+                requires_foreign_call,
+            }
+        })
+        .collect();
+    unsolved_opcodes.sort_by_key(|unsolved_opcode| unsolved_opcode.index);
+
+    let requirements = WitnessRequirements {
+        solvable: unsolved_opcodes.is_empty() && failure.is_none(),
+        missing_witnesses: unsolved_opcodes
+            .iter()
+            .flat_map(|unsolved_opcode| unsolved_opcode.missing_witnesses.iter().copied())
+            .collect(),
+        unsolved_opcodes,
+        failure,
+    };
+
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&requirements).unwrap().into())
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use acvm::{acir::native_types::WitnessMap, FieldElement};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Map, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use super::solver::{SolverStats, WitnessSnapshot};
+use crate::{js_witness_map::field_element_to_js_string, JsWitnessMap};
+
+#[wasm_bindgen(typescript_custom_section)]
+const EXECUTION_RESULT: &'static str = r#"
+/**
+* @typedef {Object} SolverStats - Statistics describing the work done to solve a circuit.
+* @property {number} passes - The number of passes made over the unsolved opcodes.
+* @property {number} opcodeAttempts - The number of attempts made to solve an opcode, including those which stalled.
+* @property {Record<string, number>} stalls - The number of times an opcode failed to make progress, keyed by the name of the opcode.
+* @property {number} deferredBrilligCalls - The number of times a Brillig opcode was deferred as its inputs were not yet known.
+* @property {Record<number, number>} brilligSteps - The number of Brillig VM steps executed by each Brillig opcode, keyed by the index of the opcode.
+*/
+export type SolverStats = {
+  passes: number;
+  opcodeAttempts: number;
+  stalls: Record<string, number>;
+  deferredBrilligCalls: number;
+  brilligSteps: Record<number, number>;
+}
+
+/**
+* @typedef {Object} ExecutionResult - The result of executing a circuit.
+* @property {WitnessMap} witness - The solved witness.
+* @property {SolverStats} stats - Statistics gathered while solving the witness.
+* @property {Map<number, string[]>} memoryBlocks - The final contents of each memory block, keyed by the block's id.
+* @property {WitnessSnapshot[]} snapshots - The witness snapshots requested by `snapshotOpcodes`, in the order in which they were taken.
+* @property {string} fingerprint - A hash of the circuit, the foreign calls made and the build of this package. Executions with the same fingerprint simulated the same computation with the same oracle data.
+*/
+export type ExecutionResult = {
+  witness: WitnessMap;
+  stats: SolverStats;
+  memoryBlocks: Map<number, string[]>;
+  snapshots: WitnessSnapshot[];
+  fingerprint: string;
+}
+
+/**
+* @typedef {Object} WitnessSnapshot - The witnesses assigned between two snapshot points.
+* @property {number} opcodeIndex - The index of the opcode whose solving triggered this snapshot.
+* @property {WitnessMap} assignedWitnesses - The witnesses assigned since the previous snapshot, or since execution began for the first snapshot.
+*/
+export type WitnessSnapshot = {
+  opcodeIndex: number;
+  assignedWitnesses: WitnessMap;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "ExecutionResult")]
+    pub type JsExecutionResult;
+}
+
+/// The result of executing a circuit with the [`Solver`][super::solver::Solver].
+pub(crate) struct ExecutionResult {
+    pub(crate) witness: WitnessMap,
+    pub(crate) stats: SolverStats,
+    pub(crate) memory_blocks: BTreeMap<u32, Vec<FieldElement>>,
+    pub(crate) snapshots: Vec<WitnessSnapshot>,
+    /// The fingerprint of the execution, if it was recorded.
+    pub(crate) fingerprint: Option<String>,
+}
+
+impl From<ExecutionResult> for JsExecutionResult {
+    fn from(result: ExecutionResult) -> Self {
+        let js_result = Object::new();
+        let set = |key: &str, value: &JsValue| {
+            Reflect::set(&js_result, &JsValue::from_str(key), value)
+                .expect("setting a property on an `Object` should not fail");
+        };
+
+        set("witness", &JsWitnessMap::from(result.witness));
+        set("stats", &<JsValue as JsValueSerdeExt>::from_serde(&result.stats).unwrap());
+
+        let memory_blocks = Map::new();
+        for (block_id, contents) in result.memory_blocks {
+            let contents: Array = contents.iter().map(field_element_to_js_string).collect();
+            memory_blocks.set(&JsValue::from(block_id), &contents);
+        }
+        set("memoryBlocks", &memory_blocks);
+
+        let snapshots: Array = result
+            .snapshots
+            .into_iter()
+            .map(|snapshot| {
+                let js_snapshot = Object::new();
+                Reflect::set(
+                    &js_snapshot,
+                    &JsValue::from_str("opcodeIndex"),
+                    &JsValue::from(snapshot.opcode_index as u32),
+                )
+                .and_then(|_| {
+                    Reflect::set(
+                        &js_snapshot,
+                        &JsValue::from_str("assignedWitnesses"),
+                        &JsWitnessMap::from(snapshot.assigned_witnesses),
+                    )
+                })
+                .expect("setting a property on an `Object` should not fail");
+                js_snapshot
+            })
+            .collect();
+        set("snapshots", &snapshots);
+        if let Some(fingerprint) = result.fingerprint {
+            set("fingerprint", &JsValue::from(fingerprint));
+        }
+
+        js_result.unchecked_into()
+    }
+}
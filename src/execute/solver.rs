@@ -0,0 +1,1117 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    rc::Rc,
+};
+
+use acvm::{
+    acir::{
+        brillig::ForeignCallResult,
+        circuit::{
+            brillig::{Brillig, BrilligInputs, BrilligOutputs},
+            directives::{Directive, LogInfo, QuotientDirective},
+            opcodes::{BlockId, MemOp},
+            Opcode, OpcodeLabel,
+        },
+        native_types::{Expression, Witness, WitnessMap},
+        FieldElement,
+    },
+    pwg::{ACVMStatus, OpcodeNotSolvable, OpcodeResolutionError, ACVM},
+};
+
+use js_sys::{Function, Reflect};
+use serde::Serialize;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::foreign_call::{
+    resolve_brillig, resolve_brillig_concurrently, ForeignCallBitSizes, ForeignCallHandler,
+};
+
+use super::{
+    debugger::{
+        Breakpoints, BrilligInspector, BrilligPauseReason, BrilligStepper, CurrentLocation,
+        OpcodeLocation, Watchpoints,
+    },
+    invariants::Invariants,
+    step_limit::{check_brillig_steps, count_brillig_steps},
+    SimulatedBackend,
+};
+
+/// The witnesses assigned while solving an opcode.
+type Assignments = BTreeMap<Witness, FieldElement>;
+
+/// The outcome of attempting to solve a single opcode.
+enum OpcodeStatus {
+    /// All of the opcode's outputs have been assigned.
+    Solved,
+    /// Some, but not all, of the opcode's outputs have been assigned.
+    InProgress,
+    /// The opcode cannot make progress until more of the witness has been solved.
+    Stalled(OpcodeNotSolvable),
+}
+
+/// The reason the [`Solver`] could not solve every opcode.
+pub(crate) enum SolverError {
+    /// None of the remaining opcodes could make progress with the witnesses assigned so far.
+    Stalled(SolverStalled),
+    /// An opcode could not be solved, e.g. due to an unsatisfied constraint or a failing foreign call.
+    Failed(String),
+}
+
+impl std::fmt::Display for SolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::Stalled(stalled) => stalled.fmt(f),
+            SolverError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+/// A pass over the unsolved opcodes made no progress, so solving can't continue.
+#[derive(Debug, Clone)]
+pub(crate) struct SolverStalled {
+    /// Why the first of the unsolved opcodes couldn't be solved.
+    pub(crate) reason: OpcodeNotSolvable,
+    /// The indices of the opcodes which remain unsolved, in ascending order.
+    pub(crate) unsolved_opcodes: Vec<usize>,
+    /// The number of passes made over the opcodes before stalling, if known.
+    pub(crate) passes: Option<u32>,
+}
+
+impl std::fmt::Display for SolverStalled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// The number of unsolved opcodes to list in the message. All of them are available on the error.
+        const MAX_LISTED_OPCODES: usize = 10;
+
+        write!(f, "Solver stalled")?;
+        if let Some(passes) = self.passes {
+            write!(f, " after {passes} passes")?;
+        }
+        let listed: Vec<String> = self
+            .unsolved_opcodes
+            .iter()
+            .take(MAX_LISTED_OPCODES)
+            .map(|index| index.to_string())
+            .collect();
+        write!(f, " with {} unsolved opcodes ({}", self.unsolved_opcodes.len(), listed.join(", "))?;
+        if self.unsolved_opcodes.len() > MAX_LISTED_OPCODES {
+            write!(f, ", ...")?;
+        }
+        write!(f, "): {}", OpcodeResolutionError::OpcodeNotSolvable(self.reason.clone()))
+    }
+}
+
+/// Statistics describing the work done by the [`Solver`] to solve a circuit.
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SolverStats {
+    /// The number of passes made over the unresolved opcodes.
+    pub(crate) passes: u32,
+    /// The number of attempts made to solve an opcode, including those which stalled.
+    pub(crate) opcode_attempts: u32,
+    /// The number of times an opcode failed to make progress, keyed by the name of the opcode.
+    pub(crate) stalls: BTreeMap<String, u32>,
+    /// The number of times a Brillig opcode was deferred without being run as its inputs were not yet known.
+    pub(crate) deferred_brillig_calls: u32,
+    /// The number of Brillig opcodes executed by each Brillig opcode, keyed by its index, if metering has been enabled.
+    pub(crate) brillig_steps: BTreeMap<usize, u64>,
+}
+
+/// The witnesses assigned since the previous snapshot, recorded when a snapshot opcode was solved.
+pub(crate) struct WitnessSnapshot {
+    /// The index of the opcode whose solving triggered the snapshot.
+    pub(crate) opcode_index: usize,
+    pub(crate) assigned_witnesses: WitnessMap,
+}
+
+/// The state needed to record [`WitnessSnapshot`]s as the circuit is solved.
+struct Snapshots {
+    /// Indices of the opcodes which trigger a snapshot once solved.
+    opcode_indices: BTreeSet<usize>,
+    /// Witnesses assigned since the last snapshot was taken.
+    pending: Assignments,
+    taken: Vec<WitnessSnapshot>,
+}
+
+/// Solves a circuit one opcode at a time, allowing the witnesses assigned by each opcode to be observed.
+///
+/// Opcodes are attempted in the same order as the ACVM, with stalled opcodes being retried in later passes.
+/// Each opcode is solved by an ACVM which only holds the witnesses the opcode references, with the exception
+/// of memory opcodes which share state across opcodes and so are solved here directly.
+///
+/// Between passes, stalled Brillig opcodes are moved behind any other stalled opcodes. Running a Brillig
+/// function is expensive so it's worth giving the opcodes which it may depend upon a chance to be solved first.
+pub(crate) struct Solver<'a> {
+    backend: &'a SimulatedBackend,
+    opcodes: &'a [Opcode],
+
+    /// Indices of the opcodes which remain to be solved, in the order in which they will be attempted.
+    unresolved_opcodes: Vec<usize>,
+
+    /// Witnesses assigned by opcodes which have made progress without being fully solved.
+    partial_assignments: HashMap<usize, Assignments>,
+
+    /// The current contents of each memory block initialized by a `MemoryInit` opcode.
+    memory_blocks: HashMap<BlockId, HashMap<u32, FieldElement>>,
+
+    /// Indices of the `MemoryInit` and `MemoryOp` opcodes of each block which remain to be solved.
+    ///
+    /// Memory opcodes must be applied in program order, so each waits until the earlier opcodes on its block are solved.
+    unsolved_memory_opcodes: HashMap<BlockId, BTreeSet<usize>>,
+
+    witness_map: WitnessMap,
+
+    stats: SolverStats,
+
+    /// The total time spent attempting each opcode in milliseconds, if profiling has been enabled.
+    opcode_times: Option<Vec<f64>>,
+
+    /// The bit sizes which foreign call results are checked against, if checking has been enabled.
+    foreign_call_bit_sizes: Option<ForeignCallBitSizes>,
+
+    /// The witness snapshots taken so far, if snapshots have been enabled.
+    snapshots: Option<Snapshots>,
+
+    /// The maximum number of Brillig opcodes a single Brillig call may execute, if limited.
+    max_brillig_steps: Option<usize>,
+
+    /// Whether the number of Brillig opcodes executed by each Brillig call is recorded in the stats.
+    meter_brillig_steps: bool,
+
+    /// Whether consecutive Brillig opcodes which are ready to run make their foreign calls at the same time.
+    concurrent_foreign_calls: bool,
+
+    /// The breakpoints which pause solving, if any have been set.
+    breakpoints: Option<Breakpoints>,
+
+    /// Steps through Brillig calls one instruction at a time, if enabled.
+    brillig_stepper: Option<BrilligStepper>,
+
+    /// Exposes the state of the Brillig VM whenever a Brillig call halts, if enabled.
+    brillig_inspector: Option<BrilligInspector>,
+
+    /// Updated with the opcode being attempted, if set.
+    current_location: Option<CurrentLocation>,
+
+    /// The watchpoints which pause solving when their witness is assigned, if any have been set.
+    watchpoints: Option<Watchpoints>,
+
+    /// The invariants which are checked after each pass, if any have been set.
+    invariants: Option<Rc<Invariants>>,
+}
+
+/// An opcode which is being solved by an ACVM holding only the witnesses the opcode references.
+struct OpcodeExecution<'a> {
+    opcode_index: usize,
+    opcode: &'a Opcode,
+    acvm: ACVM<&'a SimulatedBackend>,
+    /// The foreign call results the ACVM will run a Brillig opcode with, used to check the step limit
+    /// and to meter the steps it took.
+    foreign_call_results: Vec<ForeignCallResult>,
+    /// The number of Brillig instructions stepped through so far, or `None` if the opcode isn't being stepped through.
+    stepped_brillig_instructions: Option<usize>,
+}
+
+impl OpcodeExecution<'_> {
+    fn resolve_foreign_call(&mut self, result: ForeignCallResult) {
+        self.foreign_call_results.push(result.clone());
+        self.acvm.resolve_pending_foreign_call(result);
+    }
+}
+
+impl Snapshots {
+    /// Adds the witnesses assigned by solving `opcode_index`, taking a snapshot if it's one of the snapshot opcodes.
+    fn record(&mut self, opcode_index: usize, assigned_witnesses: &Assignments) {
+        self.pending.extend(assigned_witnesses.clone());
+        if self.opcode_indices.contains(&opcode_index) {
+            let assigned_witnesses = std::mem::take(&mut self.pending).into();
+            self.taken.push(WitnessSnapshot { opcode_index, assigned_witnesses });
+        }
+    }
+}
+
+impl<'a> Solver<'a> {
+    pub(crate) fn new(
+        backend: &'a SimulatedBackend,
+        opcodes: &'a [Opcode],
+        initial_witness: WitnessMap,
+    ) -> Self {
+        let mut unsolved_memory_opcodes: HashMap<BlockId, BTreeSet<usize>> = HashMap::new();
+        for (index, opcode) in opcodes.iter().enumerate() {
+            if let Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } = opcode
+            {
+                unsolved_memory_opcodes.entry(*block_id).or_default().insert(index);
+            }
+        }
+
+        Solver {
+            backend,
+            opcodes,
+            unresolved_opcodes: (0..opcodes.len()).collect(),
+            partial_assignments: HashMap::new(),
+            memory_blocks: HashMap::new(),
+            unsolved_memory_opcodes,
+            witness_map: initial_witness,
+            stats: SolverStats::default(),
+            opcode_times: None,
+            foreign_call_bit_sizes: None,
+            snapshots: None,
+            max_brillig_steps: None,
+            meter_brillig_steps: false,
+            concurrent_foreign_calls: false,
+            breakpoints: None,
+            brillig_stepper: None,
+            brillig_inspector: None,
+            current_location: None,
+            watchpoints: None,
+            invariants: None,
+        }
+    }
+
+    /// Records the time spent attempting each opcode, including any time spent waiting on foreign calls.
+    pub(crate) fn enable_profiling(&mut self) {
+        self.opcode_times = Some(vec![0.0; self.opcodes.len()]);
+    }
+
+    /// Rejects foreign call results which don't fit within the bit size that the Brillig bytecode uses them as.
+    pub(crate) fn check_foreign_call_bit_sizes(&mut self) {
+        self.foreign_call_bit_sizes = Some(ForeignCallBitSizes::from_opcodes(self.opcodes));
+    }
+
+    /// Fails execution if a Brillig call executes more than `max_steps` Brillig opcodes.
+    pub(crate) fn limit_brillig_steps(&mut self, max_steps: usize) {
+        self.max_brillig_steps = Some(max_steps);
+    }
+
+    /// Records the number of Brillig opcodes executed by each Brillig call in the stats.
+    ///
+    /// Each Brillig call is run a second time to count its steps, roughly doubling the time spent in Brillig.
+    pub(crate) fn enable_brillig_metering(&mut self) {
+        self.meter_brillig_steps = true;
+    }
+
+    /// Solves runs of consecutive Brillig opcodes whose inputs are known together, making the foreign calls they
+    /// require at the same time rather than one after another.
+    ///
+    /// Should one of these foreign calls fail, those still pending are cancelled rather than awaited.
+    pub(crate) fn enable_concurrent_foreign_calls(&mut self) {
+        self.concurrent_foreign_calls = true;
+    }
+
+    /// Pauses solving before each attempt at an opcode with one of the `breakpoints`.
+    pub(crate) fn enable_breakpoints(&mut self, breakpoints: Breakpoints) {
+        self.breakpoints = Some(breakpoints);
+    }
+
+    /// Steps through each Brillig call one instruction at a time before it is solved.
+    pub(crate) fn enable_brillig_stepping(&mut self, brillig_stepper: BrilligStepper) {
+        self.brillig_stepper = Some(brillig_stepper);
+    }
+
+    /// Exposes the registers and memory of the Brillig VM each time a Brillig call halts for a foreign call or fails.
+    pub(crate) fn enable_brillig_inspection(&mut self, brillig_inspector: BrilligInspector) {
+        self.brillig_inspector = Some(brillig_inspector);
+    }
+
+    /// Pauses solving each time one of the witnesses watched by `watchpoints` is assigned.
+    pub(crate) fn enable_watchpoints(&mut self, watchpoints: Watchpoints) {
+        self.watchpoints = Some(watchpoints);
+    }
+
+    /// Checks `invariants` against the witness after each pass, failing as soon as one is violated.
+    pub(crate) fn enable_invariants(&mut self, invariants: Rc<Invariants>) {
+        self.invariants = Some(invariants);
+    }
+
+    /// Keeps `current_location` updated with the opcode being attempted.
+    pub(crate) fn track_location(&mut self, current_location: CurrentLocation) {
+        self.current_location = Some(current_location);
+    }
+
+    /// Records the witnesses assigned since the previous snapshot each time one of `opcode_indices` is solved.
+    pub(crate) fn enable_snapshots(&mut self, opcode_indices: &[usize]) {
+        self.snapshots = Some(Snapshots {
+            opcode_indices: opcode_indices.iter().copied().collect(),
+            pending: Assignments::new(),
+            taken: Vec::new(),
+        });
+    }
+
+    /// Removes and returns the snapshots taken so far, in the order in which their opcodes were solved.
+    pub(crate) fn take_snapshots(&mut self) -> Vec<WitnessSnapshot> {
+        self.snapshots
+            .as_mut()
+            .map_or_else(Vec::new, |snapshots| std::mem::take(&mut snapshots.taken))
+    }
+
+    /// Returns the total time spent attempting each opcode in milliseconds, if profiling has been enabled.
+    pub(crate) fn opcode_times(&self) -> Option<&[f64]> {
+        self.opcode_times.as_deref()
+    }
+
+    /// Returns a reference to the witness solved so far.
+    pub(crate) fn witness_map(&self) -> &WitnessMap {
+        &self.witness_map
+    }
+
+    /// Returns statistics on the work done to solve the circuit so far.
+    pub(crate) fn stats(&self) -> &SolverStats {
+        &self.stats
+    }
+
+    /// Returns the opcodes which remain to be solved, along with their indices.
+    pub(crate) fn unresolved_opcodes(&self) -> impl Iterator<Item = (usize, &'a Opcode)> + '_ {
+        let opcodes = self.opcodes;
+        self.unresolved_opcodes.iter().map(move |&index| (index, &opcodes[index]))
+    }
+
+    /// Returns the contents of each memory block initialized by a `MemoryInit` opcode, keyed by the block's id.
+    ///
+    /// Any unwritten slots within a block are filled with zero.
+    pub(crate) fn memory_blocks(&self) -> BTreeMap<u32, Vec<FieldElement>> {
+        self.memory_blocks
+            .iter()
+            .map(|(block_id, block)| {
+                let length = block.keys().max().map_or(0, |index| *index as usize + 1);
+                let mut contents = vec![FieldElement::zero(); length];
+                for (index, value) in block {
+                    contents[*index as usize] = *value;
+                }
+                (block_id.0, contents)
+            })
+            .collect()
+    }
+
+    /// Consumes the solver, returning the solved witness.
+    pub(crate) fn finalize(self) -> WitnessMap {
+        self.witness_map
+    }
+
+    /// Solves all of the opcodes, resolving any foreign calls through `foreign_call_handler`.
+    ///
+    /// If no handler is provided then no foreign calls are made and any Brillig opcode which requires one stalls.
+    ///
+    /// `on_opcode_solved` is called with the index of each opcode as it is solved, along with the witnesses it assigned.
+    pub(crate) async fn solve(
+        &mut self,
+        foreign_call_handler: Option<&ForeignCallHandler>,
+        mut on_opcode_solved: impl FnMut(usize, &Opcode, WitnessMap),
+    ) -> Result<(), SolverError> {
+        while !self.unresolved_opcodes.is_empty() {
+            self.stats.passes += 1;
+            let mut stalled = true;
+            let mut opcode_not_solvable = None;
+
+            let mut unresolved_opcodes = Vec::new();
+            let mut pending_opcodes = std::mem::take(&mut self.unresolved_opcodes).into_iter();
+            while let Some(opcode_index) = pending_opcodes.next() {
+                let opcodes = self.opcodes;
+                if let Some(current_location) = &self.current_location {
+                    current_location.set(Some(OpcodeLocation {
+                        acir_index: opcode_index,
+                        brillig_index: None,
+                    }));
+                }
+                if let Some(breakpoints) = &mut self.breakpoints {
+                    if breakpoints.pauses_at(opcode_index) {
+                        let paused = breakpoints
+                            .pause(opcode_index, &opcodes[opcode_index], &self.witness_map)
+                            .await;
+                        if let Err(message) = paused {
+                            unresolved_opcodes.push(opcode_index);
+                            unresolved_opcodes.extend(pending_opcodes);
+                            self.unresolved_opcodes = unresolved_opcodes;
+                            return Err(SolverError::Failed(message));
+                        }
+                    }
+                }
+
+                // Consecutive Brillig opcodes which are ready to run are solved alongside each other so that
+                // their foreign calls can be made at once.
+                let mut batch = vec![opcode_index];
+                if self.concurrent_foreign_calls
+                    && self.brillig_stepper.is_none()
+                    && self.brillig_inspector.is_none()
+                    && foreign_call_handler.is_some()
+                    && self.is_ready_brillig(&opcodes[opcode_index])
+                {
+                    while let Some(&next_index) = pending_opcodes.as_slice().first() {
+                        // Opcodes with a breakpoint start a new batch so that execution pauses before them.
+                        let pauses = self
+                            .breakpoints
+                            .as_ref()
+                            .map_or(false, |breakpoints| breakpoints.pauses_at(next_index));
+                        if pauses || !self.is_ready_brillig(&opcodes[next_index]) {
+                            break;
+                        }
+                        batch.push(next_index);
+                        pending_opcodes.next();
+                    }
+                }
+
+                self.stats.opcode_attempts += batch.len() as u32;
+                let start = self.opcode_times.is_some().then(now);
+                let results = match (batch.as_slice(), foreign_call_handler) {
+                    (&[opcode_index], _) => self
+                        .solve_opcode(opcode_index, &opcodes[opcode_index], foreign_call_handler)
+                        .await
+                        .map(|(status, assigned_witnesses)| {
+                            vec![(opcode_index, status, assigned_witnesses)]
+                        }),
+                    (_, Some(foreign_call_handler)) => {
+                        self.solve_brillig_concurrently(&batch, foreign_call_handler).await
+                    }
+                    (_, None) => unreachable!("Brillig opcodes are only batched with a handler"),
+                };
+                if let (Some(opcode_times), Some(start)) = (&mut self.opcode_times, start) {
+                    // The opcodes in a batch are solved together so the time is shared between them.
+                    let elapsed = (now() - start) / batch.len() as f64;
+                    for &opcode_index in &batch {
+                        opcode_times[opcode_index] += elapsed;
+                    }
+                }
+                let results = match results {
+                    Ok(results) => results,
+                    Err(message) => {
+                        // Leave the opcodes which were not attempted in this pass as unresolved.
+                        unresolved_opcodes.extend(batch);
+                        unresolved_opcodes.extend(pending_opcodes);
+                        self.unresolved_opcodes = unresolved_opcodes;
+                        return Err(SolverError::Failed(message));
+                    }
+                };
+                if let Some(watchpoints) = &self.watchpoints {
+                    for (opcode_index, _, assigned_witnesses) in &results {
+                        let paused = watchpoints
+                            .notify(*opcode_index, &opcodes[*opcode_index], assigned_witnesses)
+                            .await;
+                        if let Err(message) = paused {
+                            unresolved_opcodes.extend(pending_opcodes);
+                            self.unresolved_opcodes = unresolved_opcodes;
+                            return Err(SolverError::Failed(message));
+                        }
+                    }
+                }
+
+                for (opcode_index, status, assigned_witnesses) in results {
+                    let opcode = &opcodes[opcode_index];
+                    match status {
+                        OpcodeStatus::Solved => {
+                            stalled = false;
+                            let assigned_witnesses =
+                                match self.partial_assignments.remove(&opcode_index) {
+                                    Some(mut previous_assignments) => {
+                                        previous_assignments.extend(assigned_witnesses);
+                                        previous_assignments
+                                    }
+                                    None => assigned_witnesses,
+                                };
+                            if let Some(snapshots) = &mut self.snapshots {
+                                snapshots.record(opcode_index, &assigned_witnesses);
+                            }
+                            on_opcode_solved(opcode_index, opcode, assigned_witnesses.into());
+                        }
+                        OpcodeStatus::InProgress => {
+                            stalled = false;
+                            if let Some(snapshots) = &mut self.snapshots {
+                                snapshots.pending.extend(assigned_witnesses.clone());
+                            }
+                            self.partial_assignments
+                                .entry(opcode_index)
+                                .or_default()
+                                .extend(assigned_witnesses);
+                            unresolved_opcodes.push(opcode_index);
+                        }
+                        OpcodeStatus::Stalled(not_solvable) => {
+                            *self.stats.stalls.entry(opcode.name().to_string()).or_default() += 1;
+                            opcode_not_solvable.get_or_insert(not_solvable);
+                            unresolved_opcodes.push(opcode_index);
+                        }
+                    }
+                }
+            }
+            // A stable sort preserves the relative order of the opcodes within each group.
+            let opcodes = self.opcodes;
+            unresolved_opcodes.sort_by_key(|&index| matches!(opcodes[index], Opcode::Brillig(_)));
+            self.unresolved_opcodes = unresolved_opcodes;
+
+            if let Some(invariants) = &self.invariants {
+                invariants.check(&self.witness_map).await.map_err(SolverError::Failed)?;
+            }
+
+            if stalled && !self.unresolved_opcodes.is_empty() {
+                let mut unsolved_opcodes = self.unresolved_opcodes.clone();
+                unsolved_opcodes.sort_unstable();
+                return Err(SolverError::Stalled(SolverStalled {
+                    reason: opcode_not_solvable
+                        .expect("infallible: cannot be stalled and None at the same time"),
+                    unsolved_opcodes,
+                    passes: Some(self.stats.passes),
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that every opcode is satisfied by the witness the solver was created with, rather than solving it.
+    ///
+    /// Unlike [`Solver::solve`], this doesn't stop at the first failure. The index of each opcode which is violated,
+    /// or which references a witness missing from the witness, is returned along with a description of the issue.
+    /// No foreign calls are made so the outputs of Brillig opcodes which require one are not checked.
+    pub(crate) async fn check(&mut self) -> Vec<(usize, String)> {
+        let opcodes = self.opcodes;
+        let mut violations = Vec::new();
+        for (opcode_index, opcode) in opcodes.iter().enumerate() {
+            let violation = match self.solve_opcode(opcode_index, opcode, None).await {
+                Ok((OpcodeStatus::Solved, assigned_witnesses)) if assigned_witnesses.is_empty() => {
+                    continue
+                }
+                Ok((OpcodeStatus::Stalled(not_solvable), _)) => {
+                    OpcodeResolutionError::OpcodeNotSolvable(not_solvable).to_string()
+                }
+                Ok((_, assigned_witnesses)) => {
+                    let missing_witnesses: Vec<_> = assigned_witnesses
+                        .keys()
+                        .map(|witness| witness.witness_index().to_string())
+                        .collect();
+                    format!(
+                        "missing assignment for witness indices {}",
+                        missing_witnesses.join(", ")
+                    )
+                }
+                Err(message) => message,
+            };
+            violations.push((opcode_index, violation));
+        }
+        violations
+    }
+
+    async fn solve_opcode(
+        &mut self,
+        opcode_index: usize,
+        opcode: &'a Opcode,
+        foreign_call_handler: Option<&ForeignCallHandler>,
+    ) -> Result<(OpcodeStatus, Assignments), String> {
+        match opcode {
+            Opcode::Brillig(brillig) if !self.brillig_inputs_known(brillig) => {
+                self.stats.deferred_brillig_calls += 1;
+                let missing_witness = brillig_input_witnesses(brillig)
+                    .into_iter()
+                    .find(|witness| !self.witness_map.contains_key(witness))
+                    .expect("some input is unknown");
+                let not_solvable = OpcodeNotSolvable::MissingAssignment(missing_witness.0);
+                Ok((OpcodeStatus::Stalled(not_solvable), Assignments::new()))
+            }
+            Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } => {
+                self.solve_memory_opcode(opcode_index, *block_id, opcode)
+            }
+            _ => self.solve_with_acvm(opcode_index, opcode, foreign_call_handler).await,
+        }
+    }
+
+    async fn solve_with_acvm(
+        &mut self,
+        opcode_index: usize,
+        opcode: &'a Opcode,
+        foreign_call_handler: Option<&ForeignCallHandler>,
+    ) -> Result<(OpcodeStatus, Assignments), String> {
+        let mut execution = self.start_execution(opcode_index, opcode);
+        let status = loop {
+            if let (Some(brillig_stepper), Opcode::Brillig(brillig), Some(stepped)) =
+                (&self.brillig_stepper, opcode, execution.stepped_brillig_instructions)
+            {
+                execution.stepped_brillig_instructions = brillig_stepper
+                    .step(
+                        self.backend,
+                        opcode_index,
+                        brillig,
+                        execution.acvm.witness_map(),
+                        &execution.foreign_call_results,
+                        stepped,
+                    )
+                    .await?;
+            }
+            let status = self.advance_execution(&mut execution);
+            if let (Some(brillig_inspector), Opcode::Brillig(brillig)) =
+                (&self.brillig_inspector, opcode)
+            {
+                let reason = match &status {
+                    Ok(None) => Some(BrilligPauseReason::ForeignCall),
+                    Err(_) => Some(BrilligPauseReason::Failure),
+                    Ok(Some(_)) => None,
+                };
+                if let Some(reason) = reason {
+                    brillig_inspector
+                        .pause(
+                            reason,
+                            self.backend,
+                            opcode_index,
+                            brillig,
+                            execution.acvm.witness_map(),
+                            &execution.foreign_call_results,
+                        )
+                        .await?;
+                }
+            }
+            if let Some(status) = status? {
+                break status;
+            }
+
+            let Some(foreign_call_handler) = foreign_call_handler else {
+                // Without a handler the outputs of the Brillig opcode can never be assigned.
+                // If every witness it references is already known then there's nothing left to solve.
+                let output = opcode_witnesses(opcode)
+                    .into_iter()
+                    .find(|witness| !self.witness_map.contains_key(witness));
+                break match output {
+                    Some(output) => {
+                        OpcodeStatus::Stalled(OpcodeNotSolvable::MissingAssignment(output.0))
+                    }
+                    None => OpcodeStatus::Solved,
+                };
+            };
+            while let Some(foreign_call) = execution.acvm.get_pending_foreign_call() {
+                let result = resolve_brillig(
+                    foreign_call_handler,
+                    foreign_call,
+                    self.foreign_call_bit_sizes.as_ref(),
+                )
+                .await?;
+                execution.resolve_foreign_call(result);
+            }
+        };
+
+        Ok(self.finish_execution(execution, status))
+    }
+
+    /// Solves the Brillig opcodes at `opcode_indices`, all of whose inputs are known, alongside each other.
+    ///
+    /// Each opcode is run until it either finishes or requires a foreign call, with the foreign calls required by
+    /// all of them then being made at once. This repeats until every opcode has finished.
+    async fn solve_brillig_concurrently(
+        &mut self,
+        opcode_indices: &[usize],
+        foreign_call_handler: &ForeignCallHandler,
+    ) -> Result<Vec<(usize, OpcodeStatus, Assignments)>, String> {
+        let opcodes = self.opcodes;
+        let mut executions: Vec<(OpcodeExecution<'a>, Option<OpcodeStatus>)> = opcode_indices
+            .iter()
+            .map(|&opcode_index| (self.start_execution(opcode_index, &opcodes[opcode_index]), None))
+            .collect();
+
+        loop {
+            for (execution, status) in &mut executions {
+                if status.is_none() {
+                    *status = self.advance_execution(execution)?;
+                }
+            }
+
+            let foreign_calls: Vec<_> = executions
+                .iter()
+                .filter(|(_, status)| status.is_none())
+                .map(|(execution, _)| {
+                    execution
+                        .acvm
+                        .get_pending_foreign_call()
+                        .expect("a foreign call should be pending")
+                })
+                .collect();
+            if foreign_calls.is_empty() {
+                break;
+            }
+
+            let results = resolve_brillig_concurrently(
+                foreign_call_handler,
+                &foreign_calls,
+                self.foreign_call_bit_sizes.as_ref(),
+            )
+            .await?;
+            let waiting = executions.iter_mut().filter(|(_, status)| status.is_none());
+            for ((execution, _), result) in waiting.zip(results) {
+                execution.resolve_foreign_call(result);
+            }
+        }
+
+        Ok(executions
+            .into_iter()
+            .map(|(execution, status)| {
+                let opcode_index = execution.opcode_index;
+                let status = status.expect("every opcode has finished");
+                let (status, assigned_witnesses) = self.finish_execution(execution, status);
+                (opcode_index, status, assigned_witnesses)
+            })
+            .collect())
+    }
+
+    /// Creates an ACVM to solve `opcode`, holding only the witnesses which `opcode` references.
+    fn start_execution(&self, opcode_index: usize, opcode: &'a Opcode) -> OpcodeExecution<'a> {
+        let mut local_witness_map = WitnessMap::new();
+        for witness in opcode_witnesses(opcode) {
+            if let Some(value) = self.witness_map.get(&witness) {
+                local_witness_map.insert(witness, *value);
+            }
+        }
+
+        let foreign_call_results = match opcode {
+            Opcode::Brillig(brillig) => brillig.foreign_call_results.clone(),
+            _ => Vec::new(),
+        };
+        let acvm = ACVM::new(self.backend, vec![opcode.clone()], local_witness_map);
+        let stepped_brillig_instructions = self.brillig_stepper.as_ref().map(|_| 0);
+        OpcodeExecution {
+            opcode_index,
+            opcode,
+            acvm,
+            foreign_call_results,
+            stepped_brillig_instructions,
+        }
+    }
+
+    /// Runs the ACVM of `execution` until it halts, returning `None` if it halted as a foreign call is required.
+    fn advance_execution(
+        &self,
+        execution: &mut OpcodeExecution<'a>,
+    ) -> Result<Option<OpcodeStatus>, String> {
+        let OpcodeExecution { opcode_index, opcode, acvm, foreign_call_results, .. } = execution;
+        if let (Opcode::Brillig(brillig), Some(max_steps)) = (opcode, self.max_brillig_steps) {
+            check_brillig_steps(
+                self.backend,
+                brillig,
+                acvm.witness_map(),
+                foreign_call_results,
+                max_steps,
+                *opcode_index,
+            )?;
+        }
+        match acvm.solve() {
+            ACVMStatus::Solved => Ok(Some(OpcodeStatus::Solved)),
+            ACVMStatus::InProgress => {
+                unreachable!("Execution should not stop while in `InProgress` state.")
+            }
+            ACVMStatus::Failure(OpcodeResolutionError::OpcodeNotSolvable(not_solvable)) => {
+                Ok(Some(OpcodeStatus::Stalled(not_solvable)))
+            }
+            ACVMStatus::Failure(OpcodeResolutionError::UnsatisfiedConstrain { .. }) => {
+                // The ACVM only knows the opcode's position within its own single opcode circuit.
+                let error = OpcodeResolutionError::UnsatisfiedConstrain {
+                    opcode_label: OpcodeLabel::Resolved(*opcode_index as u64),
+                };
+                Err(error.to_string())
+            }
+            ACVMStatus::Failure(error) => Err(error.to_string()),
+            ACVMStatus::RequiresForeignCall => Ok(None),
+        }
+    }
+
+    /// Copies the witnesses assigned by the ACVM of `execution` into the solver's witness map.
+    fn finish_execution(
+        &mut self,
+        execution: OpcodeExecution<'a>,
+        status: OpcodeStatus,
+    ) -> (OpcodeStatus, Assignments) {
+        let OpcodeExecution { opcode_index, opcode, acvm, foreign_call_results, .. } = execution;
+        if let (Opcode::Brillig(brillig), OpcodeStatus::Solved, true) =
+            (opcode, &status, self.meter_brillig_steps)
+        {
+            let steps = count_brillig_steps(
+                self.backend,
+                brillig,
+                acvm.witness_map(),
+                &foreign_call_results,
+            );
+            if let Some(steps) = steps {
+                *self.stats.brillig_steps.entry(opcode_index).or_default() += steps;
+            }
+        }
+
+        let mut assigned_witnesses = Assignments::new();
+        for witness in opcode_witnesses(opcode) {
+            if self.witness_map.contains_key(&witness) {
+                continue;
+            }
+            if let Some(value) = acvm.witness_map().get(&witness) {
+                self.witness_map.insert(witness, *value);
+                assigned_witnesses.insert(witness, *value);
+            }
+        }
+
+        // A stalled opcode may still have made progress, e.g. a `Block` opcode which solved part of its trace.
+        let status = match status {
+            OpcodeStatus::Stalled(_) if !assigned_witnesses.is_empty() => OpcodeStatus::InProgress,
+            status => status,
+        };
+        (status, assigned_witnesses)
+    }
+
+    /// Returns whether `opcode` is a Brillig opcode which will be run, rather than deferred, if attempted now.
+    fn is_ready_brillig(&self, opcode: &Opcode) -> bool {
+        matches!(opcode, Opcode::Brillig(brillig) if self.brillig_inputs_known(brillig))
+    }
+
+    /// Returns whether all of the inputs to `brillig` are known, or if it will be skipped due to its predicate.
+    fn brillig_inputs_known(&self, brillig: &Brillig) -> bool {
+        let skipped = brillig.predicate.as_ref().map_or(false, |predicate| {
+            evaluate(predicate, &self.witness_map).to_const().map_or(false, |value| value.is_zero())
+        });
+        skipped
+            || brillig_input_witnesses(brillig)
+                .iter()
+                .all(|witness| self.witness_map.contains_key(witness))
+    }
+
+    /// Applies the `MemoryInit` or `MemoryOp` opcode at `opcode_index` to the contents of `block_id`.
+    ///
+    /// The opcode stalls while an earlier opcode on the same block is unsolved, otherwise a read could observe the
+    /// block before a pending write has been applied.
+    fn solve_memory_opcode(
+        &mut self,
+        opcode_index: usize,
+        block_id: BlockId,
+        opcode: &Opcode,
+    ) -> Result<(OpcodeStatus, Assignments), String> {
+        let unsolved_opcodes = self.unsolved_memory_opcodes.entry(block_id).or_default();
+        if let Some(&earlier_index) = unsolved_opcodes.range(..opcode_index).next() {
+            let missing_witness = opcode_witnesses(&self.opcodes[earlier_index])
+                .into_iter()
+                .find(|witness| !self.witness_map.contains_key(witness))
+                .expect("an earlier memory opcode is only unsolved while it waits on a witness");
+            let not_solvable = OpcodeNotSolvable::MissingAssignment(missing_witness.0);
+            return Ok((OpcodeStatus::Stalled(not_solvable), Assignments::new()));
+        }
+
+        let result = match opcode {
+            Opcode::MemoryInit { init, .. } => {
+                Ok((self.init_memory(block_id, init), Assignments::new()))
+            }
+            Opcode::MemoryOp { op, .. } => self.solve_memory_op(opcode_index, block_id, op),
+            _ => unreachable!("only memory opcodes are applied to memory blocks"),
+        };
+        // An opcode which fails no longer holds back later opcodes, so that `check` can report their own issues.
+        if !matches!(result, Ok((OpcodeStatus::Stalled(_), _))) {
+            if let Some(unsolved_opcodes) = self.unsolved_memory_opcodes.get_mut(&block_id) {
+                unsolved_opcodes.remove(&opcode_index);
+            }
+        }
+        result
+    }
+
+    fn init_memory(&mut self, block_id: BlockId, init: &[Witness]) -> OpcodeStatus {
+        let mut block = HashMap::with_capacity(init.len());
+        for (index, witness) in init.iter().enumerate() {
+            match self.witness_map.get(witness) {
+                Some(value) => block.insert(index as u32, *value),
+                None => {
+                    return OpcodeStatus::Stalled(OpcodeNotSolvable::MissingAssignment(witness.0))
+                }
+            };
+        }
+        self.memory_blocks.insert(block_id, block);
+        OpcodeStatus::Solved
+    }
+
+    fn solve_memory_op(
+        &mut self,
+        opcode_index: usize,
+        block_id: BlockId,
+        op: &MemOp,
+    ) -> Result<(OpcodeStatus, Assignments), String> {
+        let missing_assignment = |expression: &Expression| {
+            let witness =
+                any_witness(expression).expect("non-constant expressions contain a witness");
+            Ok((
+                OpcodeStatus::Stalled(OpcodeNotSolvable::MissingAssignment(witness.0)),
+                Assignments::new(),
+            ))
+        };
+
+        let unsatisfied = |reason: String| {
+            let error = OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_label: OpcodeLabel::Resolved(opcode_index as u64),
+            };
+            Err(format!("{error}: {reason}"))
+        };
+
+        let operation = evaluate(&op.operation, &self.witness_map);
+        let Some(operation) = operation.to_const() else {
+            return missing_assignment(&operation);
+        };
+        let index = evaluate(&op.index, &self.witness_map);
+        let Some(index) = index.to_const() else {
+            return missing_assignment(&index);
+        };
+        let Some(index) = index.try_to_u64().and_then(|index| u32::try_from(index).ok()) else {
+            return unsatisfied(format!(
+                "memory index 0x{} does not fit within a u32",
+                index.to_hex()
+            ));
+        };
+
+        let value = evaluate(&op.value, &self.witness_map);
+        let block = self.memory_blocks.entry(block_id).or_default();
+        let is_read = operation.is_zero();
+        if let Some(value) = value.to_const() {
+            if !is_read {
+                block.insert(index, value);
+                return Ok((OpcodeStatus::Solved, Assignments::new()));
+            }
+            // A read of an already known value must agree with the contents of memory.
+            return match block.get(&index) {
+                Some(stored_value) if *stored_value == value => {
+                    Ok((OpcodeStatus::Solved, Assignments::new()))
+                }
+                Some(_) => unsatisfied(format!("memory index {index} holds a different value")),
+                None => unsatisfied(format!("memory index {index} is read before it is written")),
+            };
+        }
+
+        // All known witnesses have been folded into the constant term so a solvable read has a single unknown.
+        match (is_read, value.mul_terms.as_slice(), value.linear_combinations.as_slice()) {
+            (true, [], [(coefficient, witness)]) => match block.get(&index) {
+                Some(stored_value) => {
+                    let witness_value = (*stored_value - value.q_c) / *coefficient;
+                    self.witness_map.insert(*witness, witness_value);
+
+                    let mut assigned_witnesses = Assignments::new();
+                    assigned_witnesses.insert(*witness, witness_value);
+                    Ok((OpcodeStatus::Solved, assigned_witnesses))
+                }
+                // Nothing will ever be written to the slot before this read so waiting on it can't help.
+                None => unsatisfied(format!("memory index {index} is read before it is written")),
+            },
+            _ => missing_assignment(&value),
+        }
+    }
+}
+
+/// Returns the current time in milliseconds.
+pub(super) fn now() -> f64 {
+    // `performance.now()` has a far higher resolution than `Date.now()` but isn't available in every environment.
+    let performance = Reflect::get(&js_sys::global(), &JsValue::from_str("performance"))
+        .ok()
+        .filter(|performance| performance.is_object());
+    performance
+        .and_then(|performance| {
+            let now: Function =
+                Reflect::get(&performance, &JsValue::from_str("now")).ok()?.dyn_into().ok()?;
+            now.call0(&performance).ok()?.as_f64()
+        })
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+/// Partially evaluates `expression`, replacing every witness with a known value in `witness_map` by that value.
+fn evaluate(expression: &Expression, witness_map: &WitnessMap) -> Expression {
+    let mut result = Expression::default();
+    for (coefficient, lhs, rhs) in &expression.mul_terms {
+        match (witness_map.get(lhs), witness_map.get(rhs)) {
+            (Some(lhs), Some(rhs)) => result.q_c += *coefficient * *lhs * *rhs,
+            (Some(lhs), None) => result.linear_combinations.push((*coefficient * *lhs, *rhs)),
+            (None, Some(rhs)) => result.linear_combinations.push((*coefficient * *rhs, *lhs)),
+            (None, None) => result.mul_terms.push((*coefficient, *lhs, *rhs)),
+        }
+    }
+    for (coefficient, witness) in &expression.linear_combinations {
+        match witness_map.get(witness) {
+            Some(value) => result.q_c += *coefficient * *value,
+            None => result.linear_combinations.push((*coefficient, *witness)),
+        }
+    }
+    result.q_c += expression.q_c;
+    result
+}
+
+fn any_witness(expression: &Expression) -> Option<Witness> {
+    expression
+        .linear_combinations
+        .first()
+        .map(|(_, witness)| *witness)
+        .or_else(|| expression.mul_terms.first().map(|(_, lhs, _)| *lhs))
+}
+
+fn expression_witnesses(expression: &Expression, witnesses: &mut Vec<Witness>) {
+    for (_, lhs, rhs) in &expression.mul_terms {
+        witnesses.push(*lhs);
+        witnesses.push(*rhs);
+    }
+    witnesses.extend(expression.linear_combinations.iter().map(|(_, witness)| *witness));
+}
+
+/// Returns every witness which `brillig` reads, including those in its predicate.
+pub(super) fn brillig_input_witnesses(brillig: &Brillig) -> Vec<Witness> {
+    let mut witnesses = Vec::new();
+    for input in &brillig.inputs {
+        match input {
+            BrilligInputs::Single(expression) => expression_witnesses(expression, &mut witnesses),
+            BrilligInputs::Array(expressions) => {
+                for expression in expressions {
+                    expression_witnesses(expression, &mut witnesses);
+                }
+            }
+        }
+    }
+    if let Some(predicate) = &brillig.predicate {
+        expression_witnesses(predicate, &mut witnesses);
+    }
+    witnesses
+}
+
+/// Returns every witness which `opcode` reads or assigns.
+pub(super) fn opcode_witnesses(opcode: &Opcode) -> Vec<Witness> {
+    let mut witnesses = Vec::new();
+    match opcode {
+        Opcode::Arithmetic(expression) => expression_witnesses(expression, &mut witnesses),
+        Opcode::BlackBoxFuncCall(call) => {
+            witnesses.extend(call.get_inputs_vec().iter().map(|input| input.witness));
+            witnesses.extend(call.get_outputs_vec());
+        }
+        Opcode::Directive(directive) => match directive {
+            Directive::Invert { x, result } => witnesses.extend([*x, *result]),
+            Directive::Quotient(QuotientDirective { a, b, q, r, predicate }) => {
+                expression_witnesses(a, &mut witnesses);
+                expression_witnesses(b, &mut witnesses);
+                if let Some(predicate) = predicate {
+                    expression_witnesses(predicate, &mut witnesses);
+                }
+                witnesses.extend([*q, *r]);
+            }
+            Directive::ToLeRadix { a, b, .. } => {
+                expression_witnesses(a, &mut witnesses);
+                witnesses.extend(b);
+            }
+            Directive::PermutationSort { inputs, bits, .. } => {
+                for expression in inputs.iter().flatten() {
+                    expression_witnesses(expression, &mut witnesses);
+                }
+                witnesses.extend(bits);
+            }
+            Directive::Log(LogInfo::WitnessOutput(log_witnesses)) => {
+                witnesses.extend(log_witnesses)
+            }
+            Directive::Log(LogInfo::FinalizedOutput(_)) => {}
+        },
+        Opcode::Block(block) | Opcode::ROM(block) | Opcode::RAM(block) => {
+            for op in &block.trace {
+                expression_witnesses(&op.operation, &mut witnesses);
+                expression_witnesses(&op.index, &mut witnesses);
+                expression_witnesses(&op.value, &mut witnesses);
+            }
+        }
+        Opcode::Brillig(brillig) => {
+            witnesses.extend(brillig_input_witnesses(brillig));
+            for output in &brillig.outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => witnesses.push(*witness),
+                    BrilligOutputs::Array(output_witnesses) => witnesses.extend(output_witnesses),
+                }
+            }
+        }
+        Opcode::MemoryInit { init, .. } => witnesses.extend(init),
+        Opcode::MemoryOp { op, .. } => {
+            expression_witnesses(&op.operation, &mut witnesses);
+            expression_witnesses(&op.index, &mut witnesses);
+            expression_witnesses(&op.value, &mut witnesses);
+        }
+    }
+    witnesses.sort_unstable();
+    witnesses.dedup();
+    witnesses
+}
@@ -0,0 +1,118 @@
+use acvm::{
+    acir::{
+        brillig::{ForeignCallResult, Value},
+        circuit::brillig::{Brillig, BrilligInputs},
+        native_types::WitnessMap,
+    },
+    brillig_vm::{Registers, VMStatus, VM},
+    pwg::get_value,
+    FieldElement,
+};
+
+use super::SimulatedBackend;
+
+/// Runs `brillig` against `witness_map` for at most `max_steps` Brillig opcodes, returning an error identifying
+/// the opcode and program counter it had reached if it was still running once they were exhausted.
+///
+/// The ACVM runs Brillig to completion without any way to interrupt it, so this is run ahead of the ACVM to catch
+/// Brillig code which would never terminate. Brillig which the ACVM won't run, as its predicate is zero or its
+/// inputs are unknown, is not checked.
+pub(super) fn check_brillig_steps(
+    backend: &SimulatedBackend,
+    brillig: &Brillig,
+    witness_map: &WitnessMap,
+    foreign_call_results: &[ForeignCallResult],
+    max_steps: usize,
+    opcode_index: usize,
+) -> Result<(), String> {
+    with_brillig_vm(backend, brillig, witness_map, foreign_call_results, |mut vm| {
+        for _ in 0..max_steps {
+            if !matches!(vm.process_opcode(), VMStatus::InProgress) {
+                return Ok(());
+            }
+        }
+
+        Err(format!(
+            "Brillig opcode {opcode_index} exceeded the limit of {max_steps} steps at program counter {}",
+            vm.program_counter()
+        ))
+    })
+    .unwrap_or(Ok(()))
+}
+
+/// Returns the number of Brillig opcodes executed by running `brillig` against `witness_map`, replaying
+/// `foreign_call_results` in place of making any foreign calls.
+///
+/// The ACVM doesn't report how much work a Brillig call did, so this reruns it once the ACVM has solved it.
+/// Returns `None` if the ACVM wouldn't have run `brillig`, as its predicate is zero or its inputs are unknown.
+pub(super) fn count_brillig_steps(
+    backend: &SimulatedBackend,
+    brillig: &Brillig,
+    witness_map: &WitnessMap,
+    foreign_call_results: &[ForeignCallResult],
+) -> Option<u64> {
+    with_brillig_vm(backend, brillig, witness_map, foreign_call_results, |mut vm| {
+        let mut steps = 1;
+        while matches!(vm.process_opcode(), VMStatus::InProgress) {
+            steps += 1;
+        }
+        steps
+    })
+}
+
+/// Calls `f` with a Brillig VM which has been loaded with `brillig` in the same way as the ACVM's Brillig solver.
+///
+/// Returns `None` without calling `f` if the ACVM wouldn't run `brillig`.
+fn with_brillig_vm<T>(
+    backend: &SimulatedBackend,
+    brillig: &Brillig,
+    witness_map: &WitnessMap,
+    foreign_call_results: &[ForeignCallResult],
+    f: impl FnOnce(VM<'_, &SimulatedBackend>) -> T,
+) -> Option<T> {
+    load_brillig_vm(&backend, brillig, witness_map, foreign_call_results).map(f)
+}
+
+/// Returns a Brillig VM which has been loaded with `brillig` in the same way as the ACVM's Brillig solver, replaying
+/// `foreign_call_results` in place of making any foreign calls.
+///
+/// Returns `None` if the ACVM wouldn't run `brillig`, as its predicate is zero or its inputs are unknown.
+pub(super) fn load_brillig_vm<'b, 'a>(
+    backend: &'b &'a SimulatedBackend,
+    brillig: &Brillig,
+    witness_map: &WitnessMap,
+    foreign_call_results: &[ForeignCallResult],
+) -> Option<VM<'b, &'a SimulatedBackend>> {
+    let predicate = match &brillig.predicate {
+        Some(predicate) => get_value(predicate, witness_map).ok(),
+        None => Some(FieldElement::one()),
+    };
+    if predicate.map_or(true, |predicate| predicate.is_zero()) {
+        return None;
+    }
+
+    let mut input_registers: Vec<Value> = Vec::new();
+    let mut input_memory: Vec<Value> = Vec::new();
+    for input in &brillig.inputs {
+        match input {
+            BrilligInputs::Single(expr) => {
+                input_registers.push(get_value(expr, witness_map).ok()?.into());
+            }
+            BrilligInputs::Array(exprs) => {
+                let memory_pointer = input_memory.len();
+                for expr in exprs {
+                    input_memory.push(get_value(expr, witness_map).ok()?.into());
+                }
+                input_registers.push(Value::from(memory_pointer));
+            }
+        }
+    }
+
+    Some(VM::new(
+        Registers::load(input_registers),
+        input_memory,
+        brillig.bytecode.clone(),
+        foreign_call_results.to_vec(),
+        backend,
+    ))
+}
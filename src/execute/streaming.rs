@@ -0,0 +1,94 @@
+use acvm::acir::circuit::PublicInputs;
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::Function;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::format::OpcodeStream;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CIRCUIT_SUMMARY: &'static str = r#"
+/**
+* @typedef {Object} CircuitSummary - The parts of a circuit other than its opcodes.
+* @property {CircuitFormat} format - The serialization format which the circuit was encoded in.
+* @property {number} currentWitnessIndex - The highest witness index used by the circuit.
+* @property {number} opcodeCount - The number of opcodes in the circuit.
+* @property {number[]} publicParameters - The witness indices of the circuit's public parameters, in ascending order.
+* @property {number[]} returnValues - The witness indices of the circuit's return values, in ascending order.
+*/
+export type CircuitSummary = {
+  format: CircuitFormat;
+  currentWitnessIndex: number;
+  opcodeCount: number;
+  publicParameters: number[];
+  returnValues: number[];
+}
+
+/**
+* A callback which is passed each opcode of a circuit as it's parsed.
+* @callback OpcodeCallback
+* @param {number} index - The index of the opcode within the circuit.
+* @param {string} opcode - A human readable description of the opcode.
+*/
+export type OpcodeCallback = (index: number, opcode: string) => void;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "CircuitSummary")]
+    pub type JsCircuitSummary;
+
+    #[wasm_bindgen(extends = Function, typescript_type = "OpcodeCallback")]
+    pub type JsOpcodeCallback;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CircuitSummary {
+    format: &'static str,
+    current_witness_index: u32,
+    opcode_count: usize,
+    public_parameters: Vec<u32>,
+    return_values: Vec<u32>,
+}
+
+/// Parses a circuit one opcode at a time, passing each to `on_opcode` rather than building the whole circuit.
+///
+/// This keeps peak memory close to the size of the serialized circuit, which matters for analysis tools working on
+/// circuits of hundreds of megabytes. Circuits serialized as MessagePack can't be streamed so are parsed in full.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {OpcodeCallback} on_opcode - Called with each opcode in circuit order.
+/// @returns {CircuitSummary} The parts of the circuit other than its opcodes.
+#[wasm_bindgen(js_name = parseCircuitStreaming, skip_jsdoc)]
+pub fn parse_circuit_streaming(
+    circuit: Vec<u8>,
+    on_opcode: JsOpcodeCallback,
+) -> Result<JsCircuitSummary, JsValue> {
+    console_error_panic_hook::set_once();
+    let mut opcodes = OpcodeStream::new(&circuit)?;
+    for (index, opcode) in opcodes.by_ref().enumerate() {
+        let opcode = opcode?;
+        on_opcode.call2(
+            &JsValue::NULL,
+            &JsValue::from(index as u32),
+            &JsValue::from(opcode.to_string()),
+        )?;
+    }
+
+    let format = opcodes.format_name();
+    let current_witness_index = opcodes.current_witness_index;
+    let opcode_count = opcodes.opcode_count;
+    let (public_parameters, return_values) = opcodes.finish()?;
+    let indices = |public_inputs: PublicInputs| {
+        public_inputs.0.into_iter().map(|witness| witness.witness_index()).collect()
+    };
+    let summary = CircuitSummary {
+        format,
+        current_witness_index,
+        opcode_count,
+        public_parameters: indices(public_parameters),
+        return_values: indices(return_values),
+    };
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&summary).unwrap().into())
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use acvm::acir::{
+    circuit::{opcodes::BlackBoxFuncCall, Opcode},
+    native_types::{Witness, WitnessMap},
+};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{solver::opcode_witnesses, ExecutionError};
+use crate::{inspect::WitnessAbi, js_witness_map::field_element_to_js_string};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ERROR_SUGGESTION: &'static str = r#"
+/**
+* A machine-readable hint on how to fix a failed execution, attached to errors as `suggestions` when
+* `errorSuggestions` is set.
+*
+* `missingInputs` lists the witnesses which couldn't be solved, along with the ABI parameters they belong to if an
+* `abi` was provided. `rangeExceeded` describes a value which doesn't fit within the bit size of a range constraint.
+*/
+export type ErrorSuggestion =
+  | { kind: "missingInputs"; parameters: string[]; witnesses: number[] }
+  | { kind: "rangeExceeded"; opcodeIndex: number; witness: number; bitSize: number; value: string };
+"#;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum ErrorSuggestion {
+    #[serde(rename_all = "camelCase")]
+    MissingInputs { parameters: Vec<String>, witnesses: Vec<u32> },
+    #[serde(rename_all = "camelCase")]
+    RangeExceeded { opcode_index: usize, witness: u32, bit_size: u32, value: String },
+}
+
+/// Returns the witnesses referenced by `unsolved_opcodes` which haven't been assigned in `witness_map`.
+fn missing_witnesses(
+    opcodes: &[Opcode],
+    unsolved_opcodes: &[usize],
+    witness_map: &WitnessMap,
+) -> BTreeSet<Witness> {
+    unsolved_opcodes
+        .iter()
+        .filter_map(|&index| opcodes.get(index))
+        .flat_map(opcode_witnesses)
+        .filter(|witness| !witness_map.contains_key(witness))
+        .collect()
+}
+
+/// Returns a suggestion for each range constraint in `opcodes` whose input in `witness_map` exceeds its bit size.
+fn exceeded_ranges(opcodes: &[Opcode], witness_map: &WitnessMap) -> Vec<ErrorSuggestion> {
+    opcodes
+        .iter()
+        .enumerate()
+        .filter_map(|(opcode_index, opcode)| {
+            let Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) = opcode else {
+                return None;
+            };
+            let value = witness_map.get(&input.witness)?;
+            (value.num_bits() > input.num_bits).then(|| ErrorSuggestion::RangeExceeded {
+                opcode_index,
+                witness: input.witness.witness_index(),
+                bit_size: input.num_bits,
+                value: field_element_to_js_string(value).into(),
+            })
+        })
+        .collect()
+}
+
+impl ExecutionError {
+    /// Attaches suggestions on how to fix the error, assembled from the circuit's `opcodes` and an optional `abi`.
+    pub(crate) fn with_suggestions(mut self, opcodes: &[Opcode], abi: Option<&WitnessAbi>) -> Self {
+        let Some(stalled) = &self.stalled else {
+            self.suggestions = exceeded_ranges(opcodes, &self.partial_witness);
+            return self;
+        };
+
+        let (parameters, witnesses) = match abi {
+            // Parameters are never assigned by the circuit, so any which are missing weren't provided as inputs.
+            Some(abi) => {
+                let mut parameters = Vec::new();
+                let mut witnesses = BTreeSet::new();
+                for (name, parameter_witnesses) in &abi.param_witnesses {
+                    let unassigned: Vec<Witness> = parameter_witnesses
+                        .iter()
+                        .map(|&witness| Witness(witness))
+                        .filter(|witness| !self.partial_witness.contains_key(witness))
+                        .collect();
+                    if !unassigned.is_empty() {
+                        parameters.push(name.clone());
+                        witnesses.extend(unassigned);
+                    }
+                }
+                (parameters, witnesses)
+            }
+            None => (
+                Vec::new(),
+                missing_witnesses(opcodes, &stalled.unsolved_opcodes, &self.partial_witness),
+            ),
+        };
+        if !witnesses.is_empty() {
+            let witnesses = witnesses.iter().map(Witness::witness_index).collect();
+            self.suggestions = vec![ErrorSuggestion::MissingInputs { parameters, witnesses }];
+        }
+        self
+    }
+}
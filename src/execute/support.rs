@@ -0,0 +1,116 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::acir::{
+    circuit::{Circuit, Opcode},
+    native_types::Witness,
+    BlackBoxFunc,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{read_circuit, solver::opcode_witnesses};
+
+#[wasm_bindgen(typescript_custom_section)]
+const BLACKBOX_SUPPORT: &'static str = r#"
+/**
+* @typedef {Object} UnsupportedBlackBox - A blackbox function used by a circuit which can't be simulated.
+* @property {string} name - The name of the blackbox function.
+* @property {number[]} opcodeIndices - The indices of the opcodes which call the function.
+*/
+export type UnsupportedBlackBox = {
+  name: string;
+  opcodeIndices: number[];
+}
+
+/**
+* @typedef {Object} BlackBoxSupport - A report of whether a circuit can be fully simulated.
+* @property {UnsupportedBlackBox[]} unsupported - The blackbox functions used by the circuit which can't be simulated.
+* @property {number[]} affectedOpcodes - The indices of the opcodes which read an output of an unsupported blackbox function.
+* @property {boolean} partialExecution - Whether every opcode other than the unsupported blackbox calls can be solved.
+*/
+export type BlackBoxSupport = {
+  unsupported: UnsupportedBlackBox[];
+  affectedOpcodes: number[];
+  partialExecution: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "BlackBoxSupport")]
+    pub type JsBlackBoxSupport;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UnsupportedBlackBox {
+    name: &'static str,
+    opcode_indices: Vec<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlackBoxSupport {
+    unsupported: Vec<UnsupportedBlackBox>,
+    affected_opcodes: Vec<usize>,
+    partial_execution: bool,
+}
+
+/// Returns whether the simulator can solve calls to `function`.
+///
+/// Recursive aggregation is accepted by the ACVM without the proof being verified or the output aggregation object
+/// being assigned, so any circuit which relies on it can't be faithfully simulated.
+fn is_supported(function: BlackBoxFunc) -> bool {
+    !matches!(function, BlackBoxFunc::RecursiveAggregation)
+}
+
+/// Reports which blackbox functions used by a circuit can't be simulated and how much of the circuit depends on them.
+///
+/// This allows deciding ahead of time whether a circuit can be executed locally or must fall back to a server.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @returns {BlackBoxSupport} The unsupported blackbox functions used by the circuit and the opcodes they affect.
+#[wasm_bindgen(js_name = analyzeBlackBoxSupport, skip_jsdoc)]
+pub fn analyze_blackbox_support(circuit: Vec<u8>) -> Result<JsBlackBoxSupport, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+
+    let mut unsupported: BTreeMap<&'static str, Vec<usize>> = BTreeMap::new();
+    let mut unsupported_outputs: BTreeSet<Witness> = BTreeSet::new();
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        let Opcode::BlackBoxFuncCall(call) = opcode else {
+            continue;
+        };
+        let function = call.get_black_box_func();
+        if !is_supported(function) {
+            unsupported.entry(function.name()).or_default().push(index);
+            unsupported_outputs.extend(call.get_outputs_vec());
+        }
+    }
+
+    let affected_opcodes: Vec<usize> = circuit
+        .opcodes
+        .iter()
+        .enumerate()
+        .filter(|(_, opcode)| {
+            let is_unsupported = matches!(opcode, Opcode::BlackBoxFuncCall(call)
+                if !is_supported(call.get_black_box_func()));
+            !is_unsupported
+                && opcode_witnesses(opcode)
+                    .iter()
+                    .any(|witness| unsupported_outputs.contains(witness))
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    let support = BlackBoxSupport {
+        unsupported: unsupported
+            .into_iter()
+            .map(|(name, opcode_indices)| UnsupportedBlackBox { name, opcode_indices })
+            .collect(),
+        partial_execution: affected_opcodes.is_empty(),
+        affected_opcodes,
+    };
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&support).unwrap().into())
+}
@@ -0,0 +1,357 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use acvm::{
+    acir::{
+        circuit::Circuit,
+        native_types::{Witness, WitnessMap},
+        BlackBoxFunc,
+    },
+    BlackBoxResolutionError, FieldElement,
+};
+use js_sys::{JsString, Object, Reflect, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use super::{
+    execute_circuit_with_backend, read_circuit, ExecutionOptions, JsExecutionOptions,
+    SimulatedBackend,
+};
+use crate::{
+    barretenberg::RandomTape,
+    foreign_call::{ForeignCallHandler, ForeignCallRecorder, ForeignCallTranscript},
+    js_witness_map::JsWitnessMapLike,
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const RECORDED_EXECUTION: &'static str = r#"
+/**
+* @typedef {Object} RecordedExecution - A solved witness along with a trace of the execution which solved it.
+* @property {WitnessMap} witness - The solved witness.
+* @property {Uint8Array} trace - A trace from which `replayExecution` reproduces the execution.
+*/
+export type RecordedExecution = {
+  witness: WitnessMap;
+  trace: Uint8Array;
+}
+
+/**
+* An error thrown when a recorded execution fails.
+* @property {Uint8Array} trace - A trace from which `replayExecution` reproduces the failure.
+*/
+export type RecordedExecutionError = ExecutionError & {
+  trace: Uint8Array;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "RecordedExecution")]
+    pub type JsRecordedExecution;
+}
+
+/// The version of the trace format, which is bumped whenever a change would prevent older traces from replaying.
+const TRACE_VERSION: u32 = 1;
+
+/// Everything which an execution depends upon beyond the circuit itself.
+#[derive(Debug, Serialize, Deserialize)]
+struct ExecutionTrace {
+    version: u32,
+    /// The hex encoded value of each witness in the initial witness, keyed by its index.
+    initial_witness: Vec<(u32, String)>,
+    skip_signature_verification: bool,
+    skipped_signature_result: bool,
+    pedersen_domain_separator: Option<u32>,
+    foreign_calls: ForeignCallTranscript,
+    blackbox_calls: Vec<BlackBoxCall>,
+    random_bytes: Vec<u8>,
+}
+
+/// A call made to a blackbox function along with its result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BlackBoxCall {
+    function: String,
+    inputs: Vec<String>,
+    result: Result<Vec<String>, String>,
+}
+
+/// The output of a blackbox function, which can be stored in a trace.
+pub(super) trait TracedOutput: Sized {
+    fn encode(&self) -> Vec<String>;
+    fn decode(values: &[String]) -> Option<Self>;
+}
+
+impl TracedOutput for bool {
+    fn encode(&self) -> Vec<String> {
+        vec![FieldElement::from(*self as u128).to_hex()]
+    }
+
+    fn decode(values: &[String]) -> Option<Self> {
+        match values {
+            [value] => FieldElement::from_hex(value).map(|value| value.is_one()),
+            _ => None,
+        }
+    }
+}
+
+impl TracedOutput for (FieldElement, FieldElement) {
+    fn encode(&self) -> Vec<String> {
+        vec![self.0.to_hex(), self.1.to_hex()]
+    }
+
+    fn decode(values: &[String]) -> Option<Self> {
+        match values {
+            [x, y] => Some((FieldElement::from_hex(x)?, FieldElement::from_hex(y)?)),
+            _ => None,
+        }
+    }
+}
+
+/// Hex encodes `bytes` so that they can be stored as a blackbox function input in a trace.
+pub(super) fn encode_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The state of an execution which is being recorded into a trace, or replayed from one.
+#[derive(Debug)]
+pub(crate) enum ExecutionTracing {
+    Recording {
+        foreign_calls: Rc<ForeignCallRecorder>,
+        blackbox_calls: RefCell<Vec<BlackBoxCall>>,
+        random_tape: Rc<RefCell<RandomTape>>,
+    },
+    Replaying {
+        /// The recorded blackbox function calls, which are taken as they're replayed.
+        blackbox_calls: RefCell<Vec<Option<BlackBoxCall>>>,
+        random_tape: Rc<RefCell<RandomTape>>,
+    },
+}
+
+impl ExecutionTracing {
+    fn recording() -> ExecutionTracing {
+        ExecutionTracing::Recording {
+            foreign_calls: Rc::default(),
+            blackbox_calls: RefCell::default(),
+            random_tape: Rc::new(RefCell::new(RandomTape::Recording(Vec::new()))),
+        }
+    }
+
+    fn replaying(blackbox_calls: Vec<BlackBoxCall>, random_bytes: Vec<u8>) -> ExecutionTracing {
+        ExecutionTracing::Replaying {
+            blackbox_calls: RefCell::new(blackbox_calls.into_iter().map(Some).collect()),
+            random_tape: Rc::new(RefCell::new(RandomTape::Replaying(VecDeque::from(random_bytes)))),
+        }
+    }
+
+    /// The tape which the random bytes drawn by the embedded Barretenberg are recorded to or replayed from.
+    pub(super) fn random_tape(&self) -> &Rc<RefCell<RandomTape>> {
+        match self {
+            ExecutionTracing::Recording { random_tape, .. }
+            | ExecutionTracing::Replaying { random_tape, .. } => random_tape,
+        }
+    }
+
+    /// Wraps `foreign_call_handler` so that the foreign calls it resolves are recorded, if recording.
+    pub(super) fn record_foreign_calls(
+        &self,
+        foreign_call_handler: ForeignCallHandler,
+    ) -> ForeignCallHandler {
+        match self {
+            ExecutionTracing::Recording { foreign_calls, .. } => {
+                foreign_calls.record(&foreign_call_handler)
+            }
+            ExecutionTracing::Replaying { .. } => foreign_call_handler,
+        }
+    }
+
+    /// Records the result of solving a call to `function` with `inputs`. When replaying, the recorded result is
+    /// returned in place of calling `solve`.
+    pub(super) fn trace_blackbox<T: TracedOutput>(
+        &self,
+        function: BlackBoxFunc,
+        inputs: Vec<String>,
+        solve: impl FnOnce() -> Result<T, BlackBoxResolutionError>,
+    ) -> Result<T, BlackBoxResolutionError> {
+        match self {
+            ExecutionTracing::Recording { blackbox_calls, .. } => {
+                let result = solve();
+                let recorded_result = match &result {
+                    Ok(output) => Ok(output.encode()),
+                    Err(BlackBoxResolutionError::Failed(_, reason)) => Err(reason.clone()),
+                    Err(err) => Err(err.to_string()),
+                };
+                blackbox_calls.borrow_mut().push(BlackBoxCall {
+                    function: function.name().to_string(),
+                    inputs,
+                    result: recorded_result,
+                });
+                result
+            }
+            ExecutionTracing::Replaying { blackbox_calls, .. } => {
+                // The order in which blackbox functions are called may differ between the solvers so calls are
+                // matched by their inputs.
+                let diverged = || {
+                    BlackBoxResolutionError::Failed(
+                        function,
+                        "Execution diverged from its trace, which has no matching call".to_string(),
+                    )
+                };
+                let mut blackbox_calls = blackbox_calls.borrow_mut();
+                let call = blackbox_calls
+                    .iter_mut()
+                    .find(|call| {
+                        call.as_ref().map_or(false, |call| {
+                            call.function == function.name() && call.inputs == inputs
+                        })
+                    })
+                    .and_then(Option::take)
+                    .ok_or_else(diverged)?;
+                match call.result {
+                    Ok(output) => T::decode(&output).ok_or_else(diverged),
+                    Err(message) => Err(BlackBoxResolutionError::Failed(function, message)),
+                }
+            }
+        }
+    }
+
+    /// Returns the trace recorded for an execution of `initial_witness` with `options`.
+    fn finish(&self, initial_witness: &WitnessMap, options: &ExecutionOptions) -> Vec<u8> {
+        let ExecutionTracing::Recording { foreign_calls, blackbox_calls, random_tape } = self
+        else {
+            unreachable!("only recorded executions produce a trace")
+        };
+        let random_bytes = match &*random_tape.borrow() {
+            RandomTape::Recording(random_bytes) => random_bytes.clone(),
+            RandomTape::Replaying(_) => unreachable!("recorded executions record random bytes"),
+        };
+
+        let trace = ExecutionTrace {
+            version: TRACE_VERSION,
+            initial_witness: initial_witness
+                .clone()
+                .into_iter()
+                .map(|(witness, value)| (witness.witness_index(), value.to_hex()))
+                .collect(),
+            skip_signature_verification: options.skip_signature_verification,
+            skipped_signature_result: options.skipped_signature_result(),
+            pedersen_domain_separator: options.pedersen_domain_separator,
+            foreign_calls: foreign_calls.transcript(),
+            blackbox_calls: blackbox_calls.borrow().clone(),
+            random_bytes,
+        };
+        rmp_serde::to_vec(&trace).expect("traces should always serialize")
+    }
+}
+
+/// Executes an ACIR circuit, recording a trace from which `replayExecution` can reproduce the execution exactly.
+///
+/// The trace holds the initial witness along with the responses to any foreign calls, the results of any blackbox
+/// functions and any random values drawn by the embedded Barretenberg. If execution fails, the trace is attached to
+/// the error so that the failure can be reproduced offline.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+/// @returns {Promise<RecordedExecution>} The solved witness along with the trace of the execution.
+#[wasm_bindgen(js_name = recordExecution, skip_jsdoc)]
+pub async fn record_execution(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    options: Option<JsExecutionOptions>,
+) -> Result<JsRecordedExecution, JsValue> {
+    console_error_panic_hook::set_once();
+    let mut options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+
+    let tracing = Rc::new(ExecutionTracing::recording());
+    options.tracing = Some(tracing.clone());
+    let backend = SimulatedBackend::initialize().await;
+    let result = execute_circuit_with_backend(
+        &backend,
+        circuit.opcodes,
+        initial_witness.clone(),
+        &foreign_call_handler,
+        &options,
+    )
+    .await;
+    let trace = Uint8Array::from(tracing.finish(&initial_witness, &options).as_slice());
+
+    match result {
+        Ok(witness) => {
+            let recorded = Object::new();
+            Reflect::set(&recorded, &"witness".into(), &JsWitnessMap::from(witness))?;
+            Reflect::set(&recorded, &"trace".into(), &trace)?;
+            Ok(JsValue::from(recorded).into())
+        }
+        Err(err) => {
+            let error = err.into_js_value(&options);
+            let error = match error.as_string() {
+                Some(message) => js_sys::Error::new(&message).into(),
+                None => error,
+            };
+            Reflect::set(&error, &"trace".into(), &trace)?;
+            Err(error)
+        }
+    }
+}
+
+/// Reproduces an execution recorded by `recordExecution`.
+///
+/// Foreign calls and blackbox functions are answered from the trace, so no handler is needed. Execution fails if it
+/// makes a call which isn't in the trace, e.g. as `circuit` isn't the circuit which was recorded.
+///
+/// @param {Uint8Array} circuit - The serialized ACIR circuit which was executed to record `trace`.
+/// @param {Uint8Array} trace - The trace of the execution, as returned by `recordExecution`.
+/// @returns {Promise<WitnessMap>} The solved witness, or the error which the recorded execution failed with.
+#[wasm_bindgen(js_name = replayExecution, skip_jsdoc)]
+pub async fn replay_execution(circuit: Vec<u8>, trace: Vec<u8>) -> Result<JsWitnessMap, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let trace: ExecutionTrace = rmp_serde::from_slice(&trace)
+        .map_err(|err| JsString::from(format!("Invalid execution trace: {err}")))?;
+    if trace.version != TRACE_VERSION {
+        return Err(JsString::from(format!(
+            "Execution trace has version {}, but only version {TRACE_VERSION} can be replayed",
+            trace.version
+        ))
+        .into());
+    }
+
+    let mut initial_witness = WitnessMap::new();
+    for (index, value) in trace.initial_witness {
+        let value = FieldElement::from_hex(&value).ok_or_else(|| {
+            JsString::from(format!("Invalid execution trace: invalid value for witness {index}"))
+        })?;
+        initial_witness.insert(Witness(index), value);
+    }
+
+    let options = ExecutionOptions {
+        skip_signature_verification: trace.skip_signature_verification,
+        skipped_signature_result: Some(trace.skipped_signature_result),
+        pedersen_domain_separator: trace.pedersen_domain_separator,
+        foreign_call_transcript: Some(trace.foreign_calls),
+        tracing: Some(Rc::new(ExecutionTracing::replaying(
+            trace.blackbox_calls,
+            trace.random_bytes,
+        ))),
+        ..ExecutionOptions::default()
+    };
+    // Every foreign call is answered from the transcript so the handler is never called.
+    let foreign_call_handler = ForeignCallTranscript::missing_handler();
+
+    let backend = SimulatedBackend::initialize().await;
+    let witness_map = execute_circuit_with_backend(
+        &backend,
+        circuit.opcodes,
+        initial_witness,
+        &foreign_call_handler,
+        &options,
+    )
+    .await
+    .map_err(|err| err.into_js_value(&options))?;
+
+    Ok(witness_map.into())
+}
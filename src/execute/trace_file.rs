@@ -0,0 +1,366 @@
+//! A compact binary format for the trace of an execution, for consumption by other tools such as profilers and
+//! auditors.
+//!
+//! Unlike the traces of `recordExecution`, which are only meant to be replayed by this package, the layout of a trace
+//! file is fixed for each version:
+//!
+//! ```text
+//! trace        = magic version initial_witness steps foreign_calls
+//! magic        = "ACTR"
+//! version      = u8
+//! initial_witness = count (witness field)*
+//! steps        = count (opcode_index count (witness field)*)*
+//! foreign_calls = count (string count input* count output*)
+//! input        = count field*
+//! output       = 0x00 field | 0x01 count field*
+//! field        = u8 byte length (at most 32) followed by the big endian bytes of the value without leading zeros
+//! string       = count utf8 bytes
+//! count, witness, opcode_index = unsigned LEB128
+//! ```
+
+use std::rc::Rc;
+
+use acvm::{
+    acir::{
+        brillig::{ForeignCallOutput, Value},
+        circuit::Circuit,
+        native_types::{Witness, WitnessMap},
+    },
+    FieldElement,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, JsString, Object, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+use super::{
+    execute_circuit_with_solver, prepare_opcodes, read_circuit, solver::WitnessSnapshot,
+    ExecutionOptions, JsExecutionOptions, SimulatedBackend,
+};
+use crate::{
+    foreign_call::{
+        ForeignCallHandler, ForeignCallRecorder, ForeignCallTranscript, RecordedForeignCall,
+    },
+    js_witness_map::JsWitnessMapLike,
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TRACE_FILE: &'static str = r#"
+/**
+* @typedef {Object} TraceStep - An opcode which was solved, along with the witnesses it wrote.
+* @property {number} opcodeIndex - The index of the opcode within the circuit.
+* @property {WitnessMap} witnessWrites - The witnesses which were assigned values while solving the opcode.
+*/
+export type TraceStep = {
+  opcodeIndex: number;
+  witnessWrites: WitnessMap;
+}
+
+/**
+* @typedef {Object} ImportedTrace - The contents of a trace file written by `exportTrace`.
+* @property {number} version - The version of the trace format the file was written with.
+* @property {WitnessMap} initialWitness - The initial witness the circuit was executed with.
+* @property {TraceStep[]} steps - Each opcode in the order in which it was solved.
+* @property {ForeignCallTranscriptEntry[]} foreignCalls - Each foreign call in the order in which it was made. This can be passed as the `foreignCallTranscript` option to execute the circuit again without a handler.
+*/
+export type ImportedTrace = {
+  version: number;
+  initialWitness: WitnessMap;
+  steps: TraceStep[];
+  foreignCalls: ForeignCallTranscriptEntry[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "ImportedTrace")]
+    pub type JsImportedTrace;
+}
+
+/// The bytes which every trace file starts with.
+const TRACE_FILE_MAGIC: &[u8; 4] = b"ACTR";
+
+/// The version of the trace file format, which is bumped whenever its layout changes.
+const TRACE_FILE_VERSION: u8 = 1;
+
+/// The execution described by a trace file.
+#[derive(Debug)]
+struct TraceFile {
+    initial_witness: WitnessMap,
+    /// The index of each opcode in the order it was solved, along with the witnesses it wrote.
+    steps: Vec<(usize, WitnessMap)>,
+    foreign_calls: Vec<RecordedForeignCall>,
+}
+
+#[derive(Default)]
+struct TraceWriter {
+    bytes: Vec<u8>,
+}
+
+impl TraceWriter {
+    fn write_count(&mut self, mut value: usize) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.bytes.push(byte);
+                return;
+            }
+            self.bytes.push(byte | 0x80);
+        }
+    }
+
+    fn write_field(&mut self, value: &FieldElement) {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|&byte| byte != 0).unwrap_or(bytes.len());
+        self.bytes.push((bytes.len() - start) as u8);
+        self.bytes.extend(&bytes[start..]);
+    }
+
+    fn write_fields<'a>(&mut self, values: impl ExactSizeIterator<Item = &'a FieldElement>) {
+        self.write_count(values.len());
+        for value in values {
+            self.write_field(value);
+        }
+    }
+
+    fn write_string(&mut self, value: &str) {
+        self.write_count(value.len());
+        self.bytes.extend(value.as_bytes());
+    }
+
+    fn write_witness_map(&mut self, witness_map: &WitnessMap) {
+        let witness_map: Vec<(Witness, FieldElement)> = witness_map.clone().into_iter().collect();
+        self.write_count(witness_map.len());
+        for (witness, value) in &witness_map {
+            self.write_count(witness.witness_index() as usize);
+            self.write_field(value);
+        }
+    }
+}
+
+struct TraceReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl TraceReader<'_> {
+    fn read_bytes(&mut self, length: usize) -> Result<&[u8], String> {
+        if self.bytes.len() < length {
+            return Err("Invalid trace file: unexpected end of file".to_string());
+        }
+        let (bytes, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    fn read_count(&mut self) -> Result<usize, String> {
+        let mut value = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.read_bytes(1)?[0];
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Invalid trace file: count is too large".to_string())
+    }
+
+    fn read_field(&mut self) -> Result<FieldElement, String> {
+        let length = self.read_bytes(1)?[0] as usize;
+        if length > 32 {
+            return Err("Invalid trace file: field element is longer than 32 bytes".to_string());
+        }
+        Ok(FieldElement::from_be_bytes_reduce(self.read_bytes(length)?))
+    }
+
+    fn read_fields(&mut self) -> Result<Vec<FieldElement>, String> {
+        (0..self.read_count()?).map(|_| self.read_field()).collect()
+    }
+
+    fn read_witness_map(&mut self) -> Result<WitnessMap, String> {
+        let mut witness_map = WitnessMap::new();
+        for _ in 0..self.read_count()? {
+            let witness = u32::try_from(self.read_count()?)
+                .map_err(|_| "Invalid trace file: witness index is too large".to_string())?;
+            witness_map.insert(Witness(witness), self.read_field()?);
+        }
+        Ok(witness_map)
+    }
+
+    fn read_string(&mut self) -> Result<String, String> {
+        let length = self.read_count()?;
+        String::from_utf8(self.read_bytes(length)?.to_vec())
+            .map_err(|_| "Invalid trace file: string is not valid UTF-8".to_string())
+    }
+}
+
+impl TraceFile {
+    fn encode(&self) -> Vec<u8> {
+        let mut writer = TraceWriter::default();
+        writer.bytes.extend(TRACE_FILE_MAGIC);
+        writer.bytes.push(TRACE_FILE_VERSION);
+
+        writer.write_witness_map(&self.initial_witness);
+        writer.write_count(self.steps.len());
+        for (opcode_index, witness_writes) in &self.steps {
+            writer.write_count(*opcode_index);
+            writer.write_witness_map(witness_writes);
+        }
+        writer.write_count(self.foreign_calls.len());
+        for foreign_call in &self.foreign_calls {
+            writer.write_string(&foreign_call.name);
+            writer.write_count(foreign_call.inputs.len());
+            for input in &foreign_call.inputs {
+                writer.write_fields(input.iter());
+            }
+            writer.write_count(foreign_call.outputs.len());
+            for output in &foreign_call.outputs {
+                match output {
+                    ForeignCallOutput::Single(value) => {
+                        writer.bytes.push(0);
+                        writer.write_field(&value.to_field());
+                    }
+                    ForeignCallOutput::Array(values) => {
+                        writer.bytes.push(1);
+                        let values: Vec<FieldElement> =
+                            values.iter().map(Value::to_field).collect();
+                        writer.write_fields(values.iter());
+                    }
+                }
+            }
+        }
+        writer.bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(u8, TraceFile), String> {
+        let mut reader = TraceReader { bytes };
+        if reader.read_bytes(TRACE_FILE_MAGIC.len()).ok() != Some(&TRACE_FILE_MAGIC[..]) {
+            return Err("Invalid trace file: missing trace file header".to_string());
+        }
+        let version = reader.read_bytes(1)?[0];
+        if version != TRACE_FILE_VERSION {
+            return Err(format!(
+                "Trace file has version {version}, but only version {TRACE_FILE_VERSION} can be read"
+            ));
+        }
+
+        let initial_witness = reader.read_witness_map()?;
+        let steps = (0..reader.read_count()?)
+            .map(|_| Ok((reader.read_count()?, reader.read_witness_map()?)))
+            .collect::<Result<_, String>>()?;
+        let foreign_calls = (0..reader.read_count()?)
+            .map(|_| {
+                let name = reader.read_string()?;
+                let inputs = (0..reader.read_count()?)
+                    .map(|_| reader.read_fields())
+                    .collect::<Result<_, _>>()?;
+                let outputs = (0..reader.read_count()?)
+                    .map(|_| match reader.read_bytes(1)?[0] {
+                        0 => Ok(ForeignCallOutput::Single(Value::from(reader.read_field()?))),
+                        1 => Ok(ForeignCallOutput::Array(
+                            reader.read_fields()?.into_iter().map(Value::from).collect(),
+                        )),
+                        kind => Err(format!("Invalid trace file: unknown output kind {kind}")),
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(RecordedForeignCall { name, inputs, outputs })
+            })
+            .collect::<Result<_, String>>()?;
+        if !reader.bytes.is_empty() {
+            return Err(
+                "Invalid trace file: unexpected bytes after the end of the trace".to_string()
+            );
+        }
+
+        Ok((version, TraceFile { initial_witness, steps, foreign_calls }))
+    }
+}
+
+/// Executes an ACIR circuit and writes a trace file describing the execution.
+///
+/// The trace file records the initial witness, each opcode in the order in which it was solved along with the
+/// witnesses it wrote, and the inputs and outputs of each foreign call. Its layout is fixed for each version of the
+/// format so that other tools can read it without executing the circuit themselves.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+/// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+/// @returns {Promise<Uint8Array>} The trace file.
+#[wasm_bindgen(js_name = exportTrace, skip_jsdoc)]
+pub async fn export_trace(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+    options: Option<JsExecutionOptions>,
+) -> Result<Uint8Array, JsValue> {
+    console_error_panic_hook::set_once();
+    let mut options = ExecutionOptions::try_from(options).map_err(JsString::from)?;
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let initial_witness = WitnessMap::try_from(initial_witness)?;
+    let recorder = Rc::new(ForeignCallRecorder::default());
+    options.foreign_call_recorder = Some(recorder.clone());
+    // Taking a snapshot after every opcode gives the witnesses written by each opcode in the order they're solved.
+    options.snapshot_opcodes = Some((0..circuit.opcodes.len()).collect());
+
+    let backend = SimulatedBackend::initialize().await;
+    let opcodes = prepare_opcodes(circuit.opcodes, &options);
+    let result = execute_circuit_with_solver(
+        &backend,
+        &opcodes,
+        initial_witness.clone(),
+        &foreign_call_handler,
+        &options,
+        false,
+    )
+    .await
+    .map_err(|err| err.into_js_value(&options))?;
+
+    let trace_file = TraceFile {
+        initial_witness,
+        steps: result
+            .snapshots
+            .into_iter()
+            .map(|WitnessSnapshot { opcode_index, assigned_witnesses }| {
+                (opcode_index, assigned_witnesses)
+            })
+            .collect(),
+        foreign_calls: recorder.transcript().recorded_calls().map_err(JsString::from)?,
+    };
+    Ok(Uint8Array::from(trace_file.encode().as_slice()))
+}
+
+/// Reads a trace file written by `exportTrace`.
+///
+/// @param {Uint8Array} trace - The trace file.
+/// @returns {ImportedTrace} The execution described by the trace file.
+#[wasm_bindgen(js_name = importTrace, skip_jsdoc)]
+pub fn import_trace(trace: Vec<u8>) -> Result<JsImportedTrace, JsString> {
+    console_error_panic_hook::set_once();
+    let (version, trace_file) = TraceFile::decode(&trace)?;
+
+    let steps: Array = trace_file
+        .steps
+        .into_iter()
+        .map(|(opcode_index, witness_writes)| {
+            let step = Object::new();
+            Reflect::set(&step, &"opcodeIndex".into(), &JsValue::from(opcode_index as u32))
+                .expect("setting a property on an `Object` should not fail");
+            Reflect::set(&step, &"witnessWrites".into(), &JsWitnessMap::from(witness_writes))
+                .expect("setting a property on an `Object` should not fail");
+            JsValue::from(step)
+        })
+        .collect();
+    let foreign_calls = ForeignCallTranscript::from_recorded_calls(trace_file.foreign_calls);
+
+    let imported = Object::new();
+    let set = |key: &str, value: &JsValue| {
+        Reflect::set(&imported, &JsValue::from_str(key), value)
+            .expect("setting a property on an `Object` should not fail");
+    };
+    set("version", &JsValue::from(version));
+    set("initialWitness", &JsWitnessMap::from(trace_file.initial_witness));
+    set("steps", &steps);
+    set("foreignCalls", &<JsValue as JsValueSerdeExt>::from_serde(&foreign_calls).unwrap());
+    Ok(JsValue::from(imported).into())
+}
@@ -0,0 +1,53 @@
+use acvm::{
+    acir::{
+        circuit::{opcodes::BlackBoxFuncCall, Opcode},
+        native_types::Expression,
+    },
+    FieldElement,
+};
+
+/// Replaces each signature verification in `opcodes` with a constraint which assigns `result` to its output.
+///
+/// Verifying signatures is by far the most expensive part of executing most circuits, so skipping them
+/// allows a quick preview of the remaining witness.
+pub(crate) fn skip_signature_verification(opcodes: Vec<Opcode>, result: bool) -> Vec<Opcode> {
+    let result = FieldElement::from(result as u128);
+
+    opcodes
+        .into_iter()
+        .map(|opcode| match opcode {
+            Opcode::BlackBoxFuncCall(
+                BlackBoxFuncCall::SchnorrVerify { output, .. }
+                | BlackBoxFuncCall::EcdsaSecp256k1 { output, .. }
+                | BlackBoxFuncCall::EcdsaSecp256r1 { output, .. },
+            ) => Opcode::Arithmetic(Expression {
+                mul_terms: Vec::new(),
+                linear_combinations: vec![(FieldElement::one(), output)],
+                q_c: -result,
+            }),
+            opcode => opcode,
+        })
+        .collect()
+}
+
+/// Replaces the domain separator of each Pedersen commitment in `opcodes` with `domain_separator`.
+///
+/// This selects which of Barretenberg's generator sets is used to compute the commitment.
+pub(crate) fn override_pedersen_domain_separator(
+    opcodes: Vec<Opcode>,
+    domain_separator: u32,
+) -> Vec<Opcode> {
+    opcodes
+        .into_iter()
+        .map(|opcode| match opcode {
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Pedersen { inputs, outputs, .. }) => {
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Pedersen {
+                    inputs,
+                    domain_separator,
+                    outputs,
+                })
+            }
+            opcode => opcode,
+        })
+        .collect()
+}
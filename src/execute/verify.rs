@@ -0,0 +1,69 @@
+use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::Array;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{read_circuit, solver::Solver, SimulatedBackend};
+use crate::js_witness_map::JsWitnessMapLike;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CONSTRAINT_VIOLATION: &'static str = r#"
+/**
+* @typedef {Object} ConstraintViolation - An opcode which is not satisfied by a witness.
+* @property {number} index - The index of the opcode within the circuit.
+* @property {string} opcode - A human readable description of the opcode.
+* @property {string} message - A description of why the opcode is not satisfied.
+*/
+export type ConstraintViolation = {
+  index: number;
+  opcode: string;
+  message: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Array, typescript_type = "ConstraintViolation[]")]
+    pub type JsConstraintViolations;
+}
+
+#[derive(Serialize)]
+struct ConstraintViolation {
+    index: usize,
+    opcode: String,
+    message: String,
+}
+
+/// Checks that every opcode in an ACIR circuit is satisfied by a complete witness, without solving the circuit.
+///
+/// This is intended for auditing witnesses which were generated externally before they are used to create a proof.
+/// The outputs of Brillig opcodes which make foreign calls cannot be checked and are assumed to be correct.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMapLike} witness - The complete witness map to check against `circuit`.
+/// @returns {Promise<ConstraintViolation[]>} Each opcode which is not satisfied by `witness`, in circuit order.
+#[wasm_bindgen(js_name = verifyWitness, skip_jsdoc)]
+pub async fn verify_witness(
+    circuit: Vec<u8>,
+    witness: JsWitnessMapLike,
+) -> Result<JsConstraintViolations, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let witness = WitnessMap::try_from(witness)?;
+
+    let backend = SimulatedBackend::initialize().await;
+    let mut solver = Solver::new(&backend, &circuit.opcodes, witness);
+    let violations: Vec<_> = solver
+        .check()
+        .await
+        .into_iter()
+        .map(|(index, message)| ConstraintViolation {
+            index,
+            opcode: circuit.opcodes[index].to_string(),
+            message,
+        })
+        .collect();
+
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&violations).unwrap().into())
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use acvm::{
+    acir::circuit::Opcode,
+    brillig_vm::brillig::{
+        ForeignCallOutput, ForeignCallResult, Opcode as BrilligOpcode, RegisterIndex,
+        RegisterOrMemory,
+    },
+};
+
+/// The bit sizes which a circuit's Brillig bytecode expects the results of each foreign call to fit within.
+///
+/// A result's bit size is known when the register it's written to is next read as an operand of an integer
+/// operation. Registers which are overwritten or reach control flow before that point have no known bit size.
+#[derive(Debug, Default)]
+pub(crate) struct ForeignCallBitSizes {
+    /// The bit size of each output of a foreign call, keyed by the name of the foreign call.
+    outputs: HashMap<String, Vec<Option<u32>>>,
+}
+
+impl ForeignCallBitSizes {
+    /// Collects the bit sizes of the results of every foreign call made by the Brillig opcodes in `opcodes`.
+    ///
+    /// Where a foreign call is made from several places, each output takes the largest bit size known for it
+    /// so that a result is only rejected if it can't fit anywhere the foreign call is made.
+    pub(crate) fn from_opcodes(opcodes: &[Opcode]) -> ForeignCallBitSizes {
+        let mut bit_sizes = ForeignCallBitSizes::default();
+        let bytecodes = opcodes.iter().filter_map(|opcode| match opcode {
+            Opcode::Brillig(brillig) => Some(&brillig.bytecode),
+            _ => None,
+        });
+        for bytecode in bytecodes {
+            for (position, brillig_opcode) in bytecode.iter().enumerate() {
+                let BrilligOpcode::ForeignCall { function, destinations, .. } = brillig_opcode
+                else {
+                    continue;
+                };
+
+                let outputs = bit_sizes.outputs.entry(function.clone()).or_default();
+                if outputs.len() < destinations.len() {
+                    outputs.resize(destinations.len(), None);
+                }
+                for (output, destination) in outputs.iter_mut().zip(destinations) {
+                    let RegisterOrMemory::RegisterIndex(register) = destination else {
+                        continue;
+                    };
+                    if let Some(bit_size) = register_bit_size(&bytecode[position + 1..], *register)
+                    {
+                        *output = Some(output.map_or(bit_size, |known| known.max(bit_size)));
+                    }
+                }
+            }
+        }
+        bit_sizes
+    }
+
+    /// Checks that each value in the `result` of the foreign call `name` fits within its known bit size.
+    pub(crate) fn check(&self, name: &str, result: &ForeignCallResult) -> Result<(), String> {
+        let Some(outputs) = self.outputs.get(name) else {
+            return Ok(());
+        };
+        for (index, (bit_size, output)) in outputs.iter().zip(&result.values).enumerate() {
+            let (Some(bit_size), ForeignCallOutput::Single(value)) = (bit_size, output) else {
+                continue;
+            };
+            let value = value.to_field();
+            if value.num_bits() > *bit_size {
+                return Err(format!(
+                    "Foreign call `{name}` returned 0x{} as output {index}, which does not fit within the {bit_size} bits expected by the circuit",
+                    value.to_hex()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the bit size of the integer operation which next reads `register` in `bytecode`, if any.
+fn register_bit_size(bytecode: &[BrilligOpcode], register: RegisterIndex) -> Option<u32> {
+    for brillig_opcode in bytecode {
+        match brillig_opcode {
+            BrilligOpcode::BinaryIntOp { lhs, rhs, bit_size, .. }
+                if *lhs == register || *rhs == register =>
+            {
+                return Some(*bit_size)
+            }
+            BrilligOpcode::BinaryIntOp { destination, .. }
+            | BrilligOpcode::BinaryFieldOp { destination, .. }
+            | BrilligOpcode::Const { destination, .. }
+            | BrilligOpcode::Mov { destination, .. }
+            | BrilligOpcode::Load { destination, .. } => {
+                if *destination == register {
+                    return None;
+                }
+            }
+            BrilligOpcode::Store { .. } => {}
+            // The register may be read along another path or overwritten by a foreign call or blackbox function.
+            _ => return None,
+        }
+    }
+    None
+}
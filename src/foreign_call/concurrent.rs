@@ -0,0 +1,113 @@
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::Poll,
+};
+
+use acvm::{brillig_vm::brillig::ForeignCallResult, pwg::ForeignCallWaitInfo};
+use js_sys::{JsString, Promise};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use super::{
+    check_outputs, decode_brillig_outputs, format_js_err, inputs, ForeignCallBitSizes,
+    ForeignCallHandler,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    type AbortController;
+
+    // `AbortController` is missing from older runtimes, in which case foreign calls can't be notified of cancellation.
+    #[wasm_bindgen(constructor, catch)]
+    fn new() -> Result<AbortController, JsValue>;
+
+    #[wasm_bindgen(method, getter)]
+    fn signal(this: &AbortController) -> JsValue;
+
+    #[wasm_bindgen(method)]
+    fn abort(this: &AbortController, reason: &JsValue);
+}
+
+/// Makes each of `foreign_calls` through `foreign_call_handler` at once, returning their results in the same order.
+///
+/// If any foreign call fails then those which are still pending are cancelled rather than awaited, with the
+/// handler being notified through the `AbortSignal` passed alongside each call. The returned error names the
+/// cancelled foreign calls.
+pub(crate) async fn resolve_brillig_concurrently(
+    foreign_call_handler: &ForeignCallHandler,
+    foreign_calls: &[&ForeignCallWaitInfo],
+    bit_sizes: Option<&ForeignCallBitSizes>,
+) -> Result<Vec<ForeignCallResult>, String> {
+    let controller = AbortController::new().ok();
+    let signal = controller.as_ref().map_or(JsValue::UNDEFINED, AbortController::signal);
+
+    let mut pending: Vec<Option<JsFuture>> = foreign_calls
+        .iter()
+        .map(|foreign_call| {
+            let name = JsString::from(foreign_call.function.clone());
+            let inputs = inputs::encode_foreign_call_inputs(&foreign_call.inputs);
+            let outputs = foreign_call_handler.call3(&JsValue::NULL, &name, &inputs, &signal);
+            let promise = match outputs {
+                Ok(outputs) => Promise::resolve(&outputs),
+                Err(err) => Promise::reject(&err),
+            };
+            Some(JsFuture::from(promise))
+        })
+        .collect();
+    let mut outputs: Vec<Option<JsValue>> = vec![None; foreign_calls.len()];
+
+    let result = poll_fn(|cx| {
+        for (index, future) in pending.iter_mut().enumerate() {
+            let Some(js_future) = future else {
+                continue;
+            };
+            if let Poll::Ready(result) = Pin::new(js_future).poll(cx) {
+                *future = None;
+                match result {
+                    Ok(output) => outputs[index] = Some(output),
+                    Err(err) => return Poll::Ready(Err((index, err))),
+                }
+            }
+        }
+
+        if pending.iter().all(Option::is_none) {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    if let Err((failed_index, err)) = result {
+        let failed_call = &foreign_calls[failed_index].function;
+        let cancelled_calls: Vec<String> = pending
+            .iter()
+            .zip(foreign_calls)
+            .filter(|(future, _)| future.is_some())
+            .map(|(_, foreign_call)| format!("`{}`", foreign_call.function))
+            .collect();
+        if let Some(controller) = controller {
+            let reason = format!("Cancelled as the foreign call to `{failed_call}` failed");
+            controller.abort(&JsValue::from(reason));
+        }
+
+        let mut message = format!("Error awaiting `foreign_call_handler`: {}", format_js_err(err));
+        if !cancelled_calls.is_empty() {
+            message.push_str(&format!(
+                ". Cancelled pending foreign calls: {}",
+                cancelled_calls.join(", ")
+            ));
+        }
+        return Err(message);
+    }
+
+    outputs
+        .into_iter()
+        .zip(foreign_calls)
+        .map(|(output, foreign_call)| {
+            let output = check_outputs(output.expect("every foreign call has resolved"))?;
+            decode_brillig_outputs(foreign_call, output, bit_sizes)
+        })
+        .collect()
+}
@@ -0,0 +1,63 @@
+use js_sys::{Array, Function, JsString, Object, Promise, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use super::ForeignCallHandler;
+
+#[wasm_bindgen(typescript_custom_section)]
+const FOREIGN_CALL_REGISTRY: &'static str = r#"
+/**
+* A function which performs a single kind of foreign call, receiving each of the foreign call's inputs as an argument.
+* @callback ForeignCallFunction
+* @param {...string[]} inputs - The hex encoded inputs to the foreign call.
+* @returns {ForeignCallOutput[] | Promise<ForeignCallOutput[]>} The hex encoded outputs of the foreign call.
+*/
+export type ForeignCallFunction = (...inputs: ForeignCallInput[]) => ForeignCallOutput[] | Promise<ForeignCallOutput[]>;
+
+// A set of foreign call implementations, keyed by the name of the foreign call they perform.
+export type ForeignCallRegistry = Record<string, ForeignCallFunction>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "ForeignCallRegistry")]
+    pub type JsForeignCallRegistry;
+}
+
+/// Creates a `ForeignCallHandler` which dispatches each foreign call to the function registered under its name.
+///
+/// In Node, the exports of a module can be used directly as the registry, e.g.
+/// `createForeignCallDispatcher(require("./oracles"))`.
+///
+/// @param {ForeignCallRegistry} registry - The functions which perform each kind of foreign call.
+/// @param {ForeignCallHandler} fallback - A handler for any foreign calls which are missing from `registry`.
+/// @returns {ForeignCallHandler} A handler which can be passed to `executeCircuit`.
+#[wasm_bindgen(js_name = createForeignCallDispatcher, skip_jsdoc)]
+pub fn create_foreign_call_dispatcher(
+    registry: JsForeignCallRegistry,
+    fallback: Option<ForeignCallHandler>,
+) -> ForeignCallHandler {
+    console_error_panic_hook::set_once();
+
+    let dispatch = Closure::<dyn Fn(JsString, Array, JsValue) -> Promise>::new(
+        move |name: JsString, inputs: Array, signal: JsValue| {
+            let function = Reflect::get(&registry, &name)
+                .ok()
+                .and_then(|function| function.dyn_into::<Function>().ok());
+
+            let result = match (function, &fallback) {
+                (Some(function), _) => Reflect::apply(&function, &JsValue::NULL, &inputs),
+                (None, Some(fallback)) => fallback.call3(&JsValue::NULL, &name, &inputs, &signal),
+                (None, None) => {
+                    let message = format!("No foreign call registered for `{name}`");
+                    return Promise::reject(&JsValue::from(message));
+                }
+            };
+            match result {
+                Ok(outputs) => Promise::resolve(&outputs),
+                Err(err) => Promise::reject(&err),
+            }
+        },
+    );
+
+    dispatch.into_js_value().unchecked_into()
+}
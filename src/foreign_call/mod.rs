@@ -3,8 +3,21 @@ use acvm::{brillig_vm::brillig::ForeignCallResult, pwg::ForeignCallWaitInfo};
 use js_sys::JsString;
 use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
+mod bit_sizes;
+mod concurrent;
+mod dispatcher;
 mod inputs;
 mod outputs;
+mod queue;
+mod signing;
+mod transcript;
+
+pub(crate) use bit_sizes::ForeignCallBitSizes;
+pub(crate) use concurrent::resolve_brillig_concurrently;
+pub use dispatcher::create_foreign_call_dispatcher;
+pub(crate) use queue::{ForeignCallQueue, JsPendingForeignCalls};
+pub use signing::{create_signing_oracle, verify_signed_artifact};
+pub(crate) use transcript::{ForeignCallRecorder, ForeignCallTranscript, RecordedForeignCall};
 
 #[wasm_bindgen(typescript_custom_section)]
 const FOREIGN_CALL_HANDLER: &'static str = r#"
@@ -16,9 +29,10 @@ export type ForeignCallOutput = string | string[]
 * @callback ForeignCallHandler
 * @param {string} name - The identifier for the type of foreign call being performed.
 * @param {string[][]} inputs - An array of hex encoded inputs to the foreign call.
+* @param {AbortSignal} signal - Aborted if the foreign call is cancelled as another made alongside it failed. Only provided when `concurrentForeignCalls` is set.
 * @returns {Promise<string[]>} outputs - An array of hex encoded outputs containing the results of the foreign call.
 */
-export type ForeignCallHandler = (name: string, inputs: ForeignCallInput[]) => Promise<ForeignCallOutput[]>;
+export type ForeignCallHandler = (name: string, inputs: ForeignCallInput[], signal?: AbortSignal) => Promise<ForeignCallOutput[]>;
 "#;
 
 #[wasm_bindgen]
@@ -30,6 +44,7 @@ extern "C" {
 pub(super) async fn resolve_brillig(
     foreign_call_callback: &ForeignCallHandler,
     foreign_call_wait_info: &ForeignCallWaitInfo,
+    bit_sizes: Option<&ForeignCallBitSizes>,
 ) -> Result<ForeignCallResult, String> {
     // Prepare to call
     let name = JsString::from(foreign_call_wait_info.function.clone());
@@ -38,9 +53,22 @@ pub(super) async fn resolve_brillig(
     // Perform foreign call
     let outputs = perform_foreign_call(foreign_call_callback, name, inputs).await?;
 
+    decode_brillig_outputs(foreign_call_wait_info, outputs, bit_sizes)
+}
+
+/// Decodes the `outputs` returned by the foreign call described by `foreign_call_wait_info`.
+fn decode_brillig_outputs(
+    foreign_call_wait_info: &ForeignCallWaitInfo,
+    outputs: js_sys::Array,
+    bit_sizes: Option<&ForeignCallBitSizes>,
+) -> Result<ForeignCallResult, String> {
     // The Brillig VM checks that the number of return values from
     // the foreign call is valid so we don't need to do it here.
-    outputs::decode_foreign_call_result(outputs)
+    let result = outputs::decode_foreign_call_result(outputs)?;
+    if let Some(bit_sizes) = bit_sizes {
+        bit_sizes.check(&foreign_call_wait_info.function, &result)?;
+    }
+    Ok(result)
 }
 
 #[allow(dead_code)]
@@ -60,7 +88,11 @@ async fn perform_foreign_call(
         .await
         .map_err(|err| format!("Error awaiting `foreign_call_handler`: {}", format_js_err(err)))?;
 
-    // Check that result conforms to expected shape.
+    check_outputs(js_resolution)
+}
+
+/// Checks that the value a foreign call resolved to conforms to the expected shape.
+fn check_outputs(js_resolution: JsValue) -> Result<js_sys::Array, String> {
     if !js_resolution.is_array() {
         return Err("`foreign_call_handler` must return a Promise<ForeignCallValue[]>".into());
     }
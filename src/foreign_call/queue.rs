@@ -0,0 +1,123 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Function, JsString, Promise};
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use super::ForeignCallHandler;
+
+#[wasm_bindgen(typescript_custom_section)]
+const PENDING_FOREIGN_CALLS: &'static str = r#"
+/**
+* @typedef {Object} PendingForeignCalls - The foreign calls which have been made but have not yet resolved.
+* @property {number} count - The number of pending foreign calls.
+* @property {string[]} oracles - The name of each pending foreign call, in the order in which they were made.
+*/
+export type PendingForeignCalls = {
+  count: number;
+  oracles: string[];
+}
+
+/**
+* A callback which is notified each time a foreign call is made or resolves.
+* @callback ForeignCallsChangedCallback
+* @param {PendingForeignCalls} pending - The foreign calls which are still waiting on a response.
+*/
+export type ForeignCallsChangedCallback = (pending: PendingForeignCalls) => void;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "PendingForeignCalls")]
+    pub type JsPendingForeignCalls;
+}
+
+#[derive(Serialize)]
+struct PendingForeignCalls<'a> {
+    count: usize,
+    oracles: &'a [String],
+}
+
+/// Tracks the foreign calls which have been made through a `ForeignCallHandler` but have not yet resolved.
+#[derive(Debug, Default)]
+pub(crate) struct ForeignCallQueue {
+    /// The name of each pending foreign call, in the order in which they were made.
+    pending: RefCell<Vec<String>>,
+}
+
+impl ForeignCallQueue {
+    /// Returns the foreign calls which are currently pending.
+    pub(crate) fn to_js(&self) -> JsPendingForeignCalls {
+        let pending = self.pending.borrow();
+        let pending = PendingForeignCalls { count: pending.len(), oracles: &pending };
+        <JsValue as JsValueSerdeExt>::from_serde(&pending).unwrap().into()
+    }
+
+    /// Wraps `foreign_call_handler` so that each foreign call it performs is held in this queue until it resolves.
+    ///
+    /// `on_change` is called with the contents of the queue each time one of these foreign calls is made or resolves.
+    pub(crate) fn track(
+        self: &Rc<Self>,
+        foreign_call_handler: &ForeignCallHandler,
+        on_change: Option<Function>,
+    ) -> ForeignCallHandler {
+        let queue = self.clone();
+        let foreign_call_handler = Function::clone(foreign_call_handler);
+
+        let tracked_handler =
+            Closure::<dyn Fn(JsString, Array, JsValue) -> Result<Promise, JsValue>>::new(
+                move |name: JsString, inputs: Array, signal: JsValue| {
+                    let name = String::from(name);
+                    queue.push(name.clone(), on_change.as_ref());
+
+                    let outputs = match foreign_call_handler.call3(
+                        &JsValue::NULL,
+                        &JsValue::from(&name),
+                        &inputs,
+                        &signal,
+                    ) {
+                        Ok(outputs) => outputs,
+                        Err(err) => {
+                            queue.remove(&name, on_change.as_ref());
+                            return Err(err);
+                        }
+                    };
+
+                    let queue = queue.clone();
+                    let on_change = on_change.clone();
+                    Ok(future_to_promise(async move {
+                        let outputs = JsFuture::from(Promise::resolve(&outputs)).await;
+                        queue.remove(&name, on_change.as_ref());
+                        outputs
+                    }))
+                },
+            );
+
+        tracked_handler.into_js_value().unchecked_into()
+    }
+
+    fn push(&self, name: String, on_change: Option<&Function>) {
+        self.pending.borrow_mut().push(name);
+        self.notify(on_change);
+    }
+
+    fn remove(&self, name: &str, on_change: Option<&Function>) {
+        let mut pending = self.pending.borrow_mut();
+        if let Some(position) = pending.iter().position(|pending_name| pending_name == name) {
+            pending.remove(position);
+        }
+        drop(pending);
+        self.notify(on_change);
+    }
+
+    fn notify(&self, on_change: Option<&Function>) {
+        let Some(on_change) = on_change else {
+            return;
+        };
+        if let Err(err) = on_change.call1(&JsValue::NULL, &self.to_js()) {
+            log::warn!("onForeignCallsChanged callback threw an error: {err:?}");
+        }
+    }
+}
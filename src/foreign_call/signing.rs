@@ -0,0 +1,311 @@
+use std::str::FromStr;
+
+use acvm::{
+    blackbox_solver::{ecdsa_secp256k1_verify, ecdsa_secp256r1_verify, sha256},
+    FieldElement,
+};
+use js_sys::{Array, Function, JsString, Promise, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use crate::js_witness_map::{field_element_to_js_string, js_value_to_field_element};
+
+#[wasm_bindgen(typescript_custom_section)]
+const SIGNING_ORACLE: &'static str = r#"
+/**
+* A signature scheme which a circuit can verify signatures from.
+* - `ecdsa_secp256k1` and `ecdsa_secp256r1` expect the signature as the 64 bytes of `r` followed by `s`, with `s` in its low form.
+* - `schnorr` expects the 64 byte signature produced by Barretenberg's Schnorr implementation over Grumpkin.
+*/
+export type SignatureScheme = "ecdsa_secp256k1" | "ecdsa_secp256r1" | "schnorr";
+
+/**
+* A signer, such as a WebAuthn authenticator or a hardware wallet, which signs a message hash.
+* @callback Signer
+* @param {Uint8Array} messageHash - The SHA256 hash of the message to be signed.
+* @returns {Uint8Array | Promise<Uint8Array>} The signature, either DER encoded or as the raw bytes of the scheme.
+*/
+export type Signer = (messageHash: Uint8Array) => Uint8Array | Promise<Uint8Array>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = JsString, typescript_type = "SignatureScheme")]
+    pub type JsSignatureScheme;
+
+    #[wasm_bindgen(extends = Function, typescript_type = "Signer")]
+    pub type JsSigner;
+
+    #[wasm_bindgen(extends = Function, typescript_type = "ForeignCallFunction")]
+    pub type JsForeignCallFunction;
+}
+
+/// The number of bytes in the signatures which circuits verify.
+const SIGNATURE_BYTES: usize = 64;
+
+/// The order of the secp256k1 curve, big endian.
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// The order of the secp256r1 curve, big endian.
+const SECP256R1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SignatureScheme {
+    EcdsaSecp256k1,
+    EcdsaSecp256r1,
+    Schnorr,
+}
+
+impl FromStr for SignatureScheme {
+    type Err = String;
+
+    fn from_str(scheme: &str) -> Result<Self, Self::Err> {
+        match scheme {
+            "ecdsa_secp256k1" => Ok(SignatureScheme::EcdsaSecp256k1),
+            "ecdsa_secp256r1" => Ok(SignatureScheme::EcdsaSecp256r1),
+            "schnorr" => Ok(SignatureScheme::Schnorr),
+            _ => Err(format!("Unknown signature scheme: {scheme}")),
+        }
+    }
+}
+
+impl SignatureScheme {
+    /// Converts a `signature` returned by a signer into the bytes which the circuit verifies.
+    fn encode_signature(self, signature: &[u8]) -> Result<[u8; SIGNATURE_BYTES], String> {
+        let curve_order = match self {
+            SignatureScheme::EcdsaSecp256k1 => &SECP256K1_ORDER,
+            SignatureScheme::EcdsaSecp256r1 => &SECP256R1_ORDER,
+            SignatureScheme::Schnorr => {
+                return signature.try_into().map_err(|_| {
+                    format!(
+                        "Expected a {SIGNATURE_BYTES} byte Schnorr signature but got {} bytes",
+                        signature.len()
+                    )
+                });
+            }
+        };
+
+        // Hardware signers commonly return DER encoded signatures, or append a recovery id to `r || s`.
+        let (r, mut s) = match signature.len() {
+            SIGNATURE_BYTES | 65 => {
+                (to_scalar(&signature[..32])?, to_scalar(&signature[32..SIGNATURE_BYTES])?)
+            }
+            _ => decode_der_signature(signature)?,
+        };
+        if !is_scalar(&r, curve_order) || !is_scalar(&s, curve_order) {
+            return Err("Signature components must be within the order of the curve".to_string());
+        }
+        // The ACVM rejects ECDSA signatures whose `s` is in its high form, so it's replaced with `n - s`.
+        if is_high(&s, curve_order) {
+            s = sub(curve_order, &s);
+        }
+
+        let mut encoded = [0; SIGNATURE_BYTES];
+        encoded[..32].copy_from_slice(&r);
+        encoded[32..].copy_from_slice(&s);
+        Ok(encoded)
+    }
+}
+
+/// Left pads a big endian integer of at most 32 bytes, ignoring any leading zeros, to 32 bytes.
+fn to_scalar(bytes: &[u8]) -> Result<[u8; 32], String> {
+    let start = bytes.iter().position(|&byte| byte != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    if bytes.len() > 32 {
+        return Err("Signature component is larger than 32 bytes".to_string());
+    }
+    let mut scalar = [0; 32];
+    scalar[32 - bytes.len()..].copy_from_slice(bytes);
+    Ok(scalar)
+}
+
+/// Decodes the `r` and `s` components of a DER encoded ECDSA signature.
+fn decode_der_signature(signature: &[u8]) -> Result<([u8; 32], [u8; 32]), String> {
+    let invalid = || "Invalid DER encoded signature".to_string();
+
+    let read_integer = |bytes: &[u8]| -> Result<([u8; 32], usize), String> {
+        match bytes {
+            [0x02, length, rest @ ..] if rest.len() >= *length as usize => {
+                let length = *length as usize;
+                Ok((to_scalar(&rest[..length])?, 2 + length))
+            }
+            _ => Err(invalid()),
+        }
+    };
+
+    let sequence = match signature {
+        [0x30, length, rest @ ..] if rest.len() == *length as usize => rest,
+        _ => return Err(invalid()),
+    };
+    let (r, r_length) = read_integer(sequence)?;
+    let (s, s_length) = read_integer(&sequence[r_length..])?;
+    if r_length + s_length != sequence.len() {
+        return Err(invalid());
+    }
+    Ok((r, s))
+}
+
+/// Returns whether `value` is a non-zero integer less than `curve_order`.
+fn is_scalar(value: &[u8; 32], curve_order: &[u8; 32]) -> bool {
+    value.iter().any(|&byte| byte != 0) && value < curve_order
+}
+
+/// Returns whether `s` is greater than half of `curve_order`.
+fn is_high(s: &[u8; 32], curve_order: &[u8; 32]) -> bool {
+    let mut half_order = [0; 32];
+    let mut carry = 0;
+    for (half, byte) in half_order.iter_mut().zip(curve_order) {
+        *half = (carry << 7) | (byte >> 1);
+        carry = byte & 1;
+    }
+    s > &half_order
+}
+
+/// Returns `a - b` for big endian integers where `a >= b`.
+fn sub(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut difference = [0; 32];
+    let mut borrow = 0;
+    for index in (0..32).rev() {
+        let value = a[index] as i16 - b[index] as i16 - borrow;
+        borrow = (value < 0) as i16;
+        difference[index] = value.rem_euclid(256) as u8;
+    }
+    difference
+}
+
+/// Reads the bytes of the message to be signed from the first input of the foreign call.
+fn read_message(message: JsValue) -> Result<Vec<u8>, String> {
+    let message: Array =
+        message.dyn_into().map_err(|_| "Expected a message to sign".to_string())?;
+    message
+        .iter()
+        .map(|byte| {
+            let byte = js_value_to_field_element(byte).map_err(String::from)?;
+            if byte.num_bits() > 8 {
+                return Err(format!("Message byte {} does not fit in a byte", byte.to_hex()));
+            }
+            Ok(byte.to_u128() as u8)
+        })
+        .collect()
+}
+
+/// Creates a foreign call function which has `signer` sign the message passed to it, for use in a
+/// `ForeignCallRegistry`.
+///
+/// The message is passed as the foreign call's first input, with one byte per element. The signer is handed the
+/// SHA256 hash of the message and its signature is returned as the single output of the foreign call, encoded as
+/// the bytes which the circuit verifies under `scheme`.
+///
+/// @param {SignatureScheme} scheme - The signature scheme which the circuit verifies the signature under.
+/// @param {Signer} signer - Signs the hash of the message, e.g. by prompting a WebAuthn authenticator.
+/// @returns {ForeignCallFunction} A foreign call function which can be added to a `ForeignCallRegistry`.
+#[wasm_bindgen(js_name = createSigningOracle, skip_jsdoc)]
+pub fn create_signing_oracle(
+    scheme: JsSignatureScheme,
+    signer: JsSigner,
+) -> Result<JsForeignCallFunction, JsString> {
+    console_error_panic_hook::set_once();
+    let scheme = SignatureScheme::from_str(&String::from(JsString::from(scheme)))?;
+    let signer = Function::from(signer);
+
+    // Any inputs after the message are ignored.
+    let oracle = Closure::<dyn Fn(JsValue) -> Promise>::new(move |message: JsValue| {
+        let signer = signer.clone();
+        future_to_promise(async move {
+            let message = read_message(message).map_err(JsValue::from)?;
+            let message_hash = sha256(&message).expect("sha256 should not fail");
+
+            let signature =
+                signer.call1(&JsValue::NULL, &Uint8Array::from(message_hash.as_slice()))?;
+            let signature = JsFuture::from(Promise::resolve(&signature)).await?;
+            let signature = signature
+                .dyn_into::<Uint8Array>()
+                .map_err(|_| JsValue::from("Signer must return the signature as a Uint8Array"))?
+                .to_vec();
+
+            let signature = scheme.encode_signature(&signature).map_err(JsValue::from)?;
+            let output: Array = signature
+                .iter()
+                .map(|&byte| {
+                    JsValue::from(field_element_to_js_string(&FieldElement::from(byte as u128)))
+                })
+                .collect();
+            Ok(Array::of1(&output).into())
+        })
+    });
+
+    Ok(oracle.into_js_value().unchecked_into())
+}
+
+/// Reads an uncompressed public key, given either as the 64 bytes of `x` followed by `y` or in SEC1 form.
+fn read_public_key(public_key: &[u8]) -> Result<([u8; 32], [u8; 32]), String> {
+    let coordinates = match public_key {
+        [0x04, coordinates @ ..] if coordinates.len() == 64 => coordinates,
+        coordinates if coordinates.len() == 64 => coordinates,
+        _ => return Err("Expected an uncompressed public key of 64 or 65 bytes".to_string()),
+    };
+    let x = coordinates[..32].try_into().expect("coordinates should be 64 bytes");
+    let y = coordinates[32..].try_into().expect("coordinates should be 64 bytes");
+    Ok((x, y))
+}
+
+/// Checks that a compiled circuit, or any other artifact, was signed by the holder of `public_key`.
+///
+/// This lets applications which load circuits from elsewhere refuse to execute artifacts which have been tampered
+/// with. The signature is over the SHA256 hash of the artifact's bytes, as produced by signing them with a standard
+/// ECDSA implementation.
+///
+/// @param {Uint8Array} artifact - The bytes of the artifact, e.g. a serialized ACIR circuit.
+/// @param {Uint8Array} signature - The signature over `artifact`, either DER encoded or as the 64 bytes of `r` followed by `s`.
+/// @param {Uint8Array} public_key - The uncompressed public key of the signer, with or without its leading `0x04` byte.
+/// @param {SignatureScheme} scheme - The curve which the artifact was signed over. Defaults to `ecdsa_secp256k1`.
+/// @returns {boolean} Whether `signature` is a valid signature of `artifact` by `public_key`.
+#[wasm_bindgen(js_name = verifySignedArtifact, skip_jsdoc)]
+pub fn verify_signed_artifact(
+    artifact: Vec<u8>,
+    signature: Vec<u8>,
+    public_key: Vec<u8>,
+    scheme: Option<JsSignatureScheme>,
+) -> Result<bool, JsString> {
+    console_error_panic_hook::set_once();
+    let scheme = match scheme {
+        Some(scheme) => SignatureScheme::from_str(&String::from(JsString::from(scheme)))?,
+        None => SignatureScheme::EcdsaSecp256k1,
+    };
+    let (verify, curve_order, is_on_curve): (
+        fn(&[u8], &[u8; 32], &[u8; 32], &[u8; 64]) -> _,
+        _,
+        fn(&[u8]) -> bool,
+    ) = match scheme {
+        SignatureScheme::EcdsaSecp256k1 => (ecdsa_secp256k1_verify, &SECP256K1_ORDER, |key| {
+            k256::PublicKey::from_sec1_bytes(key).is_ok()
+        }),
+        SignatureScheme::EcdsaSecp256r1 => (ecdsa_secp256r1_verify, &SECP256R1_ORDER, |key| {
+            p256::PublicKey::from_sec1_bytes(key).is_ok()
+        }),
+        SignatureScheme::Schnorr => {
+            return Err("Artifacts can only be verified against ECDSA signatures".into())
+        }
+    };
+    let (public_key_x, public_key_y) = read_public_key(&public_key)?;
+    let signature = scheme.encode_signature(&signature)?;
+
+    // The ACVM panics on inputs which it can't convert into points and scalars, so they're rejected up front.
+    let sec1_public_key = [&[0x04][..], &public_key_x, &public_key_y].concat();
+    if !is_on_curve(&sec1_public_key) {
+        return Err("Public key is not a point on the curve".into());
+    }
+    let artifact_hash = sha256(&artifact).expect("sha256 should not fail");
+    if artifact_hash.as_slice() >= curve_order.as_slice() {
+        return Err("Artifact hash exceeds the order of the curve so can't be verified".into());
+    }
+
+    verify(&artifact_hash, &public_key_x, &public_key_y, &signature)
+        .map_err(|err| err.to_string().into())
+}
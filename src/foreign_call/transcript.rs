@@ -0,0 +1,318 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use acvm::{
+    acir::brillig::{ForeignCallOutput, Value},
+    blackbox_solver::sha256,
+    FieldElement,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Function, JsString, Promise};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use super::ForeignCallHandler;
+use crate::js_witness_map::{field_element_to_js_string, js_value_to_field_element};
+
+#[wasm_bindgen(typescript_custom_section)]
+const FOREIGN_CALL_TRANSCRIPT: &'static str = r#"
+/**
+* @typedef {Object} ForeignCallTranscriptEntry - A foreign call made by a previous execution along with its response.
+* @property {string} name - The identifier for the type of foreign call which was performed.
+* @property {ForeignCallInput[]} inputs - The hex encoded inputs to the foreign call.
+* @property {ForeignCallOutput[]} outputs - The hex encoded outputs returned by the foreign call.
+*/
+export type ForeignCallTranscriptEntry = {
+  name: string;
+  inputs: ForeignCallInput[];
+  outputs: ForeignCallOutput[];
+}
+"#;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum TranscriptOutput {
+    Single(String),
+    Array(Vec<String>),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct TranscriptEntry {
+    name: String,
+    inputs: Vec<Vec<String>>,
+    outputs: Vec<TranscriptOutput>,
+}
+
+impl TranscriptEntry {
+    /// Returns whether this entry records a foreign call to `name` with `inputs`.
+    fn matches(&self, name: &str, inputs: &Array) -> bool {
+        let inputs: Vec<Array> = inputs.iter().map(|input| input.unchecked_into()).collect();
+        self.name == name
+            && self.inputs.len() == inputs.len()
+            && self.inputs.iter().zip(inputs).all(|(expected, input)| {
+                expected.len() == input.length() as usize
+                    && expected.iter().zip(input.iter()).all(|(expected, value)| {
+                        parse_field(expected).ok() == js_value_to_field_element(value).ok()
+                    })
+            })
+    }
+
+    /// Returns this entry with each of its values hex encoded in the same way, however they were written.
+    fn canonicalize(&self) -> TranscriptEntry {
+        let canonicalize = |value: &String| {
+            parse_field(value).map_or_else(|_| value.clone(), |field| field.to_hex())
+        };
+        TranscriptEntry {
+            name: self.name.clone(),
+            inputs: self
+                .inputs
+                .iter()
+                .map(|input| input.iter().map(canonicalize).collect())
+                .collect(),
+            outputs: self
+                .outputs
+                .iter()
+                .map(|output| match output {
+                    TranscriptOutput::Single(value) => {
+                        TranscriptOutput::Single(canonicalize(value))
+                    }
+                    TranscriptOutput::Array(values) => {
+                        TranscriptOutput::Array(values.iter().map(canonicalize).collect())
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+fn parse_field(value: &str) -> Result<FieldElement, JsString> {
+    js_value_to_field_element(JsValue::from_str(value))
+}
+
+/// A foreign call from a transcript with its inputs and outputs parsed into field elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RecordedForeignCall {
+    pub(crate) name: String,
+    pub(crate) inputs: Vec<Vec<FieldElement>>,
+    pub(crate) outputs: Vec<ForeignCallOutput>,
+}
+
+/// Foreign call responses recorded from a previous execution, which are replayed in order rather than
+/// calling out to a `ForeignCallHandler`.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(transparent)]
+pub(crate) struct ForeignCallTranscript {
+    entries: RefCell<VecDeque<TranscriptEntry>>,
+}
+
+impl TryFrom<JsValue> for ForeignCallTranscript {
+    type Error = String;
+
+    fn try_from(js_transcript: JsValue) -> Result<Self, Self::Error> {
+        let entries: VecDeque<TranscriptEntry> =
+            <JsValue as JsValueSerdeExt>::into_serde(&js_transcript)
+                .map_err(|err| format!("invalid foreignCallTranscript: {err}"))?;
+
+        for entry in &entries {
+            let values =
+                entry.inputs.iter().flatten().chain(entry.outputs.iter().flat_map(|output| {
+                    match output {
+                        TranscriptOutput::Single(value) => std::slice::from_ref(value),
+                        TranscriptOutput::Array(values) => values.as_slice(),
+                    }
+                }));
+            for value in values {
+                parse_field(value).map_err(|err| {
+                    format!("invalid foreignCallTranscript entry for `{}`: {err}", entry.name)
+                })?;
+            }
+        }
+
+        Ok(ForeignCallTranscript { entries: RefCell::new(entries) })
+    }
+}
+
+impl ForeignCallTranscript {
+    /// Returns a hash of the foreign calls in the transcript which doesn't depend on the order they were made in.
+    pub(crate) fn sorted_hash(&self) -> [u8; 32] {
+        let mut entries: Vec<Vec<u8>> = self
+            .entries
+            .borrow()
+            .iter()
+            .map(|entry| {
+                rmp_serde::to_vec(&entry.canonicalize())
+                    .expect("transcripts should always serialize")
+            })
+            .collect();
+        entries.sort_unstable();
+
+        let mut bytes = Vec::new();
+        for entry in entries {
+            bytes.extend((entry.len() as u64).to_be_bytes());
+            bytes.extend(entry);
+        }
+        sha256(&bytes).expect("sha256 should not fail")
+    }
+
+    /// Returns the foreign calls remaining in the transcript, failing if any holds a value which isn't a field element.
+    pub(crate) fn recorded_calls(&self) -> Result<Vec<RecordedForeignCall>, String> {
+        let parse = |value: &String| {
+            parse_field(value).map_err(|err| format!("Invalid foreign call value: {err}"))
+        };
+        self.entries
+            .borrow()
+            .iter()
+            .map(|entry| {
+                let inputs = entry
+                    .inputs
+                    .iter()
+                    .map(|input| input.iter().map(parse).collect())
+                    .collect::<Result<_, _>>()?;
+                let outputs = entry
+                    .outputs
+                    .iter()
+                    .map(|output| match output {
+                        TranscriptOutput::Single(value) => {
+                            parse(value).map(|value| ForeignCallOutput::Single(Value::from(value)))
+                        }
+                        TranscriptOutput::Array(values) => values
+                            .iter()
+                            .map(|value| parse(value).map(Value::from))
+                            .collect::<Result<_, _>>()
+                            .map(ForeignCallOutput::Array),
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(RecordedForeignCall { name: entry.name.clone(), inputs, outputs })
+            })
+            .collect()
+    }
+
+    /// Returns a transcript holding `recorded_calls`, in order.
+    pub(crate) fn from_recorded_calls(recorded_calls: Vec<RecordedForeignCall>) -> Self {
+        let encode = |value: &FieldElement| String::from(field_element_to_js_string(value));
+        let entries = recorded_calls
+            .into_iter()
+            .map(|call| TranscriptEntry {
+                name: call.name,
+                inputs: call
+                    .inputs
+                    .iter()
+                    .map(|input| input.iter().map(encode).collect())
+                    .collect(),
+                outputs: call
+                    .outputs
+                    .iter()
+                    .map(|output| match output {
+                        ForeignCallOutput::Single(value) => {
+                            TranscriptOutput::Single(encode(&value.to_field()))
+                        }
+                        ForeignCallOutput::Array(values) => TranscriptOutput::Array(
+                            values.iter().map(|value| encode(&value.to_field())).collect(),
+                        ),
+                    })
+                    .collect(),
+            })
+            .collect();
+        ForeignCallTranscript { entries: RefCell::new(entries) }
+    }
+
+    /// Returns a `ForeignCallHandler` which answers each foreign call with the next entry in the transcript.
+    ///
+    /// Foreign calls which don't match the next entry, or which are made once the transcript is exhausted, are rejected.
+    pub(crate) fn into_handler(self) -> ForeignCallHandler {
+        let replay = Closure::<dyn Fn(JsString, Array) -> Promise>::new(
+            move |name: JsString, inputs: Array| {
+                let name = String::from(name);
+                let mut entries = self.entries.borrow_mut();
+                match entries.front() {
+                    Some(entry) if entry.matches(&name, &inputs) => {
+                        let entry = entries.pop_front().expect("checked above");
+                        let outputs =
+                            <JsValue as JsValueSerdeExt>::from_serde(&entry.outputs).unwrap();
+                        Promise::resolve(&outputs)
+                    }
+                    Some(entry) => {
+                        let message = format!(
+                            "Foreign call to `{name}` does not match the next transcript entry, a call to `{}`",
+                            entry.name
+                        );
+                        Promise::reject(&JsValue::from(message))
+                    }
+                    None => {
+                        let message =
+                            format!("Foreign call to `{name}` was made after the transcript ended");
+                        Promise::reject(&JsValue::from(message))
+                    }
+                }
+            },
+        );
+
+        replay.into_js_value().unchecked_into()
+    }
+
+    /// Returns a `ForeignCallHandler` which rejects every foreign call, for deterministic executions without a transcript.
+    pub(crate) fn missing_handler() -> ForeignCallHandler {
+        let reject = Closure::<dyn Fn(JsString) -> Promise>::new(move |name: JsString| {
+            let message = format!(
+                "Foreign call to `{name}` cannot be made during deterministic execution without a transcript"
+            );
+            Promise::reject(&JsValue::from(message))
+        });
+
+        reject.into_js_value().unchecked_into()
+    }
+}
+
+/// Records the foreign calls made through a `ForeignCallHandler` along with their responses, so that they can
+/// later be replayed as a [`ForeignCallTranscript`].
+#[derive(Debug, Default)]
+pub(crate) struct ForeignCallRecorder {
+    /// An entry for each foreign call in the order in which they were made, filled in once it resolves.
+    entries: RefCell<Vec<Option<TranscriptEntry>>>,
+}
+
+impl ForeignCallRecorder {
+    /// Wraps `foreign_call_handler` so that each foreign call it resolves is recorded.
+    pub(crate) fn record(
+        self: &Rc<Self>,
+        foreign_call_handler: &ForeignCallHandler,
+    ) -> ForeignCallHandler {
+        let recorder = self.clone();
+        let foreign_call_handler = Function::clone(foreign_call_handler);
+
+        let recording_handler =
+            Closure::<dyn Fn(JsString, Array, JsValue) -> Result<Promise, JsValue>>::new(
+                move |name: JsString, inputs: Array, signal: JsValue| {
+                    let outputs =
+                        foreign_call_handler.call3(&JsValue::NULL, &name, &inputs, &signal)?;
+
+                    let index = {
+                        let mut entries = recorder.entries.borrow_mut();
+                        entries.push(None);
+                        entries.len() - 1
+                    };
+                    let recorder = recorder.clone();
+                    Ok(future_to_promise(async move {
+                        let outputs = JsFuture::from(Promise::resolve(&outputs)).await?;
+                        // Responses which can't be decoded fail the execution so there's nothing to replay.
+                        let entry = <JsValue as JsValueSerdeExt>::into_serde(&inputs)
+                            .and_then(|inputs| {
+                                let outputs = <JsValue as JsValueSerdeExt>::into_serde(&outputs)?;
+                                Ok(TranscriptEntry { name: String::from(&name), inputs, outputs })
+                            })
+                            .ok();
+                        recorder.entries.borrow_mut()[index] = entry;
+                        Ok(outputs)
+                    }))
+                },
+            );
+
+        recording_handler.into_js_value().unchecked_into()
+    }
+
+    /// Returns a transcript of the foreign calls which have resolved, in the order in which they were made.
+    pub(crate) fn transcript(&self) -> ForeignCallTranscript {
+        let entries = self.entries.borrow().iter().flatten().cloned().collect();
+        ForeignCallTranscript { entries: RefCell::new(entries) }
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, JsString, Map, Object, Reflect};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::JsWitnessMap;
+
+#[wasm_bindgen(typescript_custom_section)]
+const WITNESS_INSPECTION: &'static str = r#"
+/**
+* @typedef {Object} WitnessInspectionOptions - Options which control how a witness map is inspected.
+* @property {number} limit - The maximum number of witnesses to include. Defaults to 100.
+* @property {Object} abi - The ABI of the Noir program the witness belongs to, used to label the witnesses of its parameters and return value.
+*/
+export type WitnessInspectionOptions = {
+  limit?: number;
+  abi?: {
+    param_witnesses?: Record<string, number[]>;
+    return_witnesses?: number[];
+  };
+}
+
+/**
+* @typedef {Object} WitnessInspectionRow - A single witness within a `WitnessInspection`.
+* @property {number} witness - The index of the witness.
+* @property {string} value - The value of the witness, as held in the witness map.
+* @property {string} label - The ABI parameter, or return value, which the witness belongs to. Only present if an `abi` was provided.
+*/
+export type WitnessInspectionRow = {
+  witness: number;
+  value: string;
+  label?: string;
+}
+
+/**
+* @typedef {Object} WitnessInspection - A truncated, annotated view of a witness map.
+* @property {number} size - The number of witnesses in the witness map.
+* @property {boolean} truncated - Whether some witnesses were left out due to the `limit`.
+* @property {WitnessInspectionRow[]} rows - The witnesses included in the view, in the order of the witness map.
+*/
+export type WitnessInspection = {
+  size: number;
+  truncated: boolean;
+  rows: WitnessInspectionRow[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "WitnessInspectionOptions")]
+    pub type JsWitnessInspectionOptions;
+
+    #[wasm_bindgen(typescript_type = "WitnessInspection")]
+    pub type JsWitnessInspection;
+}
+
+/// The number of witnesses included when no `limit` is given.
+const DEFAULT_LIMIT: usize = 100;
+
+/// The parts of a Noir program's ABI which locate its inputs and outputs within the witness.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct WitnessAbi {
+    #[serde(default)]
+    pub(crate) param_witnesses: BTreeMap<String, Vec<u32>>,
+    #[serde(default)]
+    pub(crate) return_witnesses: Vec<u32>,
+}
+
+impl WitnessAbi {
+    /// Returns the label of each witness belonging to a parameter or the return value, keyed by witness index.
+    ///
+    /// Parameters spanning several witnesses, such as arrays, have each witness labelled by its position.
+    fn labels(&self) -> BTreeMap<u32, String> {
+        let mut labels = BTreeMap::new();
+        let named_witnesses = self
+            .param_witnesses
+            .iter()
+            .map(|(name, witnesses)| (name.as_str(), witnesses))
+            .chain(std::iter::once(("return", &self.return_witnesses)));
+        for (name, witnesses) in named_witnesses {
+            for (position, witness) in witnesses.iter().enumerate() {
+                let label = match witnesses.len() {
+                    1 => name.to_owned(),
+                    _ => format!("{name}[{position}]"),
+                };
+                // A parameter which is also returned keeps its parameter's label.
+                labels.entry(*witness).or_insert(label);
+            }
+        }
+        labels
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WitnessInspectionOptions {
+    limit: Option<usize>,
+    abi: Option<WitnessAbi>,
+}
+
+impl TryFrom<Option<JsWitnessInspectionOptions>> for WitnessInspectionOptions {
+    type Error = String;
+
+    fn try_from(js_options: Option<JsWitnessInspectionOptions>) -> Result<Self, Self::Error> {
+        match js_options {
+            Some(js_options) => <JsValue as JsValueSerdeExt>::into_serde(&js_options)
+                .map_err(|err| format!("Invalid inspection options: {err}")),
+            None => Ok(WitnessInspectionOptions::default()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WitnessInspectionRow {
+    witness: u32,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WitnessInspection {
+    size: u32,
+    truncated: bool,
+    rows: Vec<WitnessInspectionRow>,
+}
+
+/// Builds a view of the first witnesses of `witness_map`.
+///
+/// Only the witnesses which are included are read from the map, so this is cheap even for huge witness maps.
+fn inspect(witness_map: &Map, options: &WitnessInspectionOptions) -> WitnessInspection {
+    let limit = options.limit.unwrap_or(DEFAULT_LIMIT);
+    let labels = options.abi.as_ref().map(WitnessAbi::labels);
+
+    let rows = witness_map
+        .entries()
+        .into_iter()
+        .take(limit)
+        .filter_map(|entry| {
+            let entry: Array = entry.ok()?.unchecked_into();
+            let witness = entry.get(0).as_f64()? as u32;
+            let value = entry.get(1).as_string().unwrap_or_default();
+            let label = labels.as_ref().and_then(|labels| labels.get(&witness).cloned());
+            Some(WitnessInspectionRow { witness, value, label })
+        })
+        .collect();
+
+    let size = witness_map.size();
+    WitnessInspection { size, truncated: size as usize > limit, rows }
+}
+
+/// Returns a truncated view of `witness_map`, with each witness labelled by the ABI parameter it belongs to.
+///
+/// Witness maps of large circuits hold millions of entries which are impractical to log or browse in devtools
+/// directly, so this returns a table of the first `limit` witnesses instead.
+///
+/// @param {WitnessMap} witness_map - The witness map to inspect.
+/// @param {WitnessInspectionOptions} options - Controls how many witnesses are included and how they're labelled.
+/// @returns {WitnessInspection} The number of witnesses in the witness map along with a table of the first witnesses.
+#[wasm_bindgen(js_name = inspectWitness, skip_jsdoc)]
+pub fn inspect_witness(
+    witness_map: JsWitnessMap,
+    options: Option<JsWitnessInspectionOptions>,
+) -> Result<JsWitnessInspection, JsString> {
+    console_error_panic_hook::set_once();
+    let options = WitnessInspectionOptions::try_from(options)?;
+    let inspection = inspect(&witness_map, &options);
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&inspection).unwrap().into())
+}
+
+/// Returns `value` as a `Map` if it looks like a witness map, judging by its first entry.
+fn is_witness_map(value: &JsValue) -> Option<&Map> {
+    let map = value.dyn_ref::<Map>()?;
+    let first: Array = map.entries().into_iter().next()?.ok()?.unchecked_into();
+    let is_witness_map = first.get(0).as_f64().is_some()
+        && first.get(1).as_string().map_or(false, |value| value.starts_with("0x"));
+    is_witness_map.then_some(map)
+}
+
+/// Creates a JsonML element, the format in which devtools custom formatters describe how to display a value.
+fn element(tag: &str, style: &str, children: &[JsValue]) -> JsValue {
+    let element = Array::of2(&tag.into(), &Object::new());
+    if !style.is_empty() {
+        let attributes = element.get(1);
+        Reflect::set(&attributes, &"style".into(), &style.into())
+            .expect("setting a property on an object should not fail");
+    }
+    for child in children {
+        element.push(child);
+    }
+    element.into()
+}
+
+/// Shortens a hex encoded field element by dropping its leading zeros.
+fn trim_value(value: &str) -> String {
+    match value.strip_prefix("0x") {
+        Some(digits) => match digits.trim_start_matches('0') {
+            "" => "0x0".to_owned(),
+            digits => format!("0x{digits}"),
+        },
+        None => value.to_owned(),
+    }
+}
+
+/// Displays `inspection` as a JsonML table.
+fn inspection_table(inspection: &WitnessInspection) -> JsValue {
+    let cell_style = "padding: 0 8px";
+    let mut rows: Vec<JsValue> = inspection
+        .rows
+        .iter()
+        .map(|row| {
+            let witness = format!("_{}", row.witness);
+            let label = row.label.clone().unwrap_or_default();
+            element(
+                "tr",
+                "",
+                &[
+                    element("td", cell_style, &[witness.into()]),
+                    element("td", cell_style, &[label.into()]),
+                    element("td", cell_style, &[trim_value(&row.value).into()]),
+                ],
+            )
+        })
+        .collect();
+    if inspection.truncated {
+        let remaining = format!("… {} more", inspection.size as usize - inspection.rows.len());
+        rows.push(element("tr", "", &[element("td", cell_style, &[remaining.into()])]));
+    }
+    element("table", "", &rows)
+}
+
+/// Installs a custom formatter which displays witness maps in browser devtools as a truncated table, rather than
+/// as a `Map` with every entry expanded.
+///
+/// Custom formatters must be enabled in the devtools settings before they're used.
+///
+/// @param {WitnessInspectionOptions} options - Controls how many witnesses are displayed and how they're labelled.
+#[wasm_bindgen(js_name = installWitnessFormatter, skip_jsdoc)]
+pub fn install_witness_formatter(
+    options: Option<JsWitnessInspectionOptions>,
+) -> Result<(), JsString> {
+    console_error_panic_hook::set_once();
+    let options = std::rc::Rc::new(WitnessInspectionOptions::try_from(options)?);
+
+    let header = Closure::<dyn Fn(JsValue) -> JsValue>::new(|value: JsValue| {
+        let Some(map) = is_witness_map(&value) else {
+            return JsValue::NULL;
+        };
+        element("span", "", &[format!("WitnessMap({})", map.size()).into()])
+    });
+    let has_body =
+        Closure::<dyn Fn(JsValue) -> bool>::new(|value: JsValue| is_witness_map(&value).is_some());
+    let body = Closure::<dyn Fn(JsValue) -> JsValue>::new(move |value: JsValue| {
+        let Some(map) = is_witness_map(&value) else {
+            return JsValue::NULL;
+        };
+        inspection_table(&inspect(map, &options))
+    });
+
+    let formatter = Object::new();
+    for (key, function) in [
+        ("header", header.into_js_value()),
+        ("hasBody", has_body.into_js_value()),
+        ("body", body.into_js_value()),
+    ] {
+        Reflect::set(&formatter, &key.into(), &function)
+            .expect("setting a property on an object should not fail");
+    }
+
+    let global = js_sys::global();
+    let formatters = Reflect::get(&global, &"devtoolsFormatters".into())
+        .map_err(|_| "Could not read devtoolsFormatters")?;
+    let formatters: Array = match formatters.dyn_into() {
+        Ok(formatters) => formatters,
+        Err(_) => {
+            let formatters = Array::new();
+            Reflect::set(&global, &"devtoolsFormatters".into(), &formatters)
+                .map_err(|_| "Could not set devtoolsFormatters")?;
+            formatters
+        }
+    };
+    formatters.push(&formatter);
+    Ok(())
+}
@@ -2,13 +2,19 @@ use acvm::{
     acir::native_types::{Witness, WitnessMap},
     FieldElement,
 };
-use js_sys::{JsString, Map};
-use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+use js_sys::{Array, JsString, Map, Object};
+use wasm_bindgen::{
+    prelude::{wasm_bindgen, JsValue},
+    JsCast,
+};
 
 #[wasm_bindgen(typescript_custom_section)]
 const WITNESS_MAP: &'static str = r#"
 // Map from witness index to hex string value of witness.
 export type WitnessMap = Map<number, string>;
+
+// A witness map, or a plain object or array of `[index, value]` pairs which can be converted into one.
+export type WitnessMapLike = WitnessMap | Record<number, string> | [number, string][];
 "#;
 
 // WitnessMap
@@ -21,6 +27,9 @@ extern "C" {
     #[wasm_bindgen(constructor, js_class = "Map")]
     pub fn new() -> JsWitnessMap;
 
+    #[wasm_bindgen(typescript_type = "WitnessMapLike")]
+    #[derive(Clone, Debug)]
+    pub type JsWitnessMapLike;
 }
 
 impl Default for JsWitnessMap {
@@ -54,6 +63,50 @@ impl From<JsWitnessMap> for WitnessMap {
     }
 }
 
+impl TryFrom<JsWitnessMapLike> for WitnessMap {
+    type Error = JsString;
+
+    fn try_from(value: JsWitnessMapLike) -> Result<Self, Self::Error> {
+        let value = JsValue::from(value);
+        let entries: Array = if value.is_instance_of::<Map>() {
+            Array::from(&value)
+        } else if Array::is_array(&value) {
+            value.unchecked_into()
+        } else if value.is_object() {
+            Object::entries(value.unchecked_ref())
+        } else {
+            return Err(
+                "witness map must be a Map, an object or an array of [index, value] pairs".into()
+            );
+        };
+
+        let mut witness_map = WitnessMap::new();
+        for entry in entries.iter() {
+            let entry: Array =
+                entry.dyn_into().map_err(|_| "witness map entries must be [index, value] pairs")?;
+            let witness = js_value_to_witness(&entry.get(0))?;
+            let witness_value = js_value_to_field_element(entry.get(1))?;
+            witness_map.insert(witness, witness_value);
+        }
+        Ok(witness_map)
+    }
+}
+
+/// Parses a witness index from either a number or a numeric string, as used for the keys of plain objects.
+fn js_value_to_witness(js_value: &JsValue) -> Result<Witness, JsString> {
+    let index = match js_value.as_string() {
+        Some(index) => index.parse::<f64>().ok(),
+        None => js_value.as_f64(),
+    };
+
+    match index {
+        Some(index) if index.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(&index) => {
+            Ok(Witness(index as u32))
+        }
+        _ => Err(format!("Invalid witness index: {:?}", js_value).into()),
+    }
+}
+
 pub(crate) fn js_value_to_field_element(js_value: JsValue) -> Result<FieldElement, JsString> {
     let hex_str = js_value.as_string().ok_or("failed to parse field element from non-string")?;
 
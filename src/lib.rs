@@ -1,18 +1,82 @@
 #![warn(unused_crate_dependencies, unused_extern_crates)]
 #![warn(unreachable_pub)]
 
+mod backend;
 mod barretenberg;
+mod base_witness;
+mod batch;
 mod build_info;
+mod cache;
+mod chunked;
+mod codec;
+mod compact_witness;
 mod compression;
+mod config;
+mod context;
+mod curves;
+mod debug_session;
+mod estimate;
+mod eth_abi;
 mod execute;
 mod foreign_call;
+mod inspect;
 mod js_witness_map;
 mod logging;
+mod noir_abi;
+mod opcode;
+mod pedersen;
+mod prover_toml;
 mod public_witness;
+mod schema;
+mod testing;
+mod verifier_toml;
+mod witness_ops;
+mod worker;
 
+pub use backend::{estimate_gates, get_verification_key, prove, verify_proof};
+pub use base_witness::BaseWitness;
+pub use batch::{compare_executions, execute_circuit_batch};
 pub use build_info::build_info;
-pub use compression::{compress_witness, decompress_witness};
-pub use execute::execute_circuit;
+pub use chunked::execute_circuit_chunked;
+pub use codec::{bytes_to_fields, field_from_bytes, field_to_bytes, fields_to_bytes};
+pub use compact_witness::CompactWitness;
+pub use compression::{
+    compress_bytes, compress_witness, compress_witness_stack, decompress_bytes, decompress_witness,
+    decompress_witness_stack,
+};
+pub use config::configure_simulator;
+pub use context::ExecutionContext;
+pub use curves::{
+    curve_add, curve_generator, curve_is_in_subgroup, curve_is_on_curve, curve_point_from_bytes,
+    curve_point_to_bytes, curve_scalar_mul,
+};
+pub use debug_session::DebugSession;
+pub use estimate::{estimate_circuit_cost, estimate_execution_memory};
+pub use eth_abi::{public_inputs_to_eth_abi, witness_from_eth_abi};
+pub use execute::{
+    analyze_blackbox_support, analyze_witness_requirements, end_profiling_session, execute_circuit,
+    execute_circuit_sync, execute_circuit_with_stats, export_trace, generate_execution_report,
+    import_trace, parse_circuit_streaming, profile_execution, record_execution, replay_execution,
+    simulate_and_prove, start_profiling_session, verify_witness,
+};
+pub use foreign_call::{
+    create_foreign_call_dispatcher, create_signing_oracle, verify_signed_artifact,
+};
+pub use inspect::{inspect_witness, install_witness_formatter};
 pub use js_witness_map::JsWitnessMap;
 pub use logging::{init_log_level, LogLevel};
-pub use public_witness::{get_public_parameters_witness, get_public_witness, get_return_witness};
+pub use noir_abi::{
+    abi_decode, abi_decode_return_value, abi_encode, abi_hash, abi_to_ts_type, abi_validate_inputs,
+};
+pub use opcode::{evaluate_expression, execute_brillig, solve_opcode};
+pub use pedersen::pedersen_commit;
+pub use prover_toml::abi_encode_from_toml;
+pub use public_witness::{
+    check_public_inputs, extract_private_inputs, extract_public_inputs,
+    get_public_parameters_witness, get_public_witness, get_return_witness, split_public_witness,
+};
+pub use schema::{get_schema, validate};
+pub use testing::Testing;
+pub use verifier_toml::abi_decode_to_toml;
+pub use witness_ops::{map_witness_values, witness_sketch};
+pub use worker::{serve_execution_worker, WorkerExecutor};
@@ -1,8 +1,10 @@
 use js_sys::JsString;
-use log::Level;
+use log::{Level, LevelFilter, Log, Metadata, Record};
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
+use crate::config::with_config;
+
 #[wasm_bindgen(typescript_custom_section)]
 const LOG_LEVEL: &'static str = r#"
 export type LogLevel = "OFF" | "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE";
@@ -12,6 +14,18 @@ export type LogLevel = "OFF" | "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE";
 extern "C" {
     #[wasm_bindgen(extends = JsString, typescript_type = "LogLevel")]
     pub type LogLevel;
+
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = info)]
+    fn console_info(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn console_debug(message: &str);
 }
 
 /// Sets the package's logging level.
@@ -19,13 +33,59 @@ extern "C" {
 /// @param {LogLevel} level - The maximum level of logging to be emitted.
 #[wasm_bindgen(js_name = initLogLevel, skip_jsdoc)]
 pub fn init_log_level(level: LogLevel) {
-    // Set the static variable from Rust
+    let log_level = level.as_string().unwrap();
+    let log_level = LevelFilter::from_str(&log_level).unwrap_or(LevelFilter::Error);
+    init_logging();
+    log::set_max_level(log_level);
+}
+
+/// Forwards log records to the configured `logSink`, or to the console if there is none.
+struct PackageLogger;
+
+impl Log for PackageLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = record.args().to_string();
+        match with_config(|config| config.log_sink.clone()) {
+            Some(log_sink) => {
+                // A sink which throws shouldn't interrupt execution.
+                let _ = log_sink.call2(
+                    &JsValue::NULL,
+                    &JsValue::from(record.level().as_str()),
+                    &JsValue::from(message),
+                );
+            }
+            None => {
+                let message = format!("{} {}: {message}", record.level(), record.target());
+                match record.level() {
+                    Level::Error => console_error(&message),
+                    Level::Warn => console_warn(&message),
+                    Level::Info => console_info(&message),
+                    Level::Debug | Level::Trace => console_debug(&message),
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: PackageLogger = PackageLogger;
+
+/// Installs the package's logger, which emits errors until a level is set. Later calls have no effect.
+pub(crate) fn init_logging() {
     use std::sync::Once;
 
-    let log_level = level.as_string().unwrap();
-    let log_level = Level::from_str(&log_level).unwrap_or(Level::Error);
-    static SET_HOOK: Once = Once::new();
-    SET_HOOK.call_once(|| {
-        wasm_logger::init(wasm_logger::Config::new(log_level));
+    static SET_LOGGER: Once = Once::new();
+    SET_LOGGER.call_once(|| {
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(LevelFilter::Error);
+        }
     });
 }
@@ -0,0 +1,945 @@
+use std::collections::BTreeMap;
+
+use acvm::{
+    acir::native_types::{Witness, WitnessMap},
+    blackbox_solver::sha256,
+    FieldElement,
+};
+use ark_ff::PrimeField;
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, BigInt, JsString, Object, Reflect, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{js_witness_map::field_element_to_js_string, JsWitnessMap};
+
+#[wasm_bindgen(typescript_custom_section)]
+const NOIR_ABI: &'static str = r#"
+/**
+* @typedef {Object} NoirAbi - The ABI of a Noir program, as emitted by the Noir compiler.
+*/
+export type NoirAbi = {
+  parameters: { name: string; type: NoirAbiType; visibility: AbiVisibility }[];
+  param_witnesses: Record<string, number[]>;
+  return_type: NoirAbiType | null;
+  return_witnesses: number[];
+}
+
+// The visibility of a parameter within a `NoirAbi`. `call_data` and `return_data` parameters are passed through the
+// databus, which newer Noir compilers mark as `databus`, and are neither public nor private inputs.
+export type AbiVisibility = "public" | "private" | "databus" | "call_data" | "return_data";
+
+// The type of a parameter or return value within a `NoirAbi`.
+export type NoirAbiType =
+  | { kind: "field" }
+  | { kind: "boolean" }
+  | { kind: "integer"; sign: "unsigned" | "signed"; width: number }
+  | { kind: "array"; length: number; type: NoirAbiType }
+  | { kind: "string"; length: number }
+  | { kind: "struct"; path: string; fields: [string, NoirAbiType][] }
+  | { kind: "tuple"; fields: NoirAbiType[] };
+
+// The value of a single `NoirAbi` parameter. Fields and integers are given as 0x-prefixed hex strings, decimal strings,
+// numbers or bigints and tuples as arrays. Numbers must not exceed 2^53 - 1, and negative signed integers are given
+// either as decimal strings, numbers or bigints or as hex strings in two's complement. Byte arrays may also be given as
+// a `Uint8Array`.
+export type InputValue =
+  | string
+  | number
+  | bigint
+  | boolean
+  | Uint8Array
+  | InputValue[]
+  | { [field: string]: InputValue };
+
+// A value decoded from a witness. Fields and integers take the form requested by `AbiDecodeOptions`, with negative
+// signed integers decoded as negative decimal strings or bigints and otherwise left in two's complement.
+export type DecodedValue =
+  | string
+  | bigint
+  | boolean
+  | Uint8Array
+  | DecodedValue[]
+  | { [field: string]: DecodedValue };
+
+/**
+* @typedef {Object} DecodedAbi - The parameters and return value of a Noir program, decoded from its witness.
+* @property {Record<string, DecodedValue>} inputs - The value of each of the program's parameters, keyed by name.
+* @property {DecodedValue | null} returnValue - The value returned by the program, or `null` if it doesn't return anything.
+*/
+export type DecodedAbi = {
+  inputs: Record<string, DecodedValue>;
+  returnValue: DecodedValue | null;
+}
+
+/**
+* The form in which decoded field elements and integers are returned.
+* - `hex` is a 0x-prefixed, zero padded hex string, as used in witness maps.
+* - `decimal` is a decimal string.
+* - `bytes` is a 32 byte big-endian `Uint8Array`.
+* - `bigint` is a `BigInt`.
+*/
+export type FieldFormat = "hex" | "decimal" | "bytes" | "bigint";
+
+/**
+* @typedef {Object} AbiDecodeOptions - Options which modify how values are decoded.
+* @property {FieldFormat} fieldFormat - The form in which field elements and integers are returned. Defaults to `"hex"`.
+* @property {boolean} strict - Whether to throw if an integer doesn't fit within its declared width or a boolean isn't
+* zero or one, rather than decoding it anyway. Defaults to `false`.
+*/
+export type AbiDecodeOptions = {
+  fieldFormat?: FieldFormat;
+  strict?: boolean;
+}
+
+/**
+* @typedef {Object} AbiValidationError - A problem with the value provided for a parameter.
+* @property {string} path - The location of the invalid value, e.g. `"x"`, `"x[2]"` or `"x.y"`.
+* @property {string} expectedType - The Noir type expected at `path`, e.g. `"u8"` or `"[Field; 2]"`.
+* @property {string} kind - `"missing"` if no value was given, `"overflow"` if it is outside of the range of its type,
+* otherwise `"invalid"`.
+* @property {string} message - A description of what is wrong with the value.
+*/
+export type AbiValidationError = {
+  path: string;
+  expectedType: string;
+  kind: "missing" | "invalid" | "overflow";
+  message: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "NoirAbi")]
+    pub type JsNoirAbi;
+
+    #[wasm_bindgen(typescript_type = "Record<string, InputValue>")]
+    pub type JsInputMap;
+
+    #[wasm_bindgen(extends = Array, typescript_type = "AbiValidationError[]")]
+    pub type JsAbiValidationErrors;
+
+    #[wasm_bindgen(typescript_type = "DecodedValue | null")]
+    pub type JsReturnValue;
+
+    #[wasm_bindgen(typescript_type = "AbiDecodeOptions")]
+    pub type JsAbiDecodeOptions;
+
+    #[wasm_bindgen(typescript_type = "DecodedAbi")]
+    pub type JsDecodedAbi;
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub(crate) enum AbiType {
+    Field,
+    Boolean,
+    Integer {
+        sign: String,
+        width: u32,
+    },
+    Array {
+        length: u32,
+        #[serde(rename = "type")]
+        typ: Box<AbiType>,
+    },
+    String {
+        length: u32,
+    },
+    Struct {
+        path: String,
+        fields: Vec<(String, AbiType)>,
+    },
+    Tuple {
+        fields: Vec<AbiType>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AbiVisibility {
+    Public,
+    Private,
+    #[serde(alias = "databus")]
+    CallData,
+    ReturnData,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AbiParameter {
+    pub(crate) name: String,
+    #[serde(rename = "type")]
+    pub(crate) typ: AbiType,
+    pub(crate) visibility: AbiVisibility,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Abi {
+    pub(crate) parameters: Vec<AbiParameter>,
+    pub(crate) param_witnesses: BTreeMap<String, Vec<u32>>,
+    pub(crate) return_type: Option<AbiType>,
+    #[serde(default)]
+    pub(crate) return_witnesses: Vec<u32>,
+}
+
+impl TryFrom<JsNoirAbi> for Abi {
+    type Error = JsString;
+
+    fn try_from(abi: JsNoirAbi) -> Result<Self, Self::Error> {
+        <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(abi))
+            .map_err(|err| format!("Invalid ABI: {err}").into())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum FieldFormat {
+    #[default]
+    Hex,
+    Decimal,
+    Bytes,
+    Bigint,
+}
+
+impl FieldFormat {
+    fn format(self, value: &FieldElement) -> JsValue {
+        match self {
+            FieldFormat::Hex => field_element_to_js_string(value).into(),
+            FieldFormat::Decimal => JsValue::from(value.into_repr().into_bigint().to_string()),
+            FieldFormat::Bytes => Uint8Array::from(value.to_be_bytes().as_slice()).into(),
+            FieldFormat::Bigint => bigint(&value.into_repr().into_bigint().to_string()),
+        }
+    }
+
+    /// Formats a signed integer of `width` bits held in two's complement, as a negative number if the format allows.
+    fn format_signed(self, value: &FieldElement, width: u32) -> JsValue {
+        if width == 0 || value.num_bits() != width {
+            return self.format(value);
+        }
+        let modulus = FieldElement::from(2_u128).pow(&FieldElement::from(width as u128));
+        let decimal = format!("-{}", (modulus - *value).into_repr().into_bigint());
+        match self {
+            FieldFormat::Decimal => JsValue::from(decimal),
+            FieldFormat::Bigint => bigint(&decimal),
+            FieldFormat::Hex | FieldFormat::Bytes => self.format(value),
+        }
+    }
+}
+
+/// Creates a JS `BigInt` from a decimal string.
+fn bigint(decimal: &str) -> JsValue {
+    BigInt::new(&JsValue::from_str(decimal))
+        .expect("a decimal string should convert to a `BigInt`")
+        .into()
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiDecodeOptions {
+    #[serde(default)]
+    field_format: FieldFormat,
+    #[serde(default)]
+    strict: bool,
+}
+
+/// The kind of problem with an input value.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum AbiErrorKind {
+    Missing,
+    Invalid,
+    Overflow,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiValidationError {
+    path: String,
+    expected_type: String,
+    kind: AbiErrorKind,
+    message: String,
+}
+
+/// A problem with an input value, before the path to the value is known.
+struct InputError {
+    kind: AbiErrorKind,
+    message: String,
+}
+
+impl InputError {
+    fn overflow(message: String) -> InputError {
+        InputError { kind: AbiErrorKind::Overflow, message }
+    }
+}
+
+impl From<String> for InputError {
+    fn from(message: String) -> Self {
+        InputError { kind: AbiErrorKind::Invalid, message }
+    }
+}
+
+/// Renders `typ` as it would be written in Noir source.
+fn noir_type(typ: &AbiType) -> String {
+    match typ {
+        AbiType::Field => "Field".to_owned(),
+        AbiType::Boolean => "bool".to_owned(),
+        AbiType::Integer { sign, width } => {
+            format!("{}{width}", if sign == "signed" { "i" } else { "u" })
+        }
+        AbiType::Array { length, typ } => format!("[{}; {length}]", noir_type(typ)),
+        AbiType::String { length } => format!("str<{length}>"),
+        AbiType::Struct { path, .. } => path.clone(),
+        AbiType::Tuple { fields } => {
+            let fields: Vec<String> = fields.iter().map(noir_type).collect();
+            format!("({})", fields.join(", "))
+        }
+    }
+}
+
+/// Returns whether `typ` is a `u8`.
+fn is_byte(typ: &AbiType) -> bool {
+    matches!(typ, AbiType::Integer { sign, width: 8 } if sign == "unsigned")
+}
+
+/// The largest integer which a JS number holds exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Reads an integer from a JS number, rejecting those which may have lost precision.
+fn read_number(number: f64) -> Result<i64, String> {
+    if number.fract() != 0.0 {
+        return Err(format!("Expected an integer but got {number}."));
+    }
+    if number.abs() > MAX_SAFE_INTEGER {
+        return Err(format!(
+            "{number} is larger than 2^53 - 1 so may have lost precision. Pass it as a hex string or bigint instead."
+        ));
+    }
+    Ok(number as i64)
+}
+
+/// Parses a hex string, with or without a `0x` prefix, returning `None` if it isn't valid hex.
+///
+/// Values which don't fit within the field are rejected rather than reduced modulo its order.
+fn parse_hex(hex: &str) -> Option<Result<FieldElement, InputError>> {
+    let field = FieldElement::from_hex(hex)?;
+    let digits = hex.strip_prefix("0x").unwrap_or(hex).trim_start_matches('0');
+    Some(match field.to_hex().trim_start_matches('0').eq_ignore_ascii_case(digits) {
+        true => Ok(field),
+        false => Err(InputError::overflow("Value does not fit within a field element.".to_owned())),
+    })
+}
+
+/// Reads a JS `BigInt` as whether it is negative and a field element holding its magnitude.
+fn read_bigint(value: &JsValue) -> Result<(bool, FieldElement), InputError> {
+    let hex =
+        String::from(value.unchecked_ref::<BigInt>().to_string(16).expect("16 is a valid radix"));
+    let (is_negative, digits) = match hex.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, hex.as_str()),
+    };
+    // `FieldElement::from_hex` only accepts whole bytes.
+    let digits = if digits.len() % 2 == 1 { format!("0{digits}") } else { digits.to_owned() };
+    let magnitude = parse_hex(&digits).expect("a `BigInt` should always format as valid hex")?;
+    Ok((is_negative, magnitude))
+}
+
+/// Reads a decimal string as whether it is negative and a field element holding its magnitude, returning `None` if it
+/// isn't a decimal integer.
+fn read_decimal(decimal: &str) -> Option<Result<(bool, FieldElement), InputError>> {
+    let digits = decimal.strip_prefix('-').unwrap_or(decimal);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let bigint = BigInt::new(&JsValue::from_str(decimal)).ok()?;
+    Some(read_bigint(&JsValue::from(bigint)))
+}
+
+/// Reads a field element from a 0x-prefixed hex string, a decimal string or a non-negative integer number or `BigInt`,
+/// describing the value as `expected`.
+///
+/// As with `nargo`, strings without a `0x` prefix are read as decimal.
+fn read_field(value: &JsValue, expected: &str) -> Result<FieldElement, InputError> {
+    if let Some(number) = value.as_f64() {
+        return match read_number(number)? {
+            number if number >= 0 => Ok(FieldElement::from(number as u128)),
+            _ => Err("Expected a non-negative number.".to_owned().into()),
+        };
+    }
+    if value.is_bigint() {
+        return match read_bigint(value)? {
+            (false, magnitude) => Ok(magnitude),
+            (true, _) => Err("Expected a non-negative number.".to_owned().into()),
+        };
+    }
+    let string = value.as_string().unwrap_or_default();
+    let field = match string.starts_with("0x") {
+        true => parse_hex(&string),
+        false => read_decimal(&string).map(|decimal| match decimal {
+            Ok((false, magnitude)) => Ok(magnitude),
+            Ok((true, _)) => Err("Expected a non-negative number.".to_owned().into()),
+            Err(err) => Err(err),
+        }),
+    };
+    field.unwrap_or_else(|| {
+        Err(format!("Expected {expected} as a hex or decimal string, number or bigint.").into())
+    })
+}
+
+/// Reads a boolean, which may also be given as a field element of zero or one.
+fn read_boolean(value: &JsValue) -> Result<FieldElement, InputError> {
+    match value.as_bool() {
+        Some(boolean) => Ok(FieldElement::from(boolean)),
+        None => read_field(value, "a boolean")
+            .ok()
+            .filter(|value| value.num_bits() <= 1)
+            .ok_or_else(|| "Expected a boolean.".to_owned().into()),
+    }
+}
+
+/// Reads an integer of `width` bits, encoding negative numbers and bigints in two's complement if it is signed.
+///
+/// Signed integers given as hex strings are taken to already be in two's complement form, while decimal strings may be
+/// negative. Values outside of the range of the type are reported as an overflow rather than wrapped.
+fn read_integer(sign: &str, width: u32, value: &JsValue) -> Result<FieldElement, InputError> {
+    let is_hex = value.as_string().map_or(false, |string| string.starts_with("0x"));
+    let decimal = value.as_string().filter(|_| !is_hex).and_then(|string| read_decimal(&string));
+    let (is_negative, magnitude) = match (value.as_f64(), decimal) {
+        (Some(number), _) => {
+            let number = read_number(number)?;
+            (number < 0, FieldElement::from(number.unsigned_abs() as u128))
+        }
+        (None, Some(decimal)) => decimal?,
+        (None, None) if value.is_bigint() => read_bigint(value)?,
+        (None, None) => (false, read_field(value, "an integer")?),
+    };
+
+    if sign == "signed" && !is_hex {
+        let width = width.max(1);
+        let bound = FieldElement::from(2_u128).pow(&FieldElement::from(width as u128 - 1));
+        if magnitude.num_bits() >= width && !(is_negative && magnitude == bound) {
+            let bound = 1_u128 << (width.min(128) - 1);
+            return Err(InputError::overflow(format!(
+                "Value does not fit within i{width}, which ranges from -{bound} to {}.",
+                bound - 1
+            )));
+        }
+        return Ok(match is_negative {
+            true => FieldElement::from(2_u128).pow(&FieldElement::from(width as u128)) - magnitude,
+            false => magnitude,
+        });
+    }
+    if is_negative {
+        return Err(InputError::overflow(format!(
+            "Value does not fit within u{width}, which can't be negative."
+        )));
+    }
+    if magnitude.num_bits() > width {
+        return Err(InputError::overflow(format!("Value does not fit within {width} bits.")));
+    }
+    Ok(magnitude)
+}
+
+/// Reads a value of the scalar type `typ`: a field element, boolean or integer.
+fn read_scalar(typ: &AbiType, value: &JsValue) -> Result<FieldElement, InputError> {
+    match typ {
+        AbiType::Field => read_field(value, "a field element"),
+        AbiType::Boolean => read_boolean(value),
+        AbiType::Integer { sign, width } => read_integer(sign, *width, value),
+        _ => Err(format!("{} is not a field element, boolean or integer.", noir_type(typ)).into()),
+    }
+}
+
+/// Appends `error`, a problem with the value of type `typ` at `path`, to `errors`.
+fn push_error(errors: &mut Vec<AbiValidationError>, path: &str, typ: &AbiType, error: InputError) {
+    errors.push(AbiValidationError {
+        path: path.to_owned(),
+        expected_type: noir_type(typ),
+        kind: error.kind,
+        message: error.message,
+    });
+}
+
+/// Appends the field elements encoding `value` as a value of type `typ` to `encoded`, in declaration order, and an
+/// error to `errors` for each problem with it.
+fn encode_value(
+    path: &str,
+    typ: &AbiType,
+    value: &JsValue,
+    encoded: &mut Vec<FieldElement>,
+    errors: &mut Vec<AbiValidationError>,
+) {
+    let mut error = |message: String| push_error(errors, path, typ, message.into());
+    match typ {
+        AbiType::Field | AbiType::Boolean | AbiType::Integer { .. } => {
+            match read_scalar(typ, value) {
+                Ok(field) => encoded.push(field),
+                Err(err) => push_error(errors, path, typ, err),
+            }
+        }
+        // Byte arrays are naturally held in a `Uint8Array`, whose elements always fit within a byte.
+        AbiType::Array { length, typ } if is_byte(typ) && value.is_instance_of::<Uint8Array>() => {
+            let bytes: &Uint8Array = value.unchecked_ref();
+            if bytes.length() != *length {
+                error(format!("Expected {length} bytes but got {}.", bytes.length()));
+            }
+            encoded.extend(bytes.to_vec().into_iter().map(|byte| FieldElement::from(byte as u128)));
+        }
+        AbiType::Array { length, typ } => match value.dyn_ref::<Array>() {
+            Some(array) if array.length() != *length => {
+                error(format!(
+                    "Expected an array of {length} elements but got {}.",
+                    array.length()
+                ));
+            }
+            Some(array) => {
+                for (index, element) in array.iter().enumerate() {
+                    encode_value(&format!("{path}[{index}]"), typ, &element, encoded, errors);
+                }
+            }
+            None => error(format!("Expected an array of {length} elements.")),
+        },
+        AbiType::String { length } => match value.as_string() {
+            Some(string) if string.len() != *length as usize => {
+                error(format!("Expected a string of {length} bytes but got {}.", string.len()));
+            }
+            Some(string) => {
+                encoded.extend(string.bytes().map(|byte| FieldElement::from(byte as u128)));
+            }
+            None => error(format!("Expected a string of {length} bytes.")),
+        },
+        AbiType::Struct { fields, .. } => {
+            if !value.is_object() || value.is_instance_of::<Array>() {
+                error("Expected an object.".to_owned());
+                return;
+            }
+            for (name, typ) in fields {
+                encode_field(&format!("{path}.{name}"), name, typ, value, encoded, errors);
+            }
+        }
+        AbiType::Tuple { fields } => match value.dyn_ref::<Array>() {
+            Some(array) if array.length() as usize != fields.len() => {
+                error(format!(
+                    "Expected a tuple of {} elements but got {}.",
+                    fields.len(),
+                    array.length()
+                ));
+            }
+            Some(array) => {
+                for (index, (typ, element)) in fields.iter().zip(array.iter()).enumerate() {
+                    encode_value(&format!("{path}[{index}]"), typ, &element, encoded, errors);
+                }
+            }
+            None => error(format!("Expected a tuple of {} elements.", fields.len())),
+        },
+    }
+}
+
+/// Encodes the entry `name` of `object` as a value of type `typ`, reporting it as missing if it isn't present.
+fn encode_field(
+    path: &str,
+    name: &str,
+    typ: &AbiType,
+    object: &JsValue,
+    encoded: &mut Vec<FieldElement>,
+    errors: &mut Vec<AbiValidationError>,
+) {
+    let value = Reflect::get(object, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED);
+    if value.is_undefined() {
+        let missing =
+            InputError { kind: AbiErrorKind::Missing, message: "Missing value.".to_owned() };
+        push_error(errors, path, typ, missing);
+    } else {
+        encode_value(path, typ, &value, encoded, errors);
+    }
+}
+
+/// Checks a set of inputs against the parameters of a Noir program's ABI without encoding them into a witness.
+///
+/// This is cheap enough to run on every change to a form, reporting every problem at once rather than throwing on
+/// the first.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @param {Record<string, InputValue>} inputs - The value of each of the program's parameters, keyed by name.
+/// @returns {AbiValidationError[]} The problems found with `inputs`, which is empty if they are valid.
+#[wasm_bindgen(js_name = abiValidateInputs, skip_jsdoc)]
+pub fn abi_validate_inputs(
+    abi: JsNoirAbi,
+    inputs: JsInputMap,
+) -> Result<JsAbiValidationErrors, JsString> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+    let inputs: JsValue = inputs.into();
+    if !inputs.is_object() {
+        return Err("Inputs must be an object.".into());
+    }
+
+    let mut errors = Vec::new();
+    for parameter in &abi.parameters {
+        let name = &parameter.name;
+        encode_field(name, name, &parameter.typ, &inputs, &mut Vec::new(), &mut errors);
+    }
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&errors).unwrap().into())
+}
+
+/// Builds the `AbiEncodingError` thrown when `errors` were found with the inputs to `abiEncode`.
+fn encoding_error(errors: &[AbiValidationError]) -> JsValue {
+    let messages: Vec<String> =
+        errors.iter().map(|error| format!("{}: {}", error.path, error.message)).collect();
+    let error = js_sys::Error::new(&format!("Invalid inputs: {}", messages.join(" ")));
+    error.set_name("AbiEncodingError");
+    Reflect::set(
+        &error,
+        &"errors".into(),
+        &<JsValue as JsValueSerdeExt>::from_serde(errors).unwrap(),
+    )
+    .expect("setting a property on an `Error` should not fail");
+    error.into()
+}
+
+/// Encodes a set of inputs into the initial witness of a Noir program's circuit.
+///
+/// Structs, arrays and tuples are flattened into field elements in declaration order, however deeply they are nested.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @param {Record<string, InputValue>} inputs - The value of each of the program's parameters, keyed by name.
+/// @param {Record<string, InputValue>} defaults - Optional values for parameters which are missing from `inputs`.
+/// @returns {WitnessMap} The initial witness of the program's circuit.
+/// @throws {Error} An `AbiEncodingError` listing each problem with `inputs` in its `errors` property, if any are
+/// invalid.
+#[wasm_bindgen(js_name = abiEncode, skip_jsdoc)]
+pub fn abi_encode(
+    abi: JsNoirAbi,
+    inputs: JsInputMap,
+    defaults: Option<JsInputMap>,
+) -> Result<JsWitnessMap, JsValue> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+    let inputs: JsValue = inputs.into();
+    if !inputs.is_object() {
+        return Err("Inputs must be an object.".into());
+    }
+    let inputs = match defaults.map(JsValue::from) {
+        Some(defaults) if !defaults.is_object() => return Err("Defaults must be an object.".into()),
+        Some(defaults) => with_defaults(&abi, &inputs, &defaults),
+        None => inputs,
+    };
+    Ok(encode_inputs(&abi, &inputs)?.into())
+}
+
+/// Fills in each parameter of `abi` which is missing from `inputs` with its value in `defaults`, if it has one.
+///
+/// Only whole parameters are filled in, so a parameter given in `inputs` is never merged with its default.
+fn with_defaults(abi: &Abi, inputs: &JsValue, defaults: &JsValue) -> JsValue {
+    let merged = Object::new();
+    for parameter in &abi.parameters {
+        let name = JsValue::from_str(&parameter.name);
+        let value = Reflect::get(inputs, &name)
+            .ok()
+            .filter(|value| !value.is_undefined())
+            .or_else(|| Reflect::get(defaults, &name).ok())
+            .unwrap_or(JsValue::UNDEFINED);
+        Reflect::set(&merged, &name, &value)
+            .expect("setting a property on an `Object` should not fail");
+    }
+    merged.into()
+}
+
+/// Encodes `inputs`, an object holding the value of each parameter of `abi`, into the initial witness of its circuit.
+///
+/// Invalid inputs are reported as an `AbiEncodingError`.
+pub(crate) fn encode_inputs(abi: &Abi, inputs: &JsValue) -> Result<WitnessMap, JsValue> {
+    let mut witness_map = WitnessMap::new();
+    let mut errors = Vec::new();
+    for parameter in &abi.parameters {
+        let name = &parameter.name;
+        let mut encoded = Vec::new();
+        encode_field(name, name, &parameter.typ, inputs, &mut encoded, &mut errors);
+        if !errors.is_empty() {
+            continue;
+        }
+
+        let witnesses = abi
+            .param_witnesses
+            .get(name)
+            .ok_or_else(|| format!("Invalid ABI: no witnesses for parameter {name}"))?;
+        if witnesses.len() != encoded.len() {
+            return Err(format!(
+                "Invalid ABI: parameter {name} has {} witnesses but its value encodes to {} fields",
+                witnesses.len(),
+                encoded.len()
+            )
+            .into());
+        }
+        for (&witness, value) in witnesses.iter().zip(encoded) {
+            witness_map.insert(Witness(witness), value);
+        }
+    }
+    if !errors.is_empty() {
+        return Err(encoding_error(&errors));
+    }
+    Ok(witness_map)
+}
+
+/// Decodes a value of type `typ`, found at `path`, from the field elements at the front of `values`.
+///
+/// In strict mode, integers which don't fit within their width and booleans other than zero or one are rejected.
+fn decode_value(
+    path: &str,
+    typ: &AbiType,
+    values: &mut impl Iterator<Item = FieldElement>,
+    options: &AbiDecodeOptions,
+) -> Result<JsValue, String> {
+    let field_format = options.field_format;
+    let mut next = || values.next().ok_or("Not enough witnesses to decode the value");
+    let out_of_range = |value: &FieldElement| {
+        format!("{path} holds 0x{} which does not fit within {}", value.to_hex(), noir_type(typ))
+    };
+    let value = match typ {
+        AbiType::Integer { sign, width } => {
+            let value = next()?;
+            if options.strict && value.num_bits() > *width {
+                return Err(out_of_range(&value));
+            }
+            match sign == "signed" {
+                true => field_format.format_signed(&value, *width),
+                false => field_format.format(&value),
+            }
+        }
+        AbiType::Field => field_format.format(&next()?),
+        AbiType::Boolean => {
+            let value = next()?;
+            if options.strict && value.num_bits() > 1 {
+                return Err(out_of_range(&value));
+            }
+            JsValue::from_bool(!value.is_zero())
+        }
+        AbiType::Array { length, typ } => {
+            let array = Array::new();
+            for index in 0..*length {
+                array.push(&decode_value(&format!("{path}[{index}]"), typ, values, options)?);
+            }
+            array.into()
+        }
+        AbiType::String { length } => {
+            let bytes = (0..*length)
+                .map(|_| match values.next() {
+                    Some(value) if value.num_bits() <= 8 => Ok(value.to_u128() as u8),
+                    Some(_) => Err("String contains a value which doesn't fit within a byte"),
+                    None => Err("Not enough witnesses to decode the value"),
+                })
+                .collect::<Result<Vec<u8>, _>>()?;
+            let string = String::from_utf8(bytes).map_err(|_| "String is not valid UTF-8")?;
+            JsValue::from(string)
+        }
+        AbiType::Struct { fields, .. } => {
+            let object = Object::new();
+            for (name, typ) in fields {
+                Reflect::set(
+                    &object,
+                    &JsValue::from_str(name),
+                    &decode_value(&format!("{path}.{name}"), typ, values, options)?,
+                )
+                .expect("setting a property on an `Object` should not fail");
+            }
+            object.into()
+        }
+        AbiType::Tuple { fields } => {
+            let array = Array::new();
+            for (index, typ) in fields.iter().enumerate() {
+                array.push(&decode_value(&format!("{path}[{index}]"), typ, values, options)?);
+            }
+            array.into()
+        }
+    };
+    Ok(value)
+}
+
+/// Reads the decode options passed from JS, falling back to the defaults if none were given.
+fn read_decode_options(options: Option<JsAbiDecodeOptions>) -> Result<AbiDecodeOptions, String> {
+    match options {
+        Some(options) => <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(options))
+            .map_err(|err| format!("Invalid decode options: {err}")),
+        None => Ok(AbiDecodeOptions::default()),
+    }
+}
+
+/// Decodes a value of type `typ`, found at `path`, from the values of `witnesses` in `witness_map`.
+fn decode_witnesses(
+    path: &str,
+    typ: &AbiType,
+    witnesses: &[u32],
+    witness_map: &WitnessMap,
+    options: &AbiDecodeOptions,
+) -> Result<JsValue, String> {
+    let mut values = witnesses
+        .iter()
+        .map(|&witness| {
+            witness_map
+                .get(&Witness(witness))
+                .copied()
+                .ok_or_else(|| format!("Witness {witness} not found in witness map."))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter();
+    decode_value(path, typ, &mut values, options)
+}
+
+/// Decodes the parameters and return value of a Noir program from a solved witness.
+///
+/// Structs, arrays and tuples are rebuilt from field elements in declaration order, however deeply they are nested.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @param {WitnessMap} witness_map - The solved witness of the program's circuit.
+/// @param {AbiDecodeOptions} options - Optional settings which modify how values are decoded.
+/// @returns {DecodedAbi} The value of each of the program's parameters, keyed by name, and its return value.
+#[wasm_bindgen(js_name = abiDecode, skip_jsdoc)]
+pub fn abi_decode(
+    abi: JsNoirAbi,
+    witness_map: JsWitnessMap,
+    options: Option<JsAbiDecodeOptions>,
+) -> Result<JsDecodedAbi, JsString> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+    let options = read_decode_options(options)?;
+    let witness_map = WitnessMap::from(witness_map);
+
+    let inputs = Object::new();
+    for parameter in &abi.parameters {
+        let name = &parameter.name;
+        let witnesses = abi
+            .param_witnesses
+            .get(name)
+            .ok_or_else(|| format!("Invalid ABI: no witnesses for parameter {name}"))?;
+        let value = decode_witnesses(name, &parameter.typ, witnesses, &witness_map, &options)
+            .map_err(|err| format!("Failed to decode {name}: {err}"))?;
+        Reflect::set(&inputs, &JsValue::from_str(name), &value)
+            .expect("setting a property on an `Object` should not fail");
+    }
+    let return_value = match &abi.return_type {
+        Some(return_type) => {
+            decode_witnesses("return", return_type, &abi.return_witnesses, &witness_map, &options)
+                .map_err(|err| format!("Failed to decode return value: {err}"))?
+        }
+        None => JsValue::NULL,
+    };
+
+    let decoded = Object::new();
+    Reflect::set(&decoded, &JsValue::from_str("inputs"), &inputs)
+        .expect("setting a property on an `Object` should not fail");
+    Reflect::set(&decoded, &JsValue::from_str("returnValue"), &return_value)
+        .expect("setting a property on an `Object` should not fail");
+    Ok(JsValue::from(decoded).into())
+}
+
+/// Decodes the return value of a Noir program from a solved witness, without decoding any of its parameters.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @param {WitnessMap} witness_map - The solved witness of the program's circuit.
+/// @param {AbiDecodeOptions} options - Optional settings which modify how the return value is decoded.
+/// @returns {DecodedValue | null} The value returned by the program, or `null` if it doesn't return anything.
+#[wasm_bindgen(js_name = abiDecodeReturnValue, skip_jsdoc)]
+pub fn abi_decode_return_value(
+    abi: JsNoirAbi,
+    witness_map: JsWitnessMap,
+    options: Option<JsAbiDecodeOptions>,
+) -> Result<JsReturnValue, JsString> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+    let options = read_decode_options(options)?;
+    let witness_map = WitnessMap::from(witness_map);
+    let Some(return_type) = &abi.return_type else {
+        return Ok(JsValue::NULL.into());
+    };
+
+    let value =
+        decode_witnesses("return", return_type, &abi.return_witnesses, &witness_map, &options)
+            .map_err(|err| format!("Failed to decode return value: {err}"))?;
+    Ok(value.into())
+}
+
+/// Renders `typ` as a TypeScript type, using the types accepted as inputs unless `is_output` is set.
+fn ts_type(typ: &AbiType, is_output: bool) -> String {
+    match typ {
+        AbiType::Field | AbiType::Integer { .. } if is_output => "string".to_owned(),
+        AbiType::Field | AbiType::Integer { .. } => "string | number | bigint".to_owned(),
+        AbiType::Boolean => "boolean".to_owned(),
+        AbiType::String { .. } => "string".to_owned(),
+        AbiType::Array { typ, .. } => {
+            let element = ts_type(typ, is_output);
+            let array = match element.contains(" | ") {
+                true => format!("({element})[]"),
+                false => format!("{element}[]"),
+            };
+            match is_byte(typ) && !is_output {
+                true => format!("{array} | Uint8Array"),
+                false => array,
+            }
+        }
+        AbiType::Struct { fields, .. } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, typ)| format!("{name}: {}", ts_type(typ, is_output)))
+                .collect();
+            format!("{{ {} }}", fields.join("; "))
+        }
+        AbiType::Tuple { fields } => {
+            let fields: Vec<String> = fields.iter().map(|typ| ts_type(typ, is_output)).collect();
+            format!("[{}]", fields.join(", "))
+        }
+    }
+}
+
+/// Generates TypeScript declarations describing the inputs and return value of a Noir program.
+///
+/// The declarations are an `Inputs` interface accepted by `abiValidateInputs` and a `ReturnValue` type matching the
+/// result of `abiDecodeReturnValue`.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @returns {string} TypeScript source declaring the `Inputs` and `ReturnValue` types.
+#[wasm_bindgen(js_name = abiToTsType, skip_jsdoc)]
+pub fn abi_to_ts_type(abi: JsNoirAbi) -> Result<JsString, JsString> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+
+    let mut source = String::from("export interface Inputs {\n");
+    for parameter in &abi.parameters {
+        source.push_str(&format!("  {}: {};\n", parameter.name, ts_type(&parameter.typ, false)));
+    }
+    source.push_str("}\n\n");
+    let return_type = abi.return_type.as_ref().map_or("null".to_owned(), |typ| ts_type(typ, true));
+    source.push_str(&format!("export type ReturnValue = {return_type};\n"));
+    Ok(source.into())
+}
+
+/// The parts of a `NoirAbi` which make up the program's interface, in a form which serializes canonically.
+#[derive(Serialize)]
+struct AbiInterface<'a> {
+    parameters: Vec<(&'a str, &'a AbiType, AbiVisibility)>,
+    return_type: &'a Option<AbiType>,
+}
+
+/// Returns a hash of the interface described by a Noir program's ABI: the name, type and visibility of each parameter
+/// along with the return type.
+///
+/// The hash only changes when the interface does, so it can be used as a key for caching inputs across recompilations.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @returns {string} A hex encoded sha256 hash of the program's interface.
+#[wasm_bindgen(js_name = abiHash, skip_jsdoc)]
+pub fn abi_hash(abi: JsNoirAbi) -> Result<JsString, JsString> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+
+    let interface = AbiInterface {
+        parameters: abi
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.as_str(), &parameter.typ, parameter.visibility))
+            .collect(),
+        return_type: &abi.return_type,
+    };
+    let bytes = serde_json::to_vec(&interface).expect("ABIs should always serialize");
+    let hash = sha256(&bytes).expect("sha256 should not fail");
+    let hex: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+    Ok(format!("0x{hex}").into())
+}
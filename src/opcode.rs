@@ -0,0 +1,177 @@
+use std::io::Read;
+
+use acvm::{
+    acir::{
+        circuit::{brillig::Brillig, Opcode},
+        native_types::{Expression, WitnessMap},
+    },
+    pwg::{get_value, ACVMStatus, OpcodeResolutionError, ACVM},
+};
+use flate2::bufread::GzDecoder;
+use js_sys::{JsString, Object, Reflect};
+use serde::de::DeserializeOwned;
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{
+    execute::SimulatedBackend,
+    foreign_call::{resolve_brillig, ForeignCallHandler},
+    js_witness_map::{field_element_to_js_string, JsWitnessMapLike},
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const OPCODE_RESOLUTION: &'static str = r#"
+export type OpcodeStatus = "solved" | "unsolvable" | "failed" | "requiresForeignCall";
+
+/**
+* @typedef {Object} OpcodeResolution - The result of attempting to solve a single opcode.
+* @property {OpcodeStatus} status - Whether the opcode was solved, is missing inputs, failed or is waiting on a foreign call.
+* @property {WitnessMap} assignedWitnesses - The witnesses which were assigned values while solving the opcode.
+* @property {string} error - A description of why the opcode could not be solved. Only present if `status` is `"unsolvable"` or `"failed"`.
+* @property {string} foreignCall - The name of the foreign call the opcode is waiting on. Only present if `status` is `"requiresForeignCall"`.
+*/
+export type OpcodeResolution = {
+  status: OpcodeStatus;
+  assignedWitnesses: WitnessMap;
+  error?: string;
+  foreignCall?: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, typescript_type = "OpcodeResolution")]
+    pub type JsOpcodeResolution;
+}
+
+/// Deserializes a value from the gzipped bincode format which Nargo uses for circuits.
+fn deserialize_gzipped<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+    let mut serialized = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut serialized).map_err(|err| err.to_string())?;
+    bincode::deserialize(&serialized).map_err(|err| err.to_string())
+}
+
+/// Evaluates a single ACIR arithmetic expression against a witness map.
+///
+/// @param {Uint8Array} expression - A serialized ACIR expression, encoded in the same format as circuits.
+/// @param {WitnessMapLike} witness_map - A witness map holding a value for every witness in `expression`.
+/// @returns {string} The hex encoded value of `expression`.
+#[wasm_bindgen(js_name = evaluateExpression, skip_jsdoc)]
+pub fn evaluate_expression(
+    expression: Vec<u8>,
+    witness_map: JsWitnessMapLike,
+) -> Result<JsString, JsString> {
+    console_error_panic_hook::set_once();
+    let expression: Expression = deserialize_gzipped(&expression)
+        .map_err(|err| format!("Failed to deserialize expression: {err}"))?;
+    let witness_map = WitnessMap::try_from(witness_map)?;
+
+    let value = get_value(&expression, &witness_map).map_err(|err| err.to_string())?;
+    Ok(field_element_to_js_string(&value))
+}
+
+/// Attempts to solve a single ACIR opcode against a witness map.
+///
+/// Foreign calls are not made. If the opcode requires one then the name of the foreign call is returned instead.
+///
+/// @param {Uint8Array} opcode - A serialized ACIR opcode, encoded in the same format as circuits.
+/// @param {WitnessMapLike} witness_map - A witness map holding the values of the opcode's inputs.
+/// @returns {Promise<OpcodeResolution>} The status of the opcode along with any witnesses assigned while solving it.
+#[wasm_bindgen(js_name = solveOpcode, skip_jsdoc)]
+pub async fn solve_opcode(
+    opcode: Vec<u8>,
+    witness_map: JsWitnessMapLike,
+) -> Result<JsOpcodeResolution, JsString> {
+    console_error_panic_hook::set_once();
+    let opcode: Opcode = deserialize_gzipped(&opcode)
+        .map_err(|err| format!("Failed to deserialize opcode: {err}"))?;
+    let witness_map = WitnessMap::try_from(witness_map)?;
+
+    let backend = SimulatedBackend::initialize().await;
+    let mut acvm = ACVM::new(&backend, vec![opcode], witness_map.clone());
+    let (status, error, foreign_call) = match acvm.solve() {
+        ACVMStatus::Solved => ("solved", None, None),
+        ACVMStatus::InProgress => {
+            unreachable!("Execution should not stop while in `InProgress` state.")
+        }
+        ACVMStatus::Failure(error @ OpcodeResolutionError::OpcodeNotSolvable(_)) => {
+            ("unsolvable", Some(error.to_string()), None)
+        }
+        ACVMStatus::Failure(error) => ("failed", Some(error.to_string()), None),
+        ACVMStatus::RequiresForeignCall => {
+            let foreign_call =
+                acvm.get_pending_foreign_call().expect("a foreign call should be pending");
+            ("requiresForeignCall", None, Some(foreign_call.function.clone()))
+        }
+    };
+
+    let assigned_witnesses: WitnessMap = acvm
+        .witness_map()
+        .clone()
+        .into_iter()
+        .filter(|(witness, _)| !witness_map.contains_key(witness))
+        .collect::<std::collections::BTreeMap<_, _>>()
+        .into();
+
+    let js_resolution = Object::new();
+    let set = |key: &str, value: &JsValue| {
+        Reflect::set(&js_resolution, &JsValue::from_str(key), value)
+            .expect("setting a property on an `Object` should not fail");
+    };
+    set("status", &JsValue::from_str(status));
+    set("assignedWitnesses", &JsWitnessMap::from(assigned_witnesses));
+    if let Some(error) = error {
+        set("error", &JsValue::from(error));
+    }
+    if let Some(foreign_call) = foreign_call {
+        set("foreignCall", &JsValue::from(foreign_call));
+    }
+
+    Ok(js_resolution.unchecked_into())
+}
+
+/// Runs a single Brillig program, making any foreign calls it requires, without checking any ACIR constraints.
+///
+/// This allows the result of an unconstrained function to be simulated without solving the rest of its circuit.
+///
+/// @param {Uint8Array} brillig_bytecode - A serialized ACIR Brillig opcode, encoded in the same format as circuits.
+/// @param {WitnessMapLike} inputs - A witness map holding the values of the witnesses read by the Brillig opcode's inputs.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the Brillig program.
+/// @returns {Promise<WitnessMap>} The values assigned to the Brillig opcode's outputs.
+#[wasm_bindgen(js_name = executeBrillig, skip_jsdoc)]
+pub async fn execute_brillig(
+    brillig_bytecode: Vec<u8>,
+    inputs: JsWitnessMapLike,
+    foreign_call_handler: ForeignCallHandler,
+) -> Result<JsWitnessMap, JsString> {
+    console_error_panic_hook::set_once();
+    let brillig: Brillig = deserialize_gzipped(&brillig_bytecode)
+        .map_err(|err| format!("Failed to deserialize Brillig opcode: {err}"))?;
+    let inputs = WitnessMap::try_from(inputs)?;
+
+    let backend = SimulatedBackend::initialize().await;
+    let mut acvm = ACVM::new(&backend, vec![Opcode::Brillig(brillig)], inputs.clone());
+    loop {
+        match acvm.solve() {
+            ACVMStatus::Solved => break,
+            ACVMStatus::InProgress => {
+                unreachable!("Execution should not stop while in `InProgress` state.")
+            }
+            ACVMStatus::Failure(error) => return Err(error.to_string().into()),
+            ACVMStatus::RequiresForeignCall => {
+                while let Some(foreign_call) = acvm.get_pending_foreign_call() {
+                    let result = resolve_brillig(&foreign_call_handler, foreign_call, None).await?;
+                    acvm.resolve_pending_foreign_call(result);
+                }
+            }
+        }
+    }
+
+    let outputs: WitnessMap = acvm
+        .finalize()
+        .into_iter()
+        .filter(|(witness, _)| !inputs.contains_key(witness))
+        .collect::<std::collections::BTreeMap<_, _>>()
+        .into();
+    Ok(outputs.into())
+}
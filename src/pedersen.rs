@@ -0,0 +1,39 @@
+use js_sys::{Array, JsString};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    barretenberg::{pedersen::Pedersen, Barretenberg},
+    curves::{CurvePoint, JsCurvePoint},
+    js_witness_map::{field_element_to_js_string, js_value_to_field_element},
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Array, typescript_type = "string[]")]
+    pub type JsFieldElements;
+}
+
+/// Computes the Pedersen commitment to `inputs`, matching the `Pedersen` blackbox function.
+///
+/// @param {string[]} inputs - The hex encoded field elements to commit to.
+/// @param {number} domain_separator - The index of the generators to use for the commitment. Defaults to `0`.
+/// @returns {Promise<CurvePoint>} The Pedersen commitment to `inputs`.
+#[wasm_bindgen(js_name = pedersenCommit, skip_jsdoc)]
+pub async fn pedersen_commit(
+    inputs: JsFieldElements,
+    domain_separator: Option<u32>,
+) -> Result<JsCurvePoint, JsString> {
+    console_error_panic_hook::set_once();
+    let inputs = inputs.iter().map(js_value_to_field_element).collect::<Result<Vec<_>, _>>()?;
+
+    let barretenberg = Barretenberg::new().await;
+    let (x, y) = barretenberg
+        .encrypt(inputs, domain_separator.unwrap_or_default())
+        .map_err(|err| err.to_string())?;
+
+    Ok(CurvePoint {
+        x: field_element_to_js_string(&x).into(),
+        y: field_element_to_js_string(&y).into(),
+    }
+    .into())
+}
@@ -0,0 +1,90 @@
+use js_sys::{Array, BigInt, JsString, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    noir_abi::{encode_inputs, Abi, AbiType, JsNoirAbi},
+    JsWitnessMap,
+};
+
+/// Converts `value` from a `Prover.toml` into the JS input for a value of type `typ`, reading numbers as `nargo` does.
+///
+/// Values which don't match `typ` are converted as they are, leaving the encoder to report them.
+fn toml_to_js(typ: &AbiType, value: &toml::Value) -> JsValue {
+    let is_scalar = matches!(typ, AbiType::Field | AbiType::Boolean | AbiType::Integer { .. });
+    match value {
+        toml::Value::String(string) if is_scalar && !string.is_empty() => {
+            match string.strip_prefix("0x") {
+                // `nargo` accepts any number of hex digits, while the encoder only accepts whole bytes.
+                Some(digits) if digits.len() % 2 == 1 => JsValue::from(format!("0x0{digits}")),
+                Some(_) => JsValue::from_str(string),
+                // `nargo` reads strings without a `0x` prefix as decimal.
+                None => BigInt::new(&JsValue::from_str(string))
+                    .map(JsValue::from)
+                    .unwrap_or_else(|_| JsValue::from_str(string)),
+            }
+        }
+        toml::Value::String(string) => JsValue::from_str(string),
+        toml::Value::Integer(integer) => BigInt::from(*integer).into(),
+        toml::Value::Float(float) => JsValue::from_f64(*float),
+        toml::Value::Boolean(boolean) => JsValue::from_bool(*boolean),
+        toml::Value::Datetime(datetime) => JsValue::from(datetime.to_string()),
+        toml::Value::Array(elements) => {
+            let array = Array::new();
+            for (index, element) in elements.iter().enumerate() {
+                let typ = match typ {
+                    AbiType::Array { typ, .. } => typ.as_ref(),
+                    AbiType::Tuple { fields } => fields.get(index).unwrap_or(typ),
+                    _ => typ,
+                };
+                array.push(&toml_to_js(typ, element));
+            }
+            array.into()
+        }
+        toml::Value::Table(table) => {
+            let object = Object::new();
+            for (name, value) in table {
+                let typ = match typ {
+                    AbiType::Struct { fields, .. } => {
+                        fields.iter().find(|(field, _)| field == name).map_or(typ, |(_, typ)| typ)
+                    }
+                    _ => typ,
+                };
+                Reflect::set(&object, &JsValue::from_str(name), &toml_to_js(typ, value))
+                    .expect("setting a property on an `Object` should not fail");
+            }
+            object.into()
+        }
+    }
+}
+
+/// Encodes the inputs held in a `Prover.toml` into the initial witness of a Noir program's circuit.
+///
+/// Values are read as `nargo` reads them: fields and integers may be given as TOML integers or as hex or decimal
+/// strings, structs as tables and arrays and tuples as arrays. Entries which aren't parameters of the program, such
+/// as `return`, are ignored.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @param {string} toml - The contents of a `Prover.toml` holding the value of each of the program's parameters.
+/// @returns {WitnessMap} The initial witness of the program's circuit.
+/// @throws {Error} An `AbiEncodingError` listing each problem with the inputs in its `errors` property, if any are
+/// invalid.
+#[wasm_bindgen(js_name = abiEncodeFromToml, skip_jsdoc)]
+pub fn abi_encode_from_toml(abi: JsNoirAbi, toml: String) -> Result<JsWitnessMap, JsValue> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+    let table: toml::Table = toml::from_str(&toml)
+        .map_err(|err| JsString::from(format!("Invalid Prover.toml: {err}")))?;
+
+    let inputs = Object::new();
+    for parameter in &abi.parameters {
+        if let Some(value) = table.get(&parameter.name) {
+            Reflect::set(
+                &inputs,
+                &JsValue::from_str(&parameter.name),
+                &toml_to_js(&parameter.typ, value),
+            )
+            .expect("setting a property on an `Object` should not fail");
+        }
+    }
+    Ok(encode_inputs(&abi, &inputs)?.into())
+}
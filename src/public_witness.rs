@@ -1,11 +1,134 @@
+use std::collections::BTreeMap;
+
 use acvm::acir::{
     circuit::Circuit,
     native_types::{Witness, WitnessMap},
 };
-use js_sys::JsString;
-use wasm_bindgen::prelude::wasm_bindgen;
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::Array;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+
+use crate::{
+    execute::read_circuit,
+    inspect::WitnessAbi,
+    js_witness_map::{field_element_to_js_string, js_value_to_field_element},
+    noir_abi::AbiVisibility,
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const PUBLIC_INPUT_MISMATCH: &'static str = r#"
+/**
+* @typedef {Object} PublicInputMismatch - A public input whose value differs from the one expected.
+* @property {number} index - The position of the public input within the circuit's public inputs.
+* @property {number} witness - The witness index of the public input.
+* @property {string} expected - The expected value of the public input.
+* @property {string} actual - The value of the public input in the witness map.
+*/
+export type PublicInputMismatch = {
+  index: number;
+  witness: number;
+  expected: string;
+  actual: string;
+}
+
+/**
+* @typedef {Object} SplitPublicWitness - The values of a circuit's public inputs, in the order the verifier consumes them.
+* @property {string[]} parameters - The values of the circuit's public parameters, ordered by witness index.
+* @property {string[]} returnValues - The values of the circuit's return values, ordered by witness index.
+*/
+export type SplitPublicWitness = {
+  parameters: string[];
+  returnValues: string[];
+}
+
+/**
+* @typedef {Object} ReturnWitnessOptions - Options which modify how the return witness is extracted.
+* @property {boolean} requireReturnValues - Throw if the circuit has no return values, rather than returning an empty witness map.
+*/
+export type ReturnWitnessOptions = {
+  requireReturnValues?: boolean;
+}
+
+/**
+* @typedef {Object} ParameterWitnesses - The part of a Noir program's ABI which locates each parameter within the witness.
+* @property {Record<string, number[]>} param_witnesses - The witness indices of each parameter, keyed by parameter name.
+* @property {{ name: string, visibility: AbiVisibility }[]} parameters - The visibility of each parameter. Parameters passed through the databus are neither public nor private inputs.
+*/
+export type ParameterWitnesses = {
+  param_witnesses: Record<string, number[]>;
+  parameters?: { name: string; visibility: AbiVisibility }[];
+}
 
-use crate::JsWitnessMap;
+// The values of a circuit's inputs keyed by parameter name. Parameters spanning several witnesses, such as arrays and
+// structs, are given as the list of their values in witness order.
+export type InputValues = Record<string, string | string[]>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Array, typescript_type = "string[]")]
+    pub type JsPublicInputs;
+
+    #[wasm_bindgen(extends = Array, typescript_type = "PublicInputMismatch[]")]
+    pub type JsPublicInputMismatches;
+
+    #[wasm_bindgen(typescript_type = "SplitPublicWitness")]
+    pub type JsSplitPublicWitness;
+
+    #[wasm_bindgen(typescript_type = "ReturnWitnessOptions")]
+    pub type JsReturnWitnessOptions;
+
+    #[wasm_bindgen(typescript_type = "ParameterWitnesses")]
+    pub type JsParameterWitnesses;
+
+    #[wasm_bindgen(typescript_type = "InputValues")]
+    pub type JsInputValues;
+}
+
+#[derive(Debug, Deserialize)]
+struct ParameterVisibility {
+    name: String,
+    visibility: AbiVisibility,
+}
+
+/// The parts of a Noir program's ABI needed to extract its inputs from a witness.
+#[derive(Debug, Deserialize)]
+struct InputAbi {
+    #[serde(flatten)]
+    witnesses: WitnessAbi,
+    #[serde(default)]
+    parameters: Vec<ParameterVisibility>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ReturnWitnessOptions {
+    require_return_values: bool,
+}
+
+#[derive(Serialize)]
+struct PublicInputMismatch {
+    index: usize,
+    witness: u32,
+    expected: String,
+    actual: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitPublicWitness {
+    parameters: Vec<String>,
+    return_values: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum InputValue {
+    Single(String),
+    Multiple(Vec<String>),
+}
 
 fn extract_indices(witness_map: &WitnessMap, indices: Vec<Witness>) -> Result<WitnessMap, String> {
     let mut extracted_witness_map = WitnessMap::new();
@@ -21,17 +144,29 @@ fn extract_indices(witness_map: &WitnessMap, indices: Vec<Witness>) -> Result<Wi
 
 /// Extracts a `WitnessMap` containing the witness indices corresponding to the circuit's return values.
 ///
+/// Circuits without any return values produce an empty witness map, unless `requireReturnValues` is set.
+///
 /// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
 /// @param {WitnessMap} witness_map - The completed witness map after executing the circuit.
+/// @param {ReturnWitnessOptions} options - Optional settings which modify how the return witness is extracted.
 /// @returns {WitnessMap} A witness map containing the circuit's return values.
 #[wasm_bindgen(js_name = getReturnWitness)]
 pub fn get_return_witness(
     circuit: Vec<u8>,
     witness_map: JsWitnessMap,
-) -> Result<JsWitnessMap, JsString> {
+    options: Option<JsReturnWitnessOptions>,
+) -> Result<JsWitnessMap, JsValue> {
     console_error_panic_hook::set_once();
-    let circuit: Circuit = Circuit::read(&*circuit).expect("Failed to deserialize circuit");
+    let options: ReturnWitnessOptions = match options {
+        Some(options) => <JsValue as JsValueSerdeExt>::into_serde(&options)
+            .map_err(|err| JsValue::from(format!("Invalid return witness options: {err}")))?,
+        None => ReturnWitnessOptions::default(),
+    };
+    let circuit: Circuit = read_circuit(&circuit)?;
     let witness_map = WitnessMap::from(witness_map);
+    if options.require_return_values && circuit.return_values.0.is_empty() {
+        return Err(JsValue::from("Circuit has no return values"));
+    }
 
     let return_witness =
         extract_indices(&witness_map, circuit.return_values.0.into_iter().collect())?;
@@ -48,9 +183,9 @@ pub fn get_return_witness(
 pub fn get_public_parameters_witness(
     circuit: Vec<u8>,
     solved_witness: JsWitnessMap,
-) -> Result<JsWitnessMap, JsString> {
+) -> Result<JsWitnessMap, JsValue> {
     console_error_panic_hook::set_once();
-    let circuit: Circuit = Circuit::read(&*circuit).expect("Failed to deserialize circuit");
+    let circuit: Circuit = read_circuit(&circuit)?;
     let witness_map = WitnessMap::from(solved_witness);
 
     let public_params_witness =
@@ -68,9 +203,9 @@ pub fn get_public_parameters_witness(
 pub fn get_public_witness(
     circuit: Vec<u8>,
     solved_witness: JsWitnessMap,
-) -> Result<JsWitnessMap, JsString> {
+) -> Result<JsWitnessMap, JsValue> {
     console_error_panic_hook::set_once();
-    let circuit: Circuit = Circuit::read(&*circuit).expect("Failed to deserialize circuit");
+    let circuit: Circuit = read_circuit(&circuit)?;
     let witness_map = WitnessMap::from(solved_witness);
 
     let public_witness =
@@ -78,3 +213,168 @@ pub fn get_public_witness(
 
     Ok(JsWitnessMap::from(public_witness))
 }
+
+/// Extracts the values of the circuit's public parameters and return values as two separate lists.
+///
+/// Each list is ordered by witness index, matching the order in which the verifier consumes them.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMap} witness_map - The completed witness map after executing the circuit.
+/// @returns {SplitPublicWitness} The values of the circuit's public parameters and return values.
+#[wasm_bindgen(js_name = splitPublicWitness)]
+pub fn split_public_witness(
+    circuit: Vec<u8>,
+    witness_map: JsWitnessMap,
+) -> Result<JsSplitPublicWitness, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let witness_map = WitnessMap::from(witness_map);
+
+    let values = |witnesses: Vec<Witness>| -> Result<Vec<String>, String> {
+        let extracted_witness_map = extract_indices(&witness_map, witnesses.clone())?;
+        Ok(witnesses
+            .iter()
+            .map(|witness| field_element_to_js_string(&extracted_witness_map[witness]).into())
+            .collect())
+    };
+    let split_public_witness = SplitPublicWitness {
+        parameters: values(circuit.public_parameters.0.into_iter().collect())?,
+        return_values: values(circuit.return_values.0.into_iter().collect())?,
+    };
+
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&split_public_witness).unwrap().into())
+}
+
+/// Checks that the values of the circuit's public inputs in `witness_map` match `expected_public_inputs`.
+///
+/// Public inputs are ordered by witness index, covering both the circuit's public parameters and return values.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMap} witness_map - The completed witness map after executing the circuit.
+/// @param {string[]} expected_public_inputs - The expected values of the circuit's public inputs.
+/// @returns {PublicInputMismatch[]} The public inputs which do not match their expected values. Empty if all of them match.
+#[wasm_bindgen(js_name = checkPublicInputs)]
+pub fn check_public_inputs(
+    circuit: Vec<u8>,
+    witness_map: JsWitnessMap,
+    expected_public_inputs: JsPublicInputs,
+) -> Result<JsPublicInputMismatches, JsValue> {
+    console_error_panic_hook::set_once();
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let witness_map = WitnessMap::from(witness_map);
+
+    let public_inputs: Vec<Witness> = circuit.public_inputs().0.into_iter().collect();
+    if public_inputs.len() != expected_public_inputs.length() as usize {
+        return Err(format!(
+            "Expected {} public inputs but the circuit has {}.",
+            expected_public_inputs.length(),
+            public_inputs.len()
+        )
+        .into());
+    }
+
+    let public_witness = extract_indices(&witness_map, public_inputs.clone())?;
+
+    let mut mismatches = Vec::new();
+    for (index, (witness, expected)) in
+        public_inputs.into_iter().zip(expected_public_inputs.iter()).enumerate()
+    {
+        let expected = js_value_to_field_element(expected)?;
+        let actual = public_witness[&witness];
+        if actual != expected {
+            mismatches.push(PublicInputMismatch {
+                index,
+                witness: witness.witness_index(),
+                expected: field_element_to_js_string(&expected).into(),
+                actual: field_element_to_js_string(&actual).into(),
+            });
+        }
+    }
+
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&mismatches).unwrap().into())
+}
+
+/// Extracts the values of each parameter in `abi` whose visibility matches `public`, keyed by parameter name.
+///
+/// A parameter is public if any of its witnesses are among the circuit's public parameters. Parameters which the ABI
+/// places in the databus are never extracted.
+fn extract_inputs(
+    circuit: Vec<u8>,
+    abi: JsParameterWitnesses,
+    witness_map: JsWitnessMap,
+    public: bool,
+) -> Result<JsInputValues, JsValue> {
+    let circuit: Circuit = read_circuit(&circuit)?;
+    let abi: InputAbi = <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(abi))
+        .map_err(|err| JsValue::from(format!("Invalid ABI: {err}")))?;
+    let witness_map = WitnessMap::from(witness_map);
+
+    let databus: Vec<&str> = abi
+        .parameters
+        .iter()
+        .filter(|parameter| {
+            matches!(parameter.visibility, AbiVisibility::CallData | AbiVisibility::ReturnData)
+        })
+        .map(|parameter| parameter.name.as_str())
+        .collect();
+
+    let mut inputs = BTreeMap::new();
+    for (name, witnesses) in abi.witnesses.param_witnesses {
+        if databus.contains(&name.as_str()) {
+            continue;
+        }
+        let witnesses: Vec<Witness> = witnesses.into_iter().map(Witness).collect();
+        let is_public =
+            witnesses.iter().any(|witness| circuit.public_parameters.0.contains(witness));
+        if is_public != public {
+            continue;
+        }
+
+        let extracted_witness_map = extract_indices(&witness_map, witnesses.clone())?;
+        let mut values: Vec<String> = witnesses
+            .iter()
+            .map(|witness| field_element_to_js_string(&extracted_witness_map[witness]).into())
+            .collect();
+        let value = match values.len() {
+            1 => InputValue::Single(values.remove(0)),
+            _ => InputValue::Multiple(values),
+        };
+        inputs.insert(name, value);
+    }
+
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&inputs).unwrap().into())
+}
+
+/// Extracts the values of the circuit's private inputs, keyed by parameter name.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {ParameterWitnesses} abi - The ABI of the Noir program which `circuit` was compiled from.
+/// @param {WitnessMap} witness_map - A witness map holding the circuit's inputs, such as the solved witness.
+/// @returns {InputValues} The values of the parameters which aren't public.
+#[wasm_bindgen(js_name = extractPrivateInputs)]
+pub fn extract_private_inputs(
+    circuit: Vec<u8>,
+    abi: JsParameterWitnesses,
+    witness_map: JsWitnessMap,
+) -> Result<JsInputValues, JsValue> {
+    console_error_panic_hook::set_once();
+    extract_inputs(circuit, abi, witness_map, false)
+}
+
+/// Extracts the values of the circuit's public inputs, keyed by parameter name.
+///
+/// Unlike `getPublicParametersWitness` the values are named, and return values aren't included.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {ParameterWitnesses} abi - The ABI of the Noir program which `circuit` was compiled from.
+/// @param {WitnessMap} witness_map - A witness map holding the circuit's inputs, such as the solved witness.
+/// @returns {InputValues} The values of the public parameters.
+#[wasm_bindgen(js_name = extractPublicInputs)]
+pub fn extract_public_inputs(
+    circuit: Vec<u8>,
+    abi: JsParameterWitnesses,
+    witness_map: JsWitnessMap,
+) -> Result<JsInputValues, JsValue> {
+    console_error_panic_hook::set_once();
+    extract_inputs(circuit, abi, witness_map, true)
+}
@@ -0,0 +1,222 @@
+//! JSON schemas describing the options, results and errors of execution, for consumers which can't make use of
+//! the TypeScript definitions.
+//!
+//! Only the subset of JSON Schema used by these schemas is supported by [`validate`].
+
+use acvm::FieldElement;
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::JsString;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const SCHEMA: &'static str = r#"
+export type SchemaName =
+  | "ExecutionOptions"
+  | "ExecutionResult"
+  | "ExecutionError"
+  | "RecordedExecution"
+  | "SolverStats"
+  | "WitnessMap"
+  | "ForeignCallTranscriptEntry";
+
+/**
+* @typedef {Object} SchemaValidationResult - The outcome of validating a value against a schema.
+* @property {boolean} valid - Whether the value conforms to the schema.
+* @property {string[]} errors - A description of each violation of the schema, prefixed with the path to the offending value.
+*/
+export type SchemaValidationResult = {
+  valid: boolean;
+  errors: string[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = JsString, typescript_type = "SchemaName")]
+    pub type JsSchemaName;
+
+    #[wasm_bindgen(typescript_type = "SchemaValidationResult")]
+    pub type JsSchemaValidationResult;
+}
+
+/// The JSON schema for each of the types which can be validated, keyed by name.
+const SCHEMAS: &[(&str, &str)] = &[
+    ("ExecutionOptions", include_str!("ExecutionOptions.json")),
+    ("ExecutionResult", include_str!("ExecutionResult.json")),
+    ("ExecutionError", include_str!("ExecutionError.json")),
+    ("RecordedExecution", include_str!("RecordedExecution.json")),
+    ("SolverStats", include_str!("SolverStats.json")),
+    ("WitnessMap", include_str!("WitnessMap.json")),
+    ("ForeignCallTranscriptEntry", include_str!("ForeignCallTranscriptEntry.json")),
+];
+
+/// Returns the schema named `name`.
+fn schema(name: &str) -> Result<Value, String> {
+    let (_, schema) = SCHEMAS
+        .iter()
+        .find(|(schema_name, _)| *schema_name == name)
+        .ok_or_else(|| format!("Unknown schema: {name}"))?;
+    Ok(serde_json::from_str(schema).expect("schemas should be valid JSON"))
+}
+
+#[derive(Serialize)]
+struct SchemaValidationResult {
+    valid: bool,
+    errors: Vec<String>,
+}
+
+/// Returns whether `value` is an instance of the JSON Schema `type_name`.
+fn has_type(value: &Value, type_name: &str) -> bool {
+    match type_name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().map_or(false, |n| n.fract() == 0.0)
+        }
+        _ => false,
+    }
+}
+
+/// Appends a description of each way in which `value` violates `schema` to `errors`.
+fn check(value: &Value, schema: &Map<String, Value>, path: &str, errors: &mut Vec<String>) {
+    if let Some(Value::String(reference)) = schema.get("$ref") {
+        let name = reference.trim_end_matches(".json");
+        match schema_object(name) {
+            Ok(schema) => check(value, &schema, path, errors),
+            Err(message) => errors.push(format!("{path}: {message}")),
+        }
+        return;
+    }
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(type_name)) => vec![type_name],
+        Some(Value::Array(type_names)) => type_names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|type_name| has_type(value, type_name)) {
+        errors.push(format!("{path}: expected {}", types.join(" or ")));
+        return;
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            errors.push(format!("{path}: expected one of {}", Value::Array(allowed.clone())));
+        }
+    }
+    if let Some(Value::Array(options)) = schema.get("anyOf") {
+        let matches_any = options.iter().filter_map(Value::as_object).any(|option| {
+            let mut option_errors = Vec::new();
+            check(value, option, path, &mut option_errors);
+            option_errors.is_empty()
+        });
+        if !matches_any {
+            errors.push(format!("{path}: does not match any of the allowed schemas"));
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+            if number < minimum {
+                errors.push(format!("{path}: must be at least {minimum}"));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+            if number > maximum {
+                errors.push(format!("{path}: must be at most {maximum}"));
+            }
+        }
+    }
+
+    if let (Some(Value::String(format)), Value::String(string)) = (schema.get("format"), value) {
+        // Field elements are hex encoded, as they are throughout the rest of the API.
+        if format == "field" && FieldElement::from_hex(string).is_none() {
+            errors.push(format!("{path}: expected a hex encoded field element"));
+        }
+    }
+
+    if let Value::Array(items) = value {
+        if let Some(Value::Object(item_schema)) = schema.get("items") {
+            for (index, item) in items.iter().enumerate() {
+                check(item, item_schema, &format!("{path}/{index}"), errors);
+            }
+        }
+    }
+
+    if let Value::Object(object) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{path}: missing required property `{key}`"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, property) in object {
+            let property_path = format!("{path}/{key}");
+            match (
+                properties.and_then(|properties| properties.get(key)),
+                schema.get("additionalProperties"),
+            ) {
+                (Some(Value::Object(property_schema)), _) => {
+                    check(property, property_schema, &property_path, errors)
+                }
+                (None, Some(Value::Bool(false))) => {
+                    errors.push(format!("{path}: unexpected property `{key}`"))
+                }
+                (None, Some(Value::Object(additional_schema))) => {
+                    check(property, additional_schema, &property_path, errors)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Returns the schema named `name`, which is always an object.
+fn schema_object(name: &str) -> Result<Map<String, Value>, String> {
+    match schema(name)? {
+        Value::Object(schema) => Ok(schema),
+        _ => unreachable!("schemas should be objects"),
+    }
+}
+
+/// Returns the JSON schema describing the type `schema_name`.
+///
+/// The schemas describe the JSON form of each type, so `Map`s are represented as objects and `Uint8Array`s as
+/// arrays of bytes. Options which take callbacks are omitted.
+///
+/// @param {SchemaName} schema_name - The name of the type to return the schema of.
+/// @returns {object} The JSON schema for the type.
+#[wasm_bindgen(js_name = getSchema, skip_jsdoc)]
+pub fn get_schema(schema_name: JsSchemaName) -> Result<JsValue, JsString> {
+    console_error_panic_hook::set_once();
+    let schema = schema(&String::from(JsString::from(schema_name)))?;
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&schema).unwrap())
+}
+
+/// Validates a JSON value against the schema for the type `schema_name`.
+///
+/// @param {unknown} value - The value to validate. This is converted to JSON before being validated.
+/// @param {SchemaName} schema_name - The name of the type which `value` should be an instance of.
+/// @returns {SchemaValidationResult} Whether `value` is valid, along with a description of any violations.
+#[wasm_bindgen(js_name = validate, skip_jsdoc)]
+pub fn validate(
+    value: JsValue,
+    schema_name: JsSchemaName,
+) -> Result<JsSchemaValidationResult, JsString> {
+    console_error_panic_hook::set_once();
+    let schema = schema_object(&String::from(JsString::from(schema_name)))?;
+    let value: Value = <JsValue as JsValueSerdeExt>::into_serde(&value)
+        .map_err(|err| format!("Value could not be converted to JSON: {err}"))?;
+
+    let mut errors = Vec::new();
+    check(&value, &schema, "value", &mut errors);
+    let result = SchemaValidationResult { valid: errors.is_empty(), errors };
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&result).unwrap().into())
+}
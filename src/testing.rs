@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use acvm::{
+    acir::{
+        circuit::{Circuit, Opcode, PublicInputs},
+        native_types::{Expression, Witness, WitnessMap},
+    },
+    FieldElement,
+};
+use js_sys::JsString;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    foreign_call::{ForeignCallHandler, ForeignCallTranscript},
+    ExecutionContext, JsWitnessMap,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ForeignCallTranscriptEntry[]")]
+    pub type JsForeignCallScript;
+}
+
+/// Helpers for writing tests against this package without maintaining binary fixtures.
+///
+/// The circuit is built when requested so always matches the ACIR version this package was built against.
+/// It takes public inputs `x` and `y` in witnesses 1 and 2 and returns `x + y` in witness 3.
+#[wasm_bindgen(js_name = testing)]
+pub struct Testing;
+
+#[wasm_bindgen(js_class = testing)]
+impl Testing {
+    /// Builds a trivial circuit which constrains witness 3 to be the sum of witnesses 1 and 2.
+    ///
+    /// @returns {Uint8Array} A serialized representation of the circuit.
+    #[wasm_bindgen(js_name = trivialCircuit, skip_jsdoc)]
+    pub fn trivial_circuit() -> Vec<u8> {
+        console_error_panic_hook::set_once();
+        let (x, y, sum) = (Witness(1), Witness(2), Witness(3));
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![Opcode::Arithmetic(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![
+                    (FieldElement::one(), x),
+                    (FieldElement::one(), y),
+                    (-FieldElement::one(), sum),
+                ],
+                q_c: FieldElement::zero(),
+            })],
+            public_parameters: PublicInputs([x, y].into()),
+            return_values: PublicInputs([sum].into()),
+        };
+
+        let mut bytes = Vec::new();
+        circuit.write(&mut bytes).expect("writing to a `Vec` should not fail");
+        bytes
+    }
+
+    /// Returns a complete witness which satisfies the circuit returned by `trivialCircuit`.
+    ///
+    /// @returns {WitnessMap} A witness assigning 2 and 3 as the inputs and 5 as the return value.
+    #[wasm_bindgen(js_name = knownGoodWitness, skip_jsdoc)]
+    pub fn known_good_witness() -> JsWitnessMap {
+        trivial_witness(5)
+    }
+
+    /// Returns a complete witness which fails the constraint in the circuit returned by `trivialCircuit`.
+    ///
+    /// @returns {WitnessMap} A witness assigning 2 and 3 as the inputs and 6 as the return value.
+    #[wasm_bindgen(js_name = failingWitness, skip_jsdoc)]
+    pub fn failing_witness() -> JsWitnessMap {
+        trivial_witness(6)
+    }
+
+    /// Creates a foreign call handler which responds to each foreign call with the next entry in `script`.
+    ///
+    /// Foreign calls which don't match the next entry, or which are made once the script is exhausted, are rejected.
+    ///
+    /// @param {ForeignCallTranscriptEntry[]} script - The foreign calls expected to be made, along with their responses.
+    /// @returns {ForeignCallHandler} A handler which replays `script`.
+    #[wasm_bindgen(js_name = scriptedForeignCallHandler, skip_jsdoc)]
+    pub fn scripted_foreign_call_handler(
+        script: JsForeignCallScript,
+    ) -> Result<ForeignCallHandler, JsString> {
+        console_error_panic_hook::set_once();
+        let transcript = ForeignCallTranscript::try_from(JsValue::from(script))?;
+        Ok(transcript.into_handler())
+    }
+
+    /// Marks the Barretenberg instance of `context` as having trapped, as happens when a call into it fails.
+    ///
+    /// This allows recovery from a trap to be tested without finding inputs which make Barretenberg trap.
+    ///
+    /// @param {ExecutionContext} context - The context whose backend should be treated as having trapped.
+    #[wasm_bindgen(js_name = trapBackend, skip_jsdoc)]
+    pub fn trap_backend(context: &ExecutionContext) {
+        console_error_panic_hook::set_once();
+        context.mark_backend_trapped();
+    }
+}
+
+fn trivial_witness(sum: u128) -> JsWitnessMap {
+    let witness: BTreeMap<Witness, FieldElement> = [
+        (Witness(1), FieldElement::from(2_u128)),
+        (Witness(2), FieldElement::from(3_u128)),
+        (Witness(3), FieldElement::from(sum)),
+    ]
+    .into();
+    WitnessMap::from(witness).into()
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use acvm::acir::native_types::{Witness, WitnessMap};
+use js_sys::JsString;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    js_witness_map::field_element_to_js_string,
+    noir_abi::{Abi, AbiType, AbiVisibility, JsNoirAbi},
+    JsWitnessMap,
+};
+
+/// Quotes `value` as a TOML basic string.
+fn toml_string(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Decodes the value of type `typ` held in `witnesses` as a TOML value, in the form `nargo` writes it.
+fn toml_value(
+    name: &str,
+    typ: &AbiType,
+    witnesses: &[u32],
+    witness_map: &WitnessMap,
+) -> Result<String, String> {
+    let values = witnesses
+        .iter()
+        .map(|&witness| {
+            witness_map.get(&Witness(witness)).ok_or_else(|| {
+                format!("Failed to decode {name}: witness {witness} not found in witness map.")
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let hex =
+        |value: &&acvm::FieldElement| toml_string(&String::from(field_element_to_js_string(value)));
+
+    match typ {
+        AbiType::Field | AbiType::Boolean | AbiType::Integer { .. } => match values.as_slice() {
+            [value] => Ok(hex(value)),
+            _ => Err(format!("Failed to decode {name}: expected a single witness.")),
+        },
+        AbiType::Array { typ, .. } => match typ.as_ref() {
+            AbiType::Field | AbiType::Boolean | AbiType::Integer { .. } => {
+                let elements: Vec<String> = values.iter().map(hex).collect();
+                Ok(format!("[{}]", elements.join(", ")))
+            }
+            _ => Err(format!("Failed to decode {name}: only arrays of fields, booleans and integers are supported.")),
+        },
+        AbiType::String { .. } => {
+            let bytes = values
+                .iter()
+                .map(|value| match value.num_bits() {
+                    0..=8 => Ok(value.to_u128() as u8),
+                    _ => Err(format!("Failed to decode {name}: string contains a non-byte value.")),
+                })
+                .collect::<Result<Vec<u8>, _>>()?;
+            let string = String::from_utf8(bytes)
+                .map_err(|_| format!("Failed to decode {name}: string is not valid UTF-8."))?;
+            Ok(toml_string(&string))
+        }
+        AbiType::Struct { .. } | AbiType::Tuple { .. } => {
+            Err(format!("Failed to decode {name}: structs and tuples are not supported."))
+        }
+    }
+}
+
+/// Writes the public inputs and return value of a Noir program to a string in the format of a `Verifier.toml`.
+///
+/// This allows witnesses generated in the browser to be verified with `nargo verify`. Parameters and return values
+/// which are structs or tuples, or arrays of anything other than fields, booleans and integers, aren't supported.
+///
+/// @param {NoirAbi} abi - The ABI of the Noir program, as emitted by the Noir compiler.
+/// @param {WitnessMap} witness_map - The solved witness of the program's circuit.
+/// @returns {string} The contents of a `Verifier.toml` holding the program's public inputs and return value.
+#[wasm_bindgen(js_name = abiDecodeToToml, skip_jsdoc)]
+pub fn abi_decode_to_toml(abi: JsNoirAbi, witness_map: JsWitnessMap) -> Result<JsString, JsString> {
+    console_error_panic_hook::set_once();
+    let abi = Abi::try_from(abi)?;
+    let witness_map = WitnessMap::from(witness_map);
+
+    // `nargo` writes its entries in alphabetical order.
+    let mut entries = BTreeMap::new();
+    for parameter in
+        abi.parameters.iter().filter(|parameter| parameter.visibility == AbiVisibility::Public)
+    {
+        let witnesses = abi
+            .param_witnesses
+            .get(&parameter.name)
+            .ok_or_else(|| format!("Invalid ABI: no witnesses for parameter {}", parameter.name))?;
+        let value = toml_value(&parameter.name, &parameter.typ, witnesses, &witness_map)?;
+        entries.insert(parameter.name.as_str(), value);
+    }
+    if let Some(return_type) = &abi.return_type {
+        let value = toml_value("return", return_type, &abi.return_witnesses, &witness_map)?;
+        entries.insert("return", value);
+    }
+
+    let toml: String =
+        entries.into_iter().map(|(name, value)| format!("{name} = {value}\n")).collect();
+    Ok(toml.into())
+}
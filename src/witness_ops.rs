@@ -0,0 +1,156 @@
+use acvm::{
+    acir::native_types::{Witness, WitnessMap},
+    blackbox_solver::sha256,
+    FieldElement,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::JsString;
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::{js_witness_map::JsWitnessMapLike, JsWitnessMap};
+
+#[wasm_bindgen(typescript_custom_section)]
+const WITNESS_VALUE_OP: &'static str = r#"
+/**
+* An operation applied to every value in a witness map by `mapWitnessValues`.
+* - `negate` replaces each value `x` with `-x`.
+* - `add` replaces each value `x` with `x + value`.
+* - `scale` replaces each value `x` with `x * value`.
+*/
+export type WitnessValueOp =
+  | { op: "negate" }
+  | { op: "add"; value: string }
+  | { op: "scale"; value: string };
+
+/**
+* @typedef {Object} WitnessSketch - A small deterministic sample of a witness map which holds no witness values.
+* @property {number} size - The number of witnesses in the sampled witness map.
+* @property {{ witness: number, valueHash: string }[]} samples - The sampled witnesses with a sha256 hash of each value, in ascending order.
+*/
+export type WitnessSketch = {
+  size: number;
+  samples: { witness: number; valueHash: string }[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "WitnessValueOp")]
+    pub type JsWitnessValueOp;
+
+    #[wasm_bindgen(typescript_type = "WitnessSketch")]
+    pub type JsWitnessSketch;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+enum WitnessValueOp {
+    Negate,
+    Add {
+        #[serde(deserialize_with = "deserialize_field_element")]
+        value: FieldElement,
+    },
+    Scale {
+        #[serde(deserialize_with = "deserialize_field_element")]
+        value: FieldElement,
+    },
+}
+
+impl WitnessValueOp {
+    fn apply(&self, value: FieldElement) -> FieldElement {
+        match self {
+            WitnessValueOp::Negate => -value,
+            WitnessValueOp::Add { value: constant } => value + *constant,
+            WitnessValueOp::Scale { value: constant } => value * *constant,
+        }
+    }
+}
+
+fn deserialize_field_element<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<FieldElement, D::Error> {
+    let hex_str = String::deserialize(deserializer)?;
+    FieldElement::from_hex(&hex_str)
+        .ok_or_else(|| D::Error::custom(format!("Invalid hex string: '{hex_str}'")))
+}
+
+/// Applies an arithmetic operation to every value in a witness map.
+///
+/// This allows related witnesses, e.g. for mutation testing a circuit, to be built without converting each value
+/// to a bigint in JS. All arithmetic is performed modulo the field.
+///
+/// @param {WitnessMapLike} witness_map - The witness map whose values are transformed. It is not modified.
+/// @param {WitnessValueOp} op - The operation to apply to each value.
+/// @returns {WitnessMap} A witness map holding the transformed value of each witness in `witness_map`.
+#[wasm_bindgen(js_name = mapWitnessValues, skip_jsdoc)]
+pub fn map_witness_values(
+    witness_map: JsWitnessMapLike,
+    op: JsWitnessValueOp,
+) -> Result<JsWitnessMap, JsString> {
+    console_error_panic_hook::set_once();
+    let witness_map = WitnessMap::try_from(witness_map)?;
+    let op: WitnessValueOp = <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(op))
+        .map_err(|err| format!("Invalid witness value operation: {err}"))?;
+
+    let mut transformed = WitnessMap::new();
+    for (witness, value) in witness_map {
+        transformed.insert(witness, op.apply(value));
+    }
+    Ok(transformed.into())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WitnessSample {
+    witness: u32,
+    value_hash: String,
+}
+
+#[derive(Serialize)]
+struct WitnessSketch {
+    size: usize,
+    samples: Vec<WitnessSample>,
+}
+
+/// Returns a hex encoded sha256 hash of `bytes`.
+fn hash_hex(bytes: &[u8]) -> String {
+    let hash = sha256(bytes).expect("sha256 should not fail");
+    let hex: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{hex}")
+}
+
+/// Samples up to `k` witnesses from a witness map, replacing each value with its hash.
+///
+/// The witnesses sampled depend only on their indices so sketches of witnesses for the same circuit sample the same
+/// witnesses and can be compared to spot differences between users. Values drawn from a small range, e.g. booleans,
+/// can be recovered from their hashes by brute force so shouldn't be considered private.
+///
+/// @param {WitnessMapLike} witness_map - The witness map to sample.
+/// @param {number} k - The maximum number of witnesses to sample.
+/// @returns {WitnessSketch} The sampled witnesses along with the hashes of their values.
+#[wasm_bindgen(js_name = witnessSketch, skip_jsdoc)]
+pub fn witness_sketch(witness_map: JsWitnessMapLike, k: u32) -> Result<JsWitnessSketch, JsString> {
+    console_error_panic_hook::set_once();
+    let witness_map = WitnessMap::try_from(witness_map)?;
+
+    // Witnesses are ranked by the hash of their index so that the sample is spread across the whole witness.
+    let mut witnesses: Vec<(String, Witness, FieldElement)> = witness_map
+        .into_iter()
+        .map(|(witness, value)| (hash_hex(&witness.witness_index().to_be_bytes()), witness, value))
+        .collect();
+    let size = witnesses.len();
+    witnesses.sort_unstable();
+    witnesses.truncate(k as usize);
+    witnesses.sort_unstable_by_key(|(_, witness, _)| *witness);
+
+    let samples = witnesses
+        .into_iter()
+        .map(|(_, witness, value)| WitnessSample {
+            witness: witness.witness_index(),
+            value_hash: hash_hex(&value.to_be_bytes()),
+        })
+        .collect();
+    let sketch = WitnessSketch { size, samples };
+    Ok(<JsValue as JsValueSerdeExt>::from_serde(&sketch).unwrap().into())
+}
@@ -0,0 +1,374 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use js_sys::{Array, Function, JsString, Object, Promise, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+use crate::{
+    context::JsWitnessMapPromise,
+    execute::{execute_circuit, JsExecutionOptions},
+    foreign_call::ForeignCallHandler,
+    js_witness_map::JsWitnessMapLike,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const MESSAGE_ENDPOINT: &'static str = r#"
+// Either end of a channel used to run executions on a worker, e.g. a `Worker`, the global scope within a worker or a `MessagePort`.
+export type MessageEndpoint = {
+  postMessage(message: any): void;
+  onmessage: ((event: MessageEvent) => void) | null;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "MessageEndpoint")]
+    pub type JsMessageEndpoint;
+}
+
+// Messages sent from the main thread to the worker.
+const EXECUTE: &str = "execute";
+const FOREIGN_CALL_RESULT: &str = "foreignCallResult";
+
+// Messages sent from the worker to the main thread.
+const FOREIGN_CALL: &str = "foreignCall";
+const SOLVED: &str = "solved";
+const FAILED: &str = "failed";
+
+/// Handles requests from a `WorkerExecutor`, executing circuits on the current thread.
+///
+/// This should be called from within a worker once the package has been initialized.
+/// Any foreign calls made by a circuit are sent back to the `WorkerExecutor` to be resolved.
+///
+/// @param {MessageEndpoint} endpoint - The channel to receive requests on. Defaults to the worker's global scope.
+#[wasm_bindgen(js_name = serveExecutionWorker, skip_jsdoc)]
+pub fn serve_execution_worker(endpoint: Option<JsMessageEndpoint>) {
+    console_error_panic_hook::set_once();
+    let endpoint: JsValue = endpoint.map_or_else(|| js_sys::global().into(), JsValue::from);
+
+    // Foreign calls which have been sent to the main thread and are awaiting a result, keyed by call id.
+    let pending_calls: Rc<RefCell<HashMap<u32, (Function, Function)>>> = Rc::default();
+    let next_call_id = Rc::new(RefCell::new(0u32));
+
+    let on_message = {
+        let endpoint = endpoint.clone();
+        Closure::<dyn Fn(JsValue)>::new(move |event: JsValue| {
+            let data = get(&event, "data");
+            match get(&data, "kind").as_string().as_deref() {
+                Some(EXECUTE) => {
+                    let id = get(&data, "id");
+                    let circuit = Uint8Array::new(&get(&data, "circuit")).to_vec();
+                    let initial_witness: JsWitnessMapLike = get(&data, "initialWitness").into();
+                    let options = get(&data, "options");
+                    let options = (!options.is_undefined()).then(|| options.into());
+                    let foreign_call_handler = worker_foreign_call_handler(
+                        endpoint.clone(),
+                        id.clone(),
+                        pending_calls.clone(),
+                        next_call_id.clone(),
+                    );
+
+                    let endpoint = endpoint.clone();
+                    spawn_local(async move {
+                        let result = execute_circuit(
+                            circuit,
+                            initial_witness,
+                            foreign_call_handler,
+                            options,
+                        )
+                        .await;
+                        let message = match result {
+                            Ok(witness) => message(SOLVED, &[("id", &id), ("witness", &witness)]),
+                            Err(err) => {
+                                let (error_message, partial_witness) = match err.as_string() {
+                                    Some(error_message) => (error_message, JsValue::UNDEFINED),
+                                    None => (
+                                        get(&err, "message").as_string().unwrap_or_default(),
+                                        get(&err, "partialWitness"),
+                                    ),
+                                };
+                                message(
+                                    FAILED,
+                                    &[
+                                        ("id", &id),
+                                        ("message", &JsValue::from(error_message)),
+                                        ("partialWitness", &partial_witness),
+                                    ],
+                                )
+                            }
+                        };
+                        post_message(&endpoint, &message);
+                    });
+                }
+                Some(FOREIGN_CALL_RESULT) => {
+                    let call_id = get(&data, "callId").as_f64().unwrap_or_default() as u32;
+                    let Some((resolve, reject)) = pending_calls.borrow_mut().remove(&call_id)
+                    else {
+                        log::warn!("Received a result for unknown foreign call {call_id}");
+                        return;
+                    };
+                    let error = get(&data, "error");
+                    let result = if error.is_undefined() {
+                        resolve.call1(&JsValue::NULL, &get(&data, "outputs"))
+                    } else {
+                        reject.call1(&JsValue::NULL, &error)
+                    };
+                    result.expect("resolving a `Promise` should not fail");
+                }
+                _ => {}
+            }
+        })
+    };
+    Reflect::set(&endpoint, &JsValue::from_str("onmessage"), &on_message.into_js_value())
+        .expect("setting a property on an `Object` should not fail");
+}
+
+/// Creates a `ForeignCallHandler` which forwards foreign calls made by execution `id` to the main thread.
+fn worker_foreign_call_handler(
+    endpoint: JsValue,
+    id: JsValue,
+    pending_calls: Rc<RefCell<HashMap<u32, (Function, Function)>>>,
+    next_call_id: Rc<RefCell<u32>>,
+) -> ForeignCallHandler {
+    let handler =
+        Closure::<dyn Fn(JsString, Array) -> Promise>::new(move |name: JsString, inputs: Array| {
+            let call_id = {
+                let mut next_call_id = next_call_id.borrow_mut();
+                *next_call_id += 1;
+                *next_call_id
+            };
+            let promise = Promise::new(&mut |resolve, reject| {
+                pending_calls.borrow_mut().insert(call_id, (resolve, reject));
+            });
+
+            let message = message(
+                FOREIGN_CALL,
+                &[
+                    ("id", &id),
+                    ("callId", &JsValue::from(call_id)),
+                    ("name", &name),
+                    ("inputs", &inputs),
+                ],
+            );
+            post_message(&endpoint, &message);
+            promise
+        });
+    handler.into_js_value().unchecked_into()
+}
+
+/// An execution which has been sent to the worker and is awaiting a result.
+struct PendingExecution {
+    resolve: Function,
+    reject: Function,
+    foreign_call_handler: ForeignCallHandler,
+}
+
+#[derive(Default)]
+struct ExecutorState {
+    next_id: u32,
+    executions: HashMap<u32, PendingExecution>,
+}
+
+/// Executes circuits on a worker running `serveExecutionWorker`, keeping the current thread free.
+///
+/// Foreign calls made by the circuit are passed back to this thread and resolved by the `ForeignCallHandler`
+/// provided to `executeCircuit`.
+#[wasm_bindgen]
+pub struct WorkerExecutor {
+    endpoint: JsValue,
+    state: Rc<RefCell<ExecutorState>>,
+}
+
+#[wasm_bindgen]
+impl WorkerExecutor {
+    /// Creates a new `WorkerExecutor` which sends executions to `endpoint`.
+    ///
+    /// The executor takes over the endpoint's `onmessage` handler.
+    ///
+    /// @param {MessageEndpoint} endpoint - The worker, or a port connected to it.
+    #[wasm_bindgen(constructor, skip_jsdoc)]
+    pub fn new(endpoint: JsMessageEndpoint) -> WorkerExecutor {
+        console_error_panic_hook::set_once();
+        let endpoint: JsValue = endpoint.into();
+        let state: Rc<RefCell<ExecutorState>> = Rc::default();
+
+        let on_message = {
+            let endpoint = endpoint.clone();
+            let state = state.clone();
+            Closure::<dyn Fn(JsValue)>::new(move |event: JsValue| {
+                let data = get(&event, "data");
+                let kind = get(&data, "kind").as_string();
+                let id = get(&data, "id").as_f64().unwrap_or_default() as u32;
+
+                match kind.as_deref() {
+                    Some(FOREIGN_CALL) => {
+                        let Some(foreign_call_handler) = state
+                            .borrow()
+                            .executions
+                            .get(&id)
+                            .map(|execution| execution.foreign_call_handler.clone())
+                        else {
+                            return;
+                        };
+                        let endpoint = endpoint.clone();
+                        spawn_local(async move {
+                            let call_id = get(&data, "callId");
+                            let result = resolve_foreign_call(
+                                &foreign_call_handler,
+                                &get(&data, "name"),
+                                &get(&data, "inputs"),
+                            )
+                            .await;
+                            let message = match result {
+                                Ok(outputs) => message(
+                                    FOREIGN_CALL_RESULT,
+                                    &[("callId", &call_id), ("outputs", &outputs)],
+                                ),
+                                Err(error) => message(
+                                    FOREIGN_CALL_RESULT,
+                                    &[("callId", &call_id), ("error", &JsValue::from(error))],
+                                ),
+                            };
+                            post_message(&endpoint, &message);
+                        });
+                    }
+                    Some(SOLVED) => {
+                        if let Some(execution) = state.borrow_mut().executions.remove(&id) {
+                            execution
+                                .resolve
+                                .call1(&JsValue::NULL, &get(&data, "witness"))
+                                .expect("resolving a `Promise` should not fail");
+                        }
+                    }
+                    Some(FAILED) => {
+                        if let Some(execution) = state.borrow_mut().executions.remove(&id) {
+                            // Mirror the errors thrown by `executeCircuit`.
+                            let message = get(&data, "message");
+                            let partial_witness = get(&data, "partialWitness");
+                            let error = if partial_witness.is_undefined() {
+                                message
+                            } else {
+                                let error =
+                                    js_sys::Error::new(&message.as_string().unwrap_or_default());
+                                Reflect::set(
+                                    &error,
+                                    &JsValue::from_str("partialWitness"),
+                                    &partial_witness,
+                                )
+                                .expect("setting a property on an `Error` should not fail");
+                                error.into()
+                            };
+                            execution
+                                .reject
+                                .call1(&JsValue::NULL, &error)
+                                .expect("rejecting a `Promise` should not fail");
+                        }
+                    }
+                    _ => {}
+                }
+            })
+        };
+        Reflect::set(&endpoint, &JsValue::from_str("onmessage"), &on_message.into_js_value())
+            .expect("setting a property on an `Object` should not fail");
+
+        WorkerExecutor { endpoint, state }
+    }
+
+    /// Executes an ACIR circuit on the worker to generate the solved witness from the initial witness.
+    ///
+    /// Callbacks such as `onOpcodeSolved` cannot be sent to a worker and so are not supported in `options`.
+    ///
+    /// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+    /// @param {WitnessMapLike} initial_witness - The initial witness map defining all of the inputs to `circuit`.
+    /// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the circuit.
+    /// @param {ExecutionOptions} options - Optional settings which modify how the circuit is executed.
+    /// @returns {Promise<WitnessMap>} The solved witness calculated by executing the circuit on the provided inputs.
+    #[wasm_bindgen(js_name = executeCircuit, skip_jsdoc)]
+    pub fn execute_circuit(
+        &self,
+        circuit: Vec<u8>,
+        initial_witness: JsWitnessMapLike,
+        foreign_call_handler: ForeignCallHandler,
+        options: Option<JsExecutionOptions>,
+    ) -> JsWitnessMapPromise {
+        console_error_panic_hook::set_once();
+        let mut state = self.state.borrow_mut();
+        state.next_id += 1;
+        let id = state.next_id;
+
+        let message = message(
+            EXECUTE,
+            &[
+                ("id", &JsValue::from(id)),
+                ("circuit", &Uint8Array::from(circuit.as_slice())),
+                ("initialWitness", &initial_witness),
+                ("options", &options.map_or(JsValue::UNDEFINED, JsValue::from)),
+            ],
+        );
+
+        let mut callbacks = None;
+        let promise = Promise::new(&mut |resolve, reject| callbacks = Some((resolve, reject)));
+        let (resolve, reject) = callbacks.expect("`Promise` executor is called synchronously");
+
+        // Posting fails if the message can't be cloned, e.g. if `options` contains a callback.
+        match try_post_message(&self.endpoint, &message) {
+            Ok(()) => {
+                let execution = PendingExecution { resolve, reject, foreign_call_handler };
+                state.executions.insert(id, execution);
+            }
+            Err(err) => {
+                reject.call1(&JsValue::NULL, &err).expect("rejecting a `Promise` should not fail");
+            }
+        }
+
+        JsValue::from(promise).into()
+    }
+}
+
+async fn resolve_foreign_call(
+    foreign_call_handler: &Function,
+    name: &JsValue,
+    inputs: &JsValue,
+) -> Result<JsValue, String> {
+    let promise = foreign_call_handler
+        .call2(&JsValue::NULL, name, inputs)
+        .map_err(|err| format!("Error calling `foreign_call_handler`: {}", error_message(&err)))?;
+    JsFuture::from(Promise::resolve(&promise))
+        .await
+        .map_err(|err| format!("Error awaiting `foreign_call_handler`: {}", error_message(&err)))
+}
+
+fn error_message(err: &JsValue) -> String {
+    err.as_string()
+        .or_else(|| get(err, "message").as_string())
+        .unwrap_or_else(|| "Unknown".to_owned())
+}
+
+fn message(kind: &str, fields: &[(&str, &JsValue)]) -> Object {
+    let message = Object::new();
+    let set = |key: &str, value: &JsValue| {
+        Reflect::set(&message, &JsValue::from_str(key), value)
+            .expect("setting a property on an `Object` should not fail");
+    };
+
+    set("kind", &JsValue::from_str(kind));
+    for (key, value) in fields {
+        set(key, value);
+    }
+    message
+}
+
+fn try_post_message(endpoint: &JsValue, message: &JsValue) -> Result<(), JsValue> {
+    let post_message: Function = get(endpoint, "postMessage").dyn_into()?;
+    post_message.call1(endpoint, message).map(|_| ())
+}
+
+fn post_message(endpoint: &JsValue, message: &JsValue) {
+    if let Err(err) = try_post_message(endpoint, message) {
+        log::error!("Failed to post message: {}", error_message(&err));
+    }
+}
+
+fn get(target: &JsValue, key: &str) -> JsValue {
+    Reflect::get(target, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
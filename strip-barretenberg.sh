@@ -0,0 +1,63 @@
+#!/usr/bin/env bash
+
+function require_command {
+    if ! command -v "$1" >/dev/null 2>&1; then
+        echo "Error: $1 is required but not installed." >&2
+        exit 1
+    fi
+}
+function run_or_fail {
+  "$@"
+  local status=$?
+  if [ $status -ne 0 ]; then
+    echo "Command '$*' failed with exit code $status" >&2
+    exit $status
+  fi
+}
+
+# Strips a full Barretenberg build down to the exports which are used to solve black box functions,
+# for use with the `minimal-barretenberg` feature.
+#
+# Usage: ./strip-barretenberg.sh <path to barretenberg.wasm> <output directory>
+
+require_command wasm-metadce
+require_command wasm-opt
+
+if [ $# -ne 2 ]; then
+  echo "Usage: $0 <path to barretenberg.wasm> <output directory>" >&2
+  exit 1
+fi
+
+INPUT_WASM=$1
+OUTPUT_DIR=$2
+OUTPUT_WASM=$OUTPUT_DIR/barretenberg-minimal.wasm
+
+# These must be kept in sync with the functions called from `src/barretenberg`.
+EXPORTS=(
+  memory
+  bbmalloc
+  bbfree
+  pedersen_plookup_commit_with_hash_index
+  construct_signature
+  compute_public_key
+  verify_signature
+)
+
+GRAPH_FILE=$(mktemp)
+trap 'rm -f $GRAPH_FILE' EXIT
+
+# wasm-metadce removes everything which can't be reached from a root node of the graph.
+{
+  echo "["
+  echo "  { \"name\": \"outside\", \"root\": true, \"reaches\": [$(printf '"%s",' "${EXPORTS[@]}" | sed 's/,$//')] }"
+  for export in "${EXPORTS[@]}"; do
+    echo "  ,{ \"name\": \"$export\", \"export\": \"$export\" }"
+  done
+  echo "]"
+} > $GRAPH_FILE
+
+mkdir -p $OUTPUT_DIR
+run_or_fail wasm-metadce $INPUT_WASM --graph-file $GRAPH_FILE -o $OUTPUT_WASM
+run_or_fail wasm-opt $OUTPUT_WASM -o $OUTPUT_WASM -Oz
+
+echo "Wrote $OUTPUT_WASM ($(wc -c < $INPUT_WASM) bytes -> $(wc -c < $OUTPUT_WASM) bytes)"
@@ -0,0 +1,25 @@
+import { expect } from "@esm-bundle/chai";
+import initACVM, { validate, getSchema } from "../../result/";
+
+beforeEach(async () => {
+  await initACVM();
+});
+
+it("validates JSON values against the published schemas", () => {
+  expect(
+    validate({ deterministic: true, breakpoints: [0, 2] }, "ExecutionOptions")
+  ).to.be.deep.eq({ valid: true, errors: [] });
+
+  const { valid, errors } = validate(
+    { deterministic: "yes", maxBrilligSteps: -1, unknownOption: 1 },
+    "ExecutionOptions"
+  );
+  expect(valid).to.be.false;
+  expect(errors).to.have.members([
+    "value/deterministic: expected boolean",
+    "value/maxBrilligSteps: must be at least 0",
+    "value: unexpected property `unknownOption`",
+  ]);
+
+  expect(getSchema("WitnessMap")).to.have.property("type", "object");
+});
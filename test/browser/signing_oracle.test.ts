@@ -0,0 +1,93 @@
+import { expect } from "@esm-bundle/chai";
+import initACVM, {
+  createSigningOracle,
+  verifySignedArtifact,
+} from "../../result/";
+
+beforeEach(async () => {
+  await initACVM();
+});
+
+const byte = (value: number) => "0x" + value.toString(16).padStart(64, "0");
+const toHex = (bytes: Uint8Array) =>
+  Array.from(bytes, (value) => value.toString(16).padStart(2, "0")).join("");
+
+it("encodes the signature returned by a signer for the circuit", async () => {
+  // `n - 1` for secp256k1, which is the high form of `s = 1`.
+  const highS =
+    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+  const der = "3026020101022100" + highS;
+  const derSignature = Uint8Array.from({ length: der.length / 2 }, (_, i) =>
+    parseInt(der.slice(2 * i, 2 * i + 2), 16)
+  );
+
+  let messageHash = "";
+  const oracle = createSigningOracle("ecdsa_secp256k1", (hash) => {
+    messageHash = toHex(hash);
+    return derSignature;
+  });
+  const [signature] = await oracle([byte(1), byte(2), byte(3)]);
+
+  expect(messageHash).to.be.eq(
+    "039058c6f2c0cb492c533b0a4d14ef77cc0f78abccced5287d84a1a2011cfb81"
+  );
+  const expected = new Array(64).fill(byte(0));
+  expected[31] = byte(1);
+  expected[63] = byte(1);
+  expect(signature).to.be.deep.eq(expected);
+});
+
+it("verifies the signature of a signed artifact", () => {
+  const fromHex = (hex: string) =>
+    Uint8Array.from({ length: hex.length / 2 }, (_, i) =>
+      parseInt(hex.slice(2 * i, 2 * i + 2), 16)
+    );
+  const signature = fromHex(
+    "b2d8e59d8c82db55031f3812caf0c588c5d41085313d3d7158e26a36d5e6f218" +
+      "fb9356d0ad117363fdd97fb56e3bcd3c9dd64289616267d9132b858d9eb9ea2e"
+  );
+  const publicKey = fromHex(
+    "04f973a0b87062c389d125d8199e803b832b6ac6bf7867a4f6cd87506060fc4c58" +
+      "4b4a0a3f26c988c54c236b224c48bb605b265949e65c098ecd87a581ca10e25d"
+  );
+
+  const artifact = Uint8Array.from([1, 2, 3]);
+  expect(verifySignedArtifact(artifact, signature, publicKey)).to.be.true;
+
+  const tampered = Uint8Array.from([1, 2, 4]);
+  expect(verifySignedArtifact(tampered, signature, publicKey)).to.be.false;
+});
+
+it("rejects malformed signatures and public keys of signed artifacts", () => {
+  const fromHex = (hex: string) =>
+    Uint8Array.from({ length: hex.length / 2 }, (_, i) =>
+      parseInt(hex.slice(2 * i, 2 * i + 2), 16)
+    );
+  const signature = fromHex(
+    "b2d8e59d8c82db55031f3812caf0c588c5d41085313d3d7158e26a36d5e6f218" +
+      "fb9356d0ad117363fdd97fb56e3bcd3c9dd64289616267d9132b858d9eb9ea2e"
+  );
+  const publicKey = fromHex(
+    "04f973a0b87062c389d125d8199e803b832b6ac6bf7867a4f6cd87506060fc4c58" +
+      "4b4a0a3f26c988c54c236b224c48bb605b265949e65c098ecd87a581ca10e25d"
+  );
+  const artifact = Uint8Array.from([1, 2, 3]);
+  const verify = (signature: Uint8Array, publicKey: Uint8Array) => {
+    try {
+      return verifySignedArtifact(artifact, signature, publicKey);
+    } catch (err) {
+      return err;
+    }
+  };
+
+  const zeroR = signature.slice();
+  zeroR.fill(0, 0, 32);
+  expect(verify(zeroR, publicKey)).to.contain("order of the curve");
+  const overflowingS = signature.slice();
+  overflowingS.fill(0xff, 32);
+  expect(verify(overflowingS, publicKey)).to.contain("order of the curve");
+
+  const offCurve = publicKey.slice();
+  offCurve[64] ^= 1;
+  expect(verify(signature, offCurve)).to.contain("not a point on the curve");
+});
@@ -1,5 +1,15 @@
 import { expect } from "@esm-bundle/chai";
-import initACVM, { compressWitness, decompressWitness } from "../../result/";
+import initACVM, {
+  compressBytes,
+  compressWitness,
+  decompressBytes,
+  decompressWitness,
+  CompactWitness,
+  compressWitnessStack,
+  decompressWitnessStack,
+  WitnessStack,
+  WitnessStackLike,
+} from "../../result/";
 import {
   expectedCompressedWitnessMap,
   expectedWitnessMap,
@@ -20,3 +30,84 @@ it("successfully decompresses the witness", async () => {
 
   expect(witnessMap).to.be.deep.eq(expectedWitnessMap);
 });
+
+it("successfully round-trips arbitrary bytes through compression", () => {
+  const bytes = Uint8Array.from([1, 2, 3, 4, 5, 6, 7, 8]);
+
+  expect(decompressBytes(compressBytes(bytes))).to.be.deep.eq(bytes);
+});
+
+it("prefixes compressed bytes with the version of their format", () => {
+  const bytes = Uint8Array.from([1, 2, 3, 4, 5, 6, 7, 8]);
+  const compressed = compressBytes(bytes);
+
+  expect(compressed[0]).to.be.eq(1);
+  // Gzip encoded bytes without a version, as output by Nargo, are still accepted.
+  expect(decompressBytes(compressed.slice(1))).to.be.deep.eq(bytes);
+
+  const unknownVersion = compressed.slice();
+  unknownVersion[0] = 2;
+  expect(() => decompressBytes(unknownVersion)).to.throw(
+    "Unsupported compressed bytes version 2"
+  );
+});
+
+it("holds small and full sized witness values compactly", async () => {
+  const large =
+    "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+  const small =
+    "0x0000000000000000000000000000000000000000000000000000000000000005";
+  const witnessMap = new Map([
+    [1, small],
+    [2, large],
+  ]);
+
+  const compact = new CompactWitness(witnessMap);
+
+  expect(compact.size).to.be.eq(2);
+  expect(compact.get(1)).to.be.eq(small);
+  expect(compact.get(2)).to.be.eq(large);
+  expect(compact.get(3)).to.be.undefined;
+  expect(compact.toWitnessMap()).to.be.deep.eq(witnessMap);
+});
+
+it("round trips a witness stack through compression", () => {
+  const witnessStack: WitnessStack = [
+    { index: 0, witness: new Map([[1, "0x01"]]) },
+    { index: 1, witness: new Map([[2, "0x02"]]) },
+  ];
+
+  const decompressed = decompressWitnessStack(
+    compressWitnessStack(witnessStack)
+  );
+  expect(decompressed.map(({ index }) => index)).to.be.deep.eq([0, 1]);
+  expect(decompressed[1].witness.get(2)).to.be.eq(
+    "0x0000000000000000000000000000000000000000000000000000000000000002"
+  );
+});
+
+it("rejects witness stack indices which aren't 32 bit integers", () => {
+  for (const index of [-1, 1.5, 2 ** 32, NaN, Infinity]) {
+    const witnessStack: WitnessStack = [{ index, witness: new Map() }];
+    expect(() => compressWitnessStack(witnessStack)).to.throw(
+      "index must be an integer between 0 and 2^32 - 1"
+    );
+  }
+});
+
+it("compresses witness stacks holding any witness map like value", () => {
+  const witnessStack: WitnessStackLike = [
+    { index: 0, witness: { 1: "0x01" } },
+    { index: 1, witness: [[2, "0x02"]] },
+  ];
+  const expected = compressWitnessStack([
+    { index: 0, witness: new Map([[1, "0x01"]]) },
+    { index: 1, witness: new Map([[2, "0x02"]]) },
+  ]);
+
+  expect(compressWitnessStack(witnessStack)).to.be.deep.eq(expected);
+  const invalid = [{ index: 0, witness: "0x01" }] as unknown as WitnessStack;
+  expect(() => compressWitnessStack(invalid)).to.throw(
+    "Invalid witness stack: witness map must be a Map"
+  );
+});
@@ -0,0 +1,108 @@
+import { expect } from "@esm-bundle/chai";
+import initACVM, {
+  mapWitnessValues,
+  WitnessValueOp,
+  inspectWitness,
+  witnessSketch,
+  WitnessSketch,
+  WitnessMap,
+} from "../../result/";
+
+beforeEach(async () => {
+  await initACVM();
+});
+
+const one =
+  "0x0000000000000000000000000000000000000000000000000000000000000001";
+const two =
+  "0x0000000000000000000000000000000000000000000000000000000000000002";
+const three =
+  "0x0000000000000000000000000000000000000000000000000000000000000003";
+const minusOne =
+  "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+
+it("negates every value in a witness map", () => {
+  const negated = mapWitnessValues(new Map([[1, one]]), { op: "negate" });
+
+  expect(negated).to.be.deep.eq(new Map([[1, minusOne]]));
+});
+
+it("adds a constant to every value in a witness map", () => {
+  const witnessMap = new Map([
+    [1, one],
+    [2, minusOne],
+  ]);
+
+  const shifted = mapWitnessValues(witnessMap, { op: "add", value: two });
+
+  expect(shifted).to.be.deep.eq(
+    new Map([
+      [1, three],
+      [2, one],
+    ])
+  );
+  expect(witnessMap.get(1)).to.be.eq(one);
+});
+
+it("rescales every value in a witness map", () => {
+  const scaled = mapWitnessValues({ 3: one }, { op: "scale", value: three });
+
+  expect(scaled).to.be.deep.eq(new Map([[3, three]]));
+});
+
+it("rejects unknown witness value operations", () => {
+  expect(() =>
+    mapWitnessValues(new Map([[1, one]]), {
+      op: "invert",
+    } as unknown as WitnessValueOp)
+  ).to.throw();
+});
+
+it("inspects the first witnesses of a witness map with their ABI labels", async () => {
+  const witnessMap = new Map([
+    [1, one],
+    [2, two],
+    [3, three],
+  ]);
+
+  const inspection = inspectWitness(witnessMap, {
+    limit: 2,
+    abi: { param_witnesses: { x: [1], y: [2, 3] }, return_witnesses: [3] },
+  });
+
+  expect(inspection).to.be.deep.eq({
+    size: 3,
+    truncated: true,
+    rows: [
+      { witness: 1, value: one, label: "x" },
+      { witness: 2, value: two, label: "y[0]" },
+    ],
+  });
+});
+
+it("samples the same witnesses from witness maps with different values", () => {
+  const witnessMap: WitnessMap = new Map([
+    [1, "0x01"],
+    [2, "0x02"],
+    [3, "0x03"],
+    [4, "0x04"],
+  ]);
+  const otherWitnessMap: WitnessMap = new Map([
+    [1, "0x01"],
+    [2, "0x05"],
+    [3, "0x06"],
+    [4, "0x07"],
+  ]);
+
+  const sketch: WitnessSketch = witnessSketch(witnessMap, 2);
+  const otherSketch: WitnessSketch = witnessSketch(otherWitnessMap, 2);
+
+  expect(sketch.size).to.be.eq(4);
+  expect(sketch.samples).to.have.length(2);
+  expect(sketch.samples.map(({ witness }) => witness)).to.be.deep.eq(
+    otherSketch.samples.map(({ witness }) => witness)
+  );
+  expect(witnessSketch(witnessMap, 4).samples[0].valueHash).to.be.eq(
+    witnessSketch(otherWitnessMap, 4).samples[0].valueHash
+  );
+});
@@ -0,0 +1,134 @@
+import { expect } from "chai";
+import {
+  estimateGates,
+  getVerificationKey,
+  prove,
+  ProvingBackend,
+  testing,
+  verifyProof,
+  simulateAndProve,
+  configureSimulator,
+  executeCircuit,
+} from "../../result/";
+
+it("estimates gates using the embedded Barretenberg by default", async () => {
+  const gates = await estimateGates(testing.trivialCircuit());
+
+  expect(gates).to.be.eq(1);
+});
+
+it("rejects proving without a proving backend", async () => {
+  const error = await prove(
+    testing.trivialCircuit(),
+    testing.knownGoodWitness()
+  ).catch((err) => err);
+
+  expect(error).to.be.a("string").and.contain("Provide a ProvingBackend");
+});
+
+it("routes proving and verification through a provided backend", async () => {
+  const backend: ProvingBackend = {
+    name: "TestBackend",
+    prove: async (_circuit, witness) => Uint8Array.from([witness.size]),
+    verify: (_circuit, proof, publicInputs) => proof[0] === publicInputs.size,
+    getVerificationKey: () => Uint8Array.from([1, 2, 3]),
+  };
+  const circuit = testing.trivialCircuit();
+  const witness = testing.knownGoodWitness();
+
+  const proof = await prove(circuit, witness, backend);
+
+  expect(proof).to.be.deep.eq(Uint8Array.from([3]));
+  expect(await verifyProof(circuit, proof, witness, backend)).to.be.true;
+  expect(await getVerificationKey(circuit, backend)).to.be.deep.eq(
+    Uint8Array.from([1, 2, 3])
+  );
+
+  const error = await estimateGates(circuit, backend).catch((err) => err);
+  expect(error).to.be.eq("TestBackend does not implement estimateGates");
+});
+
+it("simulates and proves a circuit in a single call", async () => {
+  const { bytecode, initialWitnessMap, expectedResult } = await import(
+    "../shared/noir_program"
+  );
+  const backend: ProvingBackend = {
+    prove: (_circuit, witness) => Uint8Array.from([witness.size]),
+  };
+  const y = initialWitnessMap.get(2) as string;
+
+  const { proof, publicInputs, witnessHash, stats } = await simulateAndProve(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { backend, expectedPublicInputs: [y, expectedResult] }
+  );
+
+  expect(proof).to.have.length(1);
+  expect([...publicInputs.values()]).to.be.deep.eq([y, expectedResult]);
+  expect(witnessHash).to.match(/^0x[0-9a-f]{64}$/);
+  expect(stats.passes).to.be.greaterThan(0);
+
+  const error = await simulateAndProve(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { backend, expectedPublicInputs: [y, y] }
+  ).catch((err) => err);
+  expect(error.stage).to.be.eq("publicInputs");
+});
+
+it("uses the backend set through configureSimulator by default", async () => {
+  configureSimulator({
+    backend: { name: "Configured", estimateGates: () => 42 },
+  });
+  try {
+    expect(await estimateGates(testing.trivialCircuit())).to.be.eq(42);
+  } finally {
+    configureSimulator({ backend: null });
+  }
+  expect(await estimateGates(testing.trivialCircuit())).to.be.eq(1);
+
+  expect(() => configureSimulator({ maxMemoryPages: 1 })).to.throw(
+    "maxMemoryPages must be an integer of at least 23"
+  );
+});
+
+it("sends logs at the configured level to the configured sink", async () => {
+  const messages: string[] = [];
+  const execute = () =>
+    executeCircuit(
+      testing.trivialCircuit(),
+      testing.knownGoodWitness(),
+      () => {
+        throw Error("unexpected oracle");
+      },
+      {
+        onOpcodeSolved: () => {
+          throw Error("observer failed");
+        },
+      }
+    );
+
+  configureSimulator({
+    logLevel: "WARN",
+    logSink: (level, message) => messages.push(`${level}: ${message}`),
+  });
+  try {
+    await execute();
+    expect(messages).to.have.length.above(0);
+    expect(messages[0]).to.contain("WARN: onOpcodeSolved callback threw");
+
+    // Both settings can be changed after logging has been set up.
+    messages.length = 0;
+    configureSimulator({ logLevel: "OFF" });
+    await execute();
+    expect(messages).to.be.empty;
+  } finally {
+    configureSimulator({ logLevel: "ERROR", logSink: null });
+  }
+});
@@ -0,0 +1,49 @@
+import { expect } from "chai";
+import {
+  fieldFromBytes,
+  fieldToBytes,
+  bytesToFields,
+  fieldsToBytes,
+} from "../../result/";
+
+it("encodes field elements in the requested byte order", () => {
+  const value =
+    "0x0000000000000000000000000000000000000000000000000000000000000102";
+
+  const bigEndian = fieldToBytes(value);
+  const littleEndian = fieldToBytes(value, { endianness: "little" });
+
+  expect(bigEndian.slice(30)).to.be.deep.eq(Uint8Array.from([1, 2]));
+  expect(littleEndian.slice(0, 2)).to.be.deep.eq(Uint8Array.from([2, 1]));
+  expect(fieldFromBytes(bigEndian)).to.be.eq(value);
+  expect(fieldFromBytes(littleEndian, { endianness: "little" })).to.be.eq(
+    value
+  );
+});
+
+it("rejects encodings which are not less than the field modulus", () => {
+  expect(() => fieldFromBytes(new Uint8Array(32).fill(255))).to.throw();
+});
+
+it("packs bytes into field elements and back", () => {
+  const bytes = Uint8Array.from([1, 2, 3, 4, 5]);
+
+  const fields = bytesToFields(bytes, { bytesPerField: 2 });
+
+  expect(fields).to.be.deep.eq([
+    "0x0000000000000000000000000000000000000000000000000000000000000102",
+    "0x0000000000000000000000000000000000000000000000000000000000000304",
+    "0x0000000000000000000000000000000000000000000000000000000000000005",
+  ]);
+  expect(fieldsToBytes(fields, bytes.length, { bytesPerField: 2 })).to.be.deep.eq(
+    bytes
+  );
+});
+
+it("rejects field elements which do not fit in a chunk", () => {
+  const fields = [
+    "0x0000000000000000000000000000000000000000000000000000000000010000",
+  ];
+
+  expect(() => fieldsToBytes(fields, 2, { bytesPerField: 2 })).to.throw();
+});
@@ -0,0 +1,36 @@
+import { expect } from "chai";
+import {
+  curveAdd,
+  curveGenerator,
+  curveIsInSubgroup,
+  curveIsOnCurve,
+  curvePointFromBytes,
+  curvePointToBytes,
+  curveScalarMul,
+} from "../../result/";
+
+it("matches the FixedBaseScalarMul blackbox when multiplying the Grumpkin generator", async () => {
+  const { initialWitnessMap, expectedWitnessMap } = await import(
+    "../shared/fixed_base_scalar_mul"
+  );
+
+  const generator = curveGenerator("grumpkin");
+  const scalar = initialWitnessMap.get(1) as string;
+  const point = curveScalarMul("grumpkin", generator, scalar);
+
+  expect(point.x).to.be.eq(expectedWitnessMap.get(2));
+  expect(point.y).to.be.eq(expectedWitnessMap.get(3));
+});
+
+it("round-trips curve points through their byte representation", async () => {
+  for (const curve of ["bn254", "grumpkin"] as const) {
+    const generator = curveGenerator(curve);
+    const point = curveAdd(curve, generator, generator);
+    expect(curveIsOnCurve(curve, point)).to.be.true;
+    expect(curveIsInSubgroup(curve, point)).to.be.true;
+
+    const bytes = curvePointToBytes(curve, point);
+    expect(bytes).to.have.length(64);
+    expect(curvePointFromBytes(curve, bytes)).to.be.deep.eq(point);
+  }
+});
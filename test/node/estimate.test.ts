@@ -0,0 +1,97 @@
+import { expect } from "chai";
+import {
+  estimateCircuitCost,
+  parseCircuitStreaming,
+  analyzeBlackBoxSupport,
+  estimateExecutionMemory,
+  configureSimulator,
+} from "../../result/";
+
+it("estimates the gate count of a circuit from its opcodes", async () => {
+  const { bytecode } = await import("../shared/foreign_call");
+
+  const cost = estimateCircuitCost(bytecode);
+
+  expect(cost.opcodeCounts).to.be.deep.eq({
+    arithmetic: 3,
+    brillig: 1,
+    invert: 1,
+  });
+  expect(cost.gates.arithmetic).to.be.eq(3);
+  expect(cost.totalGates).to.be.eq(3);
+});
+
+it("applies custom weights when estimating the gate count", async () => {
+  const { bytecode } = await import("../shared/foreign_call");
+
+  const cost = estimateCircuitCost(bytecode, { arithmetic: 2, brillig: 10 });
+
+  expect(cost.gates.arithmetic).to.be.eq(6);
+  expect(cost.gates.brillig).to.be.eq(10);
+  expect(cost.totalGates).to.be.eq(16);
+});
+
+it("rejects weights for unknown opcodes", async () => {
+  const { bytecode } = await import("../shared/foreign_call");
+
+  expect(() => estimateCircuitCost(bytecode, { unknown: 1 })).to.throw();
+});
+
+it("parses circuits one opcode at a time", async () => {
+  const { bytecode, messagePackBytecode, resultWitness } = await import(
+    "../shared/noir_program"
+  );
+
+  const streamed: string[] = [];
+  const summary = parseCircuitStreaming(bytecode, (index, opcode) => {
+    expect(index).to.be.eq(streamed.length);
+    streamed.push(opcode);
+  });
+
+  expect(summary.format).to.be.eq("gzip-bincode");
+  expect(summary.opcodeCount).to.be.eq(streamed.length);
+  expect(summary.publicParameters).to.be.deep.eq([2]);
+  expect(summary.returnValues).to.be.deep.eq([resultWitness]);
+
+  // MessagePack circuits are parsed in full before their opcodes are passed on.
+  let parsed = 0;
+  const messagePackSummary = parseCircuitStreaming(
+    messagePackBytecode,
+    () => parsed++
+  );
+  expect(messagePackSummary.format).to.be.eq("deflate-messagepack");
+  expect(parsed).to.be.eq(messagePackSummary.opcodeCount);
+  expect(messagePackSummary.returnValues).to.be.deep.eq([resultWitness]);
+});
+
+it("reports when every blackbox function can be simulated", async () => {
+  const { bytecode } = await import("../shared/pedersen");
+
+  expect(analyzeBlackBoxSupport(bytecode)).to.be.deep.eq({
+    unsupported: [],
+    affectedOpcodes: [],
+    partialExecution: true,
+  });
+});
+
+it("estimates backend memory from the configured memory limit", async () => {
+  const { bytecode } = await import("../shared/foreign_call");
+  const pageBytes = 64 * 1024;
+  const moduleBytes = 8 * 1024 * 1024;
+
+  const estimate = estimateExecutionMemory(bytecode);
+  expect(estimate.backendBytes).to.be.eq(23 * pageBytes + moduleBytes);
+  expect(estimate.totalBytes).to.be.eq(
+    estimate.witnessBytes + estimate.circuitBytes + estimate.backendBytes
+  );
+
+  configureSimulator({ maxMemoryPages: 100 });
+  try {
+    const limited = estimateExecutionMemory(bytecode);
+    expect(limited.backendBytes).to.be.eq(100 * pageBytes + moduleBytes);
+    expect(limited.witnessBytes).to.be.eq(estimate.witnessBytes);
+  } finally {
+    configureSimulator({ maxMemoryPages: null });
+  }
+  expect(estimateExecutionMemory(bytecode)).to.be.deep.eq(estimate);
+});
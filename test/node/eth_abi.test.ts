@@ -0,0 +1,81 @@
+import { expect } from "chai";
+import {
+  witnessFromEthAbi,
+  publicInputsToEthAbi,
+  executeCircuit,
+  WitnessMap,
+} from "../../result/";
+
+const field = (value: number) => "0x" + value.toString(16).padStart(64, "0");
+
+it("assigns Ethereum ABI encoded parameters to witnesses", () => {
+  const calldata = [
+    "0x12345678",
+    "0000000000000000000000000000000000000000000000000000000000000005",
+    "0000000000000000000000000000000000000000000000000000000000000060",
+    "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    "0000000000000000000000000000000000000000000000000000000000000002",
+    "0102000000000000000000000000000000000000000000000000000000000000",
+  ].join("");
+
+  const types = ["uint256", "bytes", "int8"];
+  const mapping = { 0: 1, 1: 2, 2: 10 };
+
+  const witnessMap = witnessFromEthAbi(types, calldata, mapping);
+
+  expect(witnessMap).to.be.deep.eq(
+    new Map([
+      [1, field(5)],
+      [2, field(1)],
+      [3, field(2)],
+      [10, field(255)],
+    ])
+  );
+});
+
+it("rejects array lengths and witness indices which are out of range", () => {
+  const decode = (calldata: string, mapping: Record<number, number>) => {
+    try {
+      return witnessFromEthAbi(["uint256[]"], calldata, mapping);
+    } catch (err) {
+      return err;
+    }
+  };
+  const offset = field(32).slice(2);
+
+  // The array claims far more elements than the calldata could hold.
+  const longArray = "0x" + offset + "ffffffff".padStart(64, "0");
+  expect(decode(longArray, { 0: 1 })).to.contain(
+    "expected 4294967295 array elements"
+  );
+
+  const elements = [2, 1, 2].map((value) => field(value).slice(2));
+  const twoElements = "0x" + offset + elements.join("");
+  expect(decode(twoElements, { 0: 0xffffffff })).to.contain(
+    "overflows the witness indices"
+  );
+});
+
+it("encodes public inputs as Ethereum ABI parameters", async () => {
+  const { bytecode, initialWitnessMap, expectedResult } = await import(
+    "../shared/noir_program"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  const encoded = publicInputsToEthAbi(bytecode, solvedWitness, ["uint256[]"]);
+
+  // `y` is a public parameter and is followed by the return value.
+  const y = initialWitnessMap.get(2) as string;
+  const expected = [field(32), field(2), y, expectedResult];
+  const hex = Array.from(encoded, (byte) => byte.toString(16).padStart(2, "0"));
+  expect("0x" + hex.join("")).to.be.eq(
+    "0x" + expected.map((word) => word.slice(2)).join("")
+  );
+});
@@ -1,5 +1,41 @@
 import { expect } from "chai";
-import { executeCircuit, WitnessMap, ForeignCallHandler } from "../../result/";
+import {
+  executeCircuit,
+  executeCircuitBatch,
+  executeCircuitSync,
+  WitnessMap,
+  ForeignCallHandler,
+  ExecutionError,
+  ExecutionContext,
+  executeCircuitWithStats,
+  pedersenCommit,
+  analyzeWitnessRequirements,
+  generateExecutionReport,
+  executeCircuitChunked,
+  verifyWitness,
+  createForeignCallDispatcher,
+  serveExecutionWorker,
+  WorkerExecutor,
+  profileExecution,
+  PendingForeignCalls,
+  BaseWitness,
+  CircuitDeserializationError,
+  testing,
+  recordExecution,
+  replayExecution,
+  BrilligStep,
+  BrilligPauseReason,
+  BrilligState,
+  DebugSession,
+  SourceLocation,
+  exportTrace,
+  importTrace,
+  ErrorReport,
+  compareExecutions,
+  startProfilingSession,
+  endProfilingSession,
+  ProfilingReport,
+} from "../../result/";
 
 it("successfully executes circuit and extracts return value", async () => {
   const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
@@ -146,3 +182,1396 @@ it("successfully executes a SchnorrVerify opcode", async () => {
 
   expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
 });
+
+it("successfully executes a batch of input sets", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const results = await executeCircuitBatch(
+    bytecode,
+    [initialWitnessMap, new Map()],
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  expect(results).to.have.length(2);
+
+  const solvedWitness = results[0] as WitnessMap;
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+
+  // Missing inputs should fail only the corresponding entry in the batch.
+  expect(results[1]).to.be.instanceOf(Error);
+});
+
+it("attaches the partial witness to execution errors when requested", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/noir_program"
+  );
+
+  // Drop one of the inputs so that execution fails part way through.
+  const incompleteWitnessMap: WitnessMap = new Map(initialWitnessMap);
+  incompleteWitnessMap.delete(2);
+
+  const error = await executeCircuit(
+    bytecode,
+    incompleteWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { returnPartialWitness: true }
+  ).catch((err: ExecutionError) => err);
+
+  expect(error).to.be.instanceOf(Error);
+  const partialWitness = (error as ExecutionError).partialWitness as WitnessMap;
+  expect(partialWitness.get(1)).to.be.eq(initialWitnessMap.get(1));
+});
+
+it("reports each solved opcode to the onOpcodeSolved callback", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const solvedOpcodes: number[] = [];
+  const assignedWitnesses: WitnessMap = new Map();
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      onOpcodeSolved: (index, _opcode, assigned) => {
+        solvedOpcodes.push(index);
+        assigned.forEach((value, key) => assignedWitnesses.set(key, value));
+      },
+    }
+  );
+
+  expect(solvedOpcodes).to.not.be.empty;
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+
+  // Every witness which wasn't provided as an input should have been reported as assigned by some opcode.
+  solvedWitness.forEach((value, key) => {
+    if (!initialWitnessMap.has(key)) {
+      expect(assignedWitnesses.get(key)).to.be.eq(value);
+    }
+  });
+});
+
+it("reuses an ExecutionContext across multiple executions", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const context = await ExecutionContext.create(bytecode);
+  const oracle = () => {
+    throw Error("unexpected oracle");
+  };
+
+  for (let i = 0; i < 2; i++) {
+    const solvedWitness: WitnessMap = await context.execute(
+      initialWitnessMap,
+      oracle
+    );
+    expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+  }
+
+  const solvedWitness: WitnessMap = await context.executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    oracle
+  );
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+
+  context.free();
+});
+
+it("returns solver statistics alongside the solved witness", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const { witness, stats } = await executeCircuitWithStats(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  expect(witness.get(resultWitness)).to.be.eq(expectedResult);
+  expect(stats.passes).to.be.greaterThan(0);
+  expect(stats.opcodeAttempts).to.be.greaterThan(0);
+});
+
+it("synchronously executes circuits without foreign calls", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const solvedWitness: WitnessMap = executeCircuitSync(
+    bytecode,
+    initialWitnessMap
+  );
+
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+});
+
+it("fails to synchronously execute circuits with foreign calls", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/foreign_call"
+  );
+
+  expect(() => executeCircuitSync(bytecode, initialWitnessMap)).to.throw();
+});
+
+it("accepts a plain object or array of pairs as the initial witness", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+  const oracle = () => {
+    throw Error("unexpected oracle");
+  };
+
+  const witnessRecord: Record<number, string> = Object.fromEntries(
+    initialWitnessMap.entries()
+  );
+  const fromRecord = await executeCircuit(bytecode, witnessRecord, oracle);
+  expect(fromRecord.get(resultWitness)).to.be.eq(expectedResult);
+
+  const witnessPairs: [number, string][] = Array.from(
+    initialWitnessMap.entries()
+  );
+  const fromPairs = await executeCircuit(bytecode, witnessPairs, oracle);
+  expect(fromPairs.get(resultWitness)).to.be.eq(expectedResult);
+});
+
+it("assigns the requested result to skipped signature verifications", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/schnorr_verify"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { skipSignatureVerification: true, skippedSignatureResult: false }
+  );
+
+  expect(solvedWitness.get(77)).to.be.eq(
+    "0x0000000000000000000000000000000000000000000000000000000000000000"
+  );
+});
+
+it("matches the Pedersen blackbox when computing commitments directly", async () => {
+  const { initialWitnessMap, expectedWitnessMap } = await import(
+    "../shared/pedersen"
+  );
+
+  const commitment = await pedersenCommit([
+    initialWitnessMap.get(1) as string,
+  ]);
+
+  expect(commitment.x).to.be.eq(expectedWitnessMap.get(2));
+  expect(commitment.y).to.be.eq(expectedWitnessMap.get(3));
+});
+
+it("uses the requested generators for Pedersen commitments", async () => {
+  const { bytecode, initialWitnessMap } = await import("../shared/pedersen");
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { pedersenDomainSeparator: 1 }
+  );
+
+  const commitment = await pedersenCommit(
+    [initialWitnessMap.get(1) as string],
+    1
+  );
+  expect(solvedWitness.get(2)).to.be.eq(commitment.x);
+  expect(solvedWitness.get(3)).to.be.eq(commitment.y);
+});
+
+it("reports the witnesses which could not be solved", async () => {
+  const { bytecode } = await import("../shared/pedersen");
+
+  const requirements = await analyzeWitnessRequirements(bytecode, new Map());
+
+  expect(requirements.solvable).to.be.false;
+  expect(requirements.missingWitnesses).to.be.deep.eq([1, 2, 3]);
+  expect(requirements.unsolvedOpcodes).to.have.length(1);
+  expect(requirements.unsolvedOpcodes[0].index).to.be.eq(0);
+  expect(requirements.failure).to.be.undefined;
+});
+
+it("reports a circuit as solvable when all inputs are provided", async () => {
+  const { bytecode, initialWitnessMap } = await import("../shared/pedersen");
+
+  const requirements = await analyzeWitnessRequirements(
+    bytecode,
+    initialWitnessMap
+  );
+
+  expect(requirements.solvable).to.be.true;
+  expect(requirements.missingWitnesses).to.be.empty;
+  expect(requirements.unsolvedOpcodes).to.be.empty;
+});
+
+it("generates a JSON execution report", async () => {
+  const { bytecode, initialWitnessMap, expectedWitnessMap } = await import(
+    "../shared/pedersen"
+  );
+
+  const report = JSON.parse(
+    await generateExecutionReport(
+      bytecode,
+      initialWitnessMap,
+      () => {
+        throw Error("unexpected oracle");
+      },
+      "json"
+    )
+  );
+
+  expect(report.solved).to.be.true;
+  expect(report.circuit.opcodeCounts).to.be.deep.eq({ pedersen: 1 });
+  expect(report.coverage.solvedOpcodes).to.be.eq(1);
+  expect(report.returnValues).to.be.deep.eq([
+    { witness: 2, value: expectedWitnessMap.get(2) },
+    { witness: 3, value: expectedWitnessMap.get(3) },
+  ]);
+});
+
+it("generates an HTML execution report for a failed execution", async () => {
+  const { bytecode } = await import("../shared/pedersen");
+
+  const report = await generateExecutionReport(
+    bytecode,
+    new Map(),
+    () => {
+      throw Error("unexpected oracle");
+    },
+    "html"
+  );
+
+  expect(report.startsWith("<!DOCTYPE html>")).to.be.true;
+  expect(report).to.contain("Failed: ");
+});
+
+it("returns the solved witness in chunks", async () => {
+  const { bytecode, initialWitnessMap } = await import("../shared/pedersen");
+
+  const chunks = await executeCircuitChunked(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    2
+  );
+
+  const ranges: [number, number][] = [];
+  const indices: number[] = [];
+  for await (const chunk of chunks) {
+    ranges.push([chunk.start, chunk.end]);
+    indices.push(...chunk.indices);
+    expect(chunk.values.length).to.be.eq(32 * chunk.indices.length);
+  }
+
+  expect(ranges).to.be.deep.eq([
+    [1, 2],
+    [3, 3],
+  ]);
+  expect(indices).to.be.deep.eq([1, 2, 3]);
+});
+
+it("accepts a witness which satisfies every opcode", async () => {
+  const { bytecode, expectedWitnessMap } = await import("../shared/pedersen");
+
+  const violations = await verifyWitness(bytecode, expectedWitnessMap);
+
+  expect(violations).to.be.empty;
+});
+
+it("reports opcodes which are not satisfied by a witness", async () => {
+  const { bytecode, expectedWitnessMap } = await import("../shared/pedersen");
+  const witness = new Map(expectedWitnessMap);
+  witness.set(
+    2,
+    "0x0000000000000000000000000000000000000000000000000000000000000000"
+  );
+
+  const violations = await verifyWitness(bytecode, witness);
+
+  expect(violations).to.have.length(1);
+  expect(violations[0].index).to.be.eq(0);
+});
+
+it("dispatches foreign calls to a registry of functions", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    oracleCallName,
+    oracleCallInputs,
+    oracleResponse,
+    expectedWitnessMap,
+  } = await import("../shared/foreign_call");
+
+  let observedInputs: string[][] = [];
+  const foreignCallHandler = createForeignCallDispatcher({
+    [oracleCallName]: (...inputs: string[][]) => {
+      observedInputs = inputs;
+      return oracleResponse;
+    },
+  });
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    foreignCallHandler
+  );
+
+  expect(observedInputs).to.be.deep.eq(oracleCallInputs);
+  expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+});
+
+it("executes circuits through a WorkerExecutor", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    oracleCallName,
+    oracleCallInputs,
+    oracleResponse,
+    expectedWitnessMap,
+  } = await import("../shared/foreign_call");
+
+  // Both ends of the channel live on this thread but messages are passed exactly as they would be to a worker.
+  const { port1, port2 } = new MessageChannel();
+  serveExecutionWorker(port2);
+  const executor = new WorkerExecutor(port1);
+
+  let observedName = "";
+  let observedInputs: string[][] = [];
+  const solvedWitness: WitnessMap = await executor.executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    async (name: string, inputs: string[][]) => {
+      observedName = name;
+      observedInputs = inputs;
+      return oracleResponse;
+    }
+  );
+  port1.close();
+  port2.close();
+
+  expect(observedName).to.be.eq(oracleCallName);
+  expect(observedInputs).to.be.deep.eq(oracleCallInputs);
+  expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+});
+
+it("attributes opcodes to source locations when profiling", async () => {
+  const { bytecode, initialWitnessMap } = await import("../shared/pedersen");
+  const source = "fn main(x: Field) {\n  std::hash::pedersen([x])\n}\n";
+
+  const profile = await profileExecution(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      locations: { 0: [{ span: { start: 22, end: 46 }, file: 0 }] },
+      files: { 0: { path: "src/main.nr", source } },
+    }
+  );
+
+  expect(profile.opcodes).to.be.eq("src/main.nr:2 1\n");
+  expect(profile.solveTime).to.match(/^src\/main.nr:2 \d+\n$/);
+});
+
+it("prefers blackbox functions provided in the execution options", async () => {
+  const { bytecode, initialWitnessMap } = await import("../shared/pedersen");
+  const x =
+    "0x0000000000000000000000000000000000000000000000000000000000000002";
+  const y =
+    "0x0000000000000000000000000000000000000000000000000000000000000003";
+
+  let observedInputs: string[] = [];
+  const solvedWitness = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      blackboxSolver: {
+        pedersen: (inputs: string[]) => {
+          observedInputs = inputs;
+          return [x, y];
+        },
+      },
+    }
+  );
+
+  expect(observedInputs).to.be.deep.eq([initialWitnessMap.get(1)]);
+  expect(solvedWitness.get(2)).to.be.eq(x);
+  expect(solvedWitness.get(3)).to.be.eq(y);
+});
+
+it("reports pending foreign calls while they are awaited", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    oracleCallName,
+    oracleResponse,
+    expectedWitnessMap,
+  } = await import("../shared/foreign_call");
+
+  const context = await ExecutionContext.create(bytecode);
+  let observedPending: PendingForeignCalls | undefined;
+  const changes: PendingForeignCalls[] = [];
+  const solvedWitness: WitnessMap = await context.execute(
+    initialWitnessMap,
+    async () => {
+      observedPending = context.pendingForeignCalls;
+      return oracleResponse;
+    },
+    { onForeignCallsChanged: (pending) => changes.push(pending) }
+  );
+
+  expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+  expect(observedPending).to.be.deep.eq({
+    count: 1,
+    oracles: [oracleCallName],
+  });
+  expect(changes).to.be.deep.eq([
+    { count: 1, oracles: [oracleCallName] },
+    { count: 0, oracles: [] },
+  ]);
+  expect(context.pendingForeignCalls).to.be.deep.eq({ count: 0, oracles: [] });
+
+  context.free();
+});
+
+it("executes with a shared base witness and a per-execution overlay", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const context = await ExecutionContext.create(bytecode);
+  const base = new BaseWitness(
+    new Map([[1, initialWitnessMap.get(1) as string]])
+  );
+  const overlay = new Map([[2, initialWitnessMap.get(2) as string]]);
+
+  for (let i = 0; i < 2; i++) {
+    const solvedWitness: WitnessMap = await context.executeWithBase(
+      base,
+      overlay,
+      () => {
+        throw Error("unexpected oracle");
+      }
+    );
+
+    expect(solvedWitness.has(1)).to.be.false;
+    expect(solvedWitness.get(2)).to.be.eq(initialWitnessMap.get(2));
+    expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+  }
+
+  base.free();
+  context.free();
+});
+
+it("fails deterministic executions which make foreign calls without a transcript", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse } = await import(
+    "../shared/foreign_call"
+  );
+
+  const error = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse,
+    { deterministic: true }
+  ).catch((err) => err);
+
+  expect(error).to.be.a("string").and.contain("without a transcript");
+});
+
+it("replays foreign calls from a transcript", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    oracleCallName,
+    oracleCallInputs,
+    oracleResponse,
+    expectedWitnessMap,
+  } = await import("../shared/foreign_call");
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      deterministic: true,
+      foreignCallTranscript: [
+        {
+          name: oracleCallName,
+          inputs: oracleCallInputs,
+          outputs: oracleResponse,
+        },
+      ],
+    }
+  );
+
+  expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+});
+
+it("returns the final contents of each memory block", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    expectedWitnessMap,
+    expectedMemoryBlocks,
+  } = await import("../shared/memory_op");
+
+  const { witness, memoryBlocks } = await executeCircuitWithStats(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  expect(witness).to.be.deep.eq(expectedWitnessMap);
+  expect(memoryBlocks).to.be.deep.eq(expectedMemoryBlocks);
+});
+
+it("reads memory at an index given by a witness", async () => {
+  const { indexedReadBytecode, indexedReadWitness } = await import(
+    "../shared/memory_op"
+  );
+  const one =
+    "0x0000000000000000000000000000000000000000000000000000000000000001";
+  const two =
+    "0x0000000000000000000000000000000000000000000000000000000000000002";
+  // Collecting stats solves memory opcodes with the package's own solver.
+  const execute = (initialWitness: WitnessMap) =>
+    executeCircuitWithStats(indexedReadBytecode, initialWitness, () => {
+      throw Error("unexpected oracle");
+    });
+
+  const { witness, memoryBlocks } = await execute(indexedReadWitness(one));
+  expect(witness.get(4)).to.be.eq(two);
+  // A known value is checked against memory rather than written to it.
+  const knownValue = await execute(indexedReadWitness(one, two));
+  expect(knownValue.witness).to.be.deep.eq(witness);
+  expect(knownValue.memoryBlocks).to.be.deep.eq(memoryBlocks);
+  const error = await execute(indexedReadWitness(one, one)).catch(
+    (err) => err
+  );
+  expect(error)
+    .to.be.a("string")
+    .and.contain("memory index 1 holds a different value");
+});
+
+it("rejects memory reads of unwritten or out of range indices", async () => {
+  const { indexedReadBytecode, indexedReadWitness } = await import(
+    "../shared/memory_op"
+  );
+  const two =
+    "0x0000000000000000000000000000000000000000000000000000000000000002";
+  // 2^32 would alias index 0 if truncated to a u32.
+  const outOfRange =
+    "0x0000000000000000000000000000000000000000000000000000000100000000";
+  const execute = (initialWitness: WitnessMap) =>
+    executeCircuitWithStats(indexedReadBytecode, initialWitness, () => {
+      throw Error("unexpected oracle");
+    }).catch((err) => err);
+
+  expect(await execute(indexedReadWitness(two))).to.contain(
+    "memory index 2 is read before it is written"
+  );
+  expect(await execute(indexedReadWitness(two, two))).to.contain(
+    "memory index 2 is read before it is written"
+  );
+  expect(await execute(indexedReadWitness(outOfRange))).to.contain(
+    "does not fit within a u32"
+  );
+});
+
+it("applies memory opcodes in program order", async () => {
+  const {
+    brilligWriteBytecode,
+    brilligWriteInitialWitness,
+    brilligWriteExpectedWitness,
+  } = await import("../shared/memory_op");
+
+  // The write's value is only known once the final Brillig opcode has run.
+  const { witness } = await executeCircuitWithStats(
+    brilligWriteBytecode,
+    brilligWriteInitialWitness,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  expect(witness).to.be.deep.eq(brilligWriteExpectedWitness);
+});
+
+it("executes memory circuits without panicking on a stall", async () => {
+  const {
+    brilligWriteBytecode,
+    brilligWriteInitialWitness,
+    brilligWriteExpectedWitness,
+  } = await import("../shared/memory_op");
+  const oracle = () => {
+    throw Error("unexpected oracle");
+  };
+
+  const witness = await executeCircuit(
+    brilligWriteBytecode,
+    brilligWriteInitialWitness,
+    oracle
+  );
+  expect(witness).to.be.deep.eq(brilligWriteExpectedWitness);
+
+  // The block can't be initialized without `Witness(1)`.
+  const inputs = new Map([[2, brilligWriteInitialWitness.get(2) as string]]);
+  const error = await executeCircuit(
+    brilligWriteBytecode,
+    inputs,
+    oracle
+  ).catch((err) => err);
+  expect(error.name).to.be.eq("SolverStalled");
+  expect(error.unsolvedOpcodes).to.be.deep.eq([0, 1, 2]);
+  expect(() => executeCircuitSync(brilligWriteBytecode, inputs)).to.throw(
+    "cannot be solved during synchronous execution"
+  );
+});
+
+it("executes circuits serialized as MessagePack", async () => {
+  const {
+    messagePackBytecode,
+    initialWitnessMap,
+    resultWitness,
+    expectedResult,
+  } = await import("../shared/noir_program");
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    messagePackBytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+});
+
+it("rejects with a CircuitDeserializationError when the circuit is malformed", async () => {
+  // A gzip header followed by data which is not a valid deflate stream.
+  const malformedBytecode = Uint8Array.from([
+    31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 255, 255, 255,
+  ]);
+
+  const error: CircuitDeserializationError = await executeCircuit(
+    malformedBytecode,
+    new Map(),
+    () => {
+      throw Error("unexpected oracle");
+    }
+  ).catch((err) => err);
+
+  expect(error).to.be.an.instanceOf(Error);
+  expect(error.format).to.be.eq("gzip-bincode");
+  expect(error.stage).to.be.eq("decompress");
+  expect(error.offset).to.be.a("number");
+});
+
+it("rejects foreign call results which don't fit within their bit size when checked", async () => {
+  const {
+    bytecode,
+    oracleCallName,
+    validOracleResponse,
+    oversizedOracleResponse,
+  } = await import("../shared/bit_size_foreign_call");
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    new Map(),
+    async () => [validOracleResponse],
+    { checkForeignCallBitSizes: true }
+  );
+  expect(solvedWitness.get(1)).to.be.eq(validOracleResponse);
+
+  const error = await executeCircuit(
+    bytecode,
+    new Map(),
+    async () => [oversizedOracleResponse],
+    { checkForeignCallBitSizes: true }
+  ).catch((err) => err);
+
+  expect(error).to.be.instanceOf(Error);
+  expect(error.message).to.contain(oracleCallName);
+  expect(error.message).to.contain(oversizedOracleResponse);
+});
+
+it("snapshots the witnesses assigned between each snapshot opcode", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse } = await import(
+    "../shared/foreign_call"
+  );
+
+  const { snapshots } = await executeCircuitWithStats(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse,
+    { snapshotOpcodes: [1, 2] }
+  );
+
+  expect(snapshots.map((snapshot) => snapshot.opcodeIndex)).to.be.deep.eq([
+    1, 2,
+  ]);
+  // The Brillig opcode and the first arithmetic opcode assign witnesses 3, 4, 6 and 7.
+  expect([...snapshots[0].assignedWitnesses.keys()]).to.be.deep.eq([
+    3, 4, 6, 7,
+  ]);
+  // Only the inverse is assigned by the directive.
+  expect([...snapshots[1].assignedWitnesses.keys()]).to.be.deep.eq([5]);
+});
+
+it("returns cached results for repeated executions once caching is enabled", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse, expectedWitnessMap } =
+    await import("../shared/foreign_call");
+
+  const context = await ExecutionContext.create(bytecode);
+  context.enableCache(8);
+
+  let oracleCalls = 0;
+  const oracle = async () => {
+    oracleCalls++;
+    return oracleResponse;
+  };
+
+  for (let i = 0; i < 2; i++) {
+    const solvedWitness: WitnessMap = await context.execute(
+      initialWitnessMap,
+      oracle
+    );
+    expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+  }
+  expect(oracleCalls).to.be.eq(1);
+
+  context.disableCache();
+  await context.execute(initialWitnessMap, oracle);
+  expect(oracleCalls).to.be.eq(2);
+});
+
+it("reinitializes the backend after a trap and executes again", async () => {
+  const { bytecode, initialWitnessMap, expectedWitnessMap } = await import(
+    "../shared/pedersen"
+  );
+  const handler = () => {
+    throw Error("unexpected oracle");
+  };
+
+  const context = await ExecutionContext.create(bytecode);
+  expect(await context.execute(initialWitnessMap, handler)).to.be.deep.eq(
+    expectedWitnessMap
+  );
+
+  // The pedersen opcode calls into Barretenberg, which must be replaced.
+  testing.trapBackend(context);
+  expect(await context.execute(initialWitnessMap, handler)).to.be.deep.eq(
+    expectedWitnessMap
+  );
+});
+
+it("doesn't cache executions which are observed or limited", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse } = await import(
+    "../shared/foreign_call"
+  );
+
+  const context = await ExecutionContext.create(bytecode);
+  context.enableCache(8);
+
+  let oracleCalls = 0;
+  const oracle = async () => {
+    oracleCalls++;
+    return oracleResponse;
+  };
+
+  let solvedOpcodes = 0;
+  const options = { onOpcodeSolved: () => solvedOpcodes++ };
+  for (let i = 0; i < 2; i++) {
+    await context.execute(initialWitnessMap, oracle, options);
+  }
+  expect(oracleCalls).to.be.eq(2);
+  expect(solvedOpcodes).to.be.above(0);
+
+  for (let i = 0; i < 2; i++) {
+    await context.execute(initialWitnessMap, oracle, { maxBrilligSteps: 1000 });
+  }
+  expect(oracleCalls).to.be.eq(4);
+});
+
+it("aborts Brillig calls which exceed maxBrilligSteps", async () => {
+  const { bytecode } = await import("../shared/infinite_loop");
+
+  const error = await executeCircuit(
+    bytecode,
+    new Map(),
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { maxBrilligSteps: 1000 }
+  ).catch((err) => err);
+
+  expect(error).to.be.instanceOf(Error);
+  expect(error.message).to.contain("Brillig opcode 0");
+  expect(error.message).to.contain("program counter 0");
+});
+
+it("exposes virtual files to the embedded Barretenberg", async function () {
+  this.timeout(10000);
+  const { bytecode, initialWitnessMap, expectedWitnessMap } = await import(
+    "../shared/pedersen"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { virtualFiles: [new Uint8Array([1, 2, 3])] }
+  );
+
+  expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+});
+
+it("reports the number of steps taken by each Brillig opcode", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse } = await import(
+    "../shared/foreign_call"
+  );
+
+  const { stats } = await executeCircuitWithStats(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse
+  );
+
+  // The Brillig opcode executes a field comparison followed by a foreign call.
+  expect(stats.brilligSteps).to.be.deep.eq({ 0: 2 });
+});
+
+it("reports stalled executions as a SolverStalled error", async () => {
+  const inputs = new Map([[1, testing.knownGoodWitness().get(1) as string]]);
+
+  const error = await executeCircuit(testing.trivialCircuit(), inputs, () => {
+    throw Error("unexpected oracle");
+  }).catch((err) => err);
+
+  expect(error.name).to.be.eq("SolverStalled");
+  expect(error.unsolvedOpcodes).to.be.deep.eq([0]);
+
+  const observedError = await executeCircuit(
+    testing.trivialCircuit(),
+    inputs,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { onOpcodeSolved: () => undefined }
+  ).catch((err) => err);
+
+  expect(observedError.name).to.be.eq("SolverStalled");
+  expect(observedError.passes).to.be.eq(1);
+  expect(observedError.message).to.contain("after 1 passes");
+});
+
+it("cancels pending foreign calls once a concurrent foreign call fails", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/concurrent_foreign_calls"
+  );
+
+  let firstCallCancelled = false;
+  const foreignCallHandler: ForeignCallHandler = (name, _inputs, signal) => {
+    if (name === "second") {
+      return Promise.reject("second oracle failed");
+    }
+    // The first foreign call only settles once it's cancelled.
+    return new Promise((_, reject) => {
+      signal?.addEventListener("abort", () => {
+        firstCallCancelled = true;
+        reject(signal.reason);
+      });
+    });
+  };
+
+  const error = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    foreignCallHandler,
+    { concurrentForeignCalls: true }
+  ).catch((err) => err);
+
+  expect(error).to.contain("second oracle failed");
+  expect(error).to.contain("Cancelled pending foreign calls: `first`");
+  expect(firstCallCancelled).to.be.true;
+});
+
+it("replays a recorded execution without a foreign call handler", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse, expectedWitnessMap } =
+    await import("../shared/foreign_call");
+
+  const { witness, trace } = await recordExecution(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse
+  );
+  expect(witness).to.be.deep.eq(expectedWitnessMap);
+
+  const replayedWitness = await replayExecution(bytecode, trace);
+  expect(replayedWitness).to.be.deep.eq(witness);
+});
+
+it("pauses at breakpoints until execution is resumed", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const pausedAt: number[] = [];
+  const pausedWitnesses: WitnessMap[] = [];
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      breakpoints: [0],
+      onBreakpoint: async (index, _opcode, witness) => {
+        pausedAt.push(index);
+        pausedWitnesses.push(witness);
+        // Step over the opcode with the breakpoint, then run to completion.
+        return pausedAt.length === 1 ? "step" : "continue";
+      },
+    }
+  );
+
+  expect(pausedAt).to.have.lengthOf(2);
+  expect(pausedAt[0]).to.be.eq(0);
+  expect(pausedWitnesses[0]).to.be.deep.eq(initialWitnessMap);
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+});
+
+it("steps through Brillig one instruction at a time", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse, expectedWitnessMap } =
+    await import("../shared/foreign_call");
+
+  const steps: BrilligStep[] = [];
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse,
+    {
+      onBrilligStep: (step) => {
+        steps.push(step);
+        return "step";
+      },
+    }
+  );
+
+  // The second instruction writes the oracle's response into register 1.
+  expect(steps.map((step) => step.programCounter)).to.be.deep.eq([0, 1]);
+  expect(steps[0].opcodeIndex).to.be.eq(0);
+  expect(steps[1].modifiedRegisters).to.be.deep.eq({ 1: oracleResponse[0] });
+  expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+});
+
+it("fingerprints executions by their circuit and foreign calls", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse } = await import(
+    "../shared/foreign_call"
+  );
+
+  const fingerprint = async (response: string[]) => {
+    const { fingerprint } = await executeCircuitWithStats(
+      bytecode,
+      initialWitnessMap,
+      async () => response
+    );
+    return fingerprint;
+  };
+
+  const first = await fingerprint(oracleResponse);
+  expect(first).to.match(/^0x[0-9a-f]{64}$/);
+  expect(await fingerprint(oracleResponse)).to.be.eq(first);
+});
+
+it("exposes the Brillig VM state when Brillig halts for a foreign call", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse, oracleCallInputs } =
+    await import("../shared/foreign_call");
+
+  const pauses: [BrilligPauseReason, BrilligState][] = [];
+  await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse,
+    {
+      onBrilligPause: (reason, state) => {
+        pauses.push([reason, state]);
+      },
+    }
+  );
+
+  // Register 0 holds the input which is passed to the oracle at the second instruction.
+  expect(pauses).to.have.lengthOf(1);
+  const [reason, state] = pauses[0];
+  expect(reason).to.be.eq("foreignCall");
+  expect(state.opcodeIndex).to.be.eq(0);
+  expect(state.programCounter).to.be.eq(1);
+  expect(state.registers[0]).to.be.eq(oracleCallInputs[0][0]);
+});
+
+it("reuses Barretenberg buffers between executions within a batch", async () => {
+  const { bytecode, initialWitnessMap, expectedWitnessMap } = await import(
+    "../shared/pedersen"
+  );
+
+  const results = await executeCircuitBatch(
+    bytecode,
+    [initialWitnessMap, initialWitnessMap, initialWitnessMap],
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  // Each execution writes its inputs into the buffers left behind by the previous one.
+  expect(results).to.be.deep.eq([
+    expectedWitnessMap,
+    expectedWitnessMap,
+    expectedWitnessMap,
+  ]);
+});
+
+it("maps the opcode being executed to its Noir source location", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/noir_program"
+  );
+
+  const session = new DebugSession({
+    debug_symbols: {
+      locations: { 0: [{ span: { start: 38, end: 52 }, file: 0 }] },
+    },
+    file_map: {
+      0: {
+        source: "fn main(x: Field, y: pub Field) {\n    assert(x != y);\n}\n",
+        path: "src/main.nr",
+      },
+    },
+  });
+
+  let location: SourceLocation | undefined;
+  await session.executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      breakpoints: [0],
+      onBreakpoint: () => {
+        location = session.getCurrentSourceLocation();
+      },
+    }
+  );
+
+  expect(location).to.be.deep.eq({
+    path: "src/main.nr",
+    line: 2,
+    column: 5,
+    text: "assert(x != y)",
+  });
+  expect(session.getCurrentSourceLocation()).to.be.undefined;
+});
+
+it("pauses whenever a watched witness is assigned", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const assignments: [number, string][] = [];
+  await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      watchpoints: [resultWitness],
+      onWatchpoint: (witness, value) => {
+        assignments.push([witness, value]);
+      },
+    }
+  );
+
+  expect(assignments).to.be.deep.eq([[resultWitness, expectedResult]]);
+});
+
+it("attaches the Noir call stack to errors from failing opcodes", async () => {
+  const { bytecode } = await import("../shared/noir_program");
+
+  // Every opcode is attributed to the assertion in `check`, called by `main`.
+  const callStack = [
+    { span: { start: 51, end: 62 }, file: 0 },
+    { span: { start: 112, end: 126 }, file: 0 },
+  ];
+  const session = new DebugSession({
+    debug_symbols: {
+      locations: Object.fromEntries(
+        Array.from({ length: 10 }, (_, index) => [index, callStack])
+      ),
+    },
+    file_map: {
+      0: {
+        source: [
+          "fn main(x: Field, y: pub Field) -> pub Field {",
+          "    check(x, y);",
+          "    x + y",
+          "}",
+          "",
+          "fn check(x: Field, y: Field) {",
+          "    assert(x != y);",
+          "}",
+          "",
+        ].join("\n"),
+        path: "src/main.nr",
+      },
+    },
+  });
+
+  // `x` and `y` are equal so the assertion fails.
+  const sameInputs = new Map([
+    [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+    [2, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+  ]);
+  const error = await session
+    .executeCircuit(bytecode, sameInputs, () => {
+      throw Error("unexpected oracle");
+    })
+    .then(
+      () => undefined,
+      (err: ExecutionError) => err
+    );
+
+  expect(error?.callStack).to.be.deep.eq([
+    {
+      function: "check",
+      path: "src/main.nr",
+      line: 7,
+      column: 5,
+      text: "assert(x != y)",
+    },
+    {
+      function: "main",
+      path: "src/main.nr",
+      line: 2,
+      column: 5,
+      text: "check(x, y)",
+    },
+  ]);
+});
+
+it("aborts execution once an invariant is violated", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const checkedValues: WitnessMap[] = [];
+  const error = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    {
+      invariants: [
+        {
+          name: "result below 3",
+          witnesses: [resultWitness],
+          check: (values) => {
+            checkedValues.push(values);
+            return BigInt(values.get(resultWitness) ?? 0) < 3n || "too big";
+          },
+        },
+      ],
+    }
+  ).catch((err) => err);
+
+  expect(error).to.be.eq("Invariant result below 3 violated: too big");
+  expect(checkedValues).to.be.deep.eq([
+    new Map([[resultWitness, expectedResult]]),
+  ]);
+});
+
+it("pauses before making foreign calls which are stepped into", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    oracleCallName,
+    oracleCallInputs,
+    oracleResponse,
+  } = await import("../shared/foreign_call");
+
+  let oracleCalled = false;
+  const steps: [BrilligStep, boolean][] = [];
+  await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    async () => {
+      oracleCalled = true;
+      return oracleResponse;
+    },
+    {
+      onBrilligStep: (step) => {
+        steps.push([step, oracleCalled]);
+        return "stepInto";
+      },
+    }
+  );
+
+  // Execution pauses at the second instruction before its foreign call is made
+  // and again once the instruction has executed.
+  expect(steps.map(([step]) => step.programCounter)).to.be.deep.eq([0, 1, 1]);
+  const [foreignCallStep, calledBeforePause] = steps[1];
+  expect(foreignCallStep.foreignCall).to.be.deep.eq({
+    name: oracleCallName,
+    inputs: oracleCallInputs,
+  });
+  expect(calledBeforePause).to.be.false;
+  expect(steps[2][0].foreignCall).to.be.undefined;
+});
+
+it("exports and imports trace files", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    oracleCallName,
+    oracleCallInputs,
+    oracleResponse,
+    expectedWitnessMap,
+  } = await import("../shared/foreign_call");
+
+  const traceFile = await exportTrace(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse
+  );
+  const trace = importTrace(traceFile);
+
+  expect(trace.version).to.be.eq(1);
+  expect(trace.initialWitness).to.be.deep.eq(initialWitnessMap);
+  expect(trace.foreignCalls).to.be.deep.eq([
+    { name: oracleCallName, inputs: oracleCallInputs, outputs: oracleResponse },
+  ]);
+
+  // Together, the witness writes of each step make up the solved witness.
+  const solvedWitness = new Map(initialWitnessMap);
+  for (const step of trace.steps) {
+    step.witnessWrites.forEach((value, witness) =>
+      solvedWitness.set(witness, value)
+    );
+  }
+  expect(solvedWitness).to.be.deep.eq(expectedWitnessMap);
+});
+
+it("reports failed executions without including witness values", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/foreign_call"
+  );
+
+  const reports: ErrorReport[] = [];
+  const error = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    async () => {
+      throw Error("oracle unavailable");
+    },
+    { onErrorReport: (report) => reports.push(report) }
+  ).catch((err) => err);
+
+  expect(error).to.be.a("string").and.contain("oracle unavailable");
+  expect(reports).to.have.length(1);
+  expect(reports[0].errorCode).to.be.eq("OpcodeFailed");
+  expect(reports[0].opcodeKind).to.be.eq("brillig");
+  expect(reports[0].circuitHash).to.match(/^0x[0-9a-f]{64}$/);
+  expect(reports[0].buildInfo).to.have.property("version");
+  expect(JSON.stringify(reports[0])).to.not.contain("oracle unavailable");
+});
+
+it("suggests the inputs which are missing when execution stalls", async () => {
+  const inputs = new Map([[1, testing.knownGoodWitness().get(1) as string]]);
+
+  const error = await executeCircuit(
+    testing.trivialCircuit(),
+    inputs,
+    () => {
+      throw Error("unexpected oracle");
+    },
+    { errorSuggestions: true, abi: { param_witnesses: { x: [1], y: [2] } } }
+  ).catch((err) => err);
+
+  expect(error.name).to.be.eq("SolverStalled");
+  expect(error.suggestions).to.be.deep.eq([
+    { kind: "missingInputs", parameters: ["y"], witnesses: [2] },
+  ]);
+});
+
+it("reports which public outputs differ between input sets", async () => {
+  const { bytecode, initialWitnessMap, resultWitness } = await import(
+    "../shared/noir_program"
+  );
+
+  // Only the private input `x` changes between the two input sets.
+  const otherWitnessMap: WitnessMap = new Map(initialWitnessMap);
+  otherWitnessMap.set(
+    1,
+    "0x0000000000000000000000000000000000000000000000000000000000000003"
+  );
+
+  const comparison = await compareExecutions(
+    bytecode,
+    [initialWitnessMap, otherWitnessMap],
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  expect(comparison.witnesses).to.be.deep.eq([2, resultWitness]);
+  expect(comparison.executions).to.have.length(2);
+  expect(comparison.differs).to.be.deep.eq([false, true]);
+});
+
+it("aggregates timings across the executions in a profiling session", async () => {
+  const { bytecode, initialWitnessMap, oracleCallName, oracleResponse } =
+    await import("../shared/foreign_call");
+
+  startProfilingSession();
+  let report: ProfilingReport;
+  try {
+    for (let i = 0; i < 2; i++) {
+      await executeCircuit(
+        bytecode,
+        initialWitnessMap,
+        async () => oracleResponse
+      );
+    }
+  } finally {
+    report = endProfilingSession();
+  }
+
+  expect(report.executions).to.be.eq(2);
+  expect(report.opcodes["brillig"].count).to.be.eq(2);
+  expect(report.foreignCalls[oracleCallName].count).to.be.eq(2);
+  expect(() => endProfilingSession()).to.throw(
+    "No profiling session is in progress"
+  );
+});
@@ -0,0 +1,60 @@
+import { expect } from "chai";
+import { evaluateExpression, solveOpcode, executeBrillig } from "../../result/";
+
+it("evaluates an expression against a witness map", async () => {
+  const { bytecode, witnessMap, expectedValue } = await import(
+    "../shared/expression"
+  );
+
+  expect(evaluateExpression(bytecode, witnessMap)).to.be.eq(expectedValue);
+});
+
+it("throws when an expression's witnesses are not assigned", async () => {
+  const { bytecode } = await import("../shared/expression");
+
+  expect(() => evaluateExpression(bytecode, new Map())).to.throw();
+});
+
+it("solves a single opcode", async () => {
+  const { opcodeBytecode, witnessMap, expectedOpcodeOutput } = await import(
+    "../shared/expression"
+  );
+
+  const resolution = await solveOpcode(opcodeBytecode, witnessMap);
+
+  expect(resolution.status).to.be.eq("solved");
+  expect(resolution.assignedWitnesses).to.be.deep.eq(expectedOpcodeOutput);
+});
+
+it("reports opcodes which are missing inputs as unsolvable", async () => {
+  const { opcodeBytecode } = await import("../shared/expression");
+
+  const resolution = await solveOpcode(opcodeBytecode, new Map());
+
+  expect(resolution.status).to.be.eq("unsolvable");
+  expect(resolution.error).to.be.a("string");
+  expect(resolution.assignedWitnesses.size).to.be.eq(0);
+});
+
+it("executes a Brillig opcode without checking any constraints", async () => {
+  const {
+    brilligBytecode,
+    initialWitnessMap,
+    oracleCallName,
+    oracleCallInputs,
+    oracleResponse,
+    expectedBrilligOutputs,
+  } = await import("../shared/foreign_call");
+
+  const outputs = await executeBrillig(
+    brilligBytecode,
+    initialWitnessMap,
+    async (name, inputs) => {
+      expect(name).to.be.eq(oracleCallName);
+      expect(inputs).to.be.deep.eq(oracleCallInputs);
+      return oracleResponse;
+    }
+  );
+
+  expect(outputs).to.be.deep.eq(expectedBrilligOutputs);
+});
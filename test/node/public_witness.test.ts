@@ -0,0 +1,874 @@
+import { expect } from "chai";
+import {
+  checkPublicInputs,
+  executeCircuit,
+  WitnessMap,
+  splitPublicWitness,
+  getReturnWitness,
+  extractPrivateInputs,
+  extractPublicInputs,
+  abiDecodeToToml,
+  NoirAbi,
+  abiValidateInputs,
+  abiDecodeReturnValue,
+  abiToTsType,
+  abiHash,
+  abiEncode,
+  abiDecode,
+  abiEncodeFromToml,
+} from "../../result/";
+
+it("reports public inputs which differ from their expected values", async () => {
+  const { bytecode, initialWitnessMap, expectedResult } = await import(
+    "../shared/noir_program"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  // `y` is a public parameter and is followed by the return value.
+  const y = initialWitnessMap.get(2) as string;
+  const matching = checkPublicInputs(bytecode, solvedWitness, [
+    y,
+    expectedResult,
+  ]);
+  expect(matching).to.be.empty;
+
+  const mismatches = checkPublicInputs(bytecode, solvedWitness, [y, y]);
+  expect(mismatches).to.have.length(1);
+  expect(mismatches[0].index).to.be.eq(1);
+  expect(mismatches[0].expected).to.be.eq(y);
+  expect(mismatches[0].actual).to.be.eq(expectedResult);
+});
+
+it("splits the public witness into parameters and return values", async () => {
+  const { bytecode, initialWitnessMap, expectedResult } = await import(
+    "../shared/noir_program"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  const { parameters, returnValues } = splitPublicWitness(
+    bytecode,
+    solvedWitness
+  );
+  expect(parameters).to.be.deep.eq([initialWitnessMap.get(2)]);
+  expect(returnValues).to.be.deep.eq([expectedResult]);
+});
+
+it("returns an empty return witness for circuits without return values", async () => {
+  const { bytecode, initialWitnessMap, oracleResponse } = await import(
+    "../shared/foreign_call"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    async () => oracleResponse
+  );
+
+  expect(getReturnWitness(bytecode, solvedWitness)).to.be.deep.eq(new Map());
+  expect(() =>
+    getReturnWitness(bytecode, solvedWitness, { requireReturnValues: true })
+  ).to.throw("Circuit has no return values");
+});
+
+it("extracts named inputs by their visibility", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/noir_program"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  // `x` is private while `y` is a public parameter.
+  const abi = { param_witnesses: { x: [1], y: [2] } };
+  expect(extractPrivateInputs(bytecode, abi, solvedWitness)).to.be.deep.eq({
+    x: initialWitnessMap.get(1),
+  });
+  expect(extractPublicInputs(bytecode, abi, solvedWitness)).to.be.deep.eq({
+    y: initialWitnessMap.get(2),
+  });
+});
+
+it("writes public inputs and return values as a Verifier.toml", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      { name: "y", type: { kind: "field" }, visibility: "public" },
+    ],
+    param_witnesses: { x: [1], y: [2] },
+    return_type: { kind: "field" },
+    return_witnesses: [resultWitness],
+  };
+  expect(abiDecodeToToml(abi, solvedWitness)).to.be.eq(
+    `return = "${expectedResult}"\ny = "${initialWitnessMap.get(2)}"\n`
+  );
+});
+
+it("validates inputs against an ABI", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "x",
+        type: { kind: "integer", sign: "unsigned", width: 8 },
+        visibility: "private",
+      },
+      {
+        name: "y",
+        type: { kind: "array", length: 2, type: { kind: "field" } },
+        visibility: "public",
+      },
+      { name: "z", type: { kind: "field" }, visibility: "private" },
+    ],
+    param_witnesses: { x: [1], y: [2, 3], z: [4] },
+    return_type: null,
+    return_witnesses: [],
+  };
+
+  expect(abiValidateInputs(abi, { x: 255, y: ["0x01", "0x02"], z: 3 })).to
+    .be.empty;
+  expect(abiValidateInputs(abi, { x: 256, y: ["0x01"] })).to.be.deep.eq([
+    {
+      path: "x",
+      expectedType: "u8",
+      kind: "overflow",
+      message: "Value does not fit within 8 bits.",
+    },
+    {
+      path: "y",
+      expectedType: "[Field; 2]",
+      kind: "invalid",
+      message: "Expected an array of 2 elements but got 1.",
+    },
+    {
+      path: "z",
+      expectedType: "Field",
+      kind: "missing",
+      message: "Missing value.",
+    },
+  ]);
+});
+
+it("decodes only the return value of a program", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  const abi: NoirAbi = {
+    parameters: [],
+    param_witnesses: {},
+    return_type: {
+      kind: "struct",
+      path: "Result",
+      fields: [
+        ["value", { kind: "field" }],
+        ["isSet", { kind: "boolean" }],
+      ],
+    },
+    return_witnesses: [resultWitness, 2],
+  };
+  expect(abiDecodeReturnValue(abi, solvedWitness)).to.be.deep.eq({
+    value: expectedResult,
+    isSet: true,
+  });
+  expect(
+    abiDecodeReturnValue({ ...abi, return_type: null }, solvedWitness)
+  ).to.be.null;
+});
+
+it("generates TypeScript types from an ABI", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      {
+        name: "y",
+        type: { kind: "array", length: 2, type: { kind: "boolean" } },
+        visibility: "public",
+      },
+    ],
+    param_witnesses: { x: [1], y: [2, 3] },
+    return_type: { kind: "field" },
+    return_witnesses: [4],
+  };
+
+  expect(abiToTsType(abi)).to.be.eq(
+    "export interface Inputs {\n" +
+      "  x: string | number | bigint;\n" +
+      "  y: boolean[];\n" +
+      "}\n\n" +
+      "export type ReturnValue = string;\n"
+  );
+});
+
+it("hashes the interface described by an ABI", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      { name: "y", type: { kind: "field" }, visibility: "public" },
+    ],
+    param_witnesses: { x: [1], y: [2] },
+    return_type: { kind: "field" },
+    return_witnesses: [6],
+  };
+
+  // Witness assignments may change on recompilation without changing the interface.
+  expect(abiHash({ ...abi, return_witnesses: [7] })).to.be.eq(abiHash(abi));
+  expect(
+    abiHash({
+      ...abi,
+      parameters: [
+        { name: "x", type: { kind: "field" }, visibility: "public" },
+        { name: "y", type: { kind: "field" }, visibility: "public" },
+      ],
+    })
+  ).not.to.be.eq(abiHash(abi));
+});
+
+it("decodes tuple return values as arrays", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/noir_program");
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  const abi: NoirAbi = {
+    parameters: [],
+    param_witnesses: {},
+    return_type: {
+      kind: "tuple",
+      fields: [{ kind: "field" }, { kind: "boolean" }],
+    },
+    return_witnesses: [resultWitness, 2],
+  };
+  expect(abiDecodeReturnValue(abi, solvedWitness)).to.be.deep.eq([
+    expectedResult,
+    true,
+  ]);
+  expect(abiValidateInputs(abi, {})).to.be.empty;
+});
+
+it("decodes return values in the requested field format", async () => {
+  const { bytecode, initialWitnessMap, resultWitness } = await import(
+    "../shared/noir_program"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  const abi: NoirAbi = {
+    parameters: [],
+    param_witnesses: {},
+    return_type: { kind: "field" },
+    return_witnesses: [resultWitness],
+  };
+  expect(
+    abiDecodeReturnValue(abi, solvedWitness, { fieldFormat: "decimal" })
+  ).to.be.eq("3");
+  const bytes = abiDecodeReturnValue(abi, solvedWitness, {
+    fieldFormat: "bytes",
+  }) as Uint8Array;
+  expect(bytes).to.have.length(32);
+  expect(bytes[31]).to.be.eq(3);
+});
+
+it("reports the path and expected type of nested invalid inputs", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "accounts",
+        type: {
+          kind: "array",
+          length: 2,
+          type: {
+            kind: "struct",
+            path: "Account",
+            fields: [
+              ["balance", { kind: "integer", sign: "unsigned", width: 64 }],
+            ],
+          },
+        },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { accounts: [1, 2] },
+    return_type: null,
+    return_witnesses: [],
+  };
+
+  const inputs = { accounts: [{ balance: 1 }, { balance: true }] };
+  expect(abiValidateInputs(abi, inputs)).to.be.deep.eq([
+    {
+      path: "accounts[1].balance",
+      expectedType: "u64",
+      kind: "invalid",
+      message:
+        "Expected an integer as a hex or decimal string, number or bigint.",
+    },
+  ]);
+});
+
+it("leaves databus parameters out of extracted inputs", async () => {
+  const { bytecode, initialWitnessMap } = await import(
+    "../shared/noir_program"
+  );
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    bytecode,
+    initialWitnessMap,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  // `x` is marked as passed through the databus so is neither public nor private.
+  const abi = {
+    param_witnesses: { x: [1], y: [2] },
+    parameters: [
+      { name: "x", visibility: "call_data" as const },
+      { name: "y", visibility: "public" as const },
+    ],
+  };
+  expect(extractPrivateInputs(bytecode, abi, solvedWitness)).to.be.deep.eq({});
+  expect(extractPublicInputs(bytecode, abi, solvedWitness)).to.be.deep.eq({
+    y: initialWitnessMap.get(2),
+  });
+});
+
+it("accepts a Uint8Array for byte array inputs", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "preimage",
+        type: {
+          kind: "array",
+          length: 3,
+          type: { kind: "integer", sign: "unsigned", width: 8 },
+        },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { preimage: [1, 2, 3] },
+    return_type: null,
+    return_witnesses: [],
+  };
+
+  expect(abiValidateInputs(abi, { preimage: Uint8Array.from([1, 2, 3]) })).to
+    .be.empty;
+  expect(
+    abiValidateInputs(abi, { preimage: Uint8Array.from([1, 2]) })
+  ).to.be.deep.eq([
+    {
+      path: "preimage",
+      expectedType: "[u8; 3]",
+      kind: "invalid",
+      message: "Expected 3 bytes but got 2.",
+    },
+  ]);
+  expect(abiToTsType(abi)).to.contain(
+    "preimage: (string | number | bigint)[] | Uint8Array;"
+  );
+});
+
+it("rejects decoded strings holding values which don't fit within a byte", () => {
+  const abi: NoirAbi = {
+    parameters: [],
+    param_witnesses: {},
+    return_type: { kind: "string", length: 2 },
+    return_witnesses: [1, 2],
+  };
+  const byte = (value: string) => "0x" + value.padStart(64, "0");
+
+  expect(
+    abiDecodeReturnValue(
+      abi,
+      new Map([
+        [1, byte("68")],
+        [2, byte("69")],
+      ])
+    )
+  ).to.be.eq("hi");
+  expect(() =>
+    abiDecodeReturnValue(
+      abi,
+      new Map([
+        [1, byte("68")],
+        [2, byte("0169")],
+      ])
+    )
+  ).to.throw("String contains a value which doesn't fit within a byte");
+});
+
+it("validates signed integers and numbers beyond 2^53 - 1", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "x",
+        type: { kind: "integer", sign: "signed", width: 8 },
+        visibility: "private",
+      },
+      { name: "y", type: { kind: "field" }, visibility: "private" },
+    ],
+    param_witnesses: { x: [1], y: [2] },
+    return_type: null,
+    return_witnesses: [],
+  };
+
+  expect(abiValidateInputs(abi, { x: -128, y: 2 ** 53 - 1 })).to.be.empty;
+  // Negative values may also be given in two's complement.
+  expect(abiValidateInputs(abi, { x: "0xff", y: "0x01" })).to.be.empty;
+  expect(abiValidateInputs(abi, { x: 128, y: 2 ** 53 })).to.be.deep.eq([
+    {
+      path: "x",
+      expectedType: "i8",
+      kind: "overflow",
+      message: "Value does not fit within i8, which ranges from -128 to 127.",
+    },
+    {
+      path: "y",
+      expectedType: "Field",
+      kind: "invalid",
+      message:
+        "9007199254740992 is larger than 2^53 - 1 so may have lost precision. Pass it as a hex string or bigint instead.",
+    },
+  ]);
+});
+
+it("encodes and decodes nested structs in declaration order", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "account",
+        type: {
+          kind: "struct",
+          path: "Account",
+          fields: [
+            [
+              "owner",
+              {
+                kind: "struct",
+                path: "Owner",
+                fields: [
+                  [
+                    "key",
+                    { kind: "array", length: 2, type: { kind: "field" } },
+                  ],
+                  ["active", { kind: "boolean" }],
+                ],
+              },
+            ],
+            ["balance", { kind: "integer", sign: "unsigned", width: 64 }],
+          ],
+        },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { account: [1, 2, 3, 4] },
+    return_type: null,
+    return_witnesses: [],
+  };
+  const inputs = {
+    account: { owner: { key: ["0x01", "0x02"], active: true }, balance: 3 },
+  };
+
+  const witnessMap = abiEncode(abi, inputs);
+  expect(witnessMap).to.be.deep.eq(
+    new Map([
+      [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+      [2, "0x0000000000000000000000000000000000000000000000000000000000000002"],
+      [3, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+      [4, "0x0000000000000000000000000000000000000000000000000000000000000003"],
+    ])
+  );
+  expect(
+    abiDecode(abi, witnessMap, { fieldFormat: "decimal" })
+  ).to.be.deep.eq({
+    inputs: {
+      account: { owner: { key: ["1", "2"], active: true }, balance: "3" },
+    },
+    returnValue: null,
+  });
+
+  expect(() =>
+    abiEncode(abi, { account: { owner: { key: ["0x01"] }, balance: 3 } })
+  ).to.throw(
+    "Invalid inputs: account.owner.key: Expected an array of 2 elements but got 1. account.owner.active: Missing value."
+  );
+});
+
+it("encodes and decodes strings as one byte per field", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "id",
+        type: { kind: "string", length: 3 },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { id: [1, 2, 3] },
+    return_type: { kind: "string", length: 3 },
+    return_witnesses: [1, 2, 3],
+  };
+
+  const witnessMap = abiEncode(abi, { id: "abc" });
+  expect(witnessMap.get(1)).to.be.eq(
+    "0x0000000000000000000000000000000000000000000000000000000000000061"
+  );
+  expect(abiDecode(abi, witnessMap)).to.be.deep.eq({
+    inputs: { id: "abc" },
+    returnValue: "abc",
+  });
+  expect(() => abiEncode(abi, { id: "abcd" })).to.throw(
+    "Invalid inputs: id: Expected a string of 3 bytes but got 4."
+  );
+});
+
+it("flattens arrays of structs element by element", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "notes",
+        type: {
+          kind: "array",
+          length: 2,
+          type: {
+            kind: "struct",
+            path: "Note",
+            fields: [
+              ["value", { kind: "field" }],
+              ["owner", { kind: "integer", sign: "unsigned", width: 8 }],
+            ],
+          },
+        },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { notes: [1, 2, 3, 4] },
+    return_type: null,
+    return_witnesses: [],
+  };
+  const notes = [
+    { value: 1, owner: 2 },
+    { value: 3, owner: 4 },
+  ];
+
+  const witnessMap = abiEncode(abi, { notes });
+  expect([...witnessMap.keys()]).to.be.deep.eq([1, 2, 3, 4]);
+  expect(witnessMap.get(2)).to.be.eq(
+    "0x0000000000000000000000000000000000000000000000000000000000000002"
+  );
+  expect(
+    abiDecode(abi, witnessMap, { fieldFormat: "decimal" }).inputs
+  ).to.be.deep.eq({
+    notes: [
+      { value: "1", owner: "2" },
+      { value: "3", owner: "4" },
+    ],
+  });
+  expect(
+    abiValidateInputs(abi, { notes: [notes[0], { value: 3, owner: 256 }] })
+  ).to.be.deep.eq([
+    {
+      path: "notes[1].owner",
+      expectedType: "u8",
+      kind: "overflow",
+      message: "Value does not fit within 8 bits.",
+    },
+  ]);
+});
+
+it("reports integers outside of their type's range as overflows", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "x",
+        type: { kind: "integer", sign: "unsigned", width: 8 },
+        visibility: "private",
+      },
+      {
+        name: "y",
+        type: { kind: "integer", sign: "signed", width: 8 },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { x: [1], y: [2] },
+    return_type: { kind: "integer", sign: "signed", width: 8 },
+    return_witnesses: [2],
+  };
+
+  const witnessMap = abiEncode(abi, { x: 255, y: -1 });
+  expect(witnessMap.get(2)).to.be.eq(
+    "0x00000000000000000000000000000000000000000000000000000000000000ff"
+  );
+  expect(
+    abiDecode(abi, witnessMap, { fieldFormat: "decimal" })
+  ).to.be.deep.eq({ inputs: { x: "255", y: "-1" }, returnValue: "-1" });
+
+  const errors = [
+    {
+      path: "x",
+      expectedType: "u8",
+      kind: "overflow",
+      message: "Value does not fit within u8, which can't be negative.",
+    },
+    {
+      path: "y",
+      expectedType: "i8",
+      kind: "overflow",
+      message: "Value does not fit within i8, which ranges from -128 to 127.",
+    },
+  ];
+  expect(abiValidateInputs(abi, { x: -1, y: -129 })).to.be.deep.eq(errors);
+  try {
+    abiEncode(abi, { x: -1, y: -129 });
+    expect.fail("expected abiEncode to throw");
+  } catch (err) {
+    expect((err as Error).name).to.be.eq("AbiEncodingError");
+    expect((err as { errors: unknown }).errors).to.be.deep.eq(errors);
+  }
+});
+
+it("accepts bigints for fields and integers", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      {
+        name: "y",
+        type: { kind: "integer", sign: "signed", width: 64 },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { x: [1], y: [2] },
+    return_type: null,
+    return_witnesses: [],
+  };
+
+  const witnessMap = abiEncode(abi, { x: 2n ** 64n, y: -(2n ** 63n) });
+  expect(witnessMap.get(1)).to.be.eq(
+    "0x0000000000000000000000000000000000000000000000010000000000000000"
+  );
+  expect(witnessMap.get(2)).to.be.eq(
+    "0x0000000000000000000000000000000000000000000000008000000000000000"
+  );
+  expect(abiValidateInputs(abi, { x: 1n, y: 2n ** 63n })).to.be.deep.eq([
+    {
+      path: "y",
+      expectedType: "i64",
+      kind: "overflow",
+      message:
+        "Value does not fit within i64, which ranges from -9223372036854775808 to 9223372036854775807.",
+    },
+  ]);
+});
+
+it("decodes fields and integers as bigints when requested", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      {
+        name: "y",
+        type: { kind: "integer", sign: "signed", width: 32 },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { x: [1], y: [2] },
+    return_type: null,
+    return_witnesses: [],
+  };
+
+  const witnessMap = abiEncode(abi, { x: 2n ** 64n, y: -5 });
+  expect(
+    abiDecode(abi, witnessMap, { fieldFormat: "bigint" }).inputs
+  ).to.be.deep.eq({ x: 2n ** 64n, y: -5n });
+});
+
+it("rejects out of range integers and booleans when decoding strictly", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      {
+        name: "x",
+        type: { kind: "integer", sign: "signed", width: 8 },
+        visibility: "private",
+      },
+      {
+        name: "flags",
+        type: { kind: "array", length: 2, type: { kind: "boolean" } },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { x: [1], flags: [2, 3] },
+    return_type: null,
+    return_witnesses: [],
+  };
+  const field = (value: string) => "0x" + value.padStart(64, "0");
+  const witnessMap = (x: string, flag: string) =>
+    new Map([
+      [1, field(x)],
+      [2, field("01")],
+      [3, field(flag)],
+    ]);
+
+  expect(
+    abiDecode(abi, witnessMap("ff", "00"), {
+      fieldFormat: "decimal",
+      strict: true,
+    }).inputs
+  ).to.be.deep.eq({ x: "-1", flags: [true, false] });
+  // Out of range values are only rejected when requested.
+  expect(abiDecode(abi, witnessMap("0100", "02")).inputs).to.be.deep.eq({
+    x: field("0100"),
+    flags: [true, true],
+  });
+  expect(() =>
+    abiDecode(abi, witnessMap("0100", "00"), { strict: true })
+  ).to.throw(`x holds ${field("0100")} which does not fit within i8`);
+  expect(() =>
+    abiDecode(abi, witnessMap("ff", "02"), { strict: true })
+  ).to.throw(`flags[1] holds ${field("02")} which does not fit within bool`);
+});
+
+it("encodes the inputs held in a Prover.toml", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      {
+        name: "point",
+        type: {
+          kind: "struct",
+          path: "Point",
+          fields: [
+            ["x", { kind: "integer", sign: "unsigned", width: 32 }],
+            ["y", { kind: "integer", sign: "signed", width: 32 }],
+          ],
+        },
+        visibility: "public",
+      },
+      {
+        name: "name",
+        type: { kind: "string", length: 2 },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { x: [1], point: [2, 3], name: [4, 5] },
+    return_type: null,
+    return_witnesses: [],
+  };
+  const toml =
+    'x = "0x1"\n' +
+    'name = "12"\n' +
+    'return = "0x03"\n' +
+    "\n" +
+    "[point]\n" +
+    'x = "10"\n' +
+    "y = -1\n";
+
+  expect(
+    abiDecode(abi, abiEncodeFromToml(abi, toml), { fieldFormat: "decimal" })
+      .inputs
+  ).to.be.deep.eq({ x: "1", point: { x: "10", y: "-1" }, name: "12" });
+  expect(() => abiEncodeFromToml(abi, "x = ")).to.throw("Invalid Prover.toml");
+  expect(() => abiEncodeFromToml(abi, 'x = "0x01"')).to.throw(
+    "point: Missing value."
+  );
+});
+
+it("fills in missing parameters from defaults", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      {
+        name: "padding",
+        type: { kind: "array", length: 2, type: { kind: "field" } },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { x: [1], padding: [2, 3] },
+    return_type: null,
+    return_witnesses: [],
+  };
+  const defaults = { x: 0, padding: [0, 0] };
+
+  const witnessMap = abiEncode(abi, { x: 7 }, defaults);
+  expect(
+    abiDecode(abi, witnessMap, { fieldFormat: "decimal" }).inputs
+  ).to.be.deep.eq({ x: "7", padding: ["0", "0"] });
+  expect(() => abiEncode(abi, { x: 7 })).to.throw("padding: Missing value.");
+});
+
+it("reads strings without a 0x prefix as decimal", () => {
+  const abi: NoirAbi = {
+    parameters: [
+      { name: "x", type: { kind: "field" }, visibility: "private" },
+      {
+        name: "y",
+        type: { kind: "integer", sign: "signed", width: 8 },
+        visibility: "private",
+      },
+    ],
+    param_witnesses: { x: [1], y: [2] },
+    return_type: null,
+    return_witnesses: [],
+  };
+
+  const witnessMap = abiEncode(abi, { x: "10", y: "-1" });
+  expect(witnessMap).to.be.deep.eq(abiEncode(abi, { x: "0x0a", y: "0xff" }));
+  expect(abiValidateInputs(abi, { x: "-10", y: "128" })).to.be.deep.eq([
+    {
+      path: "x",
+      expectedType: "Field",
+      kind: "invalid",
+      message: "Expected a non-negative number.",
+    },
+    {
+      path: "y",
+      expectedType: "i8",
+      kind: "overflow",
+      message: "Value does not fit within i8, which ranges from -128 to 127.",
+    },
+  ]);
+  expect(abiValidateInputs(abi, { x: "ff", y: 0 })).to.have.length(1);
+});
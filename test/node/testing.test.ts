@@ -0,0 +1,47 @@
+import { expect } from "chai";
+import {
+  executeCircuit,
+  testing,
+  verifyWitness,
+  WitnessMap,
+} from "../../result/";
+
+it("builds a trivial circuit which executes to the known good witness", async () => {
+  const knownGoodWitness = testing.knownGoodWitness();
+  const inputs = new Map([
+    [1, knownGoodWitness.get(1) as string],
+    [2, knownGoodWitness.get(2) as string],
+  ]);
+
+  const solvedWitness: WitnessMap = await executeCircuit(
+    testing.trivialCircuit(),
+    inputs,
+    () => {
+      throw Error("unexpected oracle");
+    }
+  );
+
+  expect(solvedWitness).to.be.deep.eq(knownGoodWitness);
+});
+
+it("provides witnesses which pass and fail the trivial circuit", async () => {
+  const circuit = testing.trivialCircuit();
+
+  const passing = await verifyWitness(circuit, testing.knownGoodWitness());
+  const failing = await verifyWitness(circuit, testing.failingWitness());
+
+  expect(passing).to.be.empty;
+  expect(failing).to.have.lengthOf(1);
+});
+
+it("replays a script of foreign call responses", async () => {
+  const handler = testing.scriptedForeignCallHandler([
+    { name: "get_value", inputs: [["0x01"]], outputs: ["0x02"] },
+  ]);
+
+  const outputs = await handler("get_value", [["0x01"]]);
+  expect(outputs).to.be.deep.eq(["0x02"]);
+
+  const error = await handler("get_value", [["0x01"]]).catch((err) => err);
+  expect(error).to.be.a("string");
+});
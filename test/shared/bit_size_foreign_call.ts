@@ -0,0 +1,46 @@
+// let brillig_data = Brillig {
+//     inputs: vec![],
+//     outputs: vec![BrilligOutputs::Simple(Witness(1))], // Output Register 0
+//     foreign_call_results: vec![],
+//     bytecode: vec![
+//         brillig::Opcode::Const {
+//             destination: RegisterIndex::from(1),
+//             value: Value::from(0u128),
+//         },
+//         brillig::Opcode::ForeignCall {
+//             function: "get_u8".into(),
+//             destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+//             inputs: vec![],
+//         },
+//         // Treats the result of the foreign call as a u8.
+//         brillig::Opcode::BinaryIntOp {
+//             destination: RegisterIndex::from(2),
+//             op: BinaryIntOp::Add,
+//             bit_size: 8,
+//             lhs: RegisterIndex::from(0),
+//             rhs: RegisterIndex::from(1),
+//         },
+//     ],
+//     predicate: None,
+// };
+
+// let circuit = Circuit {
+//     current_witness_index: 1,
+//     opcodes: vec![Opcode::Brillig(brillig_data)],
+//     public_parameters: PublicInputs::default(),
+//     return_values: PublicInputs::default(),
+// };
+
+export const bytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 165, 142, 59, 10, 0, 32, 12, 67, 83, 5,
+  233, 113, 28, 29, 189, 137, 147, 120, 1, 189, 191, 136, 138, 165, 232, 228,
+  91, 250, 73, 32, 33, 0, 132, 137, 195, 129, 30, 187, 21, 222, 253, 143, 107,
+  250, 79, 88, 116, 40, 185, 166, 22, 100, 178, 102, 104, 70, 220, 172, 180, 43,
+  29, 207, 2, 23, 120, 239, 0, 0, 0,
+]);
+
+export const oracleCallName = "get_u8";
+export const validOracleResponse =
+  "0x000000000000000000000000000000000000000000000000000000000000002a";
+export const oversizedOracleResponse =
+  "0x00000000000000000000000000000000000000000000000000000000000001ff";
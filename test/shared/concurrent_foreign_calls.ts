@@ -0,0 +1,41 @@
+import { WitnessMap } from "../../result/";
+
+// fn oracle_call(name: &str, input: Witness, output: Witness) -> Opcode {
+//     Opcode::Brillig(Brillig {
+//         inputs: vec![BrilligInputs::Single(Expression {
+//             mul_terms: vec![],
+//             linear_combinations: vec![(FieldElement::one(), input)],
+//             q_c: FieldElement::zero(),
+//         })],
+//         outputs: vec![BrilligOutputs::Simple(output)],
+//         foreign_call_results: vec![],
+//         bytecode: vec![BrilligOpcode::ForeignCall {
+//             function: name.into(),
+//             destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+//             inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+//         }],
+//         predicate: None,
+//     })
+// }
+
+// let circuit = Circuit {
+//     current_witness_index: 3,
+//     opcodes: vec![
+//         oracle_call("first", Witness(1), Witness(2)),
+//         oracle_call("second", Witness(1), Witness(3)),
+//     ],
+//     public_parameters: PublicInputs::default(),
+//     return_values: PublicInputs::default(),
+// };
+
+export const bytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 213, 144, 93, 10, 128, 32, 16, 132, 71, 37,
+  233, 56, 118, 131, 238, 210, 15, 244, 82, 144, 221, 159, 132, 54, 240, 31, 65,
+  95, 252, 96, 216, 97, 31, 150, 217, 17, 0, 56, 62, 164, 17, 67, 200, 191, 155,
+  105, 170, 58, 38, 214, 238, 150, 178, 243, 242, 72, 230, 209, 104, 32, 191,
+  31, 183, 126, 114, 15, 58, 244, 222, 134, 72, 180, 33, 201, 235, 109, 185,
+  206, 181, 184, 14, 159, 23, 99, 168, 113, 1, 57, 2, 0, 0,
+]);
+export const initialWitnessMap: WitnessMap = new Map([
+  [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+]);
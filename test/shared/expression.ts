@@ -0,0 +1,38 @@
+// let expression = Expression {
+//     mul_terms: vec![(FieldElement::from(2u128), Witness(1), Witness(2))],
+//     linear_combinations: vec![(FieldElement::from(3u128), Witness(1))],
+//     q_c: FieldElement::from(5u128),
+// };
+export const bytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 173, 204, 193, 13, 0, 0, 4, 67, 81, 53,
+  145, 16, 119, 251, 79, 69, 194, 6, 250, 147, 166, 183, 7, 217, 234, 222, 126,
+  57, 198, 208, 25, 184, 110, 128, 103, 101, 3, 213, 132, 24, 214, 244, 0, 0, 0,
+]);
+
+export const witnessMap = new Map([
+  [1, "0x0000000000000000000000000000000000000000000000000000000000000002"],
+  [2, "0x0000000000000000000000000000000000000000000000000000000000000003"],
+]);
+
+// 2 * 2 * 3 + 3 * 2 + 5 = 23
+export const expectedValue =
+  "0x0000000000000000000000000000000000000000000000000000000000000017";
+
+// let opcode = Opcode::Arithmetic(Expression {
+//     mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+//     linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+//     q_c: FieldElement::zero(),
+// });
+export const opcodeBytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 173, 206, 177, 13, 128, 48, 12, 68, 81, 3,
+  11, 217, 177, 157, 156, 59, 86, 33, 194, 217, 127, 4, 132, 72, 73, 7, 191,
+  185, 238, 233, 136, 136, 22, 122, 218, 231, 242, 183, 228, 246, 214, 23, 87,
+  185, 154, 101, 43, 41, 42, 7, 151, 232, 112, 54, 239, 21, 2, 113, 248, 89,
+  160, 154, 48, 180, 232, 209, 56, 196, 52, 101, 120, 232, 152, 240, 246, 223,
+  71, 190, 0, 204, 210, 220, 97, 248, 0, 0, 0,
+]);
+
+// 2 * 3 = 6
+export const expectedOpcodeOutput = new Map([
+  [3, "0x0000000000000000000000000000000000000000000000000000000000000006"],
+]);
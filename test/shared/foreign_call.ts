@@ -86,6 +86,16 @@ export const bytecode = Uint8Array.from([
   217, 160, 216, 7, 129, 220, 173, 245, 81, 118, 167, 152, 127, 128, 243, 214,
   250, 11, 123, 58, 90, 201, 243, 4, 0, 0,
 ]);
+
+// `brillig_data` serialized on its own, as accepted by `executeBrillig`.
+export const brilligBytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 173, 144, 49, 10, 0, 33, 12, 4, 47, 185,
+  227, 244, 57, 250, 3, 63, 99, 97, 99, 33, 226, 251, 109, 162, 44, 152, 206,
+  108, 51, 97, 9, 3, 9, 63, 103, 88, 152, 132, 225, 46, 145, 12, 93, 108, 231,
+  10, 202, 233, 102, 238, 23, 156, 191, 112, 117, 78, 249, 53, 206, 31, 116, 36,
+  244, 224, 41, 117, 228, 214, 73, 217, 194, 110, 103, 2, 69, 66, 151, 231, 227,
+  1, 0, 0,
+]);
 export const initialWitnessMap: WitnessMap = new Map([
   [1, "0x0000000000000000000000000000000000000000000000000000000000000002"],
   [2, "0x0000000000000000000000000000000000000000000000000000000000000003"],
@@ -109,3 +119,9 @@ export const expectedWitnessMap = new Map([
   [6, "0x0000000000000000000000000000000000000000000000000000000000000005"],
   [7, "0x0000000000000000000000000000000000000000000000000000000000000000"],
 ]);
+
+export const expectedBrilligOutputs = new Map([
+  [3, "0x135b52945a13d9aa49b9b57c33cd568ba9ae5ce9ca4a2d06e7f3fbd4c6666667"],
+  [6, "0x0000000000000000000000000000000000000000000000000000000000000005"],
+  [7, "0x0000000000000000000000000000000000000000000000000000000000000000"],
+]);
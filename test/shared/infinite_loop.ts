@@ -0,0 +1,21 @@
+// let brillig_data = Brillig {
+//     inputs: vec![],
+//     outputs: vec![],
+//     foreign_call_results: vec![],
+//     // Jumps back to the start forever.
+//     bytecode: vec![brillig::Opcode::Jump { location: 0 }],
+//     predicate: None,
+// };
+
+// let circuit = Circuit {
+//     current_witness_index: 0,
+//     opcodes: vec![Opcode::Brillig(brillig_data)],
+//     public_parameters: PublicInputs::default(),
+//     return_values: PublicInputs::default(),
+// };
+
+export const bytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 125, 202, 177, 13, 0, 0, 4, 0, 176, 74,
+  196, 255, 39, 27, 196, 72, 231, 66, 24, 229, 182, 39, 159, 163, 1, 218, 55,
+  252, 207, 77, 0, 0, 0,
+]);
@@ -0,0 +1,152 @@
+// let block_id = BlockId(0);
+// let opcodes = vec![
+//     Opcode::MemoryInit { block_id, init: vec![Witness(1), Witness(2)] },
+//     Opcode::MemoryOp {
+//         block_id,
+//         op: MemOp {
+//             operation: Expression::one(),
+//             index: Expression::one(),
+//             value: Expression::from(Witness(3)),
+//         },
+//     },
+//     Opcode::MemoryOp {
+//         block_id,
+//         op: MemOp {
+//             operation: Expression::zero(),
+//             index: Expression::zero(),
+//             value: Expression::from(Witness(4)),
+//         },
+//     },
+// ];
+
+// let circuit = Circuit {
+//     current_witness_index: 4,
+//     opcodes,
+//     public_parameters: PublicInputs::default(),
+//     return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(4)])),
+// };
+export const bytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 181, 144, 177, 13, 0, 32, 12, 195, 82, 90,
+  9, 206, 41, 31, 240, 255, 85, 8, 145, 145, 141, 198, 139, 59, 89, 85, 2, 128,
+  227, 50, 232, 70, 27, 239, 142, 55, 139, 206, 63, 166, 186, 107, 197, 93, 175,
+  107, 165, 120, 219, 84, 119, 171, 183, 13, 225, 143, 167, 189, 1, 229, 37,
+  210, 187, 240, 2, 0, 0,
+]);
+
+export const initialWitnessMap = new Map([
+  [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+  [2, "0x0000000000000000000000000000000000000000000000000000000000000002"],
+  [3, "0x0000000000000000000000000000000000000000000000000000000000000003"],
+]);
+
+export const expectedWitnessMap = new Map([
+  [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+  [2, "0x0000000000000000000000000000000000000000000000000000000000000002"],
+  [3, "0x0000000000000000000000000000000000000000000000000000000000000003"],
+  [4, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+]);
+
+// The block's second element is overwritten with the value of `Witness(3)`.
+export const expectedMemoryBlocks = new Map([
+  [
+    0,
+    [
+      "0x0000000000000000000000000000000000000000000000000000000000000001",
+      "0x0000000000000000000000000000000000000000000000000000000000000003",
+    ],
+  ],
+]);
+
+// let block_id = BlockId(0);
+// let opcodes = vec![
+//     Opcode::MemoryInit { block_id, init: vec![Witness(1), Witness(2)] },
+//     Opcode::MemoryOp {
+//         block_id,
+//         op: MemOp {
+//             operation: Expression::zero(),
+//             index: Expression::from(Witness(3)),
+//             value: Expression::from(Witness(4)),
+//         },
+//     },
+// ];
+//
+// let circuit = Circuit {
+//     current_witness_index: 4,
+//     opcodes,
+//     public_parameters: PublicInputs::default(),
+//     return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(4)])),
+// };
+export const indexedReadBytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 213, 142, 49, 14, 0, 16, 20, 67, 31, 126,
+  194, 113, 184, 129, 251, 159, 74, 68, 37, 6, 27, 6, 111, 105, 187, 180, 53,
+  192, 51, 72, 210, 153, 157, 124, 100, 79, 149, 230, 67, 88, 246, 46, 246, 150,
+  240, 193, 71, 123, 248, 177, 119, 55, 117, 85, 227, 55, 224, 1, 0, 0,
+]);
+
+// The initial witness for `indexedReadBytecode`, reading `index` of a block
+// holding 1 and 2.
+export const indexedReadWitness = (index: string, value?: string) =>
+  new Map([
+    [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+    [2, "0x0000000000000000000000000000000000000000000000000000000000000002"],
+    [3, index],
+    ...(value === undefined ? [] : [[4, value] as [number, string]]),
+  ]);
+
+// let block_id = BlockId(0);
+// let opcodes = vec![
+//     Opcode::MemoryInit { block_id, init: vec![Witness(1)] },
+//     Opcode::MemoryOp {
+//         block_id,
+//         op: MemOp {
+//             operation: Expression::one(),
+//             index: Expression::zero(),
+//             value: Expression::from(Witness(3)),
+//         },
+//     },
+//     Opcode::MemoryOp {
+//         block_id,
+//         op: MemOp {
+//             operation: Expression::zero(),
+//             index: Expression::zero(),
+//             value: Expression::from(Witness(4)),
+//         },
+//     },
+//     // Copies `Witness(2)` into `Witness(3)`.
+//     Opcode::Brillig(Brillig {
+//         inputs: vec![BrilligInputs::Single(Expression::from(Witness(2)))],
+//         outputs: vec![BrilligOutputs::Simple(Witness(3))],
+//         foreign_call_results: vec![],
+//         bytecode: vec![BrilligOpcode::Stop],
+//         predicate: None,
+//     }),
+// ];
+//
+// let circuit = Circuit {
+//     current_witness_index: 4,
+//     opcodes,
+//     public_parameters: PublicInputs::default(),
+//     return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(4)])),
+// };
+export const brilligWriteBytecode = Uint8Array.from([
+  31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 213, 208, 177, 13, 0, 33, 12, 3, 64, 255,
+  19, 1, 21, 179, 132, 13, 216, 127, 42, 132, 160, 136, 80, 58, 76, 129, 27,
+  119, 209, 197, 2, 64, 48, 147, 87, 127, 166, 19, 252, 180, 213, 122, 150, 122,
+  233, 174, 98, 251, 133, 229, 13, 68, 227, 229, 109, 245, 181, 109, 133, 104,
+  140, 198, 103, 195, 54, 255, 68, 179, 245, 6, 199, 92, 188, 71, 198, 104, 29,
+  129, 230, 254, 133, 197, 3, 0, 0,
+]);
+
+export const brilligWriteInitialWitness = new Map([
+  [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+  [2, "0x0000000000000000000000000000000000000000000000000000000000000005"],
+]);
+
+// The read must observe the write of the Brillig output rather than the
+// block's initial value, even though the Brillig opcode comes last.
+export const brilligWriteExpectedWitness = new Map([
+  [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
+  [2, "0x0000000000000000000000000000000000000000000000000000000000000005"],
+  [3, "0x0000000000000000000000000000000000000000000000000000000000000005"],
+  [4, "0x0000000000000000000000000000000000000000000000000000000000000005"],
+]);
@@ -17,6 +17,21 @@ export const bytecode = Uint8Array.from([
   38, 61, 74, 76, 5, 0, 0,
 ]);
 
+// The same circuit serialized as MessagePack and compressed with raw deflate,
+// as produced by ACIR's `serialize-messagepack` feature.
+export const messagePackBytecode = Uint8Array.from([
+  205, 147, 189, 13, 194, 48, 16, 133, 69, 254, 88, 199, 23, 219, 201, 185, 3,
+  137, 134, 49, 72, 184, 8, 23, 80, 88, 86, 122, 111, 128, 99, 196, 8, 72, 176,
+  17, 219, 64, 65, 147, 218, 70, 202, 13, 240, 73, 223, 123, 247, 110, 235, 187,
+  123, 109, 141, 182, 167, 51, 89, 221, 135, 107, 152, 222, 27, 22, 119, 176,
+  250, 50, 56, 107, 132, 160, 182, 38, 224, 112, 96, 181, 234, 80, 50, 33, 187,
+  6, 1, 65, 162, 60, 214, 200, 57, 161, 192, 86, 117, 170, 101, 10, 4, 39, 24,
+  164, 226, 195, 15, 146, 37, 96, 228, 209, 42, 204, 61, 119, 218, 80, 111, 245,
+  72, 238, 177, 191, 140, 100, 236, 148, 23, 179, 200, 124, 136, 79, 44, 47,
+  124, 2, 223, 50, 129, 111, 114, 179, 210, 47, 164, 201, 217, 155, 47, 35, 110,
+  248, 207, 246, 98, 25, 41, 182, 87, 197, 55, 230, 51, 95, 125, 0,
+]);
+
 export const initialWitnessMap: WitnessMap = new Map([
   [1, "0x0000000000000000000000000000000000000000000000000000000000000001"],
   [2, "0x0000000000000000000000000000000000000000000000000000000000000002"],